@@ -0,0 +1,21 @@
+-- TLS certificates presented on a port, collected during discovery
+
+CREATE TABLE certificates (
+    id UUID PRIMARY KEY,
+    network_id UUID NOT NULL REFERENCES networks(id) ON DELETE CASCADE,
+    host_id UUID NOT NULL REFERENCES hosts(id) ON DELETE CASCADE,
+    port_id UUID NOT NULL REFERENCES ports(id) ON DELETE CASCADE,
+    subject TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+    sans TEXT[] NOT NULL DEFAULT '{}',
+    fingerprint TEXT NOT NULL,
+    not_before TIMESTAMPTZ NOT NULL,
+    not_after TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(port_id)
+);
+
+CREATE INDEX idx_certificates_network ON certificates(network_id);
+CREATE INDEX idx_certificates_host ON certificates(host_id);
+CREATE INDEX idx_certificates_not_after ON certificates(not_after);
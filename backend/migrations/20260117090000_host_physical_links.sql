@@ -0,0 +1,3 @@
+-- Physical links to neighboring devices, read from a host's LLDP neighbor table
+
+ALTER TABLE hosts ADD COLUMN physical_links JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,5 @@
+-- SNMP credential profiles per network, and the profile that answered on each port
+
+ALTER TABLE networks ADD COLUMN snmp_credentials JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE ports ADD COLUMN snmp_credential_id UUID;
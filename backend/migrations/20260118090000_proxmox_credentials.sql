@@ -0,0 +1,3 @@
+-- Proxmox VE API tokens per network
+
+ALTER TABLE networks ADD COLUMN proxmox_credentials JSONB NOT NULL DEFAULT '[]';
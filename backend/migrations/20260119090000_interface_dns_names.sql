@@ -0,0 +1,3 @@
+-- Names from imported DNS zones pointing at an interface's address
+
+ALTER TABLE interfaces ADD COLUMN dns_names TEXT[] NOT NULL DEFAULT '{}';
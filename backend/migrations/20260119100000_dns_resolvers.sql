@@ -0,0 +1,3 @@
+-- DNS servers for reverse lookups per network
+
+ALTER TABLE networks ADD COLUMN dns_resolvers JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,3 @@
+-- Named port sets and scan timings per network, referenced by network discoveries
+
+ALTER TABLE networks ADD COLUMN scan_profiles JSONB NOT NULL DEFAULT '[]';
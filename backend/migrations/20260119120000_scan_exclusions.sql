@@ -0,0 +1,3 @@
+-- Addresses, ranges and MACs network discovery must never probe
+
+ALTER TABLE networks ADD COLUMN scan_exclusions JSONB NOT NULL DEFAULT '[]';
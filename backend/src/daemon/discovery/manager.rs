@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -6,8 +7,13 @@ use tokio_util::sync::CancellationToken;
 use crate::daemon::discovery::service::base::{
     DaemonDiscoveryService, DiscoveryRunner, RunsDiscovery,
 };
+use crate::daemon::discovery::service::dhcp_leases::DhcpLeaseDiscovery;
+use crate::daemon::discovery::service::dns_zones::DnsZoneDiscovery;
 use crate::daemon::discovery::service::docker::DockerScanDiscovery;
+use crate::daemon::discovery::service::kubernetes::KubernetesDiscovery;
 use crate::daemon::discovery::service::network::NetworkScanDiscovery;
+use crate::daemon::discovery::service::passive::PassiveDiscovery;
+use crate::daemon::discovery::service::proxmox::ProxmoxDiscovery;
 use crate::daemon::discovery::service::self_report::SelfReportDiscovery;
 use crate::daemon::runtime::service::LOG_TARGET;
 use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
@@ -62,11 +68,67 @@ impl DaemonDiscoverySessionManager {
             DiscoveryType::Network {
                 subnet_ids,
                 host_naming_fallback,
+                scan_profile_id,
             } => self.clone().spawn_discovery(
                 DiscoveryRunner::new(
                     self.discovery_service.clone(),
                     self.clone(),
-                    NetworkScanDiscovery::new(subnet_ids.clone(), *host_naming_fallback),
+                    NetworkScanDiscovery::new(
+                        subnet_ids.clone(),
+                        *host_naming_fallback,
+                        *scan_profile_id,
+                    ),
+                ),
+                request.clone(),
+                cancel_token,
+            ),
+            DiscoveryType::DhcpLeases { source } => self.clone().spawn_discovery(
+                DiscoveryRunner::new(
+                    self.discovery_service.clone(),
+                    self.clone(),
+                    DhcpLeaseDiscovery::new(source.clone()),
+                ),
+                request.clone(),
+                cancel_token,
+            ),
+            DiscoveryType::Kubernetes {
+                kubeconfig_path,
+                context,
+            } => self.clone().spawn_discovery(
+                DiscoveryRunner::new(
+                    self.discovery_service.clone(),
+                    self.clone(),
+                    KubernetesDiscovery::new(kubeconfig_path.clone(), context.clone()),
+                ),
+                request.clone(),
+                cancel_token,
+            ),
+            DiscoveryType::Proxmox { credential_ids } => self.clone().spawn_discovery(
+                DiscoveryRunner::new(
+                    self.discovery_service.clone(),
+                    self.clone(),
+                    ProxmoxDiscovery::new(credential_ids.clone()),
+                ),
+                request.clone(),
+                cancel_token,
+            ),
+            DiscoveryType::Passive {
+                duration,
+                subnet_ids,
+            } => self.clone().spawn_discovery(
+                DiscoveryRunner::new(
+                    self.discovery_service.clone(),
+                    self.clone(),
+                    PassiveDiscovery::new(Duration::from_secs(*duration), subnet_ids.clone()),
+                ),
+                request.clone(),
+                cancel_token,
+            ),
+            DiscoveryType::DnsZone { source } => self.clone().spawn_discovery(
+                DiscoveryRunner::new(
+                    self.discovery_service.clone(),
+                    self.clone(),
+                    DnsZoneDiscovery::new(source.clone()),
                 ),
                 request.clone(),
                 cancel_token,
@@ -10,22 +10,28 @@ use crate::{
     daemon::{
         discovery::{manager::DaemonDiscoverySessionManager, types::base::DiscoveryCriticalError},
         shared::api_client::DaemonApiClient,
+        utils::reverse_proxy::{ReverseProxyClient, ReverseProxyTarget, reverse_proxy_targets},
     },
     server::{
+        certificates::r#impl::base::Certificate,
         discovery::r#impl::types::{DiscoveryType, HostNamingFallback},
         groups::r#impl::base::Group,
         services::{
-            definitions::{docker_container::DockerContainer, open_ports::OpenPorts},
+            definitions::{
+                docker_container::DockerContainer, kubernetes_pod::KubernetesPod,
+                open_ports::OpenPorts,
+            },
             r#impl::{
                 base::{
                     DiscoverySessionServiceMatchParams, ServiceMatchBaselineParams,
                     ServiceMatchServiceParams,
                 },
+                endpoints::CertificateResponse,
                 patterns::MatchConfidence,
                 virtualization::{DockerVirtualization, ServiceVirtualization},
             },
         },
-        shared::types::entities::{DiscoveryMetadata, EntitySource},
+        shared::types::entities::{DiscoveryMetadata, EntitySource, HostnameSource},
     },
 };
 use anyhow::{Error, anyhow};
@@ -43,12 +49,19 @@ use crate::{
         utils::base::{PlatformDaemonUtils, create_system_utils},
     },
     server::{
-        daemons::r#impl::api::{DaemonDiscoveryRequest, DiscoveryUpdatePayload},
+        daemons::r#impl::api::{DaemonDiscoveryRequest, DiscoveryUpdatePayload, SubnetRttStats},
+        discovery::r#impl::dns_zones::DnsRecord,
         hosts::r#impl::{
-            api::{DiscoveryHostRequest, HostResponse},
+            api::{
+                DiscoveredLibvirtDomain, DiscoveredLinkNeighbor, DiscoveredProxmoxGuest,
+                DiscoveryHostRequest, DiscoveryLibvirtDomainsRequest,
+                DiscoveryLinkNeighborsRequest, DiscoveryProxmoxGuestsRequest,
+                DiscoveryProxyRoutesRequest, HostResponse,
+            },
             base::{Host, HostBase},
         },
         interfaces::r#impl::base::Interface,
+        networks::scan_exclusions::SkippedLog,
         ports::r#impl::base::{Port, PortType},
         services::{
             definitions::{ServiceDefinitionRegistry, gateway::Gateway},
@@ -59,6 +72,7 @@ use crate::{
         },
         shared::types::metadata::HasId,
         subnets::r#impl::base::Subnet,
+        tags::r#impl::base::Tag,
     },
 };
 
@@ -88,6 +102,16 @@ pub struct DiscoverySession {
     pub gateway_ips: Vec<IpAddr>,
     pub last_progress: Arc<AtomicU8>,
     pub last_progress_report_time: Arc<AtomicU64>,
+    /// Reverse proxies created during the session, their routes are read once it finishes
+    pub reverse_proxies: Arc<std::sync::Mutex<Vec<ReverseProxyTarget>>>,
+    /// Latest per-subnet round-trip times, sent along with every update
+    pub rtt_stats: Arc<std::sync::Mutex<Vec<SubnetRttStats>>>,
+    /// Addresses left alone because of scan exclusions, sent along with every update
+    pub skipped: Arc<std::sync::Mutex<SkippedLog>>,
+    /// MAC exclusions some hosts couldn't be checked against, sent along with every update
+    pub unenforced_exclusions: Arc<std::sync::Mutex<Vec<Uuid>>>,
+    /// Zone records naming addresses without a discovered host, sent along with every update
+    pub dangling_records: Arc<std::sync::Mutex<Vec<DnsRecord>>>,
 }
 
 impl DiscoverySession {
@@ -97,6 +121,11 @@ impl DiscoverySession {
             gateway_ips,
             last_progress: Arc::new(AtomicU8::new(0)),
             last_progress_report_time: Arc::new(AtomicU64::new(0)),
+            reverse_proxies: Arc::new(std::sync::Mutex::new(Vec::new())),
+            rtt_stats: Arc::new(std::sync::Mutex::new(Vec::new())),
+            skipped: Arc::new(std::sync::Mutex::new(SkippedLog::default())),
+            unenforced_exclusions: Arc::new(std::sync::Mutex::new(Vec::new())),
+            dangling_records: Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 }
@@ -194,11 +223,15 @@ pub trait RunsDiscovery: AsRef<DaemonDiscoveryService> + Send + Sync {
         let session = self.as_ref().get_session().await?;
         let discovery_type = self.discovery_type();
 
-        let payload = DiscoveryUpdatePayload::from_state_and_update(
+        let mut payload = DiscoveryUpdatePayload::from_state_and_update(
             discovery_type,
             session.info.clone(),
             update,
         );
+        payload.rtt_stats = session.rtt_stats.lock().unwrap().clone();
+        payload.skipped = session.skipped.lock().unwrap().summaries().to_vec();
+        payload.unenforced_exclusions = session.unenforced_exclusions.lock().unwrap().clone();
+        payload.dangling_records = session.dangling_records.lock().unwrap().clone();
 
         let path = format!("/api/v1/discovery/{}/update", session.info.session_id);
 
@@ -311,6 +344,8 @@ pub trait DiscoversNetworkedEntities:
 
         match &discovery_result {
             Ok(_) => {
+                self.report_reverse_proxy_routes(&session).await;
+
                 tracing::info!(
                     session_id = %session_id,
                     progress = 100,
@@ -375,10 +410,79 @@ pub trait DiscoversNetworkedEntities:
         Ok(())
     }
 
+    /// Read the routes of the reverse proxies created during the session. The server
+    /// records each route whose upstream is a discovered service as a request path.
+    async fn report_reverse_proxy_routes(&self, session: &DiscoverySession) {
+        let targets = std::mem::take(&mut *session.reverse_proxies.lock().unwrap());
+        if targets.is_empty() {
+            return;
+        }
+
+        let config_store = &self.as_ref().config_store;
+        let npm_credentials = config_store
+            .get_npm_credentials()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Ignoring invalid Nginx Proxy Manager credentials");
+                None
+            });
+        let npm_database = config_store.get_npm_database().await.unwrap_or_default();
+
+        let client = match ReverseProxyClient::new(npm_credentials, npm_database) {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!(error = %e, "Skipping reverse proxy routes");
+                return;
+            }
+        };
+
+        let source = EntitySource::Discovery {
+            metadata: vec![DiscoveryMetadata::new(
+                self.discovery_type(),
+                session.info.daemon_id,
+            )],
+        };
+
+        for target in targets {
+            let routes = match client.routes(&target).await {
+                Ok(routes) if routes.is_empty() => continue,
+                Ok(routes) => routes,
+                Err(e) => {
+                    tracing::warn!(proxy = ?target.kind, address = %target.address, error = %e, "Failed to read reverse proxy routes");
+                    continue;
+                }
+            };
+
+            let request = DiscoveryProxyRoutesRequest {
+                service_id: target.service_id,
+                routes,
+                source: source.clone(),
+            };
+
+            match self
+                .as_ref()
+                .api_client
+                .post::<_, Vec<Group>>(
+                    "/api/v1/groups/discovery/proxy-routes",
+                    &request,
+                    "Failed to report reverse proxy routes",
+                )
+                .await
+            {
+                Ok(groups) => {
+                    tracing::info!(proxy = ?target.kind, address = %target.address, groups = groups.len(), "Reported reverse proxy routes")
+                }
+                Err(e) => {
+                    tracing::warn!(proxy = ?target.kind, address = %target.address, error = %e, "Failed to report reverse proxy routes")
+                }
+            }
+        }
+    }
+
     async fn process_host<'a>(
         &self,
         params: ServiceMatchBaselineParams<'a>,
-        hostname: Option<String>,
+        hostname: Option<(String, HostnameSource)>,
         host_naming_fallback: HostNamingFallback,
     ) -> Result<Option<(Host, Vec<Interface>, Vec<Port>, Vec<Service>)>, Error> {
         let ServiceMatchBaselineParams::<'a> { interface, .. } = params;
@@ -394,6 +498,10 @@ pub trait DiscoversNetworkedEntities:
         let session = self.as_ref().get_session().await?;
         let gateway_ips = session.gateway_ips.clone();
         let discovery_type = self.discovery_type();
+        let (hostname, hostname_source) = hostname.unzip();
+
+        let mut metadata = DiscoveryMetadata::new(discovery_type.clone(), daemon_id);
+        metadata.hostname_source = hostname_source;
 
         // Create host - children (interfaces, ports, services) are passed separately
         let mut host = Host::new(HostBase {
@@ -403,10 +511,11 @@ pub trait DiscoversNetworkedEntities:
             network_id,
             description: None,
             source: EntitySource::Discovery {
-                metadata: vec![DiscoveryMetadata::new(discovery_type.clone(), daemon_id)],
+                metadata: vec![metadata],
             },
             virtualization: None,
             hidden: false,
+            physical_links: Vec::new(),
         });
 
         // Store interfaces separately to pass to server
@@ -429,6 +538,13 @@ pub trait DiscoversNetworkedEntities:
 
         if let Some(hostname) = hostname {
             host.base.name = hostname;
+        } else if host_naming_fallback == HostNamingFallback::Mdns
+            && let Some(mdns_name) = params
+                .mdns_responses
+                .iter()
+                .find_map(|response| response.hostname.clone())
+        {
+            host.base.name = mdns_name
         } else if host_naming_fallback == HostNamingFallback::BestService
             && let Some(best_service_name) = best_service_name
         {
@@ -481,12 +597,15 @@ pub trait DiscoversNetworkedEntities:
             } else if s.id() == OpenPorts.id() {
                 // Catch-all for open ports, should be dead last
                 3
-            } else if s.id() == DockerContainer.id() || s.id() == Gateway.id() {
-                // Docker Containers and Gateways need to go second to last last
+            } else if s.id() == DockerContainer.id()
+                || s.id() == KubernetesPod.id()
+                || s.id() == Gateway.id()
+            {
+                // Docker Containers, Kubernetes Pods and Gateways need to go second to last last
                 // Other generic services should be able to get matched first
                 2
             } else {
-                // Generic services that aren't Docker Container or Gateway
+                // Generic services that aren't Docker Container, Kubernetes Pod or Gateway
                 1
             }
         });
@@ -514,11 +633,13 @@ pub trait DiscoversNetworkedEntities:
                 && !container_matched
             {
                 // If a container was matched w the provided virtualization, no others can be matched
-                if let Some(ServiceVirtualization::Docker(DockerVirtualization {
-                    container_id: Some(_),
-                    ..
-                })) = &service.base.virtualization
-                {
+                if matches!(
+                    &service.base.virtualization,
+                    Some(ServiceVirtualization::Docker(DockerVirtualization {
+                        container_id: Some(_),
+                        ..
+                    })) | Some(ServiceVirtualization::Kubernetes(_))
+                ) {
                     container_matched = true
                 }
 
@@ -559,6 +680,26 @@ pub trait DiscoversNetworkedEntities:
 /// This handles transient failures during server switchovers (blue-green deployments).
 const ENTITY_CREATION_MAX_RETRIES: u32 = 5;
 
+/// Key each certificate to the discovered port it was read from.
+/// Certificates from ports that didn't make it into `ports` are dropped.
+pub fn certificates_for_ports(
+    ports: &[Port],
+    certificate_responses: &[CertificateResponse],
+) -> Vec<Certificate> {
+    certificate_responses
+        .iter()
+        .filter_map(|response| {
+            ports
+                .iter()
+                .find(|port| {
+                    port.base.port_type.number() == response.port_type.number()
+                        && port.base.port_type.protocol() == response.port_type.protocol()
+                })
+                .map(|port| Certificate::new_hostless(port.id, response.certificate.clone()))
+        })
+        .collect()
+}
+
 #[async_trait]
 pub trait CreatesDiscoveredEntities:
     AsRef<DaemonDiscoveryService> + Send + Sync + RunsDiscovery
@@ -569,14 +710,22 @@ pub trait CreatesDiscoveredEntities:
         interfaces: Vec<Interface>,
         ports: Vec<Port>,
         services: Vec<Service>,
+        certificates: Vec<Certificate>,
     ) -> Result<HostResponse, Error> {
-        let request = DiscoveryHostRequest {
+        self.submit_host(DiscoveryHostRequest {
             host,
             interfaces,
             ports,
             services,
-        };
-        self.as_ref()
+            certificates,
+            neighbors_read: false,
+        })
+        .await
+    }
+
+    async fn submit_host(&self, request: DiscoveryHostRequest) -> Result<HostResponse, Error> {
+        let host_response: HostResponse = self
+            .as_ref()
             .api_client
             .post_with_retry(
                 "/api/v1/hosts/discovery",
@@ -584,6 +733,65 @@ pub trait CreatesDiscoveredEntities:
                 "Failed to create host",
                 ENTITY_CREATION_MAX_RETRIES,
             )
+            .await?;
+
+        if let Ok(session) = self.as_ref().get_session().await {
+            session
+                .reverse_proxies
+                .lock()
+                .unwrap()
+                .extend(reverse_proxy_targets(&host_response));
+        }
+
+        Ok(host_response)
+    }
+
+    async fn report_link_neighbors(
+        &self,
+        neighbors: Vec<DiscoveredLinkNeighbor>,
+    ) -> Result<(), Error> {
+        let request = DiscoveryLinkNeighborsRequest { neighbors };
+        self.as_ref()
+            .api_client
+            .post_no_data(
+                "/api/v1/hosts/discovery/neighbors",
+                &request,
+                "Failed to report link neighbors",
+            )
+            .await
+    }
+
+    async fn report_proxmox_guests(
+        &self,
+        guests: Vec<DiscoveredProxmoxGuest>,
+    ) -> Result<(), Error> {
+        let request = DiscoveryProxmoxGuestsRequest { guests };
+        self.as_ref()
+            .api_client
+            .post_no_data(
+                "/api/v1/hosts/discovery/proxmox-guests",
+                &request,
+                "Failed to report Proxmox guests",
+            )
+            .await
+    }
+
+    async fn report_libvirt_domains(
+        &self,
+        service_id: Uuid,
+        domains: Vec<DiscoveredLibvirtDomain>,
+    ) -> Result<(), Error> {
+        let request = DiscoveryLibvirtDomainsRequest {
+            service_id,
+            domains,
+        };
+        self.as_ref()
+            .api_client
+            .post_no_data(
+                "/api/v1/hosts/discovery/libvirt-domains",
+                &request,
+                "Failed to report libvirt domains",
+            )
             .await
     }
 
@@ -599,6 +807,20 @@ pub trait CreatesDiscoveredEntities:
             .await
     }
 
+    async fn get_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        self.as_ref()
+            .api_client
+            .get("/api/v1/subnets", "Failed to get subnets")
+            .await
+    }
+
+    async fn get_tags(&self) -> Result<Vec<Tag>, Error> {
+        self.as_ref()
+            .api_client
+            .get("/api/v1/tags?limit=0", "Failed to get tags")
+            .await
+    }
+
     async fn create_service(&self, service: &Service) -> Result<Service, Error> {
         self.as_ref()
             .api_client
@@ -615,7 +837,7 @@ pub trait CreatesDiscoveredEntities:
         self.as_ref()
             .api_client
             .post_with_retry(
-                "/api/v1/groups",
+                "/api/v1/groups/discovery",
                 group,
                 "Failed to create group",
                 ENTITY_CREATION_MAX_RETRIES,
@@ -0,0 +1,185 @@
+use crate::daemon::discovery::service::base::{
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+};
+use crate::daemon::utils::base::DaemonUtils;
+use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
+use crate::server::discovery::r#impl::dhcp_leases::{
+    DhcpLease, DhcpLeaseSource, lease_host, parse_kea_command_response, parse_leases,
+};
+use crate::server::discovery::r#impl::types::DiscoveryType;
+use crate::server::shared::types::entities::DiscoveryMetadata;
+use crate::server::subnets::r#impl::base::Subnet;
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use std::net::IpAddr;
+use tokio_util::sync::CancellationToken;
+
+pub struct DhcpLeaseDiscovery {
+    source: DhcpLeaseSource,
+}
+
+impl DhcpLeaseDiscovery {
+    pub fn new(source: DhcpLeaseSource) -> Self {
+        Self { source }
+    }
+}
+
+impl CreatesDiscoveredEntities for DiscoveryRunner<DhcpLeaseDiscovery> {}
+
+#[async_trait]
+impl RunsDiscovery for DiscoveryRunner<DhcpLeaseDiscovery> {
+    fn discovery_type(&self) -> DiscoveryType {
+        DiscoveryType::DhcpLeases {
+            source: self.domain.source.clone(),
+        }
+    }
+
+    async fn discover(
+        &self,
+        request: DaemonDiscoveryRequest,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        self.start_discovery(request).await?;
+
+        let discovery_result = self.import_leases(cancel.clone()).await;
+
+        self.finish_discovery(discovery_result, cancel.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscoversNetworkedEntities for DiscoveryRunner<DhcpLeaseDiscovery> {
+    async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
+        self.as_ref()
+            .utils
+            .get_own_routing_table_gateway_ips()
+            .await
+    }
+
+    /// Leases are only imported into subnets that already exist, a DHCP server
+    /// can hand out addresses for scopes the daemon isn't attached to
+    async fn discover_create_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        self.get_subnets().await
+    }
+}
+
+impl DiscoveryRunner<DhcpLeaseDiscovery> {
+    async fn import_leases(&self, cancel: CancellationToken) -> Result<(), Error> {
+        let session = self.as_ref().get_session().await?;
+
+        let leases = self.read_leases().await?;
+        let subnets = self.discover_create_subnets().await?;
+        let total = leases.len();
+
+        let mut imported = 0;
+        let mut outside_subnets = 0;
+
+        for (index, lease) in leases.iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery was cancelled"));
+            }
+
+            let Some(subnet) = subnets.iter().find(|s| s.base.cidr.contains(&lease.ip)) else {
+                outside_subnets += 1;
+                continue;
+            };
+
+            let metadata = DiscoveryMetadata::new(self.discovery_type(), session.info.daemon_id);
+            let (host, interface) = lease_host(lease, subnet, metadata);
+
+            match self
+                .create_host(host, vec![interface], Vec::new(), Vec::new(), Vec::new())
+                .await
+            {
+                Ok(_) => imported += 1,
+                Err(e) => {
+                    tracing::warn!(ip = %lease.ip, mac = %lease.mac, error = %e, "Failed to import DHCP lease");
+                }
+            }
+
+            self.report_scanning_progress(((index + 1) * 100 / total.max(1)) as u8)
+                .await?;
+        }
+
+        tracing::info!(
+            leases = total,
+            imported,
+            outside_subnets,
+            "DHCP lease import complete"
+        );
+
+        Ok(())
+    }
+
+    async fn read_leases(&self) -> Result<Vec<DhcpLease>, Error> {
+        match &self.domain.source {
+            DhcpLeaseSource::File { format, path } => {
+                let contents = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(|e| anyhow!("Failed to read lease file {}: {}", path, e))?;
+                parse_leases(*format, &contents)
+            }
+            DhcpLeaseSource::KeaControlSocket { path } => {
+                let response = query_kea_control_socket(path).await?;
+                parse_kea_command_response(&response)
+            }
+            DhcpLeaseSource::Uploaded { .. } => Err(anyhow!(
+                "Uploaded lease files are imported by the server, not by daemons"
+            )),
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn query_kea_control_socket(path: &str) -> Result<String, Error> {
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::time::timeout;
+
+    /// Kea answers from memory, so a socket that stays quiet this long is stuck
+    const KEA_SOCKET_TIMEOUT: Duration = Duration::from_secs(30);
+    /// Cap on a Kea response, far above the lease list of any real server
+    const MAX_RESPONSE_BYTES: u64 = 64 * 1024 * 1024;
+
+    let mut stream = timeout(KEA_SOCKET_TIMEOUT, tokio::net::UnixStream::connect(path))
+        .await
+        .map_err(|_| anyhow!("Timed out connecting to Kea control socket {}", path))?
+        .map_err(|e| anyhow!("Failed to connect to Kea control socket {}: {}", path, e))?;
+
+    timeout(
+        KEA_SOCKET_TIMEOUT,
+        stream.write_all(br#"{ "command": "lease4-get-all" }"#),
+    )
+    .await
+    .map_err(|_| anyhow!("Timed out sending command to Kea control socket {}", path))??;
+
+    // Kea closes the connection once the full response has been sent
+    let mut response = Vec::new();
+    timeout(
+        KEA_SOCKET_TIMEOUT,
+        (&mut stream)
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_end(&mut response),
+    )
+    .await
+    .map_err(|_| anyhow!("Timed out reading from Kea control socket {}", path))??;
+
+    if response.len() as u64 >= MAX_RESPONSE_BYTES {
+        return Err(anyhow!(
+            "Kea control socket {} response exceeds {} bytes",
+            path,
+            MAX_RESPONSE_BYTES
+        ));
+    }
+
+    String::from_utf8(response)
+        .map_err(|e| anyhow!("Kea control socket {} sent invalid UTF-8: {}", path, e))
+}
+
+#[cfg(not(unix))]
+async fn query_kea_control_socket(_path: &str) -> Result<String, Error> {
+    Err(anyhow!("Kea control sockets are only supported on Unix"))
+}
@@ -0,0 +1,205 @@
+use crate::daemon::discovery::service::base::{
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+};
+use crate::daemon::utils::base::DaemonUtils;
+use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
+use crate::server::discovery::r#impl::dns_zones::{
+    DnsZoneImportResponse, DnsZoneSource, zone_origin, zone_records,
+};
+use crate::server::discovery::r#impl::types::DiscoveryType;
+use crate::server::hosts::r#impl::api::DiscoveryDnsRecordsRequest;
+use crate::server::subnets::r#impl::base::Subnet;
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use hickory_proto::op::{Message, MessageType, OpCode, Query, ResponseCode};
+use hickory_proto::rr::{Record, RecordType};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+/// How long to wait for the connection and for each message of the transfer
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the whole transfer may take, however steadily the server keeps sending
+const TRANSFER_DEADLINE: Duration = Duration::from_secs(300);
+/// Transfers past this many records are abandoned rather than held in memory
+const MAX_TRANSFER_RECORDS: usize = 500_000;
+
+pub struct DnsZoneDiscovery {
+    source: DnsZoneSource,
+}
+
+impl DnsZoneDiscovery {
+    pub fn new(source: DnsZoneSource) -> Self {
+        Self { source }
+    }
+}
+
+impl CreatesDiscoveredEntities for DiscoveryRunner<DnsZoneDiscovery> {}
+
+#[async_trait]
+impl RunsDiscovery for DiscoveryRunner<DnsZoneDiscovery> {
+    fn discovery_type(&self) -> DiscoveryType {
+        DiscoveryType::DnsZone {
+            source: self.domain.source.clone(),
+        }
+    }
+
+    async fn discover(
+        &self,
+        request: DaemonDiscoveryRequest,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        self.start_discovery(request).await?;
+
+        let discovery_result = self.import_zone(cancel.clone()).await;
+
+        self.finish_discovery(discovery_result, cancel.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscoversNetworkedEntities for DiscoveryRunner<DnsZoneDiscovery> {
+    async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
+        self.as_ref()
+            .utils
+            .get_own_routing_table_gateway_ips()
+            .await
+    }
+
+    /// Zone records only name hosts that were already discovered, they never create subnets
+    async fn discover_create_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        self.get_subnets().await
+    }
+}
+
+impl DiscoveryRunner<DnsZoneDiscovery> {
+    async fn import_zone(&self, cancel: CancellationToken) -> Result<(), Error> {
+        let DnsZoneSource::Axfr { server, zone } = &self.domain.source else {
+            return Err(anyhow!(
+                "Uploaded zone files are imported by the server, not by daemons"
+            ));
+        };
+
+        let records = zone_records(&transfer_zone(server, zone).await?);
+
+        if cancel.is_cancelled() {
+            return Err(Error::msg("Discovery was cancelled"));
+        }
+
+        self.report_scanning_progress(50).await?;
+
+        let request = DiscoveryDnsRecordsRequest {
+            zone: zone.clone(),
+            records,
+        };
+
+        let response: DnsZoneImportResponse = self
+            .as_ref()
+            .api_client
+            .post(
+                "/api/v1/hosts/discovery/dns-records",
+                &request,
+                "Failed to report DNS zone records",
+            )
+            .await?;
+
+        tracing::info!(
+            zone = %zone,
+            records = request.records.len(),
+            matched = response.matched,
+            dangling = response.dangling.len(),
+            "DNS zone import complete"
+        );
+
+        // Sent with the session's remaining updates so they show up alongside the results
+        let session = self.as_ref().get_session().await?;
+        *session.dangling_records.lock().unwrap() = response.dangling;
+
+        Ok(())
+    }
+}
+
+/// Transfer a zone over TCP (RFC 5936). The server streams the zone as a series of
+/// messages which starts and ends with the zone's SOA record.
+async fn transfer_zone(server: &str, zone: &str) -> Result<Vec<Record>, Error> {
+    let address = match server.parse::<IpAddr>() {
+        Ok(ip) => SocketAddr::new(ip, 53).to_string(),
+        Err(_) if server.contains(':') => server.to_string(),
+        Err(_) => format!("{}:53", server),
+    };
+
+    let mut stream = timeout(TRANSFER_TIMEOUT, TcpStream::connect(&address))
+        .await
+        .map_err(|_| anyhow!("Timed out connecting to DNS server {}", address))?
+        .map_err(|e| anyhow!("Failed to connect to DNS server {}: {}", address, e))?;
+
+    let mut query = Message::new();
+    query
+        .set_id(fastrand::u16(..))
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .add_query(Query::query(zone_origin(zone)?, RecordType::AXFR));
+
+    let query = query.to_vec()?;
+
+    let transfer = async {
+        stream.write_u16(query.len() as u16).await?;
+        stream.write_all(&query).await?;
+
+        let mut records = Vec::new();
+        let mut soa_records = 0;
+
+        while soa_records < 2 {
+            let length = timeout(TRANSFER_TIMEOUT, stream.read_u16()).await??;
+            let mut buffer = vec![0; length as usize];
+            timeout(TRANSFER_TIMEOUT, stream.read_exact(&mut buffer)).await??;
+
+            let mut response = Message::from_vec(&buffer)?;
+            if response.response_code() != ResponseCode::NoError {
+                return Err(anyhow!(
+                    "{} refused the transfer of {}: {}",
+                    address,
+                    zone,
+                    response.response_code()
+                ));
+            }
+
+            let answers = response.take_answers();
+            if answers.is_empty() {
+                return Err(anyhow!("{} ended the transfer of {} early", address, zone));
+            }
+
+            soa_records += answers
+                .iter()
+                .filter(|r| r.record_type() == RecordType::SOA)
+                .count();
+            records.extend(answers);
+
+            if records.len() > MAX_TRANSFER_RECORDS {
+                return Err(anyhow!(
+                    "Transfer of {} from {} exceeds {} records",
+                    zone,
+                    address,
+                    MAX_TRANSFER_RECORDS
+                ));
+            }
+        }
+
+        Ok(records)
+    };
+
+    timeout(TRANSFER_DEADLINE, transfer).await.map_err(|_| {
+        anyhow!(
+            "Transfer of {} from {} took longer than {}s",
+            zone,
+            address,
+            TRANSFER_DEADLINE.as_secs()
+        )
+    })?
+}
@@ -3,28 +3,44 @@ use anyhow::{Error, Result};
 use async_trait::async_trait;
 use bollard::{
     Docker,
-    query_parameters::{InspectContainerOptions, ListContainersOptions, ListNetworksOptions},
+    query_parameters::{
+        InspectContainerOptions, ListContainersOptions, ListNetworksOptions, ListNodesOptions,
+        ListServicesOptions, ListTasksOptions,
+    },
     secret::{ContainerInspectResponse, ContainerSummary, PortTypeEnum},
 };
 use cidr::IpCidr;
 use futures::future::try_join_all;
 use futures::stream::{self, StreamExt};
+use std::fmt::Display;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::{collections::HashMap, net::IpAddr, sync::OnceLock};
+use std::{collections::HashMap, net::IpAddr, sync::RwLock};
 use strum::IntoDiscriminant;
+use tokio::net::lookup_host;
 use tokio_util::sync::CancellationToken;
 
-use crate::daemon::discovery::service::base::RunsDiscovery;
+use crate::daemon::discovery::service::base::{RunsDiscovery, certificates_for_ports};
 use crate::daemon::discovery::types::base::DiscoverySessionUpdate;
 use crate::daemon::utils::base::DaemonUtils;
-use crate::daemon::utils::scanner::scan_endpoints;
+use crate::daemon::utils::docker_endpoint::DockerEndpoint;
+use crate::daemon::utils::docker_labels::{
+    ContainerLabelOverrides, HIDDEN_LABEL, TAGS_LABEL, container_binding_id, label_groups,
+};
+use crate::daemon::utils::docker_swarm::{SwarmNode, remote_swarm_nodes};
+use crate::daemon::utils::scanner::{
+    SCAN_TIMEOUT, banner_ports, scan_banners, scan_certificates, scan_endpoints, tls_ports,
+};
 use crate::server::bindings::r#impl::base::{Binding, BindingDiscriminants};
 use crate::server::discovery::r#impl::types::{DiscoveryType, HostNamingFallback};
+use crate::server::groups::r#impl::base::{Group, GroupBase};
 use crate::server::hosts::r#impl::base::HostBase;
 use crate::server::interfaces::r#impl::base::ALL_INTERFACES_IP;
 use crate::server::ports::r#impl::base::Port;
+use crate::server::services::definitions::docker_daemon::Docker as DockerDaemon;
+use crate::server::services::definitions::docker_swarm::DockerSwarm;
+use crate::server::services::definitions::podman::Podman;
 use crate::server::services::r#impl::base::{Service, ServiceBase, ServiceMatchBaselineParams};
 use crate::server::services::r#impl::definitions::ServiceDefinition;
 use crate::server::services::r#impl::endpoints::{Endpoint, EndpointResponse};
@@ -34,6 +50,7 @@ use crate::server::services::r#impl::virtualization::{
 };
 use crate::server::shared::storage::traits::Storable;
 use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource};
+use crate::server::shared::types::metadata::{EntityMetadataProvider, HasId};
 use crate::server::subnets::r#impl::base::Subnet;
 use crate::server::subnets::r#impl::types::SubnetTypeDiscriminants;
 use crate::{
@@ -51,9 +68,15 @@ use mac_address::MacAddress;
 use uuid::Uuid;
 
 type IpPortHashMap = HashMap<IpAddr, Vec<PortType>>;
+/// Container labels by container ID, and the hosts and services created for containers
+type EngineDiscovery = (
+    HashMap<String, ContainerLabelOverrides>,
+    Vec<(Host, Vec<Service>)>,
+);
 
 pub struct DockerScanDiscovery {
-    docker_client: OnceLock<Docker>,
+    /// Engine being discovered, set for each configured endpoint in turn
+    engine: RwLock<Option<DockerEngine>>,
     host_id: Uuid,
     host_naming_fallback: HostNamingFallback,
 }
@@ -63,6 +86,8 @@ pub struct ProcessContainerParams<'a> {
     pub container: &'a ContainerInspectResponse,
     pub container_summary: &'a ContainerSummary,
     pub docker_service_id: &'a Uuid,
+    pub label_overrides: &'a HashMap<String, ContainerLabelOverrides>,
+    pub tag_ids_by_name: &'a HashMap<String, Uuid>,
     pub cancel: CancellationToken,
 }
 
@@ -70,7 +95,7 @@ pub struct ProcessContainerParams<'a> {
 impl RunsDiscovery for DiscoveryRunner<DockerScanDiscovery> {
     fn discovery_type(&self) -> DiscoveryType {
         DiscoveryType::Docker {
-            host_id: self.domain.host_id,
+            host_id: self.domain.engine_host_id(),
             host_naming_fallback: self.domain.host_naming_fallback,
         }
     }
@@ -80,89 +105,43 @@ impl RunsDiscovery for DiscoveryRunner<DockerScanDiscovery> {
         request: DaemonDiscoveryRequest,
         cancel: CancellationToken,
     ) -> Result<(), Error> {
-        let daemon_id = self.as_ref().config_store.get_id().await?;
-        let network_id = self
-            .as_ref()
-            .config_store
-            .get_network_id()
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Network ID not set"))?;
-
-        let docker_proxy = self.as_ref().config_store.get_docker_proxy().await;
-        let docker_proxy_ssl_info = self.as_ref().config_store.get_docker_proxy_ssl_info().await;
-
-        let docker = self
-            .as_ref()
-            .utils
-            .new_local_docker_client(docker_proxy, docker_proxy_ssl_info)
-            .await?;
-        self.domain
-            .docker_client
-            .set(docker.clone())
-            .map_err(|_| anyhow!("Failed to set docker client"))?;
-
-        let container_list = self.get_containers_to_scan().await?;
+        let engines = self.connect_engines().await?;
 
         self.start_discovery(request).await?;
 
-        // Get and create docker and host subnets
-        let subnets = self.discover_create_subnets().await?;
+        let mut label_overrides: HashMap<String, ContainerLabelOverrides> = HashMap::new();
+        let mut container_data: Vec<(Host, Vec<Service>)> = Vec::new();
+        let mut failed = 0;
 
-        // Get host interfaces (needed for docker daemon service host matching)
-        let interface_filter = self.as_ref().config_store.get_interface_filter().await?;
-        let (mut host_interfaces, _, _) = self
-            .as_ref()
-            .utils
-            .get_own_interfaces(
-                self.discovery_type(),
-                daemon_id,
-                network_id,
-                &interface_filter,
-            )
-            .await?;
+        for engine in &engines {
+            if cancel.is_cancelled() {
+                break;
+            }
 
-        // Update interface subnet IDs to match created subnets (they may differ if subnets already existed)
-        for interface in &mut host_interfaces {
-            if let Some(subnet) = subnets
-                .iter()
-                .find(|s| s.base.cidr.contains(&interface.base.ip_address))
-            {
-                interface.base.subnet_id = subnet.id;
+            match self.discover_engine(engine.clone(), cancel.clone()).await {
+                Ok((engine_overrides, mut engine_data)) => {
+                    label_overrides.extend(engine_overrides);
+                    container_data.append(&mut engine_data);
+                }
+                Err(e) => {
+                    tracing::warn!(engine = %engine, error = %e, "Container engine discovery failed");
+                    failed += 1;
+                }
             }
         }
 
-        // Create service for docker daemon (pass interfaces for proper host matching)
-        let (_, services) = self.create_docker_daemon_service(&host_interfaces).await?;
-
-        let docker_daemon_service = services
-            .first()
-            .ok_or_else(|| anyhow!("Docker daemon service was not created, aborting"))?;
-
-        // Get container info
-        let containers = self.get_containers_and_summaries().await?;
-
-        // Combine host interfaces + subnets to get a map of containers to the interfaces they have + subnets those interfaces are for
-        let containers_interfaces_and_subnets =
-            self.get_container_interfaces(&containers, &subnets, &mut host_interfaces);
-
-        let discovered_hosts_services = self
-            .scan_and_process_containers(
-                cancel.clone(),
-                containers,
-                &containers_interfaces_and_subnets,
-                &docker_daemon_service.id,
-            )
-            .await;
+        // Groups and the session itself belong to the daemon's own host
+        self.domain.set_engine(None);
 
-        if let Ok(ref container_data) = discovered_hosts_services {
-            tracing::info!(
-                total_containers = %container_list.len(),
-                discovered = %container_data.len(),
-                "Docker scan complete"
-            );
+        if failed < engines.len()
+            && let Err(e) = self
+                .create_label_groups(&label_overrides, &container_data)
+                .await
+        {
+            tracing::warn!(error = %e, "Failed to create groups from container labels");
         }
 
-        let discovery_result = if discovered_hosts_services.is_ok() {
+        let discovery_result = if failed < engines.len() {
             Ok(())
         } else {
             Err(anyhow::Error::msg(""))
@@ -175,14 +154,67 @@ impl RunsDiscovery for DiscoveryRunner<DockerScanDiscovery> {
     }
 }
 
+/// A container engine and the host its containers are attributed to
+#[derive(Clone)]
+struct DockerEngine {
+    client: Docker,
+    host_id: Uuid,
+    /// Docker or Podman
+    definition: Box<dyn ServiceDefinition>,
+    /// `None` for the local socket or `docker_proxy`
+    endpoint: Option<DockerEndpoint>,
+    /// Set for engines on another host
+    remote: Option<RemoteEngineHost>,
+}
+
+#[derive(Clone)]
+struct RemoteEngineHost {
+    ip: IpAddr,
+    name: String,
+}
+
+impl Display for DockerEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.endpoint {
+            Some(endpoint) => write!(f, "{}", endpoint),
+            None => write!(f, "local"),
+        }
+    }
+}
+
 impl DockerScanDiscovery {
     pub fn new(host_id: Uuid, host_naming_fallback: HostNamingFallback) -> Self {
         Self {
-            docker_client: OnceLock::new(),
+            engine: RwLock::new(None),
             host_id,
             host_naming_fallback,
         }
     }
+
+    fn engine(&self) -> Result<DockerEngine, Error> {
+        self.engine
+            .read()
+            .ok()
+            .and_then(|engine| engine.clone())
+            .ok_or_else(|| anyhow!("Docker client unavailable"))
+    }
+
+    fn set_engine(&self, engine: Option<DockerEngine>) {
+        if let Ok(mut current) = self.engine.write() {
+            *current = engine;
+        }
+    }
+
+    fn docker_client(&self) -> Result<Docker, Error> {
+        Ok(self.engine()?.client)
+    }
+
+    /// Host containers of the current engine are attributed to
+    fn engine_host_id(&self) -> Uuid {
+        self.engine()
+            .map(|engine| engine.host_id)
+            .unwrap_or(self.host_id)
+    }
 }
 
 impl CreatesDiscoveredEntities for DiscoveryRunner<DockerScanDiscovery> {}
@@ -190,11 +222,7 @@ impl CreatesDiscoveredEntities for DiscoveryRunner<DockerScanDiscovery> {}
 #[async_trait]
 impl DiscoversNetworkedEntities for DiscoveryRunner<DockerScanDiscovery> {
     async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
-        let docker = self
-            .domain
-            .docker_client
-            .get()
-            .ok_or_else(|| anyhow!("Docker client unavailable"))?;
+        let docker = self.domain.docker_client()?;
 
         let gateway_ips: Vec<IpAddr> = docker
             .list_networks(None::<ListNetworksOptions>)
@@ -230,28 +258,38 @@ impl DiscoversNetworkedEntities for DiscoveryRunner<DockerScanDiscovery> {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Network ID not set"))?;
 
-        let interface_filter = self.as_ref().config_store.get_interface_filter().await?;
-        let (_, host_subnets, _) = self
-            .as_ref()
-            .utils
-            .get_own_interfaces(
-                self.discovery_type(),
-                daemon_id,
-                network_id,
-                &interface_filter,
-            )
-            .await?;
+        let engine = self.domain.engine()?;
 
-        let docker = self
-            .domain
-            .docker_client
-            .get()
-            .ok_or_else(|| anyhow!("Docker client unavailable"))?;
+        // A remote engine's host must be on a subnet that was already discovered
+        let host_subnets: Vec<Subnet> = match &engine.remote {
+            Some(remote) => self
+                .get_subnets()
+                .await?
+                .into_iter()
+                .filter(|s| s.base.cidr.contains(&remote.ip))
+                .collect(),
+            None => {
+                let interface_filter = self.as_ref().config_store.get_interface_filter().await?;
+                let (_, host_subnets, _) = self
+                    .as_ref()
+                    .utils
+                    .get_own_interfaces(
+                        self.discovery_type(),
+                        daemon_id,
+                        network_id,
+                        &interface_filter,
+                    )
+                    .await?;
+                host_subnets
+            }
+        };
+
+        let docker = engine.client;
 
         let docker_subnets = self
             .as_ref()
             .utils
-            .get_subnets_from_docker_networks(daemon_id, network_id, docker, self.discovery_type())
+            .get_subnets_from_docker_networks(daemon_id, network_id, &docker, self.discovery_type())
             .await?;
 
         // Extract host CIDRs - host interfaces take precedence over Docker networks
@@ -275,6 +313,288 @@ impl DiscoversNetworkedEntities for DiscoveryRunner<DockerScanDiscovery> {
 }
 
 impl DiscoveryRunner<DockerScanDiscovery> {
+    /// Connect to the local Docker socket or proxy, and to every configured endpoint.
+    /// Engines that can't be reached are skipped, it's an error only if none can be.
+    async fn connect_engines(&self) -> Result<Vec<DockerEngine>, Error> {
+        let config_store = &self.as_ref().config_store;
+        let utils = &self.as_ref().utils;
+
+        let endpoints = config_store.get_docker_endpoints().await?;
+        let mut engines = Vec::new();
+
+        match utils
+            .new_local_docker_client(
+                config_store.get_docker_proxy().await,
+                config_store.get_docker_proxy_ssl_info().await,
+            )
+            .await
+        {
+            Ok(client) => engines.push(DockerEngine {
+                definition: engine_definition(&client).await,
+                client,
+                host_id: self.domain.host_id,
+                endpoint: None,
+                remote: None,
+            }),
+            Err(e) if !endpoints.is_empty() => {
+                tracing::warn!(error = %e, "Local Docker unavailable, discovering configured endpoints only");
+            }
+            Err(e) => return Err(e),
+        }
+
+        for endpoint in endpoints {
+            let client = match utils
+                .new_docker_client(&endpoint, config_store.get_docker_proxy_ssl_info().await)
+                .await
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!(endpoint = %endpoint, error = %e, "Skipping unreachable Docker endpoint");
+                    continue;
+                }
+            };
+
+            let remote = match endpoint.remote_host() {
+                Some((host, port)) => {
+                    let Some(ip) = lookup_host((host, port))
+                        .await
+                        .ok()
+                        .and_then(|mut addrs| addrs.next())
+                        .map(|addr| addr.ip())
+                    else {
+                        tracing::warn!(endpoint = %endpoint, "Skipping Docker endpoint, couldn't resolve its host");
+                        continue;
+                    };
+
+                    let name = client
+                        .info()
+                        .await
+                        .ok()
+                        .and_then(|info| info.name)
+                        .unwrap_or_else(|| host.to_string());
+
+                    Some(RemoteEngineHost { ip, name })
+                }
+                None => None,
+            };
+
+            engines.push(DockerEngine {
+                definition: engine_definition(&client).await,
+                client,
+                // Remote engines move to their own host once it's created
+                host_id: self.domain.host_id,
+                endpoint: Some(endpoint),
+                remote,
+            });
+        }
+
+        if engines.is_empty() {
+            return Err(anyhow!(
+                "Couldn't connect to any of the configured Docker endpoints"
+            ));
+        }
+
+        Ok(engines)
+    }
+
+    /// Discover the containers of one engine
+    async fn discover_engine(
+        &self,
+        mut engine: DockerEngine,
+        cancel: CancellationToken,
+    ) -> Result<EngineDiscovery, Error> {
+        self.domain.set_engine(Some(engine.clone()));
+
+        let daemon_id = self.as_ref().config_store.get_id().await?;
+        let network_id = self
+            .as_ref()
+            .config_store
+            .get_network_id()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Network ID not set"))?;
+
+        let container_list = self.get_containers_to_scan().await?;
+
+        // A remote engine's host is created first, so Docker subnets are attributed to it
+        let remote_interface = match &engine.remote {
+            Some(remote) => {
+                let subnet = self
+                    .get_subnets()
+                    .await?
+                    .into_iter()
+                    .find(|s| s.base.cidr.contains(&remote.ip))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "{} isn't in a known subnet, run a network discovery on its network first",
+                            remote.ip
+                        )
+                    })?;
+
+                Some(Interface::new(InterfaceBase {
+                    network_id,
+                    host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                    name: None,
+                    subnet_id: subnet.id,
+                    ip_address: remote.ip,
+                    mac_address: None,
+                    position: 0,
+                    dns_names: Vec::new(),
+                }))
+            }
+            None => None,
+        };
+
+        let remote_engine_services = match &remote_interface {
+            Some(interface) => {
+                let (engine_host, services) = self
+                    .create_docker_daemon_service(std::slice::from_ref(interface))
+                    .await?;
+                engine.host_id = engine_host.id;
+                self.domain.set_engine(Some(engine.clone()));
+                Some((engine_host, services))
+            }
+            None => None,
+        };
+
+        // Get and create docker and host subnets
+        let subnets = self.discover_create_subnets().await?;
+
+        // Get host interfaces (needed for docker daemon service host matching)
+        let mut host_interfaces = match remote_interface {
+            Some(interface) => vec![interface],
+            None => {
+                let interface_filter = self.as_ref().config_store.get_interface_filter().await?;
+                let (host_interfaces, _, _) = self
+                    .as_ref()
+                    .utils
+                    .get_own_interfaces(
+                        self.discovery_type(),
+                        daemon_id,
+                        network_id,
+                        &interface_filter,
+                    )
+                    .await?;
+                host_interfaces
+            }
+        };
+
+        // Update interface subnet IDs to match created subnets (they may differ if subnets already existed)
+        for interface in &mut host_interfaces {
+            if let Some(subnet) = subnets
+                .iter()
+                .find(|s| s.base.cidr.contains(&interface.base.ip_address))
+            {
+                interface.base.subnet_id = subnet.id;
+            }
+        }
+
+        // Create service for docker daemon (pass interfaces for proper host matching)
+        let (engine_host, services) = match remote_engine_services {
+            Some(engine_services) => engine_services,
+            None => self.create_docker_daemon_service(&host_interfaces).await?,
+        };
+
+        let docker_daemon_service = services
+            .iter()
+            .find(|s| s.base.service_definition.id() == engine.definition.id())
+            .ok_or_else(|| anyhow!("Docker daemon service was not created, aborting"))?;
+
+        // Get container info
+        let containers = self.get_containers_and_summaries().await?;
+
+        // Swarm tasks on other nodes are only visible through a manager
+        let swarm_nodes = self.get_remote_swarm_nodes().await;
+
+        // scanopy.* labels by container ID
+        let mut label_overrides: HashMap<String, ContainerLabelOverrides> = containers
+            .iter()
+            .filter_map(|(container, _)| {
+                let labels = container.config.as_ref().and_then(|c| c.labels.as_ref());
+                Some((
+                    container.id.clone()?,
+                    ContainerLabelOverrides::from_labels(
+                        container.name.as_deref().unwrap_or_default(),
+                        labels,
+                    )
+                    .with_host(&engine_host.base.name),
+                ))
+            })
+            .collect();
+
+        // Stacks span the swarm, they're grouped under the manager listing them
+        for swarm_service in swarm_nodes.iter().flat_map(|node| node.services.iter()) {
+            label_overrides.insert(
+                swarm_service.container_id.clone(),
+                ContainerLabelOverrides::from_labels(
+                    &swarm_service.container_name,
+                    Some(&swarm_service.labels),
+                )
+                .with_host(&engine_host.base.name),
+            );
+        }
+
+        let containers: Vec<(ContainerInspectResponse, ContainerSummary)> = containers
+            .into_iter()
+            .filter(|(container, _)| {
+                let hidden = container
+                    .id
+                    .as_ref()
+                    .and_then(|id| label_overrides.get(id))
+                    .is_some_and(|overrides| overrides.hidden);
+                if hidden {
+                    tracing::debug!(
+                        container = ?container.name,
+                        "Skipping container labelled {}",
+                        HIDDEN_LABEL
+                    );
+                }
+                !hidden
+            })
+            .collect();
+
+        let tag_ids_by_name = self.get_label_tag_ids(&label_overrides).await;
+
+        // Combine host interfaces + subnets to get a map of containers to the interfaces they have + subnets those interfaces are for
+        let containers_interfaces_and_subnets =
+            self.get_container_interfaces(&containers, &subnets, &mut host_interfaces);
+
+        let mut container_data = self
+            .scan_and_process_containers(
+                cancel.clone(),
+                containers,
+                &containers_interfaces_and_subnets,
+                &docker_daemon_service.id,
+                &label_overrides,
+                &tag_ids_by_name,
+            )
+            .await?;
+
+        tracing::info!(
+            engine = %engine,
+            total_containers = %container_list.len(),
+            discovered = %container_data.len(),
+            "Docker scan complete"
+        );
+
+        for node in &swarm_nodes {
+            if cancel.is_cancelled() {
+                break;
+            }
+
+            match self
+                .discover_swarm_node(node, &subnets, &label_overrides, &tag_ids_by_name)
+                .await
+            {
+                Ok(mut node_data) => container_data.append(&mut node_data),
+                Err(e) => {
+                    tracing::warn!(node = %node.hostname, error = %e, "Failed to discover swarm node");
+                }
+            }
+        }
+
+        Ok((label_overrides, container_data))
+    }
+
     /// Create docker daemon service which has container relationship with docker daemon service
     /// Takes host_interfaces to enable proper host matching via MAC/IP addresses
     pub async fn create_docker_daemon_service(
@@ -289,32 +609,63 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Network ID not set"))?;
 
-        let host_id = self.domain.host_id;
+        let engine = self.domain.engine()?;
+        // A remote engine's host is matched on its interface by the server, or created
+        let host_id = match engine.remote {
+            Some(_) => Uuid::new_v4(),
+            None => engine.host_id,
+        };
 
-        let docker_service_definition = crate::server::services::definitions::docker_daemon::Docker;
+        let (metadata, details) = match &engine.endpoint {
+            Some(endpoint) => (
+                DiscoveryMetadata::new(self.discovery_type(), daemon_id),
+                MatchDetails::new_certain(&format!(
+                    "{} API at {}",
+                    ServiceDefinition::name(&engine.definition),
+                    endpoint
+                )),
+            ),
+            None => (
+                DiscoveryMetadata::new(DiscoveryType::SelfReport { host_id }, daemon_id),
+                MatchDetails::new_certain("Docker daemon self-report"),
+            ),
+        };
+
+        // Remote engines are reached on a TCP port of their host
+        let mut ports = Vec::new();
+        let mut bindings = Vec::new();
+        if let Some((_, port)) = engine.endpoint.as_ref().and_then(|e| e.remote_host())
+            && let Some(interface) = host_interfaces.first()
+        {
+            let port = Port::new_hostless(PortType::new_tcp(port));
+            bindings.push(Binding::new_port_serviceless(port.id, Some(interface.id)));
+            ports.push(port);
+        }
 
         let docker_service = Service::new(ServiceBase {
-            name: ServiceDefinition::name(&docker_service_definition).to_string(),
-            service_definition: Box::new(docker_service_definition),
-            bindings: vec![],
+            name: ServiceDefinition::name(&engine.definition).to_string(),
+            service_definition: engine.definition.clone(),
+            bindings,
             host_id,
             tags: Vec::new(),
             network_id,
             virtualization: None,
             source: EntitySource::DiscoveryWithMatch {
-                metadata: vec![DiscoveryMetadata::new(
-                    DiscoveryType::SelfReport { host_id },
-                    daemon_id,
-                )],
-                details: MatchDetails::new_certain("Docker daemon self-report"),
+                metadata: vec![metadata],
+                details,
             },
             position: 0,
         });
 
+        let (name, hostname) = match &engine.remote {
+            Some(remote) => (remote.name.clone(), Some(remote.name.clone())),
+            None => ("Docker Daemon Host".to_string(), None),
+        };
+
         let mut temp_docker_daemon_host = Host::new(HostBase {
-            name: "Docker Daemon Host".to_string(),
+            name,
             network_id,
-            hostname: None,
+            hostname,
             description: None,
             source: EntitySource::Discovery {
                 metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
@@ -322,16 +673,18 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             virtualization: None,
             hidden: false,
             tags: Vec::new(),
+            physical_links: Vec::new(),
         });
-        temp_docker_daemon_host.id = self.domain.host_id;
+        temp_docker_daemon_host.id = host_id;
 
         // Pass host_interfaces separately - server will create them with the correct host_id
         let host_response = self
             .create_host(
                 temp_docker_daemon_host,
                 host_interfaces.to_vec(),
-                vec![], // No ports for docker daemon host
+                ports,
                 vec![docker_service],
+                vec![],
             )
             .await?;
 
@@ -344,6 +697,8 @@ impl DiscoveryRunner<DockerScanDiscovery> {
         containers: Vec<(ContainerInspectResponse, ContainerSummary)>,
         containers_interfaces_and_subnets: &HashMap<String, Vec<(Interface, Subnet)>>,
         docker_service_id: &Uuid,
+        label_overrides: &HashMap<String, ContainerLabelOverrides>,
+        tag_ids_by_name: &HashMap<String, Uuid>,
     ) -> Result<Vec<(Host, Vec<Service>)>> {
         let total_containers = containers.len();
 
@@ -369,6 +724,8 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                             container: &container,
                             container_summary: &container_summary,
                             docker_service_id,
+                            label_overrides,
+                            tag_ids_by_name,
                             cancel,
                         })
                         .await;
@@ -462,6 +819,8 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             container,
             cancel,
             docker_service_id,
+            label_overrides,
+            tag_ids_by_name,
             ..
         } = params;
 
@@ -473,7 +832,10 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                 .unwrap_or(&"Unknown Container Name".to_string())
         );
 
-        let host_ip = self.as_ref().utils.get_own_ip_address()?;
+        let host_ip = match self.domain.engine()?.remote {
+            Some(remote) => remote.ip,
+            None => self.as_ref().utils.get_own_ip_address()?,
+        };
 
         if let Some(Some(p)) = container.config.as_ref().map(|c| c.exposed_ports.as_ref()) {
             let open_ports: Vec<PortType> = p
@@ -490,11 +852,31 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                 Some(open_ports.clone()),
                 None,
                 port_scan_batch_size,
+                SCAN_TIMEOUT,
             ))
             .await
             .map_err(|e| anyhow!("Scan task panicked: {}", e))?
             .map_err(|e| anyhow!("Endpoint scanning error: {}", e))?;
 
+            let banner_responses = scan_banners(
+                host_ip,
+                cancel.clone(),
+                banner_ports(&open_ports, &endpoint_responses, &HashMap::new()),
+                port_scan_batch_size,
+                SCAN_TIMEOUT,
+            )
+            .await?;
+
+            let certificate_responses = scan_certificates(
+                host_ip,
+                cancel.clone(),
+                tls_ports(&open_ports, &endpoint_responses, &banner_responses),
+                port_scan_batch_size,
+                SCAN_TIMEOUT,
+                SCAN_TIMEOUT,
+            )
+            .await?;
+
             let empty_vec_ref = &vec![];
 
             let container_interfaces_and_subnets = containers_interfaces_and_subnets
@@ -507,6 +889,11 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                     interface,
                     all_ports: &open_ports,
                     endpoint_responses: &endpoint_responses,
+                    banner_responses: &banner_responses,
+                    certificate_responses: &certificate_responses,
+                    mdns_responses: &Vec::new(),
+                    upnp_responses: &Vec::new(),
+                    netbios_response: &None,
                     virtualization: &Some(ServiceVirtualization::Docker(DockerVirtualization {
                         container_name: container
                             .name
@@ -521,10 +908,18 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                     .process_host(params, None, self.domain.host_naming_fallback)
                     .await
                 {
-                    host.id = self.domain.host_id;
+                    host.id = self.domain.engine_host_id();
+                    let services = self.apply_container_labels(
+                        label_overrides,
+                        tag_ids_by_name,
+                        container_id,
+                        services,
+                    );
+                    let certificates = certificates_for_ports(&ports, &certificate_responses);
 
-                    if let Ok(host_response) =
-                        self.create_host(host, interfaces, ports, services).await
+                    if let Ok(host_response) = self
+                        .create_host(host, interfaces, ports, services, certificates)
+                        .await
                     {
                         return Ok::<Option<(Host, Vec<Service>)>, Error>(Some((
                             host_response.to_host(),
@@ -549,6 +944,8 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             container_summary,
             cancel,
             docker_service_id,
+            label_overrides,
+            tag_ids_by_name,
             ..
         } = params;
 
@@ -606,6 +1003,11 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                         interface,
                         all_ports: container_ports_on_interface,
                         endpoint_responses: &endpoint_responses,
+                        banner_responses: &Vec::new(),
+                        certificate_responses: &Vec::new(),
+                        mdns_responses: &Vec::new(),
+                        upnp_responses: &Vec::new(),
+                        netbios_response: &None,
                         virtualization: &Some(ServiceVirtualization::Docker(
                             DockerVirtualization {
                                 container_name: container
@@ -624,7 +1026,7 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             {
                 // Add information that we have from docker context to processed host + services
 
-                host.id = self.domain.host_id;
+                host.id = self.domain.engine_host_id();
 
                 // Add all interfaces relevant to container to the interfaces vec
                 container_interfaces_and_subnets.iter().for_each(|(i, _)| {
@@ -779,8 +1181,15 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                     });
                 });
 
+                let services = self.apply_container_labels(
+                    label_overrides,
+                    tag_ids_by_name,
+                    container_id,
+                    services,
+                );
+
                 if let Ok(host_response) = self
-                    .create_host(host, interfaces, ports, services.clone())
+                    .create_host(host, interfaces, ports, services, Vec::new())
                     .await
                 {
                     return Ok::<Option<(Host, Vec<Service>)>, Error>(Some((
@@ -795,12 +1204,329 @@ impl DiscoveryRunner<DockerScanDiscovery> {
         Ok(None)
     }
 
+    /// Nodes of the swarm other than this one with the services running on them.
+    /// Empty unless the Docker daemon is a swarm manager.
+    async fn get_remote_swarm_nodes(&self) -> Vec<SwarmNode> {
+        let Ok(docker) = self.domain.docker_client() else {
+            return Vec::new();
+        };
+
+        let swarm = match docker.info().await {
+            Ok(info) => info.swarm,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to get Docker system info");
+                return Vec::new();
+            }
+        };
+
+        let Some(local_node_id) = swarm
+            .filter(|swarm| swarm.control_available == Some(true))
+            .and_then(|swarm| swarm.node_id)
+        else {
+            return Vec::new();
+        };
+
+        let swarm_state = tokio::try_join!(
+            docker.list_nodes(None::<ListNodesOptions>),
+            docker.list_services(None::<ListServicesOptions>),
+            docker.list_tasks(None::<ListTasksOptions>),
+        );
+
+        match swarm_state {
+            Ok((nodes, services, tasks)) => {
+                let remote = remote_swarm_nodes(&local_node_id, &nodes, &services, &tasks);
+                tracing::info!(nodes = remote.len(), "Listed remote swarm nodes");
+                remote
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to list swarm nodes, services and tasks");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Create a host for a remote swarm node with its Docker daemon, then upsert the
+    /// swarm services with running tasks on it onto that host
+    async fn discover_swarm_node(
+        &self,
+        node: &SwarmNode,
+        subnets: &[Subnet],
+        label_overrides: &HashMap<String, ContainerLabelOverrides>,
+        tag_ids_by_name: &HashMap<String, Uuid>,
+    ) -> Result<Vec<(Host, Vec<Service>)>, Error> {
+        let Some(subnet) = subnets.iter().find(|s| s.base.cidr.contains(&node.ip)) else {
+            tracing::debug!(node = %node.hostname, ip = %node.ip, "Swarm node is outside known subnets, skipping");
+            return Ok(Vec::new());
+        };
+
+        let session = self.as_ref().get_session().await?;
+        let network_id = session.info.network_id;
+        let daemon_id = session.info.daemon_id;
+
+        let node_host = Host::new(HostBase {
+            name: node.hostname.clone(),
+            hostname: Some(node.hostname.clone()),
+            tags: Vec::new(),
+            network_id,
+            description: Some(if node.is_manager {
+                "Docker swarm manager".to_string()
+            } else {
+                "Docker swarm worker".to_string()
+            }),
+            source: EntitySource::Discovery {
+                metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
+            },
+            virtualization: None,
+            hidden: false,
+            physical_links: Vec::new(),
+        });
+
+        let interface = Interface::new(InterfaceBase {
+            network_id,
+            host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+            name: None,
+            subnet_id: subnet.id,
+            ip_address: node.ip,
+            mac_address: None,
+            position: 0,
+            dns_names: Vec::new(),
+        });
+
+        let source = EntitySource::DiscoveryWithMatch {
+            metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
+            details: MatchDetails::new_certain("Docker swarm node"),
+        };
+
+        let mut ports = Vec::new();
+        let mut services = vec![Service::new(ServiceBase {
+            name: ServiceDefinition::name(&DockerDaemon).to_string(),
+            service_definition: Box::new(DockerDaemon),
+            bindings: vec![Binding::new_interface_serviceless(interface.id)],
+            host_id: node_host.id,
+            tags: Vec::new(),
+            network_id,
+            virtualization: None,
+            source: source.clone(),
+            position: 0,
+        })];
+
+        if node.is_manager {
+            let port = Port::new_hostless(PortType::new_tcp(2377));
+            services.push(Service::new(ServiceBase {
+                name: ServiceDefinition::name(&DockerSwarm).to_string(),
+                service_definition: Box::new(DockerSwarm),
+                bindings: vec![Binding::new_port_serviceless(port.id, Some(interface.id))],
+                host_id: node_host.id,
+                tags: Vec::new(),
+                network_id,
+                virtualization: None,
+                source,
+                position: 1,
+            }));
+            ports.push(port);
+        }
+
+        let node_response = self
+            .create_host(node_host, vec![interface], ports, services, Vec::new())
+            .await?;
+
+        let docker_service_id = node_response
+            .services
+            .iter()
+            .find(|s| s.base.service_definition.id() == DockerDaemon.id())
+            .map(|s| s.id)
+            .ok_or_else(|| anyhow!("Docker daemon service was not created on swarm node"))?;
+
+        let Some(node_interface) = node_response
+            .interfaces
+            .iter()
+            .find(|i| i.base.ip_address == node.ip)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut container_data = Vec::new();
+
+        for swarm_service in &node.services {
+            let overrides = label_overrides.get(&swarm_service.container_id);
+            if overrides.is_some_and(|o| o.hidden) {
+                tracing::debug!(
+                    service = %swarm_service.name,
+                    "Skipping swarm service labelled {}",
+                    HIDDEN_LABEL
+                );
+                continue;
+            }
+
+            let virtualization = Some(ServiceVirtualization::Docker(DockerVirtualization {
+                container_name: Some(swarm_service.container_name.clone()),
+                container_id: Some(swarm_service.container_id.clone()),
+                service_id: docker_service_id,
+            }));
+
+            let Some((mut host, mut interfaces, ports, services)) = self
+                .process_host(
+                    ServiceMatchBaselineParams {
+                        subnet,
+                        interface: node_interface,
+                        all_ports: &swarm_service.published_ports,
+                        endpoint_responses: &Vec::new(),
+                        banner_responses: &Vec::new(),
+                        certificate_responses: &Vec::new(),
+                        mdns_responses: &Vec::new(),
+                        upnp_responses: &Vec::new(),
+                        netbios_response: &None,
+                        virtualization: &virtualization,
+                    },
+                    None,
+                    self.domain.host_naming_fallback,
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            // Swarm services are upserted onto their node's host
+            host.id = node_response.id;
+            host.base.name = node_response.name.clone();
+            host.base.hostname = node_response.hostname.clone();
+            host.base.description = node_response.description.clone();
+
+            for interface in &node_response.interfaces {
+                if !interfaces.contains(interface) {
+                    interfaces.push(interface.clone());
+                }
+            }
+
+            let services = self.apply_container_labels(
+                label_overrides,
+                tag_ids_by_name,
+                &swarm_service.container_id,
+                services,
+            );
+
+            let host_response = self
+                .create_host(host, interfaces, ports, services, Vec::new())
+                .await?;
+
+            container_data.push((host_response.to_host(), host_response.services));
+        }
+
+        Ok(container_data)
+    }
+
+    /// Tag IDs by lowercased name, fetched only when a container has a `scanopy.tags` label
+    async fn get_label_tag_ids(
+        &self,
+        label_overrides: &HashMap<String, ContainerLabelOverrides>,
+    ) -> HashMap<String, Uuid> {
+        if label_overrides.values().all(|o| o.tags.is_empty()) {
+            return HashMap::new();
+        }
+
+        match self.get_tags().await {
+            Ok(tags) => tags
+                .into_iter()
+                .map(|tag| (tag.base.name.to_lowercase(), tag.id))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to get tags, ignoring {} labels", TAGS_LABEL);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Apply a container's `scanopy.*` labels to the services discovered for it
+    fn apply_container_labels(
+        &self,
+        label_overrides: &HashMap<String, ContainerLabelOverrides>,
+        tag_ids_by_name: &HashMap<String, Uuid>,
+        container_id: &str,
+        services: Vec<Service>,
+    ) -> Vec<Service> {
+        let Some(overrides) = label_overrides.get(container_id) else {
+            return services;
+        };
+
+        let tag_ids: Vec<Uuid> = overrides
+            .tags
+            .iter()
+            .filter_map(|name| {
+                let tag_id = tag_ids_by_name.get(&name.to_lowercase()).copied();
+                if tag_id.is_none() {
+                    tracing::warn!(
+                        container = %overrides.container_name,
+                        tag = %name,
+                        "Tag in {} label doesn't exist, create it first",
+                        TAGS_LABEL
+                    );
+                }
+                tag_id
+            })
+            .collect();
+
+        overrides.apply_to_services(services, &tag_ids)
+    }
+
+    /// Create the groups declared through `scanopy.group` and `scanopy.depends_on` labels,
+    /// and one per compose project or swarm stack
+    async fn create_label_groups(
+        &self,
+        label_overrides: &HashMap<String, ContainerLabelOverrides>,
+        container_data: &[(Host, Vec<Service>)],
+    ) -> Result<(), Error> {
+        if label_overrides
+            .values()
+            .all(|o| o.group.is_none() && o.project.is_none() && o.depends_on.is_empty())
+        {
+            return Ok(());
+        }
+
+        let services: Vec<Service> = container_data
+            .iter()
+            .flat_map(|(_, services)| services.iter().cloned())
+            .collect();
+
+        let containers: Vec<(&ContainerLabelOverrides, Uuid)> = label_overrides
+            .iter()
+            .filter_map(|(container_id, overrides)| {
+                Some((overrides, container_binding_id(&services, container_id)?))
+            })
+            .collect();
+
+        let session = self.as_ref().get_session().await?;
+
+        for label_group in label_groups(&containers) {
+            let group = Group {
+                base: GroupBase {
+                    name: label_group.name,
+                    network_id: session.info.network_id,
+                    description: Some(label_group.description),
+                    group_type: label_group.group_type,
+                    binding_ids: label_group.binding_ids,
+                    source: EntitySource::Discovery {
+                        metadata: vec![DiscoveryMetadata::new(
+                            self.discovery_type(),
+                            session.info.daemon_id,
+                        )],
+                    },
+                    color: label_group.group_type.discriminant().color(),
+                    edge_style: Default::default(),
+                    tags: Vec::new(),
+                },
+                ..Default::default()
+            };
+
+            if let Err(e) = self.create_group(&group).await {
+                tracing::warn!(group = %group.base.name, error = %e, "Failed to create group from container labels");
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn get_containers_to_scan(&self) -> Result<Vec<ContainerSummary>, Error> {
-        let docker = self
-            .domain
-            .docker_client
-            .get()
-            .ok_or_else(|| anyhow!("Docker client unavailable"))?;
+        let docker = self.domain.docker_client()?;
 
         docker
             .list_containers(None::<ListContainersOptions>)
@@ -811,11 +1537,7 @@ impl DiscoveryRunner<DockerScanDiscovery> {
     pub async fn get_containers_and_summaries(
         &self,
     ) -> Result<Vec<(ContainerInspectResponse, ContainerSummary)>, Error> {
-        let docker = self
-            .domain
-            .docker_client
-            .get()
-            .ok_or_else(|| anyhow!("Docker client unavailable"))?;
+        let docker = self.domain.docker_client()?;
 
         let container_summaries = self.get_containers_to_scan().await?;
 
@@ -856,11 +1578,7 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                 .push((*host_ip, *host_port));
         }
 
-        let docker = self
-            .domain
-            .docker_client
-            .get()
-            .ok_or_else(|| anyhow!("Docker client unavailable"))?;
+        let docker = self.domain.docker_client()?;
 
         let all_endpoints = Service::all_discovery_endpoints();
 
@@ -1203,6 +1921,7 @@ impl DiscoveryRunner<DockerScanDiscovery> {
                                                 mac_address,
                                                 name: Some(network_name.to_owned()),
                                                 position: 0,
+                                                dns_names: Vec::new(),
                                             }),
                                             subnet.clone(),
                                         ));
@@ -1235,3 +1954,23 @@ impl DiscoveryRunner<DockerScanDiscovery> {
             .collect()
     }
 }
+
+/// Podman serves the Docker API too, it names itself in the version components
+async fn engine_definition(client: &Docker) -> Box<dyn ServiceDefinition> {
+    let is_podman = client
+        .version()
+        .await
+        .ok()
+        .and_then(|version| version.components)
+        .is_some_and(|components| {
+            components
+                .iter()
+                .any(|component| component.name.contains("Podman"))
+        });
+
+    if is_podman {
+        Box::new(Podman)
+    } else {
+        Box::new(DockerDaemon)
+    }
+}
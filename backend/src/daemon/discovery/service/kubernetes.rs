@@ -0,0 +1,1104 @@
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use cidr::IpCidr;
+use k8s_openapi::api::core::v1::{Node, Pod, Service as KubernetesService, ServicePort};
+use k8s_openapi::api::networking::v1::Ingress;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::{Api, ListParams};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use kube::{Client, Config};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
+use std::str::FromStr;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::daemon::discovery::service::base::{
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+};
+use crate::daemon::utils::base::DaemonUtils;
+use crate::server::bindings::r#impl::base::Binding;
+use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
+use crate::server::discovery::r#impl::types::{DiscoveryType, HostNamingFallback};
+use crate::server::groups::r#impl::base::{Group, GroupBase};
+use crate::server::groups::r#impl::types::{GroupType, GroupTypeDiscriminants};
+use crate::server::hosts::r#impl::api::HostResponse;
+use crate::server::hosts::r#impl::base::{Host, HostBase};
+use crate::server::interfaces::r#impl::base::{Interface, InterfaceBase};
+use crate::server::ports::r#impl::base::{Port, PortType};
+use crate::server::services::r#impl::base::{Service, ServiceMatchBaselineParams};
+use crate::server::services::r#impl::virtualization::{
+    KubernetesVirtualization, ServiceVirtualization,
+};
+use crate::server::shared::storage::traits::Storable;
+use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource};
+use crate::server::shared::types::metadata::EntityMetadataProvider;
+use crate::server::subnets::r#impl::base::{Subnet, SubnetBase};
+use crate::server::subnets::r#impl::types::SubnetType;
+
+pub struct KubernetesDiscovery {
+    kubeconfig_path: Option<String>,
+    context: Option<String>,
+}
+
+impl KubernetesDiscovery {
+    pub fn new(kubeconfig_path: Option<String>, context: Option<String>) -> Self {
+        Self {
+            kubeconfig_path,
+            context,
+        }
+    }
+}
+
+/// Everything listed from the cluster API in one pass
+struct ClusterState {
+    nodes: Vec<Node>,
+    pods: Vec<Pod>,
+    services: Vec<KubernetesService>,
+    ingresses: Vec<Ingress>,
+}
+
+/// A pod as it was stored, keyed by namespace and name, used to resolve ingress
+/// backends to bindings once every node has been processed
+struct DiscoveredPod {
+    ip: IpAddr,
+    labels: Option<BTreeMap<String, String>>,
+    host: HostResponse,
+}
+
+impl CreatesDiscoveredEntities for DiscoveryRunner<KubernetesDiscovery> {}
+
+#[async_trait]
+impl RunsDiscovery for DiscoveryRunner<KubernetesDiscovery> {
+    fn discovery_type(&self) -> DiscoveryType {
+        DiscoveryType::Kubernetes {
+            kubeconfig_path: self.domain.kubeconfig_path.clone(),
+            context: self.domain.context.clone(),
+        }
+    }
+
+    async fn discover(
+        &self,
+        request: DaemonDiscoveryRequest,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        self.start_discovery(request).await?;
+
+        let discovery_result = self.discover_cluster(cancel.clone()).await;
+
+        self.finish_discovery(discovery_result, cancel.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscoversNetworkedEntities for DiscoveryRunner<KubernetesDiscovery> {
+    async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
+        self.as_ref()
+            .utils
+            .get_own_routing_table_gateway_ips()
+            .await
+    }
+
+    /// Node addresses are only matched against subnets that already exist, the
+    /// cluster can span networks the daemon isn't attached to. Pod subnets are
+    /// created per node while the cluster is walked.
+    async fn discover_create_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        self.get_subnets().await
+    }
+}
+
+impl DiscoveryRunner<KubernetesDiscovery> {
+    async fn client(&self) -> Result<Client, Error> {
+        let config = match &self.domain.kubeconfig_path {
+            Some(path) => {
+                let kubeconfig = Kubeconfig::read_from(path)
+                    .map_err(|e| anyhow!("Failed to read kubeconfig {}: {}", path, e))?;
+                let options = KubeConfigOptions {
+                    context: self.domain.context.clone(),
+                    ..Default::default()
+                };
+                Config::from_custom_kubeconfig(kubeconfig, &options)
+                    .await
+                    .map_err(|e| anyhow!("Failed to load kubeconfig {}: {}", path, e))?
+            }
+            // In-cluster service account, or the default kubeconfig when run outside a cluster
+            None => Config::infer()
+                .await
+                .map_err(|e| anyhow!("Failed to infer Kubernetes config: {}", e))?,
+        };
+
+        Client::try_from(config).map_err(|e| anyhow!("Failed to create Kubernetes client: {}", e))
+    }
+
+    async fn list_cluster(&self, client: &Client) -> Result<ClusterState, Error> {
+        let params = ListParams::default();
+
+        let nodes = Api::<Node>::all(client.clone()).list(&params).await?.items;
+        let pods = Api::<Pod>::all(client.clone()).list(&params).await?.items;
+        let services = Api::<KubernetesService>::all(client.clone())
+            .list(&params)
+            .await?
+            .items;
+        let ingresses = Api::<Ingress>::all(client.clone())
+            .list(&params)
+            .await?
+            .items;
+
+        Ok(ClusterState {
+            nodes,
+            pods,
+            services,
+            ingresses,
+        })
+    }
+
+    async fn discover_cluster(&self, cancel: CancellationToken) -> Result<(), Error> {
+        let client = self.client().await?;
+        let cluster = self.list_cluster(&client).await?;
+
+        tracing::info!(
+            nodes = cluster.nodes.len(),
+            pods = cluster.pods.len(),
+            services = cluster.services.len(),
+            ingresses = cluster.ingresses.len(),
+            "Listed Kubernetes cluster"
+        );
+
+        self.report_scanning_progress(5).await?;
+
+        let subnets = self.discover_create_subnets().await?;
+        let total_nodes = cluster.nodes.len();
+        let mut discovered_pods: HashMap<(String, String), DiscoveredPod> = HashMap::new();
+
+        for (index, node) in cluster.nodes.iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery session was cancelled"));
+            }
+
+            match self.discover_node(node, &cluster, &subnets, &cancel).await {
+                Ok(pods) => discovered_pods.extend(pods),
+                Err(e) => {
+                    tracing::warn!(node = ?node.metadata.name, error = %e, "Failed to discover Kubernetes node");
+                }
+            }
+
+            self.report_scanning_progress((5 + (index + 1) * 85 / total_nodes.max(1)) as u8)
+                .await?;
+        }
+
+        for ingress in &cluster.ingresses {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery session was cancelled"));
+            }
+
+            for group in self
+                .ingress_groups(ingress, &cluster, &discovered_pods)
+                .await?
+            {
+                if let Err(e) = self.create_group(&group).await {
+                    tracing::warn!(group = %group.base.name, error = %e, "Failed to create ingress request path");
+                }
+            }
+        }
+
+        tracing::info!(
+            nodes = total_nodes,
+            pods = discovered_pods.len(),
+            "Kubernetes discovery complete"
+        );
+
+        Ok(())
+    }
+
+    /// Create the node's host and pod subnet, then upsert each pod running on the
+    /// node onto the same host
+    async fn discover_node(
+        &self,
+        node: &Node,
+        cluster: &ClusterState,
+        subnets: &[Subnet],
+        cancel: &CancellationToken,
+    ) -> Result<HashMap<(String, String), DiscoveredPod>, Error> {
+        let session = self.as_ref().get_session().await?;
+        let network_id = session.info.network_id;
+        let daemon_id = session.info.daemon_id;
+
+        let node_name = node
+            .metadata
+            .name
+            .clone()
+            .ok_or_else(|| anyhow!("Node has no name"))?;
+
+        let node_interfaces: Vec<Interface> = node
+            .status
+            .as_ref()
+            .and_then(|status| status.addresses.as_ref())
+            .into_iter()
+            .flatten()
+            .filter(|address| address.type_ == "InternalIP" || address.type_ == "ExternalIP")
+            .filter_map(|address| IpAddr::from_str(&address.address).ok())
+            .filter_map(|ip| {
+                subnets
+                    .iter()
+                    .find(|s| s.base.cidr.contains(&ip))
+                    .map(|subnet| (ip, subnet))
+            })
+            .enumerate()
+            .map(|(position, (ip, subnet))| {
+                Interface::new(InterfaceBase {
+                    network_id,
+                    host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                    name: None,
+                    subnet_id: subnet.id,
+                    ip_address: ip,
+                    mac_address: None,
+                    position: position as i32,
+                    dns_names: Vec::new(),
+                })
+            })
+            .collect();
+
+        if node_interfaces.is_empty() {
+            return Err(anyhow!(
+                "None of the node's addresses are in a known subnet, run a network or self report discovery on its network first"
+            ));
+        }
+
+        let mut pod_subnets = Vec::new();
+        for cidr in node_pod_cidrs(node) {
+            let subnet = Subnet::new(SubnetBase {
+                cidr,
+                network_id,
+                name: format!("{} pods", node_name),
+                description: Some(format!("Pod network on Kubernetes node {}", node_name)),
+                subnet_type: SubnetType::KubernetesPod,
+                source: EntitySource::Discovery {
+                    metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
+                },
+                tags: Vec::new(),
+            });
+            pod_subnets.push(self.create_subnet(&subnet).await?);
+        }
+
+        let node_host = Host::new(HostBase {
+            name: node_name.clone(),
+            hostname: Some(node_name.clone()),
+            tags: Vec::new(),
+            network_id,
+            description: node
+                .status
+                .as_ref()
+                .and_then(|status| status.node_info.as_ref())
+                .map(|info| format!("Kubernetes node, kubelet {}", info.kubelet_version)),
+            source: EntitySource::Discovery {
+                metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
+            },
+            virtualization: None,
+            hidden: false,
+            physical_links: Vec::new(),
+        });
+
+        let node_response = self
+            .create_host(
+                node_host,
+                node_interfaces,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            )
+            .await?;
+
+        let mut discovered_pods = HashMap::new();
+        // A node port is open on every node, but is only bound to the first pod behind it
+        let mut bound_node_ports: HashSet<PortType> = HashSet::new();
+
+        let node_pods = cluster.pods.iter().filter(|pod| {
+            pod.spec.as_ref().and_then(|spec| spec.node_name.as_deref()) == Some(&node_name)
+                && pod
+                    .status
+                    .as_ref()
+                    .and_then(|status| status.phase.as_deref())
+                    == Some("Running")
+        });
+
+        for pod in node_pods {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery session was cancelled"));
+            }
+
+            let (Some(namespace), Some(pod_name)) =
+                (pod.metadata.namespace.clone(), pod.metadata.name.clone())
+            else {
+                continue;
+            };
+
+            match self
+                .discover_pod(
+                    pod,
+                    cluster,
+                    &node_response,
+                    subnets,
+                    &pod_subnets,
+                    &mut bound_node_ports,
+                )
+                .await
+            {
+                Ok(Some(discovered)) => {
+                    discovered_pods.insert((namespace, pod_name), discovered);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(namespace = %namespace, pod = %pod_name, error = %e, "Failed to discover Kubernetes pod");
+                }
+            }
+        }
+
+        Ok(discovered_pods)
+    }
+
+    async fn discover_pod(
+        &self,
+        pod: &Pod,
+        cluster: &ClusterState,
+        node: &HostResponse,
+        subnets: &[Subnet],
+        pod_subnets: &[Subnet],
+        bound_node_ports: &mut HashSet<PortType>,
+    ) -> Result<Option<DiscoveredPod>, Error> {
+        let namespace = pod.metadata.namespace.clone().unwrap_or_default();
+        let pod_name = pod.metadata.name.clone().unwrap_or_default();
+
+        let Some(pod_ip) = pod
+            .status
+            .as_ref()
+            .and_then(|status| status.pod_ip.as_deref())
+            .and_then(|ip| IpAddr::from_str(ip).ok())
+        else {
+            return Ok(None);
+        };
+
+        let host_network = pod
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.host_network)
+            .unwrap_or(false);
+
+        // Host network pods share the node's interface, everything else gets an
+        // interface on the node's pod subnet
+        let (interface, subnet) = if host_network {
+            let Some(interface) = node.interfaces.iter().find(|i| i.base.ip_address == pod_ip)
+            else {
+                return Ok(None);
+            };
+            let Some(subnet) = subnets.iter().find(|s| s.id == interface.base.subnet_id) else {
+                return Ok(None);
+            };
+            (interface.clone(), subnet.clone())
+        } else {
+            let Some(subnet) = pod_subnets.iter().find(|s| s.base.cidr.contains(&pod_ip)) else {
+                tracing::debug!(namespace = %namespace, pod = %pod_name, ip = %pod_ip, "Pod IP is outside the node's pod CIDR, skipping");
+                return Ok(None);
+            };
+            let interface = Interface::new(InterfaceBase {
+                network_id: node.network_id,
+                host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                name: None,
+                subnet_id: subnet.id,
+                ip_address: pod_ip,
+                mac_address: None,
+                position: 0,
+                dns_names: Vec::new(),
+            });
+            (interface, subnet.clone())
+        };
+
+        let container_ports: Vec<PortType> =
+            pod_container_ports(pod).map(|(_, port, _)| port).collect();
+
+        let virtualization = Some(ServiceVirtualization::Kubernetes(
+            KubernetesVirtualization {
+                namespace: namespace.clone(),
+                pod: pod_name.clone(),
+                workload: pod_workload(pod),
+            },
+        ));
+
+        let Some((mut host, mut interfaces, mut ports, mut services)) = self
+            .process_host(
+                ServiceMatchBaselineParams {
+                    subnet: &subnet,
+                    interface: &interface,
+                    all_ports: &container_ports,
+                    endpoint_responses: &Vec::new(),
+                    banner_responses: &Vec::new(),
+                    certificate_responses: &Vec::new(),
+                    mdns_responses: &Vec::new(),
+                    upnp_responses: &Vec::new(),
+                    netbios_response: &None,
+                    virtualization: &virtualization,
+                },
+                None,
+                HostNamingFallback::BestService,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Pods are upserted onto their node's host
+        host.id = node.id;
+        host.base.name = node.name.clone();
+        host.base.hostname = node.hostname.clone();
+        host.base.description = node.description.clone();
+
+        for node_interface in &node.interfaces {
+            if !interfaces.contains(node_interface) {
+                interfaces.push(node_interface.clone());
+            }
+        }
+
+        // Ports the pod is reachable on through the node: container host ports,
+        // and the node ports of cluster services selecting the pod
+        let mut node_exposed: Vec<PortType> = pod_container_ports(pod)
+            .filter_map(|(_, _, host_port)| host_port)
+            .collect();
+
+        for service in selecting_services(pod, &cluster.services) {
+            for service_port in service_ports(service) {
+                if let Some(node_port) = service_port.node_port {
+                    let port_type = port_type(node_port, service_port.protocol.as_deref());
+                    if bound_node_ports.insert(port_type) {
+                        node_exposed.push(port_type);
+                    }
+                }
+            }
+        }
+
+        if let Some(service) = services.iter_mut().find(|s| {
+            matches!(
+                s.base.virtualization,
+                Some(ServiceVirtualization::Kubernetes(_))
+            )
+        }) {
+            for port_type in node_exposed {
+                let port = match ports.iter().find(|p| p.base.port_type == port_type) {
+                    Some(port) => *port,
+                    None => {
+                        let port = Port::new_hostless(port_type);
+                        ports.push(port);
+                        port
+                    }
+                };
+
+                for node_interface in &node.interfaces {
+                    service.base.bindings.push(Binding::new_port_serviceless(
+                        port.id,
+                        Some(node_interface.id),
+                    ));
+                }
+            }
+        }
+
+        let host_response = self
+            .create_host(host, interfaces, ports, services, Vec::new())
+            .await?;
+
+        Ok(Some(DiscoveredPod {
+            ip: pod_ip,
+            labels: pod.metadata.labels.clone(),
+            host: host_response,
+        }))
+    }
+
+    /// Build a request path for each ingress rule path: the ingress controller's
+    /// binding on the ingress address, followed by the binding of the first pod
+    /// behind the backend service
+    async fn ingress_groups(
+        &self,
+        ingress: &Ingress,
+        cluster: &ClusterState,
+        discovered_pods: &HashMap<(String, String), DiscoveredPod>,
+    ) -> Result<Vec<Group>, Error> {
+        let session = self.as_ref().get_session().await?;
+        let namespace = ingress.metadata.namespace.clone().unwrap_or_default();
+        let ingress_name = ingress.metadata.name.clone().unwrap_or_default();
+
+        let Some(spec) = &ingress.spec else {
+            return Ok(Vec::new());
+        };
+
+        let ingress_ips: Vec<IpAddr> = ingress
+            .status
+            .as_ref()
+            .and_then(|status| status.load_balancer.as_ref())
+            .and_then(|lb| lb.ingress.as_ref())
+            .into_iter()
+            .flatten()
+            .filter_map(|lb| lb.ip.as_deref())
+            .filter_map(|ip| IpAddr::from_str(ip).ok())
+            .collect();
+
+        let entry_port = if spec.tls.as_ref().is_some_and(|tls| !tls.is_empty()) {
+            443
+        } else {
+            80
+        };
+
+        let entry_binding =
+            ingress_entry_binding(&ingress_ips, entry_port, &cluster.services, discovered_pods);
+
+        let Some(entry_binding) = entry_binding else {
+            tracing::debug!(namespace = %namespace, ingress = %ingress_name, "Couldn't find the ingress controller binding, skipping ingress");
+            return Ok(Vec::new());
+        };
+
+        let mut groups = Vec::new();
+
+        for rule in spec.rules.iter().flatten() {
+            let host = rule.host.clone().unwrap_or_else(|| "*".to_string());
+
+            for path in rule.http.iter().flat_map(|http| http.paths.iter()) {
+                let Some(backend) = &path.backend.service else {
+                    continue;
+                };
+
+                let Some(backend_binding) = service_backend_binding(
+                    &namespace,
+                    &backend.name,
+                    backend.port.as_ref().and_then(|p| p.number),
+                    backend.port.as_ref().and_then(|p| p.name.as_deref()),
+                    cluster,
+                    discovered_pods,
+                ) else {
+                    continue;
+                };
+
+                let path = path.path.clone().unwrap_or_else(|| "/".to_string());
+
+                groups.push(Group {
+                    base: GroupBase {
+                        name: format!("{}/{} {}{}", namespace, ingress_name, host, path),
+                        network_id: session.info.network_id,
+                        description: Some(format!(
+                            "Ingress {}/{} routing {}{} to service {}",
+                            namespace, ingress_name, host, path, backend.name
+                        )),
+                        group_type: GroupType::RequestPath,
+                        binding_ids: vec![entry_binding, backend_binding],
+                        source: EntitySource::Discovery {
+                            metadata: vec![DiscoveryMetadata::new(
+                                self.discovery_type(),
+                                session.info.daemon_id,
+                            )],
+                        },
+                        color: GroupTypeDiscriminants::RequestPath.color(),
+                        edge_style: Default::default(),
+                        tags: Vec::new(),
+                    },
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Pod CIDRs assigned to a node, `podCIDRs` on dual stack clusters and `podCIDR` otherwise
+fn node_pod_cidrs(node: &Node) -> Vec<IpCidr> {
+    let Some(spec) = &node.spec else {
+        return Vec::new();
+    };
+
+    spec.pod_cidrs
+        .clone()
+        .filter(|cidrs| !cidrs.is_empty())
+        .or_else(|| spec.pod_cidr.clone().map(|cidr| vec![cidr]))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|cidr| IpCidr::from_str(cidr).ok())
+        .collect()
+}
+
+/// The workload a pod belongs to. Deployments own pods through a ReplicaSet
+/// named `<deployment>-<pod-template-hash>`.
+fn pod_workload(pod: &Pod) -> Option<String> {
+    let owner = pod
+        .metadata
+        .owner_references
+        .as_ref()?
+        .iter()
+        .find(|owner| owner.controller == Some(true))?;
+
+    match owner.kind.as_str() {
+        "ReplicaSet" => Some(
+            owner
+                .name
+                .rsplit_once('-')
+                .map(|(deployment, _)| deployment.to_string())
+                .unwrap_or_else(|| owner.name.clone()),
+        ),
+        _ => Some(owner.name.clone()),
+    }
+}
+
+/// (name, container port, host port) for every port declared by the pod's containers
+fn pod_container_ports(
+    pod: &Pod,
+) -> impl Iterator<Item = (Option<&str>, PortType, Option<PortType>)> {
+    pod.spec
+        .iter()
+        .flat_map(|spec| spec.containers.iter())
+        .flat_map(|container| container.ports.iter().flatten())
+        .map(|port| {
+            let protocol = port.protocol.as_deref();
+            (
+                port.name.as_deref(),
+                port_type(port.container_port, protocol),
+                port.host_port
+                    .map(|host_port| port_type(host_port, protocol)),
+            )
+        })
+}
+
+fn port_type(number: i32, protocol: Option<&str>) -> PortType {
+    match protocol {
+        Some("UDP") => PortType::new_udp(number as u16),
+        _ => PortType::new_tcp(number as u16),
+    }
+}
+
+fn service_ports(service: &KubernetesService) -> impl Iterator<Item = &ServicePort> {
+    service
+        .spec
+        .iter()
+        .flat_map(|spec| spec.ports.iter().flatten())
+}
+
+fn labels_match(
+    selector: &BTreeMap<String, String>,
+    labels: Option<&BTreeMap<String, String>>,
+) -> bool {
+    let Some(labels) = labels else {
+        return false;
+    };
+    selector
+        .iter()
+        .all(|(key, value)| labels.get(key) == Some(value))
+}
+
+/// Cluster services in the pod's namespace whose selector matches the pod
+fn selecting_services<'a>(
+    pod: &'a Pod,
+    services: &'a [KubernetesService],
+) -> impl Iterator<Item = &'a KubernetesService> {
+    services.iter().filter(move |service| {
+        service.metadata.namespace == pod.metadata.namespace
+            && service
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.selector.as_ref())
+                .is_some_and(|selector| {
+                    !selector.is_empty() && labels_match(selector, pod.metadata.labels.as_ref())
+                })
+    })
+}
+
+/// Find the binding for `port` on the interface with `ip` among a stored host's services
+fn binding_on(host: &HostResponse, ip: IpAddr, port: PortType) -> Option<Uuid> {
+    let interface_ids: Vec<Uuid> = host
+        .interfaces
+        .iter()
+        .filter(|i| i.base.ip_address == ip)
+        .map(|i| i.id)
+        .collect();
+    let port_ids: Vec<Uuid> = host
+        .ports
+        .iter()
+        .filter(|p| p.base.port_type == port)
+        .map(|p| p.id)
+        .collect();
+
+    host.services
+        .iter()
+        .flat_map(|service: &Service| service.base.bindings.iter())
+        .find(|binding| {
+            binding.port_id().is_some_and(|id| port_ids.contains(&id))
+                && binding
+                    .interface_id()
+                    .is_some_and(|id| interface_ids.contains(&id))
+        })
+        .map(|binding| binding.id())
+}
+
+/// The ingress controller's binding: the first pod (by name) behind a LoadBalancer
+/// service that publishes one of the ingress addresses, bound on the node port for
+/// `entry_port` or on the entry port itself for host network controllers
+fn ingress_entry_binding(
+    ingress_ips: &[IpAddr],
+    entry_port: i32,
+    services: &[KubernetesService],
+    discovered_pods: &HashMap<(String, String), DiscoveredPod>,
+) -> Option<Uuid> {
+    let controller_services = services.iter().filter(|service| {
+        service
+            .status
+            .as_ref()
+            .and_then(|status| status.load_balancer.as_ref())
+            .and_then(|lb| lb.ingress.as_ref())
+            .into_iter()
+            .flatten()
+            .filter_map(|lb| lb.ip.as_deref())
+            .filter_map(|ip| IpAddr::from_str(ip).ok())
+            .any(|ip| ingress_ips.contains(&ip))
+    });
+
+    for service in controller_services {
+        let Some(service_port) = service_ports(service).find(|p| p.port == entry_port) else {
+            continue;
+        };
+        let Some(selector) = service
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.selector.as_ref())
+        else {
+            continue;
+        };
+
+        let node_port = service_port
+            .node_port
+            .map(|number| port_type(number, service_port.protocol.as_deref()));
+
+        // Controllers with several replicas enter through the first pod by name, so the
+        // group keeps the same entry binding from run to run
+        let mut controller_pods: Vec<(&String, &DiscoveredPod)> = discovered_pods
+            .iter()
+            .filter(|((namespace, _), pod)| {
+                service.metadata.namespace.as_deref() == Some(namespace.as_str())
+                    && labels_match(selector, pod.labels.as_ref())
+            })
+            .map(|((_, name), pod)| (name, pod))
+            .collect();
+        controller_pods.sort_by_key(|(name, _)| *name);
+
+        for (_, pod) in controller_pods {
+            let binding = pod.host.interfaces.iter().find_map(|interface| {
+                node_port
+                    .and_then(|port| binding_on(&pod.host, interface.base.ip_address, port))
+                    .or_else(|| {
+                        binding_on(
+                            &pod.host,
+                            interface.base.ip_address,
+                            port_type(entry_port, service_port.protocol.as_deref()),
+                        )
+                    })
+            });
+
+            if binding.is_some() {
+                return binding;
+            }
+        }
+    }
+
+    None
+}
+
+/// The binding of the first pod (by name) behind a backend service, on the
+/// service's target port
+fn service_backend_binding(
+    namespace: &str,
+    service_name: &str,
+    port_number: Option<i32>,
+    port_name: Option<&str>,
+    cluster: &ClusterState,
+    discovered_pods: &HashMap<(String, String), DiscoveredPod>,
+) -> Option<Uuid> {
+    let service = cluster.services.iter().find(|service| {
+        service.metadata.namespace.as_deref() == Some(namespace)
+            && service.metadata.name.as_deref() == Some(service_name)
+    })?;
+
+    let service_port = service_ports(service).find(|p| match (port_number, port_name) {
+        (Some(number), _) => p.port == number,
+        (None, Some(name)) => p.name.as_deref() == Some(name),
+        (None, None) => true,
+    })?;
+
+    let mut backend_pods: Vec<&Pod> = cluster
+        .pods
+        .iter()
+        .filter(|pod| {
+            selecting_services(pod, std::slice::from_ref(service))
+                .next()
+                .is_some()
+        })
+        .collect();
+    backend_pods.sort_by_key(|pod| pod.metadata.name.clone());
+
+    backend_pods.into_iter().find_map(|pod| {
+        let key = (pod.metadata.namespace.clone()?, pod.metadata.name.clone()?);
+        let discovered = discovered_pods.get(&key)?;
+
+        let target = match &service_port.target_port {
+            Some(IntOrString::Int(number)) => port_type(*number, service_port.protocol.as_deref()),
+            Some(IntOrString::String(name)) => pod_container_ports(pod)
+                .find(|(port_name, _, _)| *port_name == Some(name.as_str()))
+                .map(|(_, port, _)| port)?,
+            None => port_type(service_port.port, service_port.protocol.as_deref()),
+        };
+
+        binding_on(&discovered.host, discovered.ip, target)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::services::r#impl::base::ServiceBase;
+    use serde_json::json;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn pod(value: serde_json::Value) -> Pod {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn service(value: serde_json::Value) -> KubernetesService {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// A stored pod with a single service bound on `port` at `ip`, and that binding's ID
+    fn discovered_pod(
+        ip: &str,
+        pod_labels: &[(&str, &str)],
+        port: PortType,
+    ) -> (DiscoveredPod, Uuid) {
+        let ip: IpAddr = ip.parse().unwrap();
+        let interface = Interface::new(InterfaceBase {
+            network_id: Uuid::nil(),
+            host_id: Uuid::nil(),
+            name: None,
+            subnet_id: Uuid::nil(),
+            ip_address: ip,
+            mac_address: None,
+            position: 0,
+            dns_names: Vec::new(),
+        });
+        let port = Port::new_hostless(port);
+        let binding = Binding::new_port_serviceless(port.id, Some(interface.id));
+        let binding_id = binding.id();
+        let service = Service::new(ServiceBase {
+            bindings: vec![binding],
+            ..Default::default()
+        });
+
+        let host = HostResponse::from_host_with_children(
+            Host::new(HostBase::default()),
+            vec![interface],
+            vec![port],
+            vec![service],
+        );
+
+        (
+            DiscoveredPod {
+                ip,
+                labels: Some(labels(pod_labels)),
+                host,
+            },
+            binding_id,
+        )
+    }
+
+    #[test]
+    fn test_pod_workload() {
+        let owned_by = |kind: &str, name: &str| {
+            pod(json!({
+                "metadata": {
+                    "name": "pod",
+                    "ownerReferences": [{
+                        "apiVersion": "apps/v1",
+                        "kind": kind,
+                        "name": name,
+                        "uid": "1",
+                        "controller": true
+                    }]
+                }
+            }))
+        };
+
+        assert_eq!(
+            pod_workload(&owned_by("ReplicaSet", "web-7d4b9c6f5")),
+            Some("web".to_string())
+        );
+        assert_eq!(
+            pod_workload(&owned_by("StatefulSet", "postgres")),
+            Some("postgres".to_string())
+        );
+        assert_eq!(
+            pod_workload(&pod(json!({ "metadata": { "name": "bare" } }))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_node_pod_cidrs() {
+        let node = |spec: serde_json::Value| -> Node {
+            serde_json::from_value(json!({ "metadata": { "name": "node" }, "spec": spec })).unwrap()
+        };
+
+        assert_eq!(
+            node_pod_cidrs(&node(json!({
+                "podCIDR": "10.244.0.0/24",
+                "podCIDRs": ["10.244.0.0/24", "fd00:10:244::/64"]
+            }))),
+            vec![
+                IpCidr::from_str("10.244.0.0/24").unwrap(),
+                IpCidr::from_str("fd00:10:244::/64").unwrap()
+            ]
+        );
+        assert_eq!(
+            node_pod_cidrs(&node(json!({ "podCIDR": "10.244.1.0/24", "podCIDRs": [] }))),
+            vec![IpCidr::from_str("10.244.1.0/24").unwrap()]
+        );
+        assert!(node_pod_cidrs(&node(json!({}))).is_empty());
+    }
+
+    #[test]
+    fn test_selecting_services() {
+        let web = pod(json!({
+            "metadata": {
+                "name": "web-1",
+                "namespace": "shop",
+                "labels": { "app": "web", "tier": "frontend" }
+            }
+        }));
+        let services = vec![
+            service(json!({
+                "metadata": { "name": "web", "namespace": "shop" },
+                "spec": { "selector": { "app": "web" } }
+            })),
+            service(json!({
+                "metadata": { "name": "web", "namespace": "other" },
+                "spec": { "selector": { "app": "web" } }
+            })),
+            service(json!({
+                "metadata": { "name": "api", "namespace": "shop" },
+                "spec": { "selector": { "app": "api" } }
+            })),
+            service(json!({
+                "metadata": { "name": "external", "namespace": "shop" },
+                "spec": { "selector": {} }
+            })),
+        ];
+
+        let selecting: Vec<&KubernetesService> = selecting_services(&web, &services).collect();
+        assert_eq!(selecting.len(), 1);
+        assert_eq!(selecting[0].metadata.name.as_deref(), Some("web"));
+        assert_eq!(selecting[0].metadata.namespace.as_deref(), Some("shop"));
+
+        assert!(labels_match(
+            &labels(&[("app", "web")]),
+            web.metadata.labels.as_ref()
+        ));
+        assert!(!labels_match(&labels(&[("app", "web")]), None));
+    }
+
+    #[test]
+    fn test_service_backend_binding_uses_first_pod_by_name() {
+        let backend_pod = |name: &str| {
+            pod(json!({
+                "metadata": { "name": name, "namespace": "shop", "labels": { "app": "web" } },
+                "spec": {
+                    "containers": [{
+                        "name": "web",
+                        "ports": [{ "name": "http", "containerPort": 8080 }]
+                    }]
+                }
+            }))
+        };
+        let cluster = ClusterState {
+            nodes: Vec::new(),
+            pods: vec![backend_pod("web-b"), backend_pod("web-a")],
+            services: vec![service(json!({
+                "metadata": { "name": "web", "namespace": "shop" },
+                "spec": {
+                    "selector": { "app": "web" },
+                    "ports": [{ "port": 80, "targetPort": "http" }]
+                }
+            }))],
+            ingresses: Vec::new(),
+        };
+
+        let (pod_b, _) = discovered_pod("10.244.0.11", &[("app", "web")], PortType::new_tcp(8080));
+        let (pod_a, binding_a) =
+            discovered_pod("10.244.0.10", &[("app", "web")], PortType::new_tcp(8080));
+        let discovered_pods = HashMap::from([
+            (("shop".to_string(), "web-b".to_string()), pod_b),
+            (("shop".to_string(), "web-a".to_string()), pod_a),
+        ]);
+
+        assert_eq!(
+            service_backend_binding("shop", "web", Some(80), None, &cluster, &discovered_pods),
+            Some(binding_a)
+        );
+        assert_eq!(
+            service_backend_binding("shop", "web", Some(443), None, &cluster, &discovered_pods),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ingress_entry_binding_uses_first_controller_pod_by_name() {
+        let services = vec![service(json!({
+            "metadata": { "name": "ingress-nginx-controller", "namespace": "ingress-nginx" },
+            "spec": {
+                "type": "LoadBalancer",
+                "selector": { "app.kubernetes.io/name": "ingress-nginx" },
+                "ports": [{ "name": "http", "port": 80, "nodePort": 30080 }]
+            },
+            "status": { "loadBalancer": { "ingress": [{ "ip": "192.168.1.240" }] } }
+        }))];
+
+        let controller_labels = [("app.kubernetes.io/name", "ingress-nginx")];
+        let mut discovered_pods = HashMap::new();
+        let mut first_binding = None;
+        // Enough replicas that hash order would pick a different one now and then
+        for (i, replica) in ["h", "c", "f", "a", "g", "b", "e", "d"].iter().enumerate() {
+            let (pod, binding_id) = discovered_pod(
+                &format!("192.168.1.{}", 10 + i),
+                &controller_labels,
+                PortType::new_tcp(30080),
+            );
+            if *replica == "a" {
+                first_binding = Some(binding_id);
+            }
+            discovered_pods.insert(
+                (
+                    "ingress-nginx".to_string(),
+                    format!("ingress-nginx-controller-{}", replica),
+                ),
+                pod,
+            );
+        }
+
+        let ingress_ips: Vec<IpAddr> = vec!["192.168.1.240".parse().unwrap()];
+        assert_eq!(
+            ingress_entry_binding(&ingress_ips, 80, &services, &discovered_pods),
+            first_binding
+        );
+
+        let other_ips: Vec<IpAddr> = vec!["192.168.1.241".parse().unwrap()];
+        assert_eq!(
+            ingress_entry_binding(&other_ips, 80, &services, &discovered_pods),
+            None
+        );
+    }
+}
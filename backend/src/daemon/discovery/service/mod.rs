@@ -1,4 +1,9 @@
 pub mod base;
+pub mod dhcp_leases;
+pub mod dns_zones;
 pub mod docker;
+pub mod kubernetes;
 pub mod network;
+pub mod passive;
+pub mod proxmox;
 pub mod self_report;
@@ -1,14 +1,37 @@
 use crate::daemon::discovery::service::base::{
-    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, DiscoverySession,
+    RunsDiscovery, certificates_for_ports,
 };
 use crate::daemon::discovery::types::base::{DiscoveryCriticalError, DiscoverySessionUpdate};
 use crate::daemon::utils::arp::{self, ArpScanResult};
 use crate::daemon::utils::base::ConcurrentPipelineOps;
-use crate::daemon::utils::scanner::{can_arp_scan, scan_endpoints, scan_tcp_ports, scan_udp_ports};
+use crate::daemon::utils::icmp::{self, IcmpSweepResult};
+use crate::daemon::utils::lldp::{self, LinkListener};
+use crate::daemon::utils::mdns;
+use crate::daemon::utils::ndp;
+use crate::daemon::utils::reverse_dns::ReverseDns;
+use crate::daemon::utils::rtt::{HostRtt, RttTracker};
+use crate::daemon::utils::scanner::{
+    banner_ports, can_arp_scan, scan_banners, scan_certificates, scan_endpoints, scan_tcp_ports,
+    scan_udp_ports, tls_ports,
+};
+use crate::daemon::utils::snmp::{self, SnmpInventory};
+use crate::daemon::utils::ssdp;
+use crate::daemon::utils::syn_scan::SynScanner;
+use crate::daemon::utils::{llmnr, netbios};
 use crate::server::discovery::r#impl::types::{DiscoveryType, HostNamingFallback};
+use crate::server::hosts::r#impl::api::{DiscoveredLinkNeighbor, DiscoveryHostRequest};
+use crate::server::hosts::r#impl::base::HostBase;
+use crate::server::hosts::r#impl::links::PhysicalLink;
 use crate::server::interfaces::r#impl::base::{Interface, InterfaceBase};
-use crate::server::ports::r#impl::base::PortType;
+use crate::server::networks::dns::DnsResolver;
+use crate::server::networks::scan_exclusions::ScanExclusion;
+use crate::server::networks::scan_profiles::ScanProfile;
+use crate::server::networks::snmp::SnmpCredentialSet;
+use crate::server::ports::r#impl::base::{Port, PortType, TransportProtocol};
 use crate::server::services::r#impl::base::{Service, ServiceMatchBaselineParams};
+use crate::server::services::r#impl::endpoints::{MdnsResponse, NetbiosResponse, UpnpResponse};
+use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource, HostnameSource};
 use crate::server::subnets::r#impl::types::SubnetTypeDiscriminants;
 use crate::{
     daemon::utils::base::DaemonUtils,
@@ -21,19 +44,21 @@ use anyhow::Error;
 use async_trait::async_trait;
 use cidr::IpCidr;
 use futures::{
-    future::try_join_all,
+    future::{join_all, try_join_all},
     stream::{self, StreamExt},
 };
 use mac_address::MacAddress;
 use pnet::datalink;
 use std::collections::{HashMap, HashSet};
 use std::result::Result::Ok;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
-use std::{net::IpAddr, sync::Arc};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
 use strum::IntoDiscriminant;
 use tokio::sync::mpsc as tokio_mpsc;
-use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -48,17 +73,26 @@ const PROGRESS_ARP_PHASE: u8 = 30; // 0-30%: ARP discovery
 const PROGRESS_DEEP_SCAN_PHASE: u8 = 65; // 30-95%: Deep scanning
 const PROGRESS_GRACE_PHASE: u8 = 5; // 95-100%: Grace period
 
+/// Ports handed to the SYN scanner at a time, so deep scan progress still advances
+const SYN_SCAN_CHUNK_SIZE: usize = 4096;
+
 #[derive(Default)]
 pub struct NetworkScanDiscovery {
     subnet_ids: Option<Vec<Uuid>>,
     host_naming_fallback: HostNamingFallback,
+    scan_profile_id: Option<Uuid>,
 }
 
 impl NetworkScanDiscovery {
-    pub fn new(subnet_ids: Option<Vec<Uuid>>, host_naming_fallback: HostNamingFallback) -> Self {
+    pub fn new(
+        subnet_ids: Option<Vec<Uuid>>,
+        host_naming_fallback: HostNamingFallback,
+        scan_profile_id: Option<Uuid>,
+    ) -> Self {
         Self {
             subnet_ids,
             host_naming_fallback,
+            scan_profile_id,
         }
     }
 }
@@ -70,9 +104,25 @@ pub struct DeepScanParams<'a> {
     phase1_ports: Vec<PortType>,
     cancel: CancellationToken,
     port_scan_batch_size: usize,
+    /// TCP ports from the scan profile
+    tcp_ports: &'a [u16],
+    /// Round-trip estimate that sizes this host's probe timeouts
+    rtt: Arc<HostRtt>,
+    /// Raw socket scanner for IPv4 hosts, connect scanning is used without it
+    syn_scanner: Option<&'a SynScanner>,
     gateway_ips: &'a [IpAddr],
     /// Optional counter for batch-level progress tracking
     batches_completed: Option<&'a Arc<AtomicUsize>>,
+    /// IPv6 addresses found via NDP, keyed by MAC
+    ipv6_neighbors: &'a HashMap<MacAddress, Vec<(Ipv6Addr, Subnet)>>,
+    /// All subnets in this discovery, for addresses a host reports on its other interfaces
+    subnets: &'a [Subnet],
+    snmp_credentials: &'a SnmpCredentialSet,
+    reverse_dns: &'a ReverseDns,
+    /// DNS-SD services advertised over mDNS, keyed by the address they run on
+    mdns_responses: &'a HashMap<IpAddr, Vec<MdnsResponse>>,
+    /// UPnP device descriptions from SSDP, keyed by the address that answered
+    upnp_responses: &'a HashMap<IpAddr, Vec<UpnpResponse>>,
 }
 
 impl CreatesDiscoveredEntities for DiscoveryRunner<NetworkScanDiscovery> {}
@@ -83,6 +133,7 @@ impl RunsDiscovery for DiscoveryRunner<NetworkScanDiscovery> {
         DiscoveryType::Network {
             subnet_ids: self.domain.subnet_ids.clone(),
             host_naming_fallback: self.domain.host_naming_fallback,
+            scan_profile_id: self.domain.scan_profile_id,
         }
     }
 
@@ -94,10 +145,21 @@ impl RunsDiscovery for DiscoveryRunner<NetworkScanDiscovery> {
         // Ignore docker bridge subnets, they are discovered through Docker Discovery
         let subnets: Vec<Subnet> = self.discover_create_subnets().await?;
 
+        let config = request.config.clone();
+
         self.start_discovery(request).await?;
 
         let discovery_result = self
-            .scan_and_process_hosts(subnets, cancel.clone())
+            .scan_and_process_hosts(
+                subnets,
+                config.snmp_credentials,
+                config.dns_resolvers,
+                config
+                    .scan_profile
+                    .unwrap_or_else(ScanProfile::default_full),
+                config.scan_exclusions,
+                cancel.clone(),
+            )
             .await
             .map(|_| ());
 
@@ -179,6 +241,10 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
     async fn scan_and_process_hosts(
         &self,
         subnets: Vec<Subnet>,
+        snmp_credentials: SnmpCredentialSet,
+        dns_resolvers: Vec<DnsResolver>,
+        scan_profile: ScanProfile,
+        scan_exclusions: Vec<ScanExclusion>,
         cancel: CancellationToken,
     ) -> Result<Vec<Host>, Error> {
         let session = self.as_ref().get_session().await?;
@@ -195,7 +261,38 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             )
             .await?;
 
-        let all_ips_with_subnets: Vec<(IpAddr, Subnet)> = subnets
+        // IPv6 subnets are far too large to sweep - their hosts are found via NDP instead
+        let (ipv6_subnets, ipv4_subnets): (Vec<Subnet>, Vec<Subnet>) = subnets
+            .into_iter()
+            .partition(|s| matches!(s.base.cidr, IpCidr::V6(_)));
+        let all_subnets: Arc<Vec<Subnet>> =
+            Arc::new(ipv4_subnets.iter().chain(&ipv6_subnets).cloned().collect());
+        let snmp_credentials = Arc::new(snmp_credentials);
+        let reverse_dns = Arc::new(ReverseDns::new(dns_resolvers));
+        let tcp_ports: Arc<Vec<u16>> = Arc::new(scan_profile.tcp_ports());
+        let rtt_tracker = Arc::new(RttTracker::new(scan_profile.timeout()));
+
+        let syn_scan_rate_pps = self.as_ref().config_store.get_syn_scan_rate_pps().await?;
+        let syn_scanner = if syn_scan_rate_pps == 0 {
+            None
+        } else {
+            match SynScanner::new(syn_scan_rate_pps) {
+                Ok(scanner) => Some(Arc::new(scanner)),
+                Err(e) => {
+                    tracing::info!(error = %e, "SYN scanning unavailable, falling back to connect scanning");
+                    None
+                }
+            }
+        };
+
+        tracing::info!(
+            scan_profile = %scan_profile.name,
+            tcp_ports = tcp_ports.len(),
+            timeout_ms = scan_profile.timeout_ms,
+            "Scanning with profile"
+        );
+
+        let all_ips_with_subnets: Vec<(IpAddr, Subnet)> = ipv4_subnets
             .iter()
             .flat_map(|subnet| {
                 self.determine_scan_order(&subnet.base.cidr)
@@ -203,6 +300,38 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             })
             .collect();
 
+        // Excluded addresses are never ARPed or probed. Exclusions by MAC are applied
+        // once the MACs are known, before deep scanning.
+        let all_ips_with_subnets: Vec<(IpAddr, Subnet)> = {
+            let mut skipped = session.skipped.lock().unwrap();
+            all_ips_with_subnets
+                .into_iter()
+                .filter(
+                    |(ip, _)| match ScanExclusion::find(&scan_exclusions, *ip, None) {
+                        Some(exclusion) => {
+                            skipped.record(exclusion.id, *ip);
+                            false
+                        }
+                        None => true,
+                    },
+                )
+                .collect()
+        };
+
+        let excluded = session.skipped.lock().unwrap().total();
+        if excluded > 0 {
+            tracing::info!(
+                excluded,
+                "Leaving addresses covered by scan exclusions out of the scan"
+            );
+        }
+
+        let mac_exclusion_ids: Vec<Uuid> = scan_exclusions
+            .iter()
+            .filter(|e| e.is_by_mac())
+            .map(|e| e.id)
+            .collect();
+
         let total_ips = all_ips_with_subnets.len();
 
         // Pre-compute values used in streams
@@ -242,6 +371,85 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             total_rounds * (send_time_per_round_secs + arp::ROUND_WAIT.as_secs())
                 + arp::POST_SCAN_RECEIVE.as_secs(),
         );
+
+        // Discover IPv6 neighbors up front so their addresses can be attached to the
+        // hosts ARP finds, rather than deep scanning dual-stack hosts once per address
+        let mut ipv6_neighbors = if ipv6_subnets.is_empty() {
+            HashMap::new()
+        } else if arp_available {
+            self.discover_ipv6_neighbors(
+                &ipv6_subnets,
+                &subnet_cidr_to_mac,
+                arp_retries,
+                arp_rate_pps,
+            )
+            .await
+        } else {
+            tracing::warn!(
+                subnets = ipv6_subnets.len(),
+                "Raw socket access unavailable, skipping IPv6 neighbor discovery"
+            );
+            HashMap::new()
+        };
+        {
+            let mut skipped = session.skipped.lock().unwrap();
+            ipv6_neighbors.retain(|mac, addresses| {
+                addresses.retain(|(ip, _)| {
+                    let ip = IpAddr::V6(*ip);
+                    match ScanExclusion::find(&scan_exclusions, ip, Some(*mac)) {
+                        Some(exclusion) => {
+                            skipped.record(exclusion.id, ip);
+                            false
+                        }
+                        None => true,
+                    }
+                });
+                !addresses.is_empty()
+            });
+        }
+        let ipv6_neighbors = Arc::new(ipv6_neighbors);
+
+        // mDNS and SSDP only need a UDP socket, so search every interfaced link regardless of ARP capability
+        let multicast_sources = interfaced_ipv4_sources(&ipv4_subnets, &subnet_cidr_to_mac);
+        // Excluded responders are dropped, and SSDP doesn't fetch their descriptions
+        let is_excluded = |ip: IpAddr| {
+            let (session, scan_exclusions, mac_exclusion_ids) =
+                (&session, &scan_exclusions, &mac_exclusion_ids);
+            async move {
+                let mac = self
+                    .neighbor_mac_for_exclusions(session, ip, None, mac_exclusion_ids)
+                    .await;
+                match ScanExclusion::find(scan_exclusions, ip, mac) {
+                    Some(exclusion) => {
+                        session.skipped.lock().unwrap().record(exclusion.id, ip);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        };
+        let (mdns_responses, upnp_responses) = tokio::join!(
+            self.browse_mdns_services(&multicast_sources, &is_excluded),
+            self.search_upnp_devices(&multicast_sources, &is_excluded)
+        );
+        let mdns_responses = Arc::new(mdns_responses);
+        let upnp_responses = Arc::new(upnp_responses);
+
+        // Listen for the switches our own interfaces are plugged into while the scan runs
+        let link_listeners = if !self
+            .as_ref()
+            .config_store
+            .get_passive_link_discovery()
+            .await?
+        {
+            Vec::new()
+        } else if arp_available {
+            self.start_link_listeners(&all_subnets, &subnet_cidr_to_mac)
+        } else {
+            tracing::warn!("Raw socket access unavailable, skipping passive LLDP/CDP listening");
+            Vec::new()
+        };
+
         let pipeline_start = Instant::now();
 
         tracing::info!(
@@ -281,12 +489,13 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
         };
 
         // Get deep scan parameters with precise FD budget
-        let ports_per_host_batch = 200;
+        let ports_per_host_batch = scan_profile.batch_size.unwrap_or(200);
         let concurrent_ops = ConcurrentPipelineOps {
             arp_subnet_count,
             non_interfaced_scan_concurrency,
             discovery_ports_count: discovery_ports.len(),
             port_scan_batch_size,
+            syn_scanner_active: syn_scanner.is_some(),
         };
         let deep_scan_concurrency = self
             .as_ref()
@@ -306,6 +515,8 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
 
         // Track active ARP forwarders
         let arp_forwarders_active = Arc::new(AtomicUsize::new(0));
+        // MACs answering ARP, so IPv6-only hosts can be told apart from dual-stack ones
+        let arp_seen_macs = Arc::new(std::sync::Mutex::new(HashSet::<MacAddress>::new()));
 
         // Start ARP scanning for interfaced subnets
         if !interfaced_ips.is_empty() {
@@ -408,6 +619,8 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                         let host_tx = host_tx.clone();
                         let subnet = subnet.clone();
                         let forwarders = arp_forwarders_active.clone();
+                        let seen_macs = arp_seen_macs.clone();
+                        let rtt_tracker = rtt_tracker.clone();
                         forwarders.fetch_add(1, Ordering::SeqCst);
 
                         // Use a background thread for the blocking recv, forward via channel
@@ -415,7 +628,11 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                             let mut forwarded = 0u64;
                             loop {
                                 match arp_rx.recv_timeout(Duration::from_millis(100)) {
-                                    Ok(ArpScanResult { ip, mac }) => {
+                                    Ok(ArpScanResult { ip, mac, rtt }) => {
+                                        seen_macs.lock().unwrap().insert(mac);
+                                        if let Some(rtt) = rtt {
+                                            rtt_tracker.host(IpAddr::V4(ip), subnet.id).record(rtt);
+                                        }
                                         // Use blocking_send since we're in a std thread
                                         if host_tx
                                             .blocking_send((
@@ -467,13 +684,31 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             let host_tx = host_tx.clone();
             let discovery_ports = discovery_ports.clone();
             let cancel = cancel.clone();
+            let rtt_tracker = rtt_tracker.clone();
+            let icmp_sweep = self.as_ref().config_store.get_icmp_sweep().await?;
 
             // Spawn port scanning as a parallel task
             tokio::spawn(async move {
+                // Hosts answering the ICMP sweep go straight to deep scanning, only the
+                // rest are TCP probed
+                let non_interfaced_ips = if icmp_sweep {
+                    sweep_non_interfaced_ips(
+                        non_interfaced_ips,
+                        host_tx.clone(),
+                        rtt_tracker.clone(),
+                        cancel.clone(),
+                    )
+                    .await
+                } else {
+                    non_interfaced_ips
+                };
+
                 let results: Vec<_> = stream::iter(non_interfaced_ips)
                     .map(|(ip, subnet)| {
                         let cancel = cancel.clone();
                         let discovery_ports = discovery_ports.clone();
+                        // The first port to accept a connection gives the host its RTT
+                        let rtt = rtt_tracker.host(ip, subnet.id);
 
                         async move {
                             let result = scan_tcp_ports(
@@ -481,6 +716,7 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                                 cancel,
                                 port_scan_batch_size,
                                 discovery_ports,
+                                rtt,
                             )
                             .await;
 
@@ -504,6 +740,42 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             });
         }
 
+        // Deep scan IPv6-only hosts once ARP has finished, so dual-stack hosts
+        // are only scanned via IPv4 with their IPv6 addresses attached
+        if !ipv6_neighbors.is_empty() {
+            let host_tx = host_tx.clone();
+            let forwarders = arp_forwarders_active.clone();
+            let seen_macs = arp_seen_macs.clone();
+            let ipv6_neighbors = ipv6_neighbors.clone();
+
+            tokio::spawn(async move {
+                while forwarders.load(Ordering::SeqCst) > 0 {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                }
+
+                let ipv6_only_hosts: Vec<_> = {
+                    let seen_macs = seen_macs.lock().unwrap();
+                    ipv6_neighbors
+                        .iter()
+                        .filter(|(mac, _)| !seen_macs.contains(*mac))
+                        .filter_map(|(mac, addresses)| {
+                            addresses
+                                .first()
+                                .map(|(ip, subnet)| (IpAddr::V6(*ip), subnet.clone(), Some(*mac)))
+                        })
+                        .collect()
+                };
+
+                tracing::debug!(count = ipv6_only_hosts.len(), "Queueing IPv6-only hosts");
+
+                for host in ipv6_only_hosts {
+                    if host_tx.send(host).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
         // Drop our copy of the sender so the channel closes when all forwarders are done
         drop(host_tx);
 
@@ -523,7 +795,7 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
 
         // Batch-level progress tracking for smoother UX
         // TCP port scanning is the bulk of deep scan work (~328 batches per host for 65535 ports)
-        let batches_per_host = 65535_usize.div_ceil(ports_per_host_batch);
+        let batches_per_host = tcp_ports.len().div_ceil(ports_per_host_batch);
         let total_batches = Arc::new(AtomicUsize::new(0));
         let batches_completed = Arc::new(AtomicUsize::new(0));
 
@@ -589,13 +861,35 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                             hosts_discovered.fetch_add(1, Ordering::Relaxed);
                             *last_activity.lock().unwrap() = Instant::now();
 
+                            let mac = self
+                                .neighbor_mac_for_exclusions(&session, ip, mac, &mac_exclusion_ids)
+                                .await;
+
+                            if let Some(exclusion) =
+                                ScanExclusion::find(&scan_exclusions, ip, mac)
+                            {
+                                tracing::info!(
+                                    ip = %ip,
+                                    exclusion = %exclusion.name,
+                                    "Skipping deep scan of excluded host"
+                                );
+                                session.skipped.lock().unwrap().record(exclusion.id, ip);
                             // Spawn deep scan if under concurrency limit, otherwise buffer
-                            if pending_scans.len() < deep_scan_concurrency {
+                            } else if pending_scans.len() < deep_scan_concurrency {
                                 let cancel = cancel.clone();
                                 let gateway_ips = gateway_ips.clone();
                                 let hosts_scanned = hosts_scanned.clone();
                                 let last_activity = last_activity.clone();
                                 let batches_completed = batches_completed.clone();
+                                let ipv6_neighbors = ipv6_neighbors.clone();
+                                let all_subnets = all_subnets.clone();
+                                let snmp_credentials = snmp_credentials.clone();
+                                let reverse_dns = reverse_dns.clone();
+                                let tcp_ports = tcp_ports.clone();
+                                let syn_scanner = syn_scanner.clone();
+                                let rtt = rtt_tracker.host(ip, subnet.id);
+                                let mdns_responses = mdns_responses.clone();
+                                let upnp_responses = upnp_responses.clone();
 
                                 total_batches.fetch_add(batches_per_host, Ordering::Relaxed);
                                 pending_scans.push(Box::pin(async move {
@@ -607,8 +901,17 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                                             phase1_ports: Vec::new(),
                                             cancel,
                                             port_scan_batch_size: ports_per_host_batch,
+                                            tcp_ports: &tcp_ports,
+                                            rtt,
+                                            syn_scanner: syn_scanner.as_deref(),
                                             gateway_ips: &gateway_ips,
                                             batches_completed: Some(&batches_completed),
+                                            ipv6_neighbors: &ipv6_neighbors,
+                                            subnets: &all_subnets,
+                                            snmp_credentials: &snmp_credentials,
+                                            reverse_dns: &reverse_dns,
+                                            mdns_responses: &mdns_responses,
+                                            upnp_responses: &upnp_responses,
                                         })
                                         .await;
 
@@ -654,6 +957,15 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                         let hosts_scanned = hosts_scanned.clone();
                         let last_activity = last_activity.clone();
                         let batches_completed = batches_completed.clone();
+                        let ipv6_neighbors = ipv6_neighbors.clone();
+                        let all_subnets = all_subnets.clone();
+                        let snmp_credentials = snmp_credentials.clone();
+                        let reverse_dns = reverse_dns.clone();
+                        let tcp_ports = tcp_ports.clone();
+                        let syn_scanner = syn_scanner.clone();
+                        let rtt = rtt_tracker.host(ip, subnet.id);
+                        let mdns_responses = mdns_responses.clone();
+                        let upnp_responses = upnp_responses.clone();
 
                         pending_scans.push(Box::pin(async move {
                             let result = self
@@ -664,8 +976,17 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                                     phase1_ports: Vec::new(),
                                     cancel,
                                     port_scan_batch_size: ports_per_host_batch,
+                                    tcp_ports: &tcp_ports,
+                                    rtt,
+                                    syn_scanner: syn_scanner.as_deref(),
                                     gateway_ips: &gateway_ips,
                                     batches_completed: Some(&batches_completed),
+                                    ipv6_neighbors: &ipv6_neighbors,
+                                    subnets: &all_subnets,
+                                    snmp_credentials: &snmp_credentials,
+                                    reverse_dns: &reverse_dns,
+                                    mdns_responses: &mdns_responses,
+                                    upnp_responses: &upnp_responses,
                                 })
                                 .await;
 
@@ -731,6 +1052,7 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
                     if progress != last_progress_report || time_since_last_report >= MAX_PROGRESS_REPORT_INTERVAL {
                         last_progress_report = progress;
                         last_progress_time = Instant::now();
+                        *session.rtt_stats.lock().unwrap() = rtt_tracker.subnet_stats();
                         let _ = self.report_scanning_progress(progress.min(99)).await;
                     }
 
@@ -772,6 +1094,29 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             }
         }
 
+        if !link_listeners.is_empty() {
+            self.report_heard_link_neighbors(
+                link_listeners,
+                &all_subnets,
+                session.info.daemon_id,
+                session.info.network_id,
+            )
+            .await;
+        }
+
+        let rtt_stats = rtt_tracker.subnet_stats();
+        for stats in &rtt_stats {
+            tracing::info!(
+                subnet_id = %stats.subnet_id,
+                hosts = stats.hosts,
+                min_ms = stats.min_us / 1000,
+                avg_ms = stats.avg_us / 1000,
+                max_ms = stats.max_us / 1000,
+                "Subnet round-trip times"
+            );
+        }
+        *session.rtt_stats.lock().unwrap() = rtt_stats;
+
         self.report_discovery_update(DiscoverySessionUpdate::scanning(100))
             .await?;
 
@@ -794,8 +1139,17 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             phase1_ports,
             cancel,
             port_scan_batch_size,
+            tcp_ports,
+            rtt,
+            syn_scanner,
             gateway_ips,
             batches_completed,
+            ipv6_neighbors,
+            subnets,
+            snmp_credentials,
+            reverse_dns,
+            mdns_responses,
+            upnp_responses,
         } = params;
 
         if cancel.is_cancelled() {
@@ -803,7 +1157,9 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
         }
 
         let phase1_port_nums: HashSet<u16> = phase1_ports.iter().map(|p| p.number()).collect();
-        let remaining_tcp_ports: Vec<u16> = (1..=65535)
+        let remaining_tcp_ports: Vec<u16> = tcp_ports
+            .iter()
+            .copied()
             .filter(|p| !phase1_port_nums.contains(p))
             .collect();
 
@@ -814,20 +1170,89 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             "Starting deep scan"
         );
 
+        // SYN scanning holds no file descriptors, so it can take far larger chunks
+        let syn_scanner = match ip {
+            IpAddr::V4(ipv4) => syn_scanner.map(|scanner| (scanner, ipv4)),
+            IpAddr::V6(_) => None,
+        };
+        let chunk_size = if syn_scanner.is_some() {
+            SYN_SCAN_CHUNK_SIZE
+        } else {
+            port_scan_batch_size
+        };
+
         // Scan in batches
         let mut all_tcp_ports = Vec::new();
-        for chunk in remaining_tcp_ports.chunks(port_scan_batch_size) {
+        for chunk in remaining_tcp_ports.chunks(chunk_size) {
             if cancel.is_cancelled() {
                 return Err(Error::msg("Discovery was cancelled"));
             }
 
-            let open_ports =
-                scan_tcp_ports(ip, cancel.clone(), port_scan_batch_size, chunk.to_vec()).await?;
+            let syn_open = match syn_scanner {
+                Some((scanner, ipv4)) => {
+                    match scanner
+                        .scan(ipv4, chunk, rtt.connect_timeout(), cancel.clone())
+                        .await
+                    {
+                        Ok(open) => Some(open),
+                        Err(e) if !cancel.is_cancelled() => {
+                            tracing::debug!(
+                                ip = %ip,
+                                error = %e,
+                                "SYN scan failed, connect scanning instead"
+                            );
+                            None
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => None,
+            };
+
+            let open_ports = match syn_open {
+                Some(open) => {
+                    // Connect to the few open ports to tell TLS listeners apart, as the
+                    // connect scan does. Ports that don't accept it are kept as plain TCP.
+                    let use_https: HashMap<u16, bool> = scan_tcp_ports(
+                        ip,
+                        cancel.clone(),
+                        port_scan_batch_size,
+                        open.clone(),
+                        rtt.clone(),
+                    )
+                    .await?
+                    .into_iter()
+                    .map(|(p, h)| (p.number(), h))
+                    .collect();
+
+                    open.into_iter()
+                        .map(|p| {
+                            (
+                                PortType::new_tcp(p),
+                                use_https.get(&p).copied().unwrap_or(false),
+                            )
+                        })
+                        .collect()
+                }
+                None => {
+                    scan_tcp_ports(
+                        ip,
+                        cancel.clone(),
+                        port_scan_batch_size,
+                        chunk.to_vec(),
+                        rtt.clone(),
+                    )
+                    .await?
+                }
+            };
             all_tcp_ports.extend(open_ports);
 
-            // Update batch-level progress
+            // Update batch-level progress, counted in connect scan batches either way
             if let Some(counter) = batches_completed {
-                counter.fetch_add(1, Ordering::Relaxed);
+                counter.fetch_add(
+                    chunk.len().div_ceil(port_scan_batch_size),
+                    Ordering::Relaxed,
+                );
             }
         }
 
@@ -842,6 +1267,8 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
         open_ports.sort_by_key(|p| (p.number(), p.protocol()));
         open_ports.dedup();
 
+        let snmp_candidates = snmp_credentials.candidates(ip, &subnet.id);
+
         // UDP and endpoint scanning
         let udp_ports = scan_udp_ports(
             ip,
@@ -849,6 +1276,8 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             port_scan_batch_size,
             subnet.base.cidr,
             gateway_ips.to_vec(),
+            snmp_candidates.clone(),
+            rtt.clone(),
         )
         .await?;
         open_ports.extend(udp_ports);
@@ -863,8 +1292,29 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             ip,
             cancel.clone(),
             Some(ports_to_check),
-            Some(use_https_ports),
+            Some(use_https_ports.clone()),
             port_scan_batch_size,
+            rtt.request_timeout(),
+        )
+        .await?;
+
+        let tcp_ports: Vec<PortType> = all_tcp_ports.iter().map(|(p, _)| *p).collect();
+        let banner_responses = scan_banners(
+            ip,
+            cancel.clone(),
+            banner_ports(&tcp_ports, &endpoint_responses, &use_https_ports),
+            port_scan_batch_size,
+            rtt.connect_timeout(),
+        )
+        .await?;
+
+        let certificate_responses = scan_certificates(
+            ip,
+            cancel.clone(),
+            tls_ports(&tcp_ports, &endpoint_responses, &banner_responses),
+            port_scan_batch_size,
+            rtt.connect_timeout(),
+            rtt.request_timeout(),
         )
         .await?;
 
@@ -886,28 +1336,76 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             ip = %ip,
             open_ports = open_ports.len(),
             endpoints = endpoint_responses.len(),
+            banners = banner_responses.len(),
+            certificates = certificate_responses.len(),
             "Deep scan complete"
         );
 
-        let hostname = self.get_hostname_for_ip(ip).await?;
+        let snmp_inventory = if open_ports
+            .iter()
+            .any(|p| p.number() == 161 && p.protocol() == TransportProtocol::Udp)
+        {
+            match snmp::walk_inventory(ip, &snmp_candidates).await {
+                Ok(inventory) => Some(inventory),
+                Err(e) => {
+                    tracing::debug!(ip = %ip, error = %e, "SNMP inventory walk failed");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Queried even when DNS names the host, the workgroup/domain feeds service matching
+        let netbios_response = match ip {
+            IpAddr::V4(ipv4) => netbios::node_status(ipv4).await.unwrap_or_else(|e| {
+                tracing::debug!(ip = %ip, error = %e, "NetBIOS node status query failed");
+                None
+            }),
+            IpAddr::V6(_) => None,
+        };
+
+        let hostname = match self
+            .get_hostname_for_ip(ip, &subnet.id, reverse_dns, netbios_response.as_ref())
+            .await?
+        {
+            Some(hostname) => Some(hostname),
+            None => snmp_inventory
+                .as_ref()
+                .and_then(|inventory| inventory.system.name.clone())
+                .map(|name| (name, HostnameSource::Snmp)),
+        };
+
+        let mdns_responses = mdns_responses.get(&ip).cloned().unwrap_or_default();
+        let upnp_responses = upnp_responses.get(&ip).cloned().unwrap_or_default();
+
+        let snmp_interface = snmp_inventory
+            .as_ref()
+            .and_then(|inventory| inventory.interface_for_ip(ip));
 
         let interface = Interface::new(InterfaceBase {
             network_id: subnet.base.network_id,
             host_id: Uuid::nil(), // Placeholder - server will set correct host_id
-            name: None,
+            name: snmp_interface.map(|i| i.name.clone()),
             subnet_id: subnet.id,
             ip_address: ip,
-            mac_address: mac,
+            mac_address: mac.or(snmp_interface.and_then(|i| i.mac_address)),
             position: 0,
+            dns_names: Vec::new(),
         });
 
-        if let Ok(Some((host, interfaces, ports, services))) = self
+        if let Ok(Some((mut host, mut interfaces, mut ports, services))) = self
             .process_host(
                 ServiceMatchBaselineParams {
                     subnet,
                     interface: &interface,
                     all_ports: &open_ports,
                     endpoint_responses: &endpoint_responses,
+                    banner_responses: &banner_responses,
+                    certificate_responses: &certificate_responses,
+                    mdns_responses: &mdns_responses,
+                    upnp_responses: &upnp_responses,
+                    netbios_response: &netbios_response,
                     virtualization: &None,
                 },
                 hostname,
@@ -915,9 +1413,44 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
             )
             .await
         {
-            let services_count = services.len();
+            // Attach the host's other IPv6 addresses (SLAAC, privacy, DHCPv6) found via NDP
+            if let Some(addresses) = mac.and_then(|m| ipv6_neighbors.get(&m)) {
+                for (ipv6, ipv6_subnet) in addresses {
+                    if IpAddr::V6(*ipv6) == ip {
+                        continue;
+                    }
+                    interfaces.push(Interface::new(InterfaceBase {
+                        network_id: ipv6_subnet.base.network_id,
+                        host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                        name: None,
+                        subnet_id: ipv6_subnet.id,
+                        ip_address: IpAddr::V6(*ipv6),
+                        mac_address: mac,
+                        position: interfaces.len() as i32,
+                        dns_names: Vec::new(),
+                    }));
+                }
+            }
+
+            let neighbors_read = snmp_inventory.as_ref().is_some_and(|i| i.neighbors_read);
+            if let Some(inventory) = snmp_inventory {
+                attach_snmp_inventory(&mut host, &mut interfaces, &mut ports, inventory, subnets);
+            }
 
-            if let Ok(host_response) = self.create_host(host, interfaces, ports, services).await {
+            let services_count = services.len();
+            let certificates = certificates_for_ports(&ports, &certificate_responses);
+
+            if let Ok(host_response) = self
+                .submit_host(DiscoveryHostRequest {
+                    host,
+                    interfaces,
+                    ports,
+                    services,
+                    certificates,
+                    neighbors_read,
+                })
+                .await
+            {
                 tracing::info!(
                     ip = %ip,
                     services = services_count,
@@ -934,14 +1467,319 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
         Ok(None)
     }
 
-    async fn get_hostname_for_ip(&self, ip: IpAddr) -> Result<Option<String>, Error> {
-        match timeout(Duration::from_millis(800), async {
-            tokio::task::spawn_blocking(move || dns_lookup::lookup_addr(&ip)).await?
+    /// Resolve a hostname via reverse DNS, falling back to the host's NetBIOS name and
+    /// then an LLMNR reverse query for networks without PTR records
+    async fn get_hostname_for_ip(
+        &self,
+        ip: IpAddr,
+        subnet_id: &Uuid,
+        reverse_dns: &ReverseDns,
+        netbios_response: Option<&NetbiosResponse>,
+    ) -> Result<Option<(String, HostnameSource)>, Error> {
+        if let Some(hostname) = reverse_dns.lookup(ip, subnet_id).await {
+            return Ok(Some((hostname, HostnameSource::Dns)));
+        }
+
+        if let Some(name) = netbios_response.and_then(|response| response.name.clone()) {
+            return Ok(Some((name, HostnameSource::Netbios)));
+        }
+
+        match llmnr::reverse_lookup(ip).await {
+            Ok(Some(hostname)) => Ok(Some((hostname, HostnameSource::Llmnr))),
+            Ok(None) => Ok(None),
+            Err(e) => {
+                tracing::debug!(ip = %ip, error = %e, "LLMNR reverse lookup failed");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Find IPv6 neighbors on each interfaced IPv6 subnet, grouped by MAC address
+    async fn discover_ipv6_neighbors(
+        &self,
+        ipv6_subnets: &[Subnet],
+        subnet_cidr_to_mac: &HashMap<IpCidr, Option<MacAddress>>,
+        retries: u32,
+        rate_pps: u32,
+    ) -> HashMap<MacAddress, Vec<(Ipv6Addr, Subnet)>> {
+        // Several prefixes can share a link, so group subnets by interface and scan each once
+        let mut links: HashMap<String, (datalink::NetworkInterface, MacAddress, Vec<Subnet>)> =
+            HashMap::new();
+
+        for subnet in ipv6_subnets {
+            let cidr = subnet.base.cidr;
+
+            let Some(source_mac) = subnet_cidr_to_mac.get(&cidr).and_then(|m| *m) else {
+                tracing::warn!(cidr = %cidr, "IPv6 subnet is not on a local interface, skipping NDP scan");
+                continue;
+            };
+
+            let pnet_source_mac = pnet::util::MacAddr::from(source_mac.bytes());
+            let interface = datalink::interfaces().into_iter().find(|iface| {
+                iface.mac.unwrap_or_default() == pnet_source_mac
+                    && iface.ips.iter().any(|ip| cidr.contains(&ip.ip()))
+            });
+
+            let Some(interface) = interface else {
+                tracing::warn!(mac = %source_mac, "No interface found for MAC, skipping NDP scan");
+                continue;
+            };
+
+            links
+                .entry(interface.name.clone())
+                .or_insert_with(|| (interface, source_mac, Vec::new()))
+                .2
+                .push(subnet.clone());
+        }
+
+        let scans = links
+            .into_values()
+            .map(|(interface, source_mac, subnets)| async move {
+                let cached = self
+                    .as_ref()
+                    .utils
+                    .get_ipv6_neighbors(&interface.name)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::debug!(interface = %interface.name, error = %e, "Failed to read IPv6 neighbor cache");
+                        Vec::new()
+                    });
+
+                let cidrs: Vec<IpCidr> = subnets.iter().map(|s| s.base.cidr).collect();
+                let interface_name = interface.name.clone();
+
+                let results = match tokio::task::spawn_blocking(move || {
+                    ndp::scan_link(&interface, source_mac, &cidrs, cached, retries, rate_pps)
+                })
+                .await
+                {
+                    Ok(Ok(results)) => results,
+                    Ok(Err(e)) => {
+                        tracing::warn!(interface = %interface_name, error = %e, "NDP scan failed");
+                        Vec::new()
+                    }
+                    Err(e) => {
+                        tracing::warn!(interface = %interface_name, error = %e, "NDP scan task panicked");
+                        Vec::new()
+                    }
+                };
+
+                tracing::debug!(
+                    interface = %interface_name,
+                    found = results.len(),
+                    "NDP scan complete"
+                );
+
+                results
+                    .into_iter()
+                    .filter_map(|r| {
+                        subnets
+                            .iter()
+                            .find(|s| s.base.cidr.contains(&IpAddr::V6(r.ip)))
+                            .map(|s| (r.mac, r.ip, s.clone()))
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+        let mut neighbors: HashMap<MacAddress, Vec<(Ipv6Addr, Subnet)>> = HashMap::new();
+        for (mac, ip, subnet) in join_all(scans).await.into_iter().flatten() {
+            neighbors.entry(mac).or_default().push((ip, subnet));
+        }
+
+        // Stable ordering so the same address leads for IPv6-only hosts across runs
+        for addresses in neighbors.values_mut() {
+            addresses.sort_by_key(|(ip, _)| *ip);
+        }
+
+        tracing::info!(
+            hosts = neighbors.len(),
+            addresses = neighbors.values().map(Vec::len).sum::<usize>(),
+            "IPv6 neighbor discovery complete"
+        );
+
+        neighbors
+    }
+
+    /// MAC to check scan exclusions against for a host about to be probed. Without one from
+    /// ARP, whatever reached us from the host has put its MAC in the OS neighbor table if it's
+    /// on one of our subnets. When it stays unknown, MAC exclusions are reported as unenforced.
+    async fn neighbor_mac_for_exclusions(
+        &self,
+        session: &DiscoverySession,
+        ip: IpAddr,
+        mac: Option<MacAddress>,
+        mac_exclusion_ids: &[Uuid],
+    ) -> Option<MacAddress> {
+        if mac.is_some() || mac_exclusion_ids.is_empty() {
+            return mac;
+        }
+
+        let mac = self
+            .as_ref()
+            .utils
+            .get_mac_address_for_ip(ip)
+            .await
+            .ok()
+            .flatten();
+
+        if mac.is_none() {
+            let mut unenforced = session.unenforced_exclusions.lock().unwrap();
+            if unenforced.is_empty() {
+                tracing::warn!(
+                    ip = %ip,
+                    "MAC of a host to probe is unknown, MAC and vendor scan exclusions can't be enforced for it or hosts like it"
+                );
+                *unenforced = mac_exclusion_ids.to_vec();
+            }
+        }
+
+        mac
+    }
+
+    /// Browse DNS-SD services on each interfaced IPv4 subnet, keeping only services
+    /// that run on a non-excluded address inside the subnet they were heard on
+    async fn browse_mdns_services<F: Future<Output = bool>>(
+        &self,
+        sources: &[(IpCidr, Ipv4Addr)],
+        is_excluded: &(impl Fn(IpAddr) -> F + Sync),
+    ) -> HashMap<IpAddr, Vec<MdnsResponse>> {
+        let browses = sources.iter().map(|&(cidr, source_ip)| async move {
+            match mdns::browse(source_ip).await {
+                Ok(responses) => responses
+                    .into_iter()
+                    .filter(|(ip, _)| cidr.contains(ip))
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    tracing::warn!(cidr = %cidr, error = %e, "mDNS browse failed");
+                    Vec::new()
+                }
+            }
+        });
+
+        let mut responses: HashMap<IpAddr, Vec<MdnsResponse>> = HashMap::new();
+        for (ip, services) in join_all(browses).await.into_iter().flatten() {
+            if !is_excluded(ip).await {
+                responses.entry(ip).or_default().extend(services);
+            }
+        }
+
+        tracing::info!(
+            hosts = responses.len(),
+            services = responses.values().map(Vec::len).sum::<usize>(),
+            "mDNS service discovery complete"
+        );
+
+        responses
+    }
+
+    /// Search for UPnP devices on each interfaced IPv4 subnet, keeping only
+    /// non-excluded devices inside the subnet they were heard on
+    async fn search_upnp_devices<F: Future<Output = bool>>(
+        &self,
+        sources: &[(IpCidr, Ipv4Addr)],
+        is_excluded: &(impl Fn(IpAddr) -> F + Sync),
+    ) -> HashMap<IpAddr, Vec<UpnpResponse>> {
+        let searches = sources.iter().map(|&(cidr, source_ip)| async move {
+            let accept =
+                move |ip: IpAddr| async move { cidr.contains(&ip) && !is_excluded(ip).await };
+            match ssdp::search(source_ip, accept).await {
+                Ok(responses) => responses.into_iter().collect::<Vec<_>>(),
+                Err(e) => {
+                    tracing::warn!(cidr = %cidr, error = %e, "SSDP search failed");
+                    Vec::new()
+                }
+            }
+        });
+
+        let mut responses: HashMap<IpAddr, Vec<UpnpResponse>> = HashMap::new();
+        for (ip, devices) in join_all(searches).await.into_iter().flatten() {
+            responses.entry(ip).or_default().extend(devices);
+        }
+
+        tracing::info!(hosts = responses.len(), "UPnP device discovery complete");
+
+        responses
+    }
+
+    /// Start a passive LLDP/CDP listener on each interface attached to a scanned subnet
+    fn start_link_listeners(
+        &self,
+        subnets: &[Subnet],
+        subnet_cidr_to_mac: &HashMap<IpCidr, Option<MacAddress>>,
+    ) -> Vec<LinkListener> {
+        let mut interfaces: HashMap<String, datalink::NetworkInterface> = HashMap::new();
+
+        for subnet in subnets {
+            let cidr = subnet.base.cidr;
+
+            let Some(source_mac) = subnet_cidr_to_mac.get(&cidr).and_then(|m| *m) else {
+                continue;
+            };
+
+            let pnet_source_mac = pnet::util::MacAddr::from(source_mac.bytes());
+            if let Some(interface) = datalink::interfaces().into_iter().find(|iface| {
+                iface.mac.unwrap_or_default() == pnet_source_mac
+                    && iface.ips.iter().any(|ip| cidr.contains(&ip.ip()))
+            }) {
+                interfaces
+                    .entry(interface.name.clone())
+                    .or_insert(interface);
+            }
+        }
+
+        interfaces
+            .into_values()
+            .filter_map(|interface| match lldp::listen(&interface) {
+                Ok(listener) => Some(listener),
+                Err(e) => {
+                    tracing::warn!(interface = %interface.name, error = %e, "Failed to start LLDP/CDP listener");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Report the switches heard by the link listeners. Failures are logged rather than
+    /// failing the session, the scan results are already in.
+    async fn report_heard_link_neighbors(
+        &self,
+        listeners: Vec<LinkListener>,
+        subnets: &[Subnet],
+        daemon_id: Uuid,
+        network_id: Uuid,
+    ) {
+        let links = match tokio::task::spawn_blocking(move || {
+            listeners
+                .into_iter()
+                .flat_map(LinkListener::finish)
+                .collect::<Vec<_>>()
         })
         .await
         {
-            Ok(Ok(hostname)) => Ok(Some(hostname)),
-            _ => Ok(None),
+            Ok(links) => links,
+            Err(e) => {
+                tracing::warn!(error = %e, "LLDP/CDP listener task panicked");
+                return;
+            }
+        };
+
+        if links.is_empty() {
+            tracing::info!("No LLDP/CDP neighbors heard");
+            return;
+        }
+
+        let neighbors: Vec<DiscoveredLinkNeighbor> = links
+            .into_iter()
+            .map(|link| {
+                let metadata = DiscoveryMetadata::new(self.discovery_type(), daemon_id);
+                link_neighbor(link, subnets, network_id, metadata)
+            })
+            .collect();
+        let count = neighbors.len();
+
+        match self.report_link_neighbors(neighbors).await {
+            Ok(()) => tracing::info!(neighbors = count, "Reported LLDP/CDP neighbors"),
+            Err(e) => tracing::warn!(error = %e, "Failed to report LLDP/CDP neighbors"),
         }
     }
 
@@ -990,11 +1828,196 @@ impl DiscoveryRunner<NetworkScanDiscovery> {
 
         ips.into_iter()
     }
+}
 
-    async fn get_subnets(&self) -> Result<Vec<Subnet>, Error> {
-        self.as_ref()
-            .api_client
-            .get("/api/v1/subnets", "Failed to get subnets")
-            .await
+/// ICMP sweep the IPv4 addresses of non-interfaced subnets, sending the hosts that answer
+/// on to deep scanning. Returns the addresses that didn't answer, or all of them if no
+/// ICMP socket could be opened. Cancelling stops the sweep and forwards nothing more.
+async fn sweep_non_interfaced_ips(
+    ips: Vec<(IpAddr, Subnet)>,
+    host_tx: tokio_mpsc::Sender<(IpAddr, Subnet, Option<MacAddress>)>,
+    rtt_tracker: Arc<RttTracker>,
+    cancel: CancellationToken,
+) -> Vec<(IpAddr, Subnet)> {
+    let targets: Vec<Ipv4Addr> = ips
+        .iter()
+        .filter_map(|(ip, _)| match ip {
+            IpAddr::V4(ip) => Some(*ip),
+            IpAddr::V6(_) => None,
+        })
+        .collect();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let sweep_rx = match icmp::sweep(targets, stop.clone()) {
+        Ok(rx) => rx,
+        Err(e) => {
+            tracing::warn!(error = %e, "ICMP sweep unavailable, TCP probing all non-interfaced hosts");
+            return ips;
+        }
+    };
+
+    let subnets: HashMap<IpAddr, Subnet> = ips.iter().cloned().collect();
+    // The receiver blocks, so forward from a blocking thread as the ARP path does
+    let answered = tokio::task::spawn_blocking(move || {
+        let mut answered = HashSet::new();
+        loop {
+            if cancel.is_cancelled() {
+                stop.store(true, Ordering::Relaxed);
+                break;
+            }
+
+            let IcmpSweepResult { ip, rtt } =
+                match sweep_rx.recv_timeout(Duration::from_millis(100)) {
+                    Ok(result) => result,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                };
+            let ip = IpAddr::V4(ip);
+            let Some(subnet) = subnets.get(&ip) else {
+                continue;
+            };
+
+            if let Some(rtt) = rtt {
+                rtt_tracker.host(ip, subnet.id).record(rtt);
+            }
+            if host_tx.blocking_send((ip, subnet.clone(), None)).is_err() {
+                stop.store(true, Ordering::Relaxed);
+                break;
+            }
+            answered.insert(ip);
+        }
+        answered
+    })
+    .await
+    .unwrap_or_default();
+
+    tracing::info!(
+        answered = answered.len(),
+        remaining = ips.len() - answered.len(),
+        "ICMP sweep of non-interfaced subnets complete"
+    );
+
+    ips.into_iter()
+        .filter(|(ip, _)| !answered.contains(ip))
+        .collect()
+}
+
+/// Add the interfaces a host reports over SNMP that fall in a known subnet, its
+/// LLDP neighbors as physical links, and the credential profile that answered
+fn attach_snmp_inventory(
+    host: &mut Host,
+    interfaces: &mut Vec<Interface>,
+    ports: &mut [Port],
+    inventory: SnmpInventory,
+    subnets: &[Subnet],
+) {
+    if let Some(port) = ports.iter_mut().find(|p| {
+        p.base.port_type.number() == 161 && p.base.port_type.protocol() == TransportProtocol::Udp
+    }) {
+        port.base.snmp_credential_id = inventory.credential_id;
+    }
+
+    for snmp_interface in &inventory.interfaces {
+        for address in &snmp_interface.ip_addresses {
+            if interfaces.iter().any(|i| i.base.ip_address == *address) {
+                continue;
+            }
+
+            let Some(address_subnet) = subnets.iter().find(|s| s.base.cidr.contains(address))
+            else {
+                continue;
+            };
+
+            interfaces.push(Interface::new(InterfaceBase {
+                network_id: address_subnet.base.network_id,
+                host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                name: Some(snmp_interface.name.clone()),
+                subnet_id: address_subnet.id,
+                ip_address: *address,
+                mac_address: snmp_interface.mac_address,
+                position: interfaces.len() as i32,
+                dns_names: Vec::new(),
+            }));
+        }
     }
+
+    host.base.physical_links = inventory.physical_links;
+}
+
+/// Build the switch host for a link heard by a passive listener. It only gets an
+/// interface if its management address is in one of our subnets.
+fn link_neighbor(
+    link: PhysicalLink,
+    subnets: &[Subnet],
+    network_id: Uuid,
+    metadata: DiscoveryMetadata,
+) -> DiscoveredLinkNeighbor {
+    let host = Host::new(HostBase {
+        name: link
+            .remote_system_name
+            .clone()
+            .unwrap_or_else(|| link.remote_chassis_id.clone()),
+        hostname: link.remote_system_name.clone(),
+        tags: Vec::new(),
+        network_id,
+        description: None,
+        source: EntitySource::Discovery {
+            metadata: vec![metadata],
+        },
+        virtualization: None,
+        hidden: false,
+        physical_links: Vec::new(),
+    });
+
+    let interfaces = link
+        .remote_ip_address
+        .and_then(|ip| {
+            let subnet = subnets.iter().find(|s| s.base.cidr.contains(&ip))?;
+            Some(Interface::new(InterfaceBase {
+                network_id,
+                host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+                name: None,
+                subnet_id: subnet.id,
+                ip_address: ip,
+                mac_address: link.remote_mac_address,
+                position: 0,
+                dns_names: Vec::new(),
+            }))
+        })
+        .into_iter()
+        .collect();
+
+    DiscoveredLinkNeighbor {
+        link,
+        host,
+        interfaces,
+    }
+}
+
+/// Pair each interfaced IPv4 subnet with our own address on it, for sending multicast queries
+fn interfaced_ipv4_sources(
+    ipv4_subnets: &[Subnet],
+    subnet_cidr_to_mac: &HashMap<IpCidr, Option<MacAddress>>,
+) -> Vec<(IpCidr, Ipv4Addr)> {
+    let interfaces = datalink::interfaces();
+
+    ipv4_subnets
+        .iter()
+        .filter(|subnet| {
+            subnet_cidr_to_mac
+                .get(&subnet.base.cidr)
+                .and_then(|m| *m)
+                .is_some()
+        })
+        .filter_map(|subnet| {
+            let cidr = subnet.base.cidr;
+            interfaces
+                .iter()
+                .flat_map(|iface| iface.ips.iter())
+                .find_map(|network| match network.ip() {
+                    IpAddr::V4(ip) if cidr.contains(&network.ip()) => Some((cidr, ip)),
+                    _ => None,
+                })
+        })
+        .collect()
 }
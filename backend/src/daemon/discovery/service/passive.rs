@@ -0,0 +1,271 @@
+use crate::daemon::discovery::service::base::{
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+};
+use crate::daemon::utils::base::DaemonUtils;
+use crate::daemon::utils::passive::{self, ObservedHost, PassiveListener};
+use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
+use crate::server::discovery::r#impl::types::DiscoveryType;
+use crate::server::hosts::r#impl::base::{Host, HostBase};
+use crate::server::interfaces::r#impl::base::{Interface, InterfaceBase};
+use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource};
+use crate::server::subnets::r#impl::base::Subnet;
+use crate::server::subnets::r#impl::types::SubnetTypeDiscriminants;
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use pnet::datalink;
+use std::net::IpAddr;
+use std::time::Duration;
+use strum::IntoDiscriminant;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// How often to report progress while listening
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct PassiveDiscovery {
+    duration: Duration,
+    subnet_ids: Option<Vec<Uuid>>,
+}
+
+impl PassiveDiscovery {
+    pub fn new(duration: Duration, subnet_ids: Option<Vec<Uuid>>) -> Self {
+        Self {
+            duration,
+            subnet_ids,
+        }
+    }
+}
+
+impl CreatesDiscoveredEntities for DiscoveryRunner<PassiveDiscovery> {}
+
+#[async_trait]
+impl RunsDiscovery for DiscoveryRunner<PassiveDiscovery> {
+    fn discovery_type(&self) -> DiscoveryType {
+        DiscoveryType::Passive {
+            duration: self.domain.duration.as_secs(),
+            subnet_ids: self.domain.subnet_ids.clone(),
+        }
+    }
+
+    async fn discover(
+        &self,
+        request: DaemonDiscoveryRequest,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        let subnets = self.discover_create_subnets().await?;
+
+        self.start_discovery(request).await?;
+
+        let discovery_result = self.listen_and_create_hosts(subnets, cancel.clone()).await;
+
+        self.finish_discovery(discovery_result, cancel.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscoversNetworkedEntities for DiscoveryRunner<PassiveDiscovery> {
+    async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
+        self.as_ref()
+            .utils
+            .get_own_routing_table_gateway_ips()
+            .await
+    }
+
+    async fn discover_create_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        let daemon_id = self.as_ref().config_store.get_id().await?;
+        let network_id = self
+            .as_ref()
+            .config_store
+            .get_network_id()
+            .await?
+            .ok_or_else(|| anyhow!("Network ID not set"))?;
+
+        // Listen on specific subnets if provided in discovery type
+        if let Some(subnet_ids) = &self.domain.subnet_ids {
+            let all_subnets = self.get_subnets().await?;
+            return Ok(all_subnets
+                .into_iter()
+                .filter(|s| subnet_ids.contains(&s.id))
+                .collect());
+        }
+
+        // Listen on all interfaced subnets if not
+        let interface_filter = self.as_ref().config_store.get_interface_filter().await?;
+        let (_, subnets, _) = self
+            .as_ref()
+            .utils
+            .get_own_interfaces(
+                self.discovery_type(),
+                daemon_id,
+                network_id,
+                &interface_filter,
+            )
+            .await?;
+
+        // Docker bridge traffic never reaches the host's physical interfaces
+        let subnets: Vec<Subnet> = subnets
+            .into_iter()
+            .filter(|s| s.base.subnet_type.discriminant() != SubnetTypeDiscriminants::DockerBridge)
+            .collect();
+
+        let subnet_futures = subnets.iter().map(|subnet| self.create_subnet(subnet));
+        try_join_all(subnet_futures).await
+    }
+}
+
+impl DiscoveryRunner<PassiveDiscovery> {
+    async fn listen_and_create_hosts(
+        &self,
+        subnets: Vec<Subnet>,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        let session = self.as_ref().get_session().await?;
+
+        let listeners = start_listeners(&subnets);
+        if listeners.is_empty() {
+            return Err(anyhow!(
+                "Couldn't listen on any interface attached to the selected subnets, passive discovery needs raw socket access"
+            ));
+        }
+
+        tracing::info!(
+            interfaces = listeners.len(),
+            duration_secs = self.domain.duration.as_secs(),
+            "Listening for ARP, DHCP and mDNS traffic"
+        );
+
+        let started_at = Instant::now();
+        let deadline = started_at + self.domain.duration;
+
+        while Instant::now() < deadline {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    return Err(Error::msg("Discovery session was cancelled"));
+                }
+                _ = tokio::time::sleep_until(deadline.min(Instant::now() + PROGRESS_INTERVAL)) => {}
+            }
+
+            let progress = started_at.elapsed().as_secs_f64() / self.domain.duration.as_secs_f64();
+            let _ = self
+                .report_scanning_progress(((progress * 100.0) as u8).min(99))
+                .await;
+
+            tracing::debug!(
+                heard = listeners
+                    .iter()
+                    .map(PassiveListener::heard_count)
+                    .sum::<usize>(),
+                "Passive discovery progress"
+            );
+        }
+
+        let observed = tokio::task::spawn_blocking(move || {
+            listeners
+                .into_iter()
+                .flat_map(PassiveListener::finish)
+                .collect::<Vec<ObservedHost>>()
+        })
+        .await?;
+
+        let mut created = 0;
+        for observed_host in observed {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery session was cancelled"));
+            }
+
+            let Some(ip) = observed_host.ip.map(IpAddr::V4) else {
+                continue;
+            };
+            let Some(subnet) = subnets.iter().find(|s| s.base.cidr.contains(&ip)) else {
+                continue;
+            };
+
+            let metadata = DiscoveryMetadata::new(self.discovery_type(), session.info.daemon_id);
+            let (host, interface) = observed_host_entities(&observed_host, ip, subnet, metadata);
+
+            match self
+                .create_host(host, vec![interface], Vec::new(), Vec::new(), Vec::new())
+                .await
+            {
+                Ok(_) => created += 1,
+                Err(e) => {
+                    tracing::warn!(ip = %ip, mac = %observed_host.mac, error = %e, "Failed to create passively discovered host");
+                }
+            }
+        }
+
+        tracing::info!(hosts = created, "Passive discovery complete");
+
+        Ok(())
+    }
+}
+
+/// Start a listener on each interface attached to one of `subnets`
+fn start_listeners(subnets: &[Subnet]) -> Vec<PassiveListener> {
+    datalink::interfaces()
+        .into_iter()
+        .filter(|iface| {
+            iface.is_up()
+                && !iface.is_loopback()
+                && iface.ips.iter().any(|ip| {
+                    subnets
+                        .iter()
+                        .any(|subnet| subnet.base.cidr.contains(&ip.ip()))
+                })
+        })
+        .filter_map(|interface| match passive::listen(&interface) {
+            Ok(listener) => Some(listener),
+            Err(e) => {
+                tracing::warn!(interface = %interface.name, error = %e, "Failed to start passive listener");
+                None
+            }
+        })
+        .collect()
+}
+
+fn observed_host_entities(
+    observed_host: &ObservedHost,
+    ip: IpAddr,
+    subnet: &Subnet,
+    mut metadata: DiscoveryMetadata,
+) -> (Host, Interface) {
+    let hostname = observed_host.hostname.as_ref().map(|(name, source)| {
+        metadata.hostname_source = Some(*source);
+        name.clone()
+    });
+
+    let host = Host::new(HostBase {
+        name: hostname.clone().unwrap_or_else(|| ip.to_string()),
+        hostname,
+        tags: Vec::new(),
+        network_id: subnet.base.network_id,
+        description: observed_host
+            .vendor_class
+            .as_ref()
+            .map(|vendor_class| format!("DHCP vendor class: {}", vendor_class)),
+        source: EntitySource::Discovery {
+            metadata: vec![metadata],
+        },
+        virtualization: None,
+        hidden: false,
+        physical_links: Vec::new(),
+    });
+
+    let interface = Interface::new(InterfaceBase {
+        network_id: subnet.base.network_id,
+        host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+        name: None,
+        subnet_id: subnet.id,
+        ip_address: ip,
+        mac_address: Some(observed_host.mac),
+        position: 0,
+        dns_names: Vec::new(),
+    });
+
+    (host, interface)
+}
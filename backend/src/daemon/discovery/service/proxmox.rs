@@ -0,0 +1,117 @@
+use crate::daemon::discovery::service::base::{
+    CreatesDiscoveredEntities, DiscoversNetworkedEntities, DiscoveryRunner, RunsDiscovery,
+};
+use crate::daemon::utils::base::DaemonUtils;
+use crate::daemon::utils::proxmox::ProxmoxClient;
+use crate::server::daemons::r#impl::api::DaemonDiscoveryRequest;
+use crate::server::discovery::r#impl::types::DiscoveryType;
+use crate::server::networks::proxmox::ProxmoxCredential;
+use crate::server::subnets::r#impl::base::Subnet;
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use std::net::IpAddr;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+pub struct ProxmoxDiscovery {
+    credential_ids: Option<Vec<Uuid>>,
+}
+
+impl ProxmoxDiscovery {
+    pub fn new(credential_ids: Option<Vec<Uuid>>) -> Self {
+        Self { credential_ids }
+    }
+}
+
+impl CreatesDiscoveredEntities for DiscoveryRunner<ProxmoxDiscovery> {}
+
+#[async_trait]
+impl RunsDiscovery for DiscoveryRunner<ProxmoxDiscovery> {
+    fn discovery_type(&self) -> DiscoveryType {
+        DiscoveryType::Proxmox {
+            credential_ids: self.domain.credential_ids.clone(),
+        }
+    }
+
+    async fn discover(
+        &self,
+        request: DaemonDiscoveryRequest,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        let credentials = request.config.proxmox_credentials.clone();
+
+        self.start_discovery(request).await?;
+
+        let discovery_result = self
+            .list_and_report_guests(credentials, cancel.clone())
+            .await;
+
+        self.finish_discovery(discovery_result, cancel.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscoversNetworkedEntities for DiscoveryRunner<ProxmoxDiscovery> {
+    async fn get_gateway_ips(&self) -> Result<Vec<IpAddr>, Error> {
+        self.as_ref()
+            .utils
+            .get_own_routing_table_gateway_ips()
+            .await
+    }
+
+    /// Guests are only linked to hosts that were already discovered
+    async fn discover_create_subnets(&self) -> Result<Vec<Subnet>, Error> {
+        self.get_subnets().await
+    }
+}
+
+impl DiscoveryRunner<ProxmoxDiscovery> {
+    async fn list_and_report_guests(
+        &self,
+        credentials: Vec<ProxmoxCredential>,
+        cancel: CancellationToken,
+    ) -> Result<(), Error> {
+        if credentials.is_empty() {
+            return Err(anyhow!(
+                "No Proxmox API tokens are configured for this network"
+            ));
+        }
+
+        let total = credentials.len();
+        let mut failed = 0;
+
+        for (index, credential) in credentials.into_iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(Error::msg("Discovery session was cancelled"));
+            }
+
+            let name = credential.name.clone();
+            let guests = match ProxmoxClient::new(credential)?.list_guests().await {
+                Ok(guests) => guests,
+                Err(e) => {
+                    tracing::warn!(credential = %name, error = %e, "Failed to list Proxmox guests");
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            tracing::info!(credential = %name, guests = guests.len(), "Listed Proxmox guests");
+
+            self.report_proxmox_guests(guests).await?;
+
+            self.report_scanning_progress(((index + 1) * 100 / total) as u8)
+                .await?;
+        }
+
+        if failed == total {
+            return Err(anyhow!(
+                "Couldn't reach the Proxmox API with any of the configured tokens"
+            ));
+        }
+
+        Ok(())
+    }
+}
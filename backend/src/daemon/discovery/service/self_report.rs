@@ -9,21 +9,25 @@ use crate::{
         bindings::r#impl::base::Binding,
         daemons::r#impl::api::{DaemonCapabilities, DaemonDiscoveryRequest},
         discovery::r#impl::types::DiscoveryType,
+        hosts::r#impl::api::DiscoveredLibvirtDomain,
         interfaces::r#impl::base::{ALL_INTERFACES_IP, Interface},
         ports::r#impl::base::{Port, PortType},
         services::{
-            definitions::scanopy_daemon::ScanopyDaemon,
+            definitions::{libvirt::Libvirt, scanopy_daemon::ScanopyDaemon},
             r#impl::{base::ServiceBase, definitions::ServiceDefinition, patterns::MatchDetails},
         },
         shared::{
             storage::traits::Storable,
-            types::entities::{DiscoveryMetadata, EntitySource},
+            types::{
+                entities::{DiscoveryMetadata, EntitySource},
+                metadata::HasId,
+            },
         },
         subnets::r#impl::{base::Subnet, types::SubnetTypeDiscriminants},
     },
 };
 use crate::{
-    daemon::utils::base::DaemonUtils,
+    daemon::utils::{base::DaemonUtils, libvirt::LibvirtClient},
     server::{
         hosts::r#impl::base::{Host, HostBase},
         services::r#impl::base::Service,
@@ -232,8 +236,19 @@ impl RunsDiscovery for DiscoveryRunner<SelfReportDiscovery> {
             interfaced_subnet_ids
         );
 
-        self.update_capabilities(has_docker_socket, interfaced_subnet_ids)
-            .await?;
+        // Configured Docker endpoints are discovered even without a local socket
+        let has_docker_endpoints = !self
+            .as_ref()
+            .config_store
+            .get_docker_endpoints()
+            .await?
+            .is_empty();
+
+        self.update_capabilities(
+            has_docker_socket || has_docker_endpoints,
+            interfaced_subnet_ids,
+        )
+        .await?;
 
         // Created subnets may differ from discovered if there are existing subnets with the same CIDR, so we need to update interface subnet_id references
         // Also filter out interfaces where subnet creation didn't happen for any reason
@@ -301,6 +316,7 @@ impl RunsDiscovery for DiscoveryRunner<SelfReportDiscovery> {
             },
             hidden: false,
             virtualization: None,
+            physical_links: Vec::new(),
         };
 
         // Ports to create with the host
@@ -338,6 +354,35 @@ impl RunsDiscovery for DiscoveryRunner<SelfReportDiscovery> {
 
         services.push(daemon_service);
 
+        // A reachable libvirt socket means this host is a KVM hypervisor
+        let libvirt_domains = match self.list_libvirt_domains().await {
+            Ok(domains) => {
+                let libvirt_service_definition = Libvirt;
+                services.push(Service::new(ServiceBase {
+                    name: ServiceDefinition::name(&libvirt_service_definition).to_string(),
+                    service_definition: Box::new(libvirt_service_definition),
+                    tags: Vec::new(),
+                    network_id,
+                    bindings: interfaces
+                        .iter()
+                        .map(|i| Binding::new_interface_serviceless(i.id))
+                        .collect(),
+                    host_id: host.id,
+                    virtualization: None,
+                    source: EntitySource::DiscoveryWithMatch {
+                        metadata: vec![DiscoveryMetadata::new(self.discovery_type(), daemon_id)],
+                        details: MatchDetails::new_certain("Libvirt socket"),
+                    },
+                    position: 1,
+                }));
+                Some(domains)
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Libvirt not available - skipping VM detection");
+                None
+            }
+        };
+
         tracing::debug!(
             "Collected information about own host with local IP: {}, Hostname: {:?}",
             local_ip,
@@ -346,9 +391,24 @@ impl RunsDiscovery for DiscoveryRunner<SelfReportDiscovery> {
 
         // Pass interfaces and ports separately - server will create them with the correct host_id
         tracing::debug!("Creating host with interfaces, ports, and services");
-        self.create_host(host, interfaces.clone(), ports, services)
+        let host_response = self
+            .create_host(host, interfaces.clone(), ports, services, Vec::new())
             .await?;
 
+        if let Some(domains) = libvirt_domains {
+            let libvirt_service_id = host_response
+                .services
+                .iter()
+                .find(|s| s.base.service_definition.id() == Libvirt.id())
+                .map(|s| s.id);
+
+            if let Some(service_id) = libvirt_service_id
+                && let Err(e) = self.report_libvirt_domains(service_id, domains).await
+            {
+                tracing::warn!(error = %e, "Failed to report libvirt domains");
+            }
+        }
+
         self.report_discovery_update(DiscoverySessionUpdate {
             phase: DiscoveryPhase::Complete,
             progress: 100,
@@ -362,6 +422,31 @@ impl RunsDiscovery for DiscoveryRunner<SelfReportDiscovery> {
 }
 
 impl DiscoveryRunner<SelfReportDiscovery> {
+    async fn list_libvirt_domains(&self) -> Result<Vec<DiscoveredLibvirtDomain>, Error> {
+        let mut client = LibvirtClient::connect().await?;
+        let domains = client.list_domains().await;
+        client.close().await;
+
+        let domains = domains?;
+        for domain in &domains {
+            tracing::debug!(
+                domain = %domain.name,
+                networks = ?domain.interfaces.iter().filter_map(|i| i.source.as_deref()).collect::<Vec<_>>(),
+                "Found libvirt domain"
+            );
+        }
+        tracing::info!(domain_count = domains.len(), "Listed libvirt domains");
+
+        Ok(domains
+            .into_iter()
+            .map(|domain| DiscoveredLibvirtDomain {
+                name: domain.name,
+                uuid: domain.uuid,
+                mac_addresses: domain.interfaces.iter().map(|i| i.mac_address).collect(),
+            })
+            .collect())
+    }
+
     async fn update_capabilities(
         &self,
         has_docker_socket: bool,
@@ -85,12 +85,43 @@ impl DaemonRuntimeService {
             }
         };
 
+        // Additional engines from docker_endpoints also make container discovery possible
+        let endpoints = self.config.get_docker_endpoints().await.unwrap_or_default();
+        let mut reachable_endpoints = 0;
+        for endpoint in &endpoints {
+            let ssl_info = self.config.get_docker_proxy_ssl_info().await;
+            if self
+                .utils
+                .new_docker_client(endpoint, ssl_info)
+                .await
+                .is_ok()
+            {
+                reachable_endpoints += 1;
+            }
+        }
+        let endpoints_description = if endpoints.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ", {}/{} configured endpoints reachable",
+                reachable_endpoints,
+                endpoints.len()
+            )
+        };
+
         match self
             .utils
             .new_local_docker_client(docker_proxy, docker_proxy_ssl_info)
             .await
         {
-            Ok(_) => (true, format!("Available {}", connection_method)),
+            Ok(_) => (
+                true,
+                format!("Available {}{}", connection_method, endpoints_description),
+            ),
+            Err(_) if reachable_endpoints > 0 => (
+                true,
+                format!("Local Docker not available{}", endpoints_description),
+            ),
             Err(e) => {
                 let error_hint = if e.to_string().contains("No such file") {
                     " (socket not found - is Docker running?)"
@@ -7,10 +7,11 @@ use figment::{
     providers::{Env, Format, Json, Serialized},
 };
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::daemon::utils::docker_endpoint::DockerEndpoint;
 use crate::server::daemons::r#impl::base::DaemonMode;
 
 #[derive(Parser)]
@@ -69,6 +70,10 @@ pub struct DaemonCli {
     #[arg(long)]
     docker_proxy_ssl_chain: Option<String>,
 
+    /// Additional container engines to discover, comma-separated. Accepts local sockets (e.g. unix:///run/podman/podman.sock) and remote Docker hosts (tcp://host:2375, or https://host:2376 with ?cert=...&key=...&ca=... file paths, falling back to the docker proxy SSL config)
+    #[arg(long, value_delimiter = ',')]
+    docker_endpoints: Option<Vec<String>>,
+
     /// Select whether the daemon will Pull work from the server or have work Pushed to it. If set to Push, you will need to ensure that network you are deploying the daemon on can be reached by the server by opening/forwarding the port to the daemon, and provide the Daemon URL where the server should try to reach the daemon. If set to Pull, no port opening/forwarding is needed
     #[arg(long)]
     mode: Option<DaemonMode>,
@@ -97,9 +102,29 @@ pub struct DaemonCli {
     #[arg(long)]
     arp_rate_pps: Option<u32>,
 
+    /// Maximum SYN packets per second when port scanning with raw sockets on Linux (default: 5000). Set to 0 to always use connect scanning
+    #[arg(long)]
+    syn_scan_rate_pps: Option<u32>,
+
     /// Restrict daemon to specific network interface(s). Comma-separated for multiple (e.g., eth0,eth1). Leave empty for all interfaces. Only applies to network discovery
     #[arg(long, value_delimiter = ',')]
     interfaces: Option<Vec<String>>,
+
+    /// Listen for LLDP and CDP advertisements during network discovery to find the switch port each of the daemon's interfaces is plugged into. Requires raw socket access
+    #[arg(long)]
+    passive_link_discovery: Option<bool>,
+
+    /// Ping subnets the daemon has no interface on before TCP probing them, to find hosts with no open ports. Uses raw sockets if available, otherwise unprivileged ping sockets
+    #[arg(long)]
+    icmp_sweep: Option<bool>,
+
+    /// Nginx Proxy Manager login used to read its proxy hosts, as email:password
+    #[arg(long)]
+    npm_credentials: Option<String>,
+
+    /// Path to an Nginx Proxy Manager database.sqlite to read proxy hosts from, when its API isn't reachable
+    #[arg(long)]
+    npm_database: Option<String>,
 }
 
 /// Unified configuration struct that handles both startup and runtime config
@@ -145,15 +170,33 @@ pub struct AppConfig {
     docker_proxy_ssl_key: Option<String>,
     #[serde(default)]
     docker_proxy_ssl_chain: Option<String>,
+    /// Container engines discovered in addition to the local Docker socket or proxy
+    #[serde(default)]
+    pub docker_endpoints: Vec<String>,
     #[serde(default)]
     pub use_npcap_arp: bool,
     #[serde(default = "default_arp_retries")]
     pub arp_retries: u32,
     #[serde(default = "default_arp_rate_pps")]
     pub arp_rate_pps: u32,
+    /// SYN scan send rate, 0 disables SYN scanning
+    #[serde(default = "default_syn_scan_rate_pps")]
+    pub syn_scan_rate_pps: u32,
     /// Network interfaces to restrict scanning to. Empty means all interfaces.
     #[serde(default)]
     pub interface_filter: Vec<String>,
+    /// Listen for LLDP/CDP advertisements on the scanned interfaces during network discovery
+    #[serde(default)]
+    pub passive_link_discovery: bool,
+    /// ICMP sweep subnets without ARP reachability ahead of TCP probing
+    #[serde(default)]
+    pub icmp_sweep: bool,
+    /// Nginx Proxy Manager API login, `email:password`
+    #[serde(default)]
+    npm_credentials: Option<String>,
+    /// Nginx Proxy Manager sqlite database
+    #[serde(default)]
+    npm_database: Option<String>,
 }
 
 fn default_arp_retries() -> u32 {
@@ -164,6 +207,10 @@ fn default_arp_rate_pps() -> u32 {
     50 // Default: 50 pps, safe for most enterprise switches
 }
 
+fn default_syn_scan_rate_pps() -> u32 {
+    5000 // Default: 5000 pps, a full sweep of one host in ~15s
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -189,10 +236,16 @@ impl Default for AppConfig {
             docker_proxy_ssl_cert: None,
             docker_proxy_ssl_chain: None,
             docker_proxy_ssl_key: None,
+            docker_endpoints: Vec::new(),
             use_npcap_arp: false,
             arp_retries: default_arp_retries(),
             arp_rate_pps: default_arp_rate_pps(),
+            syn_scan_rate_pps: default_syn_scan_rate_pps(),
             interface_filter: Vec::new(),
+            passive_link_discovery: false,
+            icmp_sweep: false,
+            npm_credentials: None,
+            npm_database: None,
         }
     }
 }
@@ -288,6 +341,9 @@ impl AppConfig {
         if let Some(docker_proxy_ssl_chain) = cli_args.docker_proxy_ssl_chain {
             figment = figment.merge(("docker_proxy_ssl_chain", docker_proxy_ssl_chain));
         }
+        if let Some(docker_endpoints) = cli_args.docker_endpoints {
+            figment = figment.merge(("docker_endpoints", docker_endpoints));
+        }
         if let Some(mode) = cli_args.mode {
             figment = figment.merge(("mode", mode));
         }
@@ -306,9 +362,24 @@ impl AppConfig {
         if let Some(arp_rate_pps) = cli_args.arp_rate_pps {
             figment = figment.merge(("arp_rate_pps", arp_rate_pps));
         }
+        if let Some(syn_scan_rate_pps) = cli_args.syn_scan_rate_pps {
+            figment = figment.merge(("syn_scan_rate_pps", syn_scan_rate_pps));
+        }
         if let Some(interface) = cli_args.interfaces {
             figment = figment.merge(("interface_filter", interface));
         }
+        if let Some(passive_link_discovery) = cli_args.passive_link_discovery {
+            figment = figment.merge(("passive_link_discovery", passive_link_discovery));
+        }
+        if let Some(icmp_sweep) = cli_args.icmp_sweep {
+            figment = figment.merge(("icmp_sweep", icmp_sweep));
+        }
+        if let Some(npm_credentials) = cli_args.npm_credentials {
+            figment = figment.merge(("npm_credentials", npm_credentials));
+        }
+        if let Some(npm_database) = cli_args.npm_database {
+            figment = figment.merge(("npm_database", npm_database));
+        }
 
         let config: AppConfig = figment
             .extract()
@@ -502,6 +573,23 @@ impl ConfigStore {
         }
     }
 
+    /// Configured container engine endpoints, skipping any that don't parse
+    pub async fn get_docker_endpoints(&self) -> Result<Vec<DockerEndpoint>> {
+        let config = self.config.read().await;
+
+        Ok(config
+            .docker_endpoints
+            .iter()
+            .filter_map(|endpoint| match DockerEndpoint::from_str(endpoint) {
+                Ok(endpoint) => Some(endpoint),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Ignoring invalid Docker endpoint");
+                    None
+                }
+            })
+            .collect())
+    }
+
     pub async fn get_heartbeat_interval(&self) -> Result<u64> {
         let config = self.config.read().await;
         Ok(config.heartbeat_interval)
@@ -533,10 +621,45 @@ impl ConfigStore {
         Ok(config.arp_rate_pps)
     }
 
+    pub async fn get_syn_scan_rate_pps(&self) -> Result<u32> {
+        let config = self.config.read().await;
+        Ok(config.syn_scan_rate_pps)
+    }
+
     pub async fn get_interface_filter(&self) -> Result<Vec<String>> {
         let config = self.config.read().await;
         Ok(config.interface_filter.clone())
     }
+
+    pub async fn get_passive_link_discovery(&self) -> Result<bool> {
+        let config = self.config.read().await;
+        Ok(config.passive_link_discovery)
+    }
+
+    pub async fn get_icmp_sweep(&self) -> Result<bool> {
+        let config = self.config.read().await;
+        Ok(config.icmp_sweep)
+    }
+
+    /// Nginx Proxy Manager login as (email, password)
+    pub async fn get_npm_credentials(&self) -> Result<Option<(String, String)>> {
+        let config = self.config.read().await;
+
+        match &config.npm_credentials {
+            Some(credentials) => credentials
+                .split_once(':')
+                .map(|(email, password)| Some((email.to_string(), password.to_string())))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("npm_credentials must be formatted as email:password")
+                }),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_npm_database(&self) -> Result<Option<PathBuf>> {
+        let config = self.config.read().await;
+        Ok(config.npm_database.as_ref().map(PathBuf::from))
+    }
 }
 
 #[cfg(test)]
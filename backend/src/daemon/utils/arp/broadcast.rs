@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::net::Ipv4Addr;
 use std::time::{Duration, Instant};
 
@@ -174,6 +174,9 @@ fn scan_subnet_background(
     let found_ips = Arc::new(Mutex::new(HashSet::<Ipv4Addr>::new()));
     let sending_done = Arc::new(AtomicBool::new(false));
     let current_round = Arc::new(AtomicU32::new(1));
+    // When each target was first asked, and whether it has been asked again since.
+    // Replies to re-sent requests can't be matched to a request, so they aren't timed.
+    let sent_at = Arc::new(Mutex::new(HashMap::<Ipv4Addr, (Instant, bool)>::new()));
 
     // Stats for logging
     let total_packets_received = Arc::new(AtomicU32::new(0));
@@ -185,6 +188,7 @@ fn scan_subnet_background(
     let total_packets_received_clone = total_packets_received.clone();
     let total_arp_replies_clone = total_arp_replies.clone();
     let current_round_recv = current_round.clone();
+    let sent_at_recv = sent_at.clone();
 
     // Receiver thread - runs continuously while sending is in progress
     let receiver_handle = thread::spawn(move || {
@@ -214,6 +218,10 @@ fn scan_subnet_background(
                                 if !found.contains(&sender_ip) {
                                     found.insert(sender_ip);
                                     let mac = MacAddress::new(arp.get_sender_hw_addr().octets());
+                                    let rtt =
+                                        sent_at_recv.lock().unwrap().get(&sender_ip).and_then(
+                                            |(at, resent)| (!resent).then(|| at.elapsed()),
+                                        );
                                     let round = current_round_recv.load(Ordering::Relaxed);
 
                                     tracing::debug!(
@@ -223,7 +231,11 @@ fn scan_subnet_background(
                                         "ARP: Host discovered"
                                     );
 
-                                    let _ = result_tx.send(ArpScanResult { ip: sender_ip, mac });
+                                    let _ = result_tx.send(ArpScanResult {
+                                        ip: sender_ip,
+                                        mac,
+                                        rtt,
+                                    });
                                 }
                             }
                         }
@@ -268,6 +280,10 @@ fn scan_subnet_background(
                                 if !found.contains(&sender_ip) {
                                     found.insert(sender_ip);
                                     let mac = MacAddress::new(arp.get_sender_hw_addr().octets());
+                                    let rtt =
+                                        sent_at_recv.lock().unwrap().get(&sender_ip).and_then(
+                                            |(at, resent)| (!resent).then(|| at.elapsed()),
+                                        );
 
                                     tracing::debug!(
                                         ip = %sender_ip,
@@ -275,7 +291,11 @@ fn scan_subnet_background(
                                         "ARP: Late host discovered"
                                     );
 
-                                    let _ = result_tx.send(ArpScanResult { ip: sender_ip, mac });
+                                    let _ = result_tx.send(ArpScanResult {
+                                        ip: sender_ip,
+                                        mac,
+                                        rtt,
+                                    });
                                 }
                             }
                         }
@@ -343,6 +363,12 @@ fn scan_subnet_background(
 
             for target_ip in &round_targets {
                 let packet = build_arp_request(source_mac_pnet, source_ip, *target_ip);
+                sent_at
+                    .lock()
+                    .unwrap()
+                    .entry(*target_ip)
+                    .and_modify(|(_, resent)| *resent = true)
+                    .or_insert((Instant::now(), false));
                 match tx.send_to(&packet, None) {
                     Some(Ok(())) => sent_ok += 1,
                     Some(Err(e)) => {
@@ -72,7 +72,11 @@ async fn send_arp_single(target_ip: Ipv4Addr) -> Option<ArpScanResult> {
     .ok()
     .flatten();
 
-    result.map(|mac| ArpScanResult { ip: target_ip, mac })
+    result.map(|mac| ArpScanResult {
+        ip: target_ip,
+        mac,
+        rtt: None,
+    })
 }
 
 // Stub for non-Windows platforms
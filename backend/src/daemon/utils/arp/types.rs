@@ -1,9 +1,12 @@
 use mac_address::MacAddress;
 use std::net::Ipv4Addr;
+use std::time::Duration;
 
 /// Result of ARP scanning a single host
 #[derive(Debug, Clone)]
 pub struct ArpScanResult {
     pub ip: Ipv4Addr,
     pub mac: MacAddress,
+    /// Time from request to reply, when it's known which request was answered
+    pub rtt: Option<Duration>,
 }
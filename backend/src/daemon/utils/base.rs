@@ -1,3 +1,4 @@
+use crate::daemon::utils::docker_endpoint::DockerEndpoint;
 use crate::server::discovery::r#impl::types::DiscoveryType;
 use crate::server::interfaces::r#impl::base::{Interface, InterfaceBase};
 use crate::server::shared::storage::traits::Storable;
@@ -15,7 +16,7 @@ use mac_address::MacAddress;
 use net_route::Handle;
 use pnet::ipnetwork::IpNetwork;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv6Addr};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
@@ -35,6 +36,8 @@ pub struct ConcurrentPipelineOps {
     pub discovery_ports_count: usize,
     /// Batch size for non-interfaced port scanning
     pub port_scan_batch_size: usize,
+    /// Whether the raw SYN scan socket is open (1 FD)
+    pub syn_scanner_active: bool,
 }
 
 impl ConcurrentPipelineOps {
@@ -47,7 +50,9 @@ impl ConcurrentPipelineOps {
         let ports_per_host = self.port_scan_batch_size.min(self.discovery_ports_count);
         let non_interfaced_fds = self.non_interfaced_scan_concurrency * ports_per_host;
 
-        arp_fds + non_interfaced_fds
+        let syn_scan_fds = usize::from(self.syn_scanner_active);
+
+        arp_fds + non_interfaced_fds + syn_scan_fds
     }
 }
 
@@ -59,6 +64,15 @@ pub trait DaemonUtils {
     /// Get MAC address for an IP from ARP table
     async fn get_mac_address_for_ip(&self, ip: IpAddr) -> Result<Option<MacAddress>, Error>;
 
+    /// Get IPv6 neighbors on an interface from the OS neighbor cache.
+    /// Entries that are still being resolved have no MAC address.
+    async fn get_ipv6_neighbors(
+        &self,
+        _interface_name: &str,
+    ) -> Result<Vec<(Ipv6Addr, Option<MacAddress>)>, Error> {
+        Ok(Vec::new())
+    }
+
     fn get_fd_limit() -> Result<usize, Error>;
 
     fn get_own_ip_address(&self) -> Result<IpAddr, Error> {
@@ -198,6 +212,7 @@ pub trait DaemonUtils {
                     ip_address: ip_addr,
                     mac_address,
                     position: interfaces.len() as i32,
+                    dns_names: Vec::new(),
                 }));
             }
         }
@@ -212,12 +227,7 @@ pub trait DaemonUtils {
         docker_proxy: Result<Option<String>, Error>,
         docker_proxy_ssl_info: Result<Option<(String, String, String)>, Error>,
     ) -> Result<Docker, Error> {
-        use tokio::time::timeout;
-
-        const DOCKER_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
-
         tracing::debug!("Creating Docker client connection");
-        let start = std::time::Instant::now();
 
         let client = if let Ok(Some(docker_proxy)) = docker_proxy {
             tracing::debug!(proxy = %docker_proxy, "Using Docker proxy");
@@ -247,6 +257,70 @@ pub trait DaemonUtils {
                 .map_err(|e| anyhow::anyhow!("Failed to connect to Docker: {}", e))?
         };
 
+        self.ping_docker_client(client).await
+    }
+
+    /// Client for a configured container engine endpoint. TLS endpoints without their
+    /// own files use the docker proxy SSL files.
+    async fn new_docker_client(
+        &self,
+        endpoint: &DockerEndpoint,
+        docker_proxy_ssl_info: Result<Option<(String, String, String)>, Error>,
+    ) -> Result<Docker, Error> {
+        tracing::debug!(endpoint = %endpoint, "Creating Docker client connection");
+
+        let client = match endpoint {
+            #[cfg(unix)]
+            DockerEndpoint::Socket(path) => {
+                Docker::connect_with_unix(&path.to_string_lossy(), 4, API_DEFAULT_VERSION)
+            }
+            #[cfg(windows)]
+            DockerEndpoint::Socket(path) => {
+                Docker::connect_with_named_pipe(&path.to_string_lossy(), 4, API_DEFAULT_VERSION)
+            }
+            DockerEndpoint::Tcp { .. } => {
+                Docker::connect_with_http(&endpoint.address(), 4, API_DEFAULT_VERSION)
+            }
+            DockerEndpoint::Tls { files, .. } => {
+                let (key, cert, chain) = match (files, docker_proxy_ssl_info) {
+                    (Some(files), _) => (
+                        files.key.clone(),
+                        files.cert.clone(),
+                        files.chain.clone(),
+                    ),
+                    (None, Ok(Some((cert, key, chain)))) => {
+                        (PathBuf::from(key), PathBuf::from(cert), PathBuf::from(chain))
+                    }
+                    (None, _) => {
+                        return Err(anyhow!(
+                            "TLS endpoint {} requires its own cert, key and ca, or the docker proxy SSL cert, key and chain",
+                            endpoint
+                        ));
+                    }
+                };
+
+                Docker::connect_with_ssl(
+                    &endpoint.address(),
+                    &key,
+                    &cert,
+                    &chain,
+                    4,
+                    API_DEFAULT_VERSION,
+                )
+            }
+        }
+        .map_err(|e| anyhow!("Failed to connect to Docker at {}: {}", endpoint, e))?;
+
+        self.ping_docker_client(client).await
+    }
+
+    async fn ping_docker_client(&self, client: Docker) -> Result<Docker, Error> {
+        use tokio::time::timeout;
+
+        const DOCKER_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+        let start = std::time::Instant::now();
+
         // Add timeout to Docker ping to prevent indefinite blocking
         tracing::debug!(
             "Pinging Docker daemon (timeout: {:?})",
@@ -0,0 +1,219 @@
+//! Container engine endpoints.
+//!
+//! Besides the local Docker socket (or `docker_proxy`), a daemon can be pointed at
+//! any number of engines speaking the Docker API: other local sockets such as
+//! Podman's `/run/podman/podman.sock` or a rootless `$XDG_RUNTIME_DIR/podman/podman.sock`,
+//! and remote Docker hosts over TCP or TLS.
+//!
+//! TLS endpoints can carry their own client certificate, key and CA as query
+//! parameters, ie `https://docker-2.lan:2376?cert=/certs/cert.pem&key=/certs/key.pem&ca=/certs/ca.pem`.
+//! Those without them use the `docker_proxy_ssl_*` files.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Error, anyhow};
+use reqwest::Url;
+
+const DOCKER_TCP_PORT: u16 = 2375;
+const DOCKER_TLS_PORT: u16 = 2376;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DockerEndpoint {
+    /// Unix socket or Windows named pipe on the daemon's own host
+    Socket(PathBuf),
+    /// Unencrypted TCP, `tcp://` or `http://`
+    Tcp { host: String, port: u16 },
+    /// TLS, `https://`. Authenticates with its own files, or the `docker_proxy_ssl_*` ones.
+    Tls {
+        host: String,
+        port: u16,
+        files: Option<TlsFiles>,
+    },
+}
+
+/// Client certificate, key and CA chain for a TLS endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub chain: PathBuf,
+}
+
+impl TlsFiles {
+    fn from_url(url: &Url) -> Result<Option<Self>, Error> {
+        let mut cert = None;
+        let mut key = None;
+        let mut chain = None;
+
+        for (name, value) in url.query_pairs() {
+            let slot = match name.as_ref() {
+                "cert" => &mut cert,
+                "key" => &mut key,
+                "ca" => &mut chain,
+                other => return Err(anyhow!("Unknown TLS parameter {} in {}", other, url)),
+            };
+            *slot = Some(PathBuf::from(value.as_ref()));
+        }
+
+        match (cert, key, chain) {
+            (Some(cert), Some(key), Some(chain)) => Ok(Some(Self { cert, key, chain })),
+            (None, None, None) => Ok(None),
+            _ => Err(anyhow!(
+                "TLS endpoint {} needs all of cert, key and ca, or none of them",
+                url
+            )),
+        }
+    }
+}
+
+impl DockerEndpoint {
+    /// Remote endpoints run on another host, their containers are attributed to it
+    pub fn remote_host(&self) -> Option<(&str, u16)> {
+        match self {
+            DockerEndpoint::Socket(_) => None,
+            DockerEndpoint::Tcp { host, port } | DockerEndpoint::Tls { host, port, .. } => {
+                Some((host, *port))
+            }
+        }
+    }
+
+    /// Address in the form bollard expects
+    pub fn address(&self) -> String {
+        match self {
+            DockerEndpoint::Socket(path) => path.to_string_lossy().to_string(),
+            DockerEndpoint::Tcp { host, port } => format!("tcp://{}:{}", url_host(host), port),
+            DockerEndpoint::Tls { host, port, .. } => {
+                format!("https://{}:{}", url_host(host), port)
+            }
+        }
+    }
+}
+
+impl FromStr for DockerEndpoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.starts_with('/') {
+            return Ok(DockerEndpoint::Socket(PathBuf::from(s)));
+        }
+
+        if let Some(path) = s
+            .strip_prefix("unix://")
+            .or_else(|| s.strip_prefix("npipe://"))
+        {
+            if path.is_empty() {
+                return Err(anyhow!("Socket endpoint {} has no path", s));
+            }
+            return Ok(DockerEndpoint::Socket(PathBuf::from(path)));
+        }
+
+        let url = Url::parse(s).map_err(|e| anyhow!("Invalid endpoint {}: {}", s, e))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("Endpoint {} has no host", s))?
+            .trim_matches(['[', ']'])
+            .to_string();
+
+        match url.scheme() {
+            "tcp" | "http" => Ok(DockerEndpoint::Tcp {
+                host,
+                port: url.port().unwrap_or(DOCKER_TCP_PORT),
+            }),
+            "https" => Ok(DockerEndpoint::Tls {
+                host,
+                port: url.port().unwrap_or(DOCKER_TLS_PORT),
+                files: TlsFiles::from_url(&url)?,
+            }),
+            scheme => Err(anyhow!(
+                "Unsupported endpoint scheme {} in {}, use unix://, npipe://, tcp://, http:// or https://",
+                scheme,
+                s
+            )),
+        }
+    }
+}
+
+impl Display for DockerEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockerEndpoint::Socket(path) => write!(f, "{}", path.display()),
+            _ => write!(f, "{}", self.address()),
+        }
+    }
+}
+
+fn url_host(host: &str) -> String {
+    if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_endpoint() {
+        assert_eq!(
+            DockerEndpoint::from_str("/run/podman/podman.sock").unwrap(),
+            DockerEndpoint::Socket(PathBuf::from("/run/podman/podman.sock"))
+        );
+        assert_eq!(
+            DockerEndpoint::from_str("unix:///run/user/1000/podman/podman.sock").unwrap(),
+            DockerEndpoint::Socket(PathBuf::from("/run/user/1000/podman/podman.sock"))
+        );
+        assert_eq!(
+            DockerEndpoint::from_str("tcp://192.168.1.20").unwrap(),
+            DockerEndpoint::Tcp {
+                host: "192.168.1.20".to_string(),
+                port: 2375
+            }
+        );
+        assert_eq!(
+            DockerEndpoint::from_str("https://docker-2.lan:2400").unwrap(),
+            DockerEndpoint::Tls {
+                host: "docker-2.lan".to_string(),
+                port: 2400,
+                files: None
+            }
+        );
+        assert_eq!(
+            DockerEndpoint::from_str(
+                "https://docker-3.lan?cert=/certs/docker-3/cert.pem&key=/certs/docker-3/key.pem&ca=/certs/ca.pem"
+            )
+            .unwrap(),
+            DockerEndpoint::Tls {
+                host: "docker-3.lan".to_string(),
+                port: 2376,
+                files: Some(TlsFiles {
+                    cert: PathBuf::from("/certs/docker-3/cert.pem"),
+                    key: PathBuf::from("/certs/docker-3/key.pem"),
+                    chain: PathBuf::from("/certs/ca.pem"),
+                })
+            }
+        );
+        assert!(DockerEndpoint::from_str("https://docker-3.lan?cert=/certs/cert.pem").is_err());
+        assert!(DockerEndpoint::from_str("ssh://docker-2.lan").is_err());
+        assert!(DockerEndpoint::from_str("unix://").is_err());
+    }
+
+    #[test]
+    fn test_docker_endpoint_address() {
+        let endpoint = DockerEndpoint::from_str("http://[fd00::20]:2375").unwrap();
+
+        assert_eq!(endpoint.remote_host(), Some(("fd00::20", 2375)));
+        assert_eq!(endpoint.address(), "tcp://[fd00::20]:2375");
+        assert_eq!(
+            DockerEndpoint::from_str("/var/run/docker.sock")
+                .unwrap()
+                .remote_host(),
+            None
+        );
+    }
+}
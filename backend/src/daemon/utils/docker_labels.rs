@@ -0,0 +1,603 @@
+//! `scanopy.*` container labels.
+//!
+//! Lets compose files document their own containers: labels override what Docker
+//! discovery would otherwise detect, and can tag containers or group them together.
+//!
+//! | Label                | Effect                                                          |
+//! |----------------------|-----------------------------------------------------------------|
+//! | `scanopy.service`    | Service definition ID or name, replaces pattern matching        |
+//! | `scanopy.name`       | Name of the container's service                                 |
+//! | `scanopy.hidden`     | `true` skips the container entirely                             |
+//! | `scanopy.tags`       | Comma separated names of existing tags                          |
+//! | `scanopy.group`      | Hub and spoke group shared by every container with that value   |
+//! | `scanopy.depends_on` | Comma separated containers this one sends requests to           |
+//!
+//! Containers started by compose or `docker stack deploy` are also grouped by the
+//! project or stack their engine labelled them with, per host since project names
+//! like `app` repeat across hosts.
+
+use std::collections::HashMap;
+
+use strum::IntoDiscriminant;
+use uuid::Uuid;
+
+use crate::server::bindings::r#impl::base::{Binding, BindingDiscriminants};
+use crate::server::groups::r#impl::types::GroupType;
+use crate::server::services::definitions::ServiceDefinitionRegistry;
+use crate::server::services::r#impl::base::Service;
+use crate::server::services::r#impl::definitions::ServiceDefinition;
+use crate::server::services::r#impl::patterns::MatchDetails;
+use crate::server::services::r#impl::virtualization::ServiceVirtualization;
+use crate::server::shared::types::entities::EntitySource;
+
+pub const SERVICE_LABEL: &str = "scanopy.service";
+pub const NAME_LABEL: &str = "scanopy.name";
+pub const HIDDEN_LABEL: &str = "scanopy.hidden";
+pub const TAGS_LABEL: &str = "scanopy.tags";
+pub const GROUP_LABEL: &str = "scanopy.group";
+pub const DEPENDS_ON_LABEL: &str = "scanopy.depends_on";
+
+/// Set by compose on every container, lets `scanopy.depends_on` use compose service names
+pub const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+/// Set by `docker stack deploy` on the services and containers of a stack
+pub const STACK_NAMESPACE_LABEL: &str = "com.docker.stack.namespace";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContainerProject {
+    Compose(String),
+    Stack(String),
+}
+
+impl ContainerProject {
+    pub fn name(&self) -> &str {
+        match self {
+            ContainerProject::Compose(name) | ContainerProject::Stack(name) => name,
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            ContainerProject::Compose(name) => format!("Docker Compose project {}", name),
+            ContainerProject::Stack(name) => format!("Docker Swarm stack {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ContainerLabelOverrides {
+    pub service: Option<String>,
+    pub name: Option<String>,
+    pub hidden: bool,
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+    pub depends_on: Vec<String>,
+    pub container_name: String,
+    pub compose_service: Option<String>,
+    pub project: Option<ContainerProject>,
+    /// Name of the host whose engine runs the container
+    pub host_name: String,
+}
+
+impl ContainerLabelOverrides {
+    pub fn from_labels(container_name: &str, labels: Option<&HashMap<String, String>>) -> Self {
+        let container_name = container_name.trim_start_matches('/').to_string();
+        let Some(labels) = labels else {
+            return Self {
+                container_name,
+                ..Default::default()
+            };
+        };
+
+        let value = |key: &str| {
+            labels
+                .get(key)
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let list = |key: &str| {
+            labels
+                .get(key)
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        Self {
+            service: value(SERVICE_LABEL),
+            name: value(NAME_LABEL),
+            hidden: value(HIDDEN_LABEL).is_some_and(|v| {
+                v.eq_ignore_ascii_case("true") || v == "1" || v.eq_ignore_ascii_case("yes")
+            }),
+            tags: list(TAGS_LABEL),
+            group: value(GROUP_LABEL),
+            depends_on: list(DEPENDS_ON_LABEL),
+            container_name,
+            compose_service: value(COMPOSE_SERVICE_LABEL),
+            project: value(STACK_NAMESPACE_LABEL)
+                .map(ContainerProject::Stack)
+                .or_else(|| value(COMPOSE_PROJECT_LABEL).map(ContainerProject::Compose)),
+            host_name: String::new(),
+        }
+    }
+
+    /// Set the host whose engine runs the container, projects are grouped per host
+    pub fn with_host(mut self, host_name: &str) -> Self {
+        self.host_name = host_name.to_string();
+        self
+    }
+
+    /// Name used for the container in group names
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.container_name)
+    }
+
+    /// Whether `reference` (from another container's `scanopy.depends_on`) points at this
+    /// container, by container name, compose service name or `scanopy.name`
+    pub fn is_referred_to_by(&self, reference: &str) -> bool {
+        [
+            Some(self.container_name.as_str()),
+            self.compose_service.as_deref(),
+            self.name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|alias| alias.eq_ignore_ascii_case(reference))
+    }
+
+    /// The service definition named by `scanopy.service`, matched on ID or name
+    pub fn service_definition(&self) -> Option<Box<dyn ServiceDefinition>> {
+        let service = self.service.as_deref()?;
+
+        ServiceDefinitionRegistry::find_by_id(service).or_else(|| {
+            ServiceDefinitionRegistry::all_service_definitions()
+                .into_iter()
+                .find(|d| d.name().eq_ignore_ascii_case(service))
+        })
+    }
+
+    /// Rewrite the services pattern matching found for the container. With
+    /// `scanopy.service` they're collapsed into a single service of that definition
+    /// holding all of their bindings.
+    pub fn apply_to_services(&self, mut services: Vec<Service>, tag_ids: &[Uuid]) -> Vec<Service> {
+        if services.is_empty() {
+            return services;
+        }
+
+        if let Some(service_definition) = self.service_definition() {
+            let mut rest = services.split_off(1);
+            let service = &mut services[0];
+
+            let bindings: Vec<Binding> = rest
+                .iter_mut()
+                .flat_map(|s| std::mem::take(&mut s.base.bindings))
+                .collect();
+            for binding in bindings {
+                if !service
+                    .base
+                    .bindings
+                    .iter()
+                    .any(|b| b.base.binding_type == binding.base.binding_type)
+                {
+                    service.base.bindings.push(binding);
+                }
+            }
+            dedup_interface_bindings(&mut service.base.bindings);
+
+            let details = MatchDetails::new_certain(&format!(
+                "Docker label {}={}",
+                SERVICE_LABEL,
+                self.service.as_deref().unwrap_or_default()
+            ));
+            service.base.source = match std::mem::take(&mut service.base.source) {
+                EntitySource::Discovery { metadata }
+                | EntitySource::DiscoveryWithMatch { metadata, .. } => {
+                    EntitySource::DiscoveryWithMatch { metadata, details }
+                }
+                source => source,
+            };
+            service.base.name = service_definition.name().to_string();
+            service.base.service_definition = service_definition;
+        } else if let Some(service) = &self.service {
+            tracing::warn!(
+                service = %service,
+                "Unknown service in {} label, keeping detected services",
+                SERVICE_LABEL
+            );
+        }
+
+        if let Some(name) = &self.name {
+            services[0].base.name = name.clone();
+        }
+
+        if !tag_ids.is_empty() {
+            for service in &mut services {
+                for tag_id in tag_ids {
+                    if !service.base.tags.contains(tag_id) {
+                        service.base.tags.push(*tag_id);
+                    }
+                }
+            }
+        }
+
+        services
+    }
+}
+
+/// A group declared through `scanopy.group` or `scanopy.depends_on` labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelGroup {
+    pub name: String,
+    pub description: String,
+    pub group_type: GroupType,
+    pub binding_ids: Vec<Uuid>,
+}
+
+/// The binding standing in for a container in groups: the first port binding of its
+/// services, or their first binding if none listen on a port
+pub fn container_binding_id(services: &[Service], container_id: &str) -> Option<Uuid> {
+    let bindings: Vec<&Binding> = services
+        .iter()
+        .filter(|s| {
+            matches!(
+                &s.base.virtualization,
+                Some(ServiceVirtualization::Docker(docker))
+                    if docker.container_id.as_deref() == Some(container_id)
+            )
+        })
+        .flat_map(|s| s.base.bindings.iter())
+        .collect();
+
+    bindings
+        .iter()
+        .find(|b| b.port_id().is_some())
+        .or(bindings.first())
+        .map(|b| b.id())
+}
+
+/// Groups declared by the labels of discovered containers, each paired with the
+/// binding standing in for it
+pub fn label_groups(containers: &[(&ContainerLabelOverrides, Uuid)]) -> Vec<LabelGroup> {
+    let mut containers = containers.to_vec();
+    containers.sort_by(|(a, _), (b, _)| a.container_name.cmp(&b.container_name));
+
+    let mut groups = Vec::new();
+
+    // Containers sharing a scanopy.group value, the first one by name is the hub
+    let mut group_names: Vec<&str> = containers
+        .iter()
+        .filter_map(|(overrides, _)| overrides.group.as_deref())
+        .collect();
+    group_names.sort();
+    group_names.dedup();
+
+    for group_name in group_names {
+        let binding_ids: Vec<Uuid> = containers
+            .iter()
+            .filter(|(overrides, _)| overrides.group.as_deref() == Some(group_name))
+            .map(|(_, binding_id)| *binding_id)
+            .collect();
+
+        groups.push(LabelGroup {
+            name: group_name.to_string(),
+            description: format!("Containers labelled {}={}", GROUP_LABEL, group_name),
+            group_type: GroupType::HubAndSpoke,
+            binding_ids,
+        });
+    }
+
+    // Every compose project and swarm stack on each host, hub picked the same way
+    let mut projects: Vec<(&str, &ContainerProject)> = containers
+        .iter()
+        .filter_map(|(overrides, _)| {
+            Some((overrides.host_name.as_str(), overrides.project.as_ref()?))
+        })
+        .collect();
+    projects.sort();
+    projects.dedup();
+
+    for (host_name, project) in projects {
+        let binding_ids: Vec<Uuid> = containers
+            .iter()
+            .filter(|(overrides, _)| {
+                overrides.host_name == host_name && overrides.project.as_ref() == Some(project)
+            })
+            .map(|(_, binding_id)| *binding_id)
+            .collect();
+
+        groups.push(LabelGroup {
+            name: format!("{} ({})", project.name(), host_name),
+            description: format!("{} on {}", project.description(), host_name),
+            group_type: GroupType::HubAndSpoke,
+            binding_ids,
+        });
+    }
+
+    // A request path from each container to every container it depends on
+    for (overrides, binding_id) in &containers {
+        for reference in &overrides.depends_on {
+            let Some((dependency, dependency_binding_id)) = containers
+                .iter()
+                .find(|(other, _)| other.is_referred_to_by(reference))
+            else {
+                tracing::debug!(
+                    container = %overrides.container_name,
+                    dependency = %reference,
+                    "Container in {} label wasn't discovered",
+                    DEPENDS_ON_LABEL
+                );
+                continue;
+            };
+
+            if dependency_binding_id == binding_id {
+                continue;
+            }
+
+            groups.push(LabelGroup {
+                name: format!(
+                    "{} → {}",
+                    overrides.display_name(),
+                    dependency.display_name()
+                ),
+                description: format!(
+                    "{} labelled {}={}",
+                    overrides.container_name, DEPENDS_ON_LABEL, reference
+                ),
+                group_type: GroupType::RequestPath,
+                binding_ids: vec![*binding_id, *dependency_binding_id],
+            });
+        }
+    }
+
+    groups
+}
+
+/// Interface bindings are implied by port bindings on the same interface
+fn dedup_interface_bindings(bindings: &mut Vec<Binding>) {
+    let interfaces_with_port_binding: Vec<Uuid> = bindings
+        .iter()
+        .filter(|b| b.base.binding_type.discriminant() == BindingDiscriminants::Port)
+        .filter_map(|b| b.interface_id())
+        .collect();
+
+    bindings.retain(|b| {
+        b.base.binding_type.discriminant() == BindingDiscriminants::Port
+            || !b
+                .interface_id()
+                .is_some_and(|id| interfaces_with_port_binding.contains(&id))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::discovery::r#impl::types::DiscoveryType;
+    use crate::server::services::definitions::docker_container::DockerContainer;
+    use crate::server::services::definitions::grafana::Grafana;
+    use crate::server::services::r#impl::base::ServiceBase;
+    use crate::server::shared::storage::traits::Storable;
+    use crate::server::shared::types::entities::DiscoveryMetadata;
+    use crate::server::shared::types::metadata::HasId;
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn service(definition: Box<dyn ServiceDefinition>, bindings: Vec<Binding>) -> Service {
+        Service::new(ServiceBase {
+            name: definition.name().to_string(),
+            service_definition: definition,
+            bindings,
+            source: EntitySource::DiscoveryWithMatch {
+                metadata: vec![DiscoveryMetadata::new(
+                    DiscoveryType::SelfReport {
+                        host_id: Uuid::nil(),
+                    },
+                    Uuid::nil(),
+                )],
+                details: MatchDetails::new_certain("Fallback"),
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_from_labels() {
+        let overrides = ContainerLabelOverrides::from_labels(
+            "stack-grafana-1",
+            Some(&labels(&[
+                (SERVICE_LABEL, "Grafana"),
+                (NAME_LABEL, "Dashboards"),
+                (HIDDEN_LABEL, "TRUE"),
+                (TAGS_LABEL, "prod, monitoring,,"),
+                (DEPENDS_ON_LABEL, "prometheus"),
+                (COMPOSE_SERVICE_LABEL, "grafana"),
+                ("com.example.other", "ignored"),
+            ])),
+        );
+
+        assert_eq!(overrides.service.as_deref(), Some("Grafana"));
+        assert_eq!(overrides.name.as_deref(), Some("Dashboards"));
+        assert!(overrides.hidden);
+        assert_eq!(overrides.tags, vec!["prod", "monitoring"]);
+        assert_eq!(overrides.group, None);
+        assert_eq!(overrides.depends_on, vec!["prometheus"]);
+        assert_eq!(overrides.project, None);
+        assert!(overrides.is_referred_to_by("Grafana"));
+        assert!(overrides.is_referred_to_by("stack-grafana-1"));
+        assert!(overrides.is_referred_to_by("dashboards"));
+
+        let unlabelled = ContainerLabelOverrides::from_labels("web", None);
+        assert!(!unlabelled.hidden);
+        assert!(unlabelled.service_definition().is_none());
+    }
+
+    #[test]
+    fn test_service_label_replaces_detected_services() {
+        let interface_id = Uuid::new_v4();
+        let port_id = Uuid::new_v4();
+        let tag_id = Uuid::new_v4();
+
+        let services = vec![
+            service(
+                Box::new(DockerContainer),
+                vec![Binding::new_interface_serviceless(interface_id)],
+            ),
+            service(
+                Box::new(DockerContainer),
+                vec![Binding::new_port_serviceless(port_id, Some(interface_id))],
+            ),
+        ];
+
+        let overrides = ContainerLabelOverrides::from_labels(
+            "grafana",
+            Some(&labels(&[(SERVICE_LABEL, "grafana")])),
+        );
+        let services = overrides.apply_to_services(services, &[tag_id]);
+
+        assert_eq!(services.len(), 1);
+        let service = &services[0];
+        assert_eq!(service.base.service_definition.id(), Grafana.id());
+        assert_eq!(service.base.name, "Grafana");
+        assert_eq!(service.base.tags, vec![tag_id]);
+        assert_eq!(service.base.bindings.len(), 1);
+        assert_eq!(service.base.bindings[0].port_id(), Some(port_id));
+        let EntitySource::DiscoveryWithMatch { details, .. } = &service.base.source else {
+            panic!("Expected a discovery match source");
+        };
+        assert_eq!(
+            details.reason_string(),
+            "Docker label scanopy.service=grafana"
+        );
+    }
+
+    #[test]
+    fn test_unknown_service_label_keeps_detected_services() {
+        let services = vec![service(Box::new(DockerContainer), Vec::new())];
+
+        let overrides = ContainerLabelOverrides::from_labels(
+            "app",
+            Some(&labels(&[
+                (SERVICE_LABEL, "Not A Service"),
+                (NAME_LABEL, "App"),
+            ])),
+        );
+        let services = overrides.apply_to_services(services, &[]);
+
+        assert_eq!(services.len(), 1);
+        assert_eq!(
+            services[0].base.service_definition.id(),
+            DockerContainer.id()
+        );
+        assert_eq!(services[0].base.name, "App");
+    }
+
+    #[test]
+    fn test_project_groups() {
+        let api = ContainerLabelOverrides::from_labels(
+            "shop_api.1.x2k",
+            Some(&labels(&[(STACK_NAMESPACE_LABEL, "shop")])),
+        );
+        let web = ContainerLabelOverrides::from_labels(
+            "shop_web.1.p8d",
+            Some(&labels(&[(STACK_NAMESPACE_LABEL, "shop")])),
+        );
+        let grafana = ContainerLabelOverrides::from_labels(
+            "monitoring-grafana-1",
+            Some(&labels(&[(COMPOSE_PROJECT_LABEL, "monitoring")])),
+        );
+        // Same project name on another host
+        let other_grafana = grafana.clone().with_host("nas");
+        let (api, web, grafana) = (
+            api.with_host("manager"),
+            web.with_host("manager"),
+            grafana.with_host("manager"),
+        );
+        let (api_binding, web_binding, grafana_binding, other_grafana_binding) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+
+        let groups = label_groups(&[
+            (&web, web_binding),
+            (&grafana, grafana_binding),
+            (&other_grafana, other_grafana_binding),
+            (&api, api_binding),
+        ]);
+
+        assert_eq!(
+            groups,
+            vec![
+                LabelGroup {
+                    name: "monitoring (manager)".to_string(),
+                    description: "Docker Compose project monitoring on manager".to_string(),
+                    group_type: GroupType::HubAndSpoke,
+                    binding_ids: vec![grafana_binding],
+                },
+                LabelGroup {
+                    name: "shop (manager)".to_string(),
+                    description: "Docker Swarm stack shop on manager".to_string(),
+                    group_type: GroupType::HubAndSpoke,
+                    binding_ids: vec![api_binding, web_binding],
+                },
+                LabelGroup {
+                    name: "monitoring (nas)".to_string(),
+                    description: "Docker Compose project monitoring on nas".to_string(),
+                    group_type: GroupType::HubAndSpoke,
+                    binding_ids: vec![other_grafana_binding],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label_groups() {
+        let web = ContainerLabelOverrides::from_labels(
+            "/stack-web-1",
+            Some(&labels(&[
+                (GROUP_LABEL, "Shop"),
+                (DEPENDS_ON_LABEL, "db, cache"),
+                (NAME_LABEL, "Storefront"),
+            ])),
+        );
+        let db = ContainerLabelOverrides::from_labels(
+            "stack-db-1",
+            Some(&labels(&[
+                (GROUP_LABEL, "Shop"),
+                (COMPOSE_SERVICE_LABEL, "db"),
+            ])),
+        );
+        let (web_binding, db_binding) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let groups = label_groups(&[(&web, web_binding), (&db, db_binding)]);
+
+        assert_eq!(
+            groups,
+            vec![
+                LabelGroup {
+                    name: "Shop".to_string(),
+                    description: "Containers labelled scanopy.group=Shop".to_string(),
+                    group_type: GroupType::HubAndSpoke,
+                    binding_ids: vec![db_binding, web_binding],
+                },
+                LabelGroup {
+                    name: "Storefront → stack-db-1".to_string(),
+                    description: "stack-web-1 labelled scanopy.depends_on=db".to_string(),
+                    group_type: GroupType::RequestPath,
+                    binding_ids: vec![web_binding, db_binding],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Docker Swarm cluster state.
+//!
+//! A swarm manager's API lists every node, service and task of the swarm. Tasks
+//! running on the manager itself are regular local containers; tasks on the other
+//! nodes are only visible through these listings, and are grouped here by the node
+//! they run on.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use bollard::secret::{
+    EndpointPortConfigProtocolEnum, Node, NodeSpecRoleEnum, Service as SwarmService, Task,
+    TaskState,
+};
+
+use crate::server::ports::r#impl::base::PortType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwarmNode {
+    pub id: String,
+    pub hostname: String,
+    pub ip: IpAddr,
+    pub is_manager: bool,
+    pub services: Vec<SwarmNodeService>,
+}
+
+/// A swarm service's tasks running on one node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwarmNodeService {
+    pub name: String,
+    /// Container of the service's first running task on the node
+    pub container_id: String,
+    pub container_name: String,
+    /// Service labels overlaid with the task's container labels
+    pub labels: HashMap<String, String>,
+    pub published_ports: Vec<PortType>,
+}
+
+/// Every node of the swarm except `local_node_id`, with the services that have
+/// running tasks on it. Nodes without a usable address are skipped.
+pub fn remote_swarm_nodes(
+    local_node_id: &str,
+    nodes: &[Node],
+    services: &[SwarmService],
+    tasks: &[Task],
+) -> Vec<SwarmNode> {
+    nodes
+        .iter()
+        .filter_map(|node| {
+            let id = node.id.clone()?;
+            if id == local_node_id {
+                return None;
+            }
+
+            let ip = node_ip(node)?;
+            let hostname = node
+                .description
+                .as_ref()
+                .and_then(|d| d.hostname.clone())
+                .unwrap_or_else(|| ip.to_string());
+            let is_manager = node
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.role)
+                .is_some_and(|role| role == NodeSpecRoleEnum::MANAGER);
+
+            let mut node_services: Vec<SwarmNodeService> = Vec::new();
+
+            let running_tasks = tasks.iter().filter(|task| {
+                task.node_id.as_deref() == Some(id.as_str())
+                    && task.status.as_ref().and_then(|status| status.state)
+                        == Some(TaskState::RUNNING)
+            });
+
+            for task in running_tasks {
+                let Some(service) = services
+                    .iter()
+                    .find(|s| s.id.is_some() && s.id == task.service_id)
+                else {
+                    continue;
+                };
+                let service_name = service
+                    .spec
+                    .as_ref()
+                    .and_then(|spec| spec.name.clone())
+                    .unwrap_or_default();
+
+                if node_services.iter().any(|s| s.name == service_name) {
+                    continue;
+                }
+
+                let Some(container_id) = task
+                    .status
+                    .as_ref()
+                    .and_then(|status| status.container_status.as_ref())
+                    .and_then(|container| container.container_id.clone())
+                else {
+                    continue;
+                };
+
+                node_services.push(SwarmNodeService {
+                    container_name: task_container_name(&service_name, task),
+                    name: service_name,
+                    container_id,
+                    labels: task_labels(service, task),
+                    published_ports: published_ports(service),
+                });
+            }
+
+            Some(SwarmNode {
+                id,
+                hostname,
+                ip,
+                is_manager,
+                services: node_services,
+            })
+        })
+        .collect()
+}
+
+/// Workers report their address in the node status. Managers may report 0.0.0.0
+/// there, their manager status has the address other nodes join them on.
+fn node_ip(node: &Node) -> Option<IpAddr> {
+    let status_ip = node
+        .status
+        .as_ref()
+        .and_then(|status| status.addr.as_deref())
+        .and_then(|addr| IpAddr::from_str(addr).ok())
+        .filter(|ip| !ip.is_unspecified());
+
+    status_ip.or_else(|| {
+        node.manager_status
+            .as_ref()
+            .and_then(|status| status.addr.as_deref())
+            .and_then(|addr| SocketAddr::from_str(addr).ok())
+            .map(|addr| addr.ip())
+    })
+}
+
+/// Swarm names task containers `<service>.<slot>.<task id>`, global services use the
+/// node ID in place of the slot
+fn task_container_name(service_name: &str, task: &Task) -> String {
+    let slot = task
+        .slot
+        .map(|slot| slot.to_string())
+        .or_else(|| task.node_id.clone())
+        .unwrap_or_default();
+
+    format!(
+        "{}.{}.{}",
+        service_name,
+        slot,
+        task.id.as_deref().unwrap_or_default()
+    )
+}
+
+fn task_labels(service: &SwarmService, task: &Task) -> HashMap<String, String> {
+    let service_labels = service.spec.as_ref().and_then(|spec| spec.labels.clone());
+    let container_labels = task
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.container_spec.as_ref())
+        .and_then(|container| container.labels.clone());
+
+    let mut labels = service_labels.unwrap_or_default();
+    labels.extend(container_labels.unwrap_or_default());
+    labels
+}
+
+fn published_ports(service: &SwarmService) -> Vec<PortType> {
+    service
+        .endpoint
+        .as_ref()
+        .and_then(|endpoint| endpoint.ports.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|port| {
+            let number = u16::try_from(port.published_port?).ok()?;
+            Some(match port.protocol {
+                Some(EndpointPortConfigProtocolEnum::UDP) => PortType::new_udp(number),
+                _ => PortType::new_tcp(number),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::secret::{
+        ContainerStatus, EndpointPortConfig, ManagerStatus, NodeDescription, NodeSpec, NodeStatus,
+        ServiceEndpoint, ServiceSpec, TaskSpec, TaskSpecContainerSpec, TaskStatus,
+    };
+
+    fn node(id: &str, hostname: &str, addr: &str, role: NodeSpecRoleEnum) -> Node {
+        Node {
+            id: Some(id.to_string()),
+            description: Some(NodeDescription {
+                hostname: Some(hostname.to_string()),
+                ..Default::default()
+            }),
+            status: Some(NodeStatus {
+                addr: Some(addr.to_string()),
+                ..Default::default()
+            }),
+            spec: Some(NodeSpec {
+                role: Some(role),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn task(id: &str, service_id: &str, node_id: &str, state: TaskState) -> Task {
+        Task {
+            id: Some(id.to_string()),
+            service_id: Some(service_id.to_string()),
+            node_id: Some(node_id.to_string()),
+            slot: Some(1),
+            status: Some(TaskStatus {
+                state: Some(state),
+                container_status: Some(ContainerStatus {
+                    container_id: Some(format!("container-{}", id)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            spec: Some(TaskSpec {
+                container_spec: Some(TaskSpecContainerSpec {
+                    labels: Some(HashMap::from([(
+                        "scanopy.name".to_string(),
+                        "Web".to_string(),
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_remote_swarm_nodes() {
+        let mut manager = node("manager", "swarm-1", "0.0.0.0", NodeSpecRoleEnum::MANAGER);
+        manager.manager_status = Some(ManagerStatus {
+            addr: Some("192.168.1.10:2377".to_string()),
+            ..Default::default()
+        });
+        let nodes = vec![
+            node("local", "swarm-0", "192.168.1.5", NodeSpecRoleEnum::MANAGER),
+            manager,
+            node(
+                "worker",
+                "swarm-2",
+                "192.168.1.11",
+                NodeSpecRoleEnum::WORKER,
+            ),
+        ];
+
+        let services = vec![SwarmService {
+            id: Some("web-id".to_string()),
+            spec: Some(ServiceSpec {
+                name: Some("shop_web".to_string()),
+                labels: Some(HashMap::from([(
+                    "com.docker.stack.namespace".to_string(),
+                    "shop".to_string(),
+                )])),
+                ..Default::default()
+            }),
+            endpoint: Some(ServiceEndpoint {
+                ports: Some(vec![EndpointPortConfig {
+                    target_port: Some(80),
+                    published_port: Some(8080),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+
+        let tasks = vec![
+            task("t1", "web-id", "local", TaskState::RUNNING),
+            task("t2", "web-id", "worker", TaskState::RUNNING),
+            task("t3", "web-id", "worker", TaskState::RUNNING),
+            task("t4", "web-id", "manager", TaskState::SHUTDOWN),
+        ];
+
+        let remote = remote_swarm_nodes("local", &nodes, &services, &tasks);
+
+        assert_eq!(remote.len(), 2);
+
+        assert_eq!(remote[0].hostname, "swarm-1");
+        assert_eq!(remote[0].ip, IpAddr::from_str("192.168.1.10").unwrap());
+        assert!(remote[0].is_manager);
+        assert!(remote[0].services.is_empty());
+
+        assert_eq!(remote[1].hostname, "swarm-2");
+        assert!(!remote[1].is_manager);
+        assert_eq!(
+            remote[1].services,
+            vec![SwarmNodeService {
+                name: "shop_web".to_string(),
+                container_id: "container-t2".to_string(),
+                container_name: "shop_web.1.t2".to_string(),
+                labels: HashMap::from([
+                    ("com.docker.stack.namespace".to_string(), "shop".to_string()),
+                    ("scanopy.name".to_string(), "Web".to_string()),
+                ]),
+                published_ports: vec![PortType::new_tcp(8080)],
+            }]
+        );
+    }
+}
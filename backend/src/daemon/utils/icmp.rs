@@ -0,0 +1,268 @@
+//! ICMP echo and timestamp sweeps, to find live hosts on subnets the daemon has no
+//! interface on and so can't ARP.
+//!
+//! Hosts with no listening ports (phones, locked-down laptops) never show up in a TCP
+//! probe, but most still answer pings. A raw socket sends both echo and timestamp
+//! requests, as some firewalls only drop one of them. Without raw socket access the
+//! unprivileged ping socket is used (Linux with `net.ipv4.ping_group_range`, and macOS),
+//! which only allows echo requests.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use pnet::packet::Packet;
+use pnet::packet::icmp::{self, IcmpCode, IcmpPacket, IcmpType, IcmpTypes, MutableIcmpPacket};
+use pnet::packet::ipv4::Ipv4Packet;
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Requests per second across the whole sweep
+const SWEEP_RATE_PPS: u64 = 1000;
+/// Non-responders are asked once more
+const SWEEP_ROUNDS: u32 = 2;
+/// Wait time after each round for replies to arrive
+pub const ROUND_WAIT: Duration = Duration::from_secs(2);
+
+/// A host that answered the sweep
+#[derive(Debug, Clone)]
+pub struct IcmpSweepResult {
+    pub ip: Ipv4Addr,
+    /// Time from request to reply, when it's known which request was answered
+    pub rtt: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SocketKind {
+    Raw,
+    /// Ping socket: the kernel owns the echo identifier and only hands this socket its own replies
+    Unprivileged,
+}
+
+/// Start sweeping `targets` in the background. Hosts are sent to the returned channel as
+/// they answer, and it closes once the last round's replies have been waited for, or soon
+/// after `stop` is set. Fails if neither a raw nor a ping socket can be opened.
+pub fn sweep(
+    targets: Vec<Ipv4Addr>,
+    stop: Arc<AtomicBool>,
+) -> Result<mpsc::Receiver<IcmpSweepResult>> {
+    let (socket, kind) = open_socket()?;
+    // Both socket types are datagram based, so the std UDP socket API works for them
+    let socket = UdpSocket::from(socket);
+    socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+    let receive_socket = socket.try_clone()?;
+
+    let identifier = fastrand::u16(..);
+    let target_set: HashSet<Ipv4Addr> = targets.iter().copied().collect();
+    let found = Arc::new(Mutex::new(HashSet::<Ipv4Addr>::new()));
+    // When each target was first asked, and whether it has been asked again since.
+    // Replies to re-sent requests can't be matched to a request, so they aren't timed.
+    let sent_at = Arc::new(Mutex::new(HashMap::<Ipv4Addr, (Instant, bool)>::new()));
+    let sending_done = Arc::new(AtomicBool::new(false));
+
+    tracing::debug!(
+        targets = targets.len(),
+        socket = ?kind,
+        "Starting ICMP sweep"
+    );
+
+    let (result_tx, result_rx) = mpsc::channel();
+
+    let found_recv = found.clone();
+    let sent_at_recv = sent_at.clone();
+    let sending_done_recv = sending_done.clone();
+    thread::spawn(move || {
+        let mut buffer = [0u8; 1500];
+
+        while !sending_done_recv.load(Ordering::Relaxed) {
+            let Ok((len, SocketAddr::V4(from))) = receive_socket.recv_from(&mut buffer) else {
+                continue;
+            };
+            let ip = *from.ip();
+
+            let expected_identifier = (kind == SocketKind::Raw).then_some(identifier);
+            if !target_set.contains(&ip) || !is_reply(&buffer[..len], expected_identifier) {
+                continue;
+            }
+
+            if !found_recv.lock().unwrap().insert(ip) {
+                continue;
+            }
+
+            let rtt = sent_at_recv
+                .lock()
+                .unwrap()
+                .get(&ip)
+                .and_then(|(at, resent)| (!resent).then(|| at.elapsed()));
+
+            tracing::debug!(ip = %ip, ?rtt, "ICMP: Host discovered");
+
+            if result_tx.send(IcmpSweepResult { ip, rtt }).is_err() {
+                break;
+            }
+        }
+    });
+
+    thread::spawn(move || {
+        let send_delay = Duration::from_micros(1_000_000 / SWEEP_RATE_PPS);
+        let mut request_types = vec![IcmpTypes::EchoRequest];
+        if kind == SocketKind::Raw {
+            request_types.push(IcmpTypes::Timestamp);
+        }
+
+        'rounds: for round in 1..=SWEEP_ROUNDS {
+            let round_targets: Vec<Ipv4Addr> = {
+                let found = found.lock().unwrap();
+                targets
+                    .iter()
+                    .filter(|ip| !found.contains(ip))
+                    .copied()
+                    .collect()
+            };
+
+            if round_targets.is_empty() {
+                break;
+            }
+
+            let mut send_errors = 0u64;
+            for target in &round_targets {
+                if stop.load(Ordering::Relaxed) {
+                    break 'rounds;
+                }
+
+                sent_at
+                    .lock()
+                    .unwrap()
+                    .entry(*target)
+                    .and_modify(|(_, resent)| *resent = true)
+                    .or_insert((Instant::now(), false));
+
+                for request_type in &request_types {
+                    let packet = icmp_request(*request_type, identifier, round as u16);
+                    if socket
+                        .send_to(&packet, SocketAddr::new(IpAddr::V4(*target), 0))
+                        .is_err()
+                    {
+                        send_errors += 1;
+                    }
+                }
+
+                thread::sleep(send_delay);
+            }
+
+            tracing::debug!(
+                round,
+                targets = round_targets.len(),
+                send_errors,
+                "ICMP sweep round sent"
+            );
+
+            let round_end = Instant::now() + ROUND_WAIT;
+            while Instant::now() < round_end && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+
+        sending_done.store(true, Ordering::Relaxed);
+    });
+
+    Ok(result_rx)
+}
+
+fn open_socket() -> Result<(Socket, SocketKind)> {
+    if let Ok(socket) = Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::ICMPV4)) {
+        return Ok((socket, SocketKind::Raw));
+    }
+
+    Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::ICMPV4))
+        .map(|socket| (socket, SocketKind::Unprivileged))
+        .map_err(|e| {
+            anyhow!(
+                "Neither a raw nor an unprivileged ICMP socket could be opened: {}",
+                e
+            )
+        })
+}
+
+fn icmp_request(request_type: IcmpType, identifier: u16, sequence: u16) -> Vec<u8> {
+    // Echo requests carry no data, timestamp requests carry three 32-bit timestamps
+    // that the host fills in. The originate timestamp is left at zero.
+    let length = if request_type == IcmpTypes::Timestamp {
+        20
+    } else {
+        8
+    };
+
+    let mut buffer = vec![0u8; length];
+    let mut packet = MutableIcmpPacket::new(&mut buffer).expect("buffer fits an ICMP header");
+    packet.set_icmp_type(request_type);
+    packet.set_icmp_code(IcmpCode::new(0));
+
+    let mut payload = vec![0u8; length - 4];
+    payload[0..2].copy_from_slice(&identifier.to_be_bytes());
+    payload[2..4].copy_from_slice(&sequence.to_be_bytes());
+    packet.set_payload(&payload);
+
+    let checksum = icmp::checksum(&packet.to_immutable());
+    packet.set_checksum(checksum);
+    buffer
+}
+
+/// Whether `packet` is an echo or timestamp reply, and to our requests when the
+/// identifier is known
+fn is_reply(packet: &[u8], identifier: Option<u16>) -> bool {
+    // Raw sockets, and ping sockets on macOS, hand over the IP header too. ICMP replies
+    // start with their type (0 or 14), so a leading version nibble of 4 is unambiguous.
+    let icmp = match Ipv4Packet::new(packet) {
+        Some(ip) if ip.get_version() == 4 => ip.payload().to_vec(),
+        _ => packet.to_vec(),
+    };
+
+    let Some(icmp) = IcmpPacket::new(&icmp) else {
+        return false;
+    };
+
+    let icmp_type = icmp.get_icmp_type();
+    let is_reply = icmp_type == IcmpTypes::EchoReply || icmp_type == IcmpTypes::TimestampReply;
+    let payload = icmp.payload();
+
+    is_reply && payload.len() >= 2 && identifier.is_none_or(|id| payload[0..2] == id.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pnet::packet::ipv4::MutableIpv4Packet;
+
+    #[test]
+    fn test_request_and_reply() {
+        let request = icmp_request(IcmpTypes::Timestamp, 0x1234, 1);
+        let parsed = IcmpPacket::new(&request).unwrap();
+        assert_eq!(request.len(), 20);
+        assert_eq!(parsed.get_icmp_type(), IcmpTypes::Timestamp);
+        assert_eq!(parsed.get_checksum(), icmp::checksum(&parsed));
+
+        // Requests aren't replies
+        assert!(!is_reply(&request, None));
+
+        let mut reply = icmp_request(IcmpTypes::EchoRequest, 0x1234, 1);
+        reply[0] = IcmpTypes::EchoReply.0;
+        assert!(is_reply(&reply, Some(0x1234)));
+        assert!(!is_reply(&reply, Some(0x4321)));
+
+        // As read from a raw socket, behind the IP header
+        let mut raw = vec![0u8; 20 + reply.len()];
+        {
+            let mut ip = MutableIpv4Packet::new(&mut raw).unwrap();
+            ip.set_version(4);
+            ip.set_header_length(5);
+            ip.set_total_length((20 + reply.len()) as u16);
+            ip.set_payload(&reply);
+        }
+        assert!(is_reply(&raw, Some(0x1234)));
+    }
+}
@@ -0,0 +1,461 @@
+//! Minimal libvirt remote protocol client.
+//!
+//! Talks to the local libvirt daemon over its read-only unix socket to list the
+//! domains defined on the host along with the MAC addresses and networks of their
+//! interfaces. Only the three calls needed for that are implemented; messages are
+//! XDR encoded as described in libvirt's `remote_protocol.x`.
+
+// The wire format helpers are only driven by the unix socket client
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, bail};
+use mac_address::MacAddress;
+use uuid::Uuid;
+
+/// Read-only sockets of the monolithic libvirtd and the modular virtqemud daemons
+#[cfg(unix)]
+const SOCKET_PATHS: [&str; 2] = [
+    "/var/run/libvirt/libvirt-sock-ro",
+    "/var/run/libvirt/virtqemud-sock-ro",
+];
+const CONNECT_URI: &str = "qemu:///system";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Domain XML for large guests stays well under this
+const MAX_MESSAGE_LEN: usize = 4 * 1024 * 1024;
+
+const REMOTE_PROGRAM: u32 = 0x20008086;
+const REMOTE_PROTOCOL_VERSION: u32 = 1;
+const PROC_CONNECT_OPEN: u32 = 1;
+const PROC_CONNECT_CLOSE: u32 = 2;
+const PROC_DOMAIN_GET_XML_DESC: u32 = 14;
+const PROC_CONNECT_LIST_ALL_DOMAINS: u32 = 273;
+const MESSAGE_TYPE_CALL: u32 = 0;
+const MESSAGE_STATUS_OK: u32 = 0;
+const CONNECT_READ_ONLY: u32 = 1;
+/// Length prefix plus the six header words
+const HEADER_LEN: usize = 28;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibvirtDomain {
+    pub name: String,
+    pub uuid: Uuid,
+    pub interfaces: Vec<LibvirtDomainInterface>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibvirtDomainInterface {
+    pub mac_address: MacAddress,
+    /// Libvirt network or host bridge the interface is attached to
+    pub source: Option<String>,
+}
+
+/// Domain reference as it appears on the wire (`remote_nonnull_domain`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DomainRef {
+    name: String,
+    uuid: [u8; 16],
+    id: i32,
+}
+
+#[cfg(unix)]
+pub struct LibvirtClient {
+    stream: tokio::net::UnixStream,
+    serial: u32,
+}
+
+#[cfg(unix)]
+impl LibvirtClient {
+    /// Connect to the first read-only socket that accepts a connection
+    pub async fn connect() -> Result<Self> {
+        let mut last_error = None;
+
+        for path in SOCKET_PATHS {
+            match tokio::net::UnixStream::connect(path).await {
+                Ok(stream) => {
+                    let mut client = Self { stream, serial: 0 };
+                    client.open().await?;
+                    return Ok(client);
+                }
+                Err(e) => last_error = Some(anyhow!("{}: {}", path, e)),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow!("No libvirt socket found")))
+    }
+
+    async fn call(&mut self, procedure: u32, args: XdrWriter) -> Result<XdrReader> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        self.serial += 1;
+        let serial = self.serial;
+        let message = encode_call(procedure, serial, args);
+
+        tokio::time::timeout(REQUEST_TIMEOUT, async {
+            self.stream.write_all(&message).await?;
+
+            let mut len = [0u8; 4];
+            self.stream.read_exact(&mut len).await?;
+            let len = u32::from_be_bytes(len) as usize;
+            if !(HEADER_LEN..=MAX_MESSAGE_LEN).contains(&len) {
+                bail!("Invalid libvirt message length {}", len);
+            }
+
+            let mut reply = vec![0u8; len - 4];
+            self.stream.read_exact(&mut reply).await?;
+
+            decode_reply(reply, procedure, serial)
+        })
+        .await
+        .map_err(|_| anyhow!("Timed out waiting for libvirt"))?
+    }
+
+    async fn open(&mut self) -> Result<()> {
+        let mut args = XdrWriter::default();
+        args.optional_string(Some(CONNECT_URI));
+        args.u32(CONNECT_READ_ONLY);
+        self.call(PROC_CONNECT_OPEN, args).await?;
+        Ok(())
+    }
+
+    async fn list_domain_refs(&mut self) -> Result<Vec<DomainRef>> {
+        let mut args = XdrWriter::default();
+        // need_results, flags (0 lists both running and inactive domains)
+        args.u32(1);
+        args.u32(0);
+        let mut reply = self.call(PROC_CONNECT_LIST_ALL_DOMAINS, args).await?;
+
+        let count = reply.u32()?;
+        (0..count).map(|_| reply.domain()).collect()
+    }
+
+    async fn domain_xml(&mut self, domain: &DomainRef) -> Result<String> {
+        let mut args = XdrWriter::default();
+        args.domain(domain);
+        args.u32(0);
+        self.call(PROC_DOMAIN_GET_XML_DESC, args).await?.string()
+    }
+
+    /// Every domain on the host, running or not
+    pub async fn list_domains(&mut self) -> Result<Vec<LibvirtDomain>> {
+        let mut domains = Vec::new();
+
+        for domain_ref in self.list_domain_refs().await? {
+            let interfaces = match self
+                .domain_xml(&domain_ref)
+                .await
+                .and_then(|xml| parse_domain_interfaces(&xml))
+            {
+                Ok(interfaces) => interfaces,
+                Err(e) => {
+                    tracing::debug!(domain = %domain_ref.name, error = %e, "Failed to read libvirt domain interfaces");
+                    Vec::new()
+                }
+            };
+
+            domains.push(LibvirtDomain {
+                name: domain_ref.name,
+                uuid: Uuid::from_bytes(domain_ref.uuid),
+                interfaces,
+            });
+        }
+
+        Ok(domains)
+    }
+
+    pub async fn close(mut self) {
+        let _ = self.call(PROC_CONNECT_CLOSE, XdrWriter::default()).await;
+    }
+}
+
+#[cfg(not(unix))]
+pub struct LibvirtClient;
+
+#[cfg(not(unix))]
+impl LibvirtClient {
+    pub async fn connect() -> Result<Self> {
+        Err(anyhow!("libvirt is only supported on unix hosts"))
+    }
+
+    pub async fn list_domains(&mut self) -> Result<Vec<LibvirtDomain>> {
+        Ok(Vec::new())
+    }
+
+    pub async fn close(self) {}
+}
+
+fn encode_call(procedure: u32, serial: u32, args: XdrWriter) -> Vec<u8> {
+    let mut header = XdrWriter::default();
+    header.u32(REMOTE_PROGRAM);
+    header.u32(REMOTE_PROTOCOL_VERSION);
+    header.u32(procedure);
+    header.u32(MESSAGE_TYPE_CALL);
+    header.u32(serial);
+    header.u32(MESSAGE_STATUS_OK);
+
+    let len = (4 + header.buf.len() + args.buf.len()) as u32;
+    let mut message = len.to_be_bytes().to_vec();
+    message.extend(header.buf);
+    message.extend(args.buf);
+    message
+}
+
+/// Check a reply's header (without the length prefix) and return a reader over its body
+fn decode_reply(reply: Vec<u8>, procedure: u32, serial: u32) -> Result<XdrReader> {
+    let mut reader = XdrReader::new(reply);
+
+    let program = reader.u32()?;
+    let _version = reader.u32()?;
+    let reply_procedure = reader.u32()?;
+    let _message_type = reader.u32()?;
+    let reply_serial = reader.u32()?;
+    let status = reader.u32()?;
+
+    if program != REMOTE_PROGRAM || reply_procedure != procedure || reply_serial != serial {
+        bail!("Unexpected libvirt reply to procedure {}", procedure);
+    }
+
+    if status != MESSAGE_STATUS_OK {
+        // remote_error: code, domain, then an optional message
+        let _code = reader.u32()?;
+        let _domain = reader.u32()?;
+        let message = reader
+            .optional_string()?
+            .unwrap_or_else(|| "unknown error".to_string());
+        bail!("libvirt error: {}", message);
+    }
+
+    Ok(reader)
+}
+
+/// MAC addresses and sources of a domain's `<interface>` devices
+fn parse_domain_interfaces(xml: &str) -> Result<Vec<LibvirtDomainInterface>> {
+    let document = roxmltree::Document::parse(xml)?;
+
+    Ok(document
+        .descendants()
+        .filter(|node| node.has_tag_name("interface"))
+        .filter_map(|interface| {
+            let mac_address = interface
+                .children()
+                .find(|child| child.has_tag_name("mac"))
+                .and_then(|mac| mac.attribute("address"))
+                .and_then(|address| MacAddress::from_str(address).ok())?;
+
+            let source = interface
+                .children()
+                .find(|child| child.has_tag_name("source"))
+                .and_then(|source| {
+                    source
+                        .attribute("network")
+                        .or_else(|| source.attribute("bridge"))
+                        .or_else(|| source.attribute("dev"))
+                })
+                .map(str::to_string);
+
+            Some(LibvirtDomainInterface {
+                mac_address,
+                source,
+            })
+        })
+        .collect())
+}
+
+#[derive(Default)]
+struct XdrWriter {
+    buf: Vec<u8>,
+}
+
+impl XdrWriter {
+    fn u32(&mut self, value: u32) {
+        self.buf.extend(value.to_be_bytes());
+    }
+
+    fn opaque(&mut self, bytes: &[u8]) {
+        self.buf.extend(bytes);
+        self.buf.resize(self.buf.len().next_multiple_of(4), 0);
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.opaque(value.as_bytes());
+    }
+
+    fn optional_string(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.u32(1);
+                self.string(value);
+            }
+            None => self.u32(0),
+        }
+    }
+
+    fn domain(&mut self, domain: &DomainRef) {
+        self.string(&domain.name);
+        self.opaque(&domain.uuid);
+        self.u32(domain.id as u32);
+    }
+}
+
+struct XdrReader {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl XdrReader {
+    fn new(buf: Vec<u8>) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let padded = len.next_multiple_of(4);
+        if self.pos + padded > self.buf.len() {
+            bail!("Truncated libvirt message");
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += padded;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>> {
+        match self.u32()? {
+            0 => Ok(None),
+            _ => self.string().map(Some),
+        }
+    }
+
+    fn domain(&mut self) -> Result<DomainRef> {
+        let name = self.string()?;
+        let uuid = self.take(16)?.try_into()?;
+        let id = self.u32()? as i32;
+        Ok(DomainRef { name, uuid, id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_connect_open() {
+        let mut args = XdrWriter::default();
+        args.optional_string(Some("qemu:///system"));
+        args.u32(CONNECT_READ_ONLY);
+
+        let message = encode_call(PROC_CONNECT_OPEN, 1, args);
+
+        // Header, then present flag + length + "qemu:///system" padded to 16 bytes + flags
+        assert_eq!(message.len(), HEADER_LEN + 4 + 4 + 16 + 4);
+        assert_eq!(&message[0..4], &(message.len() as u32).to_be_bytes());
+        assert_eq!(&message[4..8], &REMOTE_PROGRAM.to_be_bytes());
+        assert_eq!(&message[12..16], &PROC_CONNECT_OPEN.to_be_bytes());
+        assert_eq!(&message[20..24], &1u32.to_be_bytes());
+        assert_eq!(&message[28..32], &1u32.to_be_bytes());
+        assert_eq!(&message[32..36], &14u32.to_be_bytes());
+        assert_eq!(&message[36..50], b"qemu:///system");
+        assert_eq!(&message[50..52], &[0, 0]);
+    }
+
+    #[test]
+    fn test_decode_list_all_domains_reply() {
+        let uuid = Uuid::parse_str("4dea22b3-1d52-d8f3-2516-782e98ab3fa0").unwrap();
+        let domains = vec![
+            DomainRef {
+                name: "web".to_string(),
+                uuid: *uuid.as_bytes(),
+                id: 3,
+            },
+            DomainRef {
+                name: "stopped-vm".to_string(),
+                uuid: *Uuid::nil().as_bytes(),
+                id: -1,
+            },
+        ];
+
+        let mut body = XdrWriter::default();
+        body.u32(REMOTE_PROGRAM);
+        body.u32(REMOTE_PROTOCOL_VERSION);
+        body.u32(PROC_CONNECT_LIST_ALL_DOMAINS);
+        body.u32(1);
+        body.u32(7);
+        body.u32(MESSAGE_STATUS_OK);
+        body.u32(domains.len() as u32);
+        domains.iter().for_each(|d| body.domain(d));
+        body.u32(domains.len() as u32);
+
+        let mut reply = decode_reply(body.buf, PROC_CONNECT_LIST_ALL_DOMAINS, 7).unwrap();
+        let count = reply.u32().unwrap();
+        let decoded: Vec<DomainRef> = (0..count).map(|_| reply.domain().unwrap()).collect();
+
+        assert_eq!(decoded, domains);
+    }
+
+    #[test]
+    fn test_decode_error_reply() {
+        let mut body = XdrWriter::default();
+        body.u32(REMOTE_PROGRAM);
+        body.u32(REMOTE_PROTOCOL_VERSION);
+        body.u32(PROC_CONNECT_OPEN);
+        body.u32(1);
+        body.u32(1);
+        body.u32(1);
+        body.u32(38);
+        body.u32(7);
+        body.optional_string(Some("authentication required"));
+
+        let error = decode_reply(body.buf, PROC_CONNECT_OPEN, 1).err().unwrap();
+
+        assert_eq!(error.to_string(), "libvirt error: authentication required");
+    }
+
+    #[test]
+    fn test_parse_domain_interfaces() {
+        let xml = r#"
+            <domain type='kvm' id='3'>
+              <name>web</name>
+              <uuid>4dea22b3-1d52-d8f3-2516-782e98ab3fa0</uuid>
+              <devices>
+                <disk type='file' device='disk'/>
+                <interface type='network'>
+                  <mac address='52:54:00:6b:3c:58'/>
+                  <source network='default' portid='a1b2'/>
+                  <model type='virtio'/>
+                </interface>
+                <interface type='bridge'>
+                  <mac address='52:54:00:aa:bb:cc'/>
+                  <source bridge='br0'/>
+                </interface>
+                <interface type='user'>
+                  <model type='virtio'/>
+                </interface>
+              </devices>
+            </domain>"#;
+
+        assert_eq!(
+            parse_domain_interfaces(xml).unwrap(),
+            vec![
+                LibvirtDomainInterface {
+                    mac_address: MacAddress::from_str("52:54:00:6b:3c:58").unwrap(),
+                    source: Some("default".to_string()),
+                },
+                LibvirtDomainInterface {
+                    mac_address: MacAddress::from_str("52:54:00:aa:bb:cc").unwrap(),
+                    source: Some("br0".to_string()),
+                },
+            ]
+        );
+    }
+}
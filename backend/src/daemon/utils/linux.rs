@@ -119,4 +119,26 @@ impl DaemonUtils for LinuxDaemonUtils {
 
         Ok(None)
     }
+
+    async fn get_ipv6_neighbors(
+        &self,
+        interface_name: &str,
+    ) -> Result<Vec<(std::net::Ipv6Addr, Option<MacAddress>)>, Error> {
+        use tokio::process::Command;
+
+        // /proc has no IPv6 equivalent of /proc/net/arp, so ask iproute2
+        let output = Command::new("ip")
+            .args(["-6", "neigh", "show", "dev", interface_name])
+            .output()
+            .await
+            .map_err(|e| anyhow!("Failed to run ip -6 neigh: {}", e))?;
+
+        if !output.status.success() {
+            return Err(anyhow!("ip -6 neigh exited with status {}", output.status));
+        }
+
+        Ok(crate::daemon::utils::ndp::parse_ip_neigh_output(
+            &String::from_utf8_lossy(&output.stdout),
+        ))
+    }
 }
@@ -0,0 +1,473 @@
+//! Passive LLDP and CDP listening.
+//!
+//! Switches advertise themselves on every port: LLDP roughly every 30 seconds and
+//! CDP every 60. Listening on the daemon's own interfaces for the length of a
+//! discovery session tells us which switch port each interface is plugged into,
+//! without needing SNMP access to the switch.
+//!
+//! Nothing is ever sent, the listener only reads frames addressed to the LLDP
+//! (`01:80:c2:00:00:0e`) and CDP (`01:00:0c:cc:cc:cc`) multicast groups.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use mac_address::MacAddress;
+use pnet::datalink::{self, Channel, NetworkInterface};
+use pnet::packet::Packet;
+use pnet::packet::ethernet::{EtherType, EthernetPacket};
+use pnet::util::MacAddr;
+
+use crate::server::hosts::r#impl::links::PhysicalLink;
+
+/// Minimum time to listen before giving up on hearing a neighbor. Covers one
+/// LLDP advertisement interval; CDP-only switches may need a longer session.
+pub const MIN_LISTEN_DURATION: Duration = Duration::from_secs(35);
+
+const LLDP_ETHERTYPE: EtherType = EtherType(0x88cc);
+const CDP_MULTICAST: MacAddr = MacAddr(0x01, 0x00, 0x0c, 0xcc, 0xcc, 0xcc);
+/// LLC/SNAP header carrying Cisco's OUI and the CDP protocol ID
+const CDP_SNAP_HEADER: [u8; 8] = [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x0c, 0x20, 0x00];
+
+/// LLDP TLV types (IEEE 802.1AB section 8.4)
+const LLDP_TLV_END: u8 = 0;
+const LLDP_TLV_CHASSIS_ID: u8 = 1;
+const LLDP_TLV_PORT_ID: u8 = 2;
+const LLDP_TLV_PORT_DESC: u8 = 4;
+const LLDP_TLV_SYS_NAME: u8 = 5;
+const LLDP_TLV_MGMT_ADDR: u8 = 8;
+
+const CHASSIS_ID_SUBTYPE_MAC_ADDRESS: u8 = 4;
+const CHASSIS_ID_SUBTYPE_NETWORK_ADDRESS: u8 = 5;
+const PORT_ID_SUBTYPE_MAC_ADDRESS: u8 = 3;
+
+/// IANA address family numbers, used by the LLDP management address TLV
+const ADDRESS_FAMILY_IPV4: u8 = 1;
+const ADDRESS_FAMILY_IPV6: u8 = 2;
+
+/// CDP TLV types
+const CDP_TLV_DEVICE_ID: u16 = 0x0001;
+const CDP_TLV_ADDRESSES: u16 = 0x0002;
+const CDP_TLV_PORT_ID: u16 = 0x0003;
+const CDP_TLV_MGMT_ADDRESSES: u16 = 0x0016;
+
+/// CDP address protocols: NLPID 0xCC for IPv4, an 802.2 SNAP header for IPv6
+const CDP_PROTOCOL_IPV4: &[u8] = &[0xcc];
+const CDP_PROTOCOL_IPV6: &[u8] = &[0xaa, 0xaa, 0x03, 0x00, 0x00, 0x00, 0x86, 0xdd];
+
+/// A background listener for LLDP and CDP advertisements on one interface.
+/// Stops when finished or dropped.
+pub struct LinkListener {
+    interface_name: String,
+    started_at: Instant,
+    stop: Arc<AtomicBool>,
+    neighbors: Arc<Mutex<Vec<PhysicalLink>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Start listening for neighbor advertisements on `interface`.
+///
+/// Links are reported with the interface's name as the local port.
+pub fn listen(interface: &NetworkInterface) -> Result<LinkListener> {
+    let own_mac = interface
+        .mac
+        .ok_or_else(|| anyhow!("Interface {} has no MAC address", interface.name))?;
+
+    let config = datalink::Config {
+        read_timeout: Some(Duration::from_millis(250)),
+        read_buffer_size: 65536,
+        ..Default::default()
+    };
+
+    let mut rx = match datalink::channel(interface, config)? {
+        Channel::Ethernet(_, rx) => rx,
+        _ => return Err(anyhow!("Unsupported channel type")),
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let neighbors: Arc<Mutex<Vec<PhysicalLink>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let handle = {
+        let stop = stop.clone();
+        let neighbors = neighbors.clone();
+        let local_port = interface.name.clone();
+
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let Ok(frame) = rx.next() else {
+                    // Read timeout, check the stop flag again
+                    continue;
+                };
+
+                let Some(link) = parse_frame(frame, own_mac, &local_port) else {
+                    continue;
+                };
+
+                let mut neighbors = neighbors.lock().unwrap();
+                // Advertisements repeat, keep the latest from each neighbor port
+                neighbors.retain(|n| {
+                    n.remote_chassis_id != link.remote_chassis_id
+                        || n.remote_port != link.remote_port
+                });
+                tracing::debug!(
+                    interface = %local_port,
+                    chassis_id = %link.remote_chassis_id,
+                    port = ?link.remote_port,
+                    system_name = ?link.remote_system_name,
+                    "Heard link neighbor"
+                );
+                neighbors.push(link);
+            }
+        })
+    };
+
+    tracing::debug!(interface = %interface.name, "Listening for LLDP/CDP advertisements");
+
+    Ok(LinkListener {
+        interface_name: interface.name.clone(),
+        started_at: Instant::now(),
+        stop,
+        neighbors,
+        handle: Some(handle),
+    })
+}
+
+impl LinkListener {
+    /// Stop listening and return the neighbors heard.
+    ///
+    /// Blocks until the listener has been running for [`MIN_LISTEN_DURATION`] unless a
+    /// neighbor has already been heard, so call from a blocking context.
+    pub fn finish(mut self) -> Vec<PhysicalLink> {
+        while self.neighbors.lock().unwrap().is_empty()
+            && self.started_at.elapsed() < MIN_LISTEN_DURATION
+        {
+            std::thread::sleep(Duration::from_millis(250));
+        }
+
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            tracing::warn!(interface = %self.interface_name, "Link listener thread panicked");
+        }
+
+        std::mem::take(&mut *self.neighbors.lock().unwrap())
+    }
+}
+
+impl Drop for LinkListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Parse an Ethernet frame carrying an LLDP or CDP advertisement
+fn parse_frame(frame: &[u8], own_mac: MacAddr, local_port: &str) -> Option<PhysicalLink> {
+    let ethernet = EthernetPacket::new(frame)?;
+
+    if ethernet.get_source() == own_mac {
+        return None;
+    }
+
+    let source_mac = mac_from_bytes(&ethernet.get_source().octets());
+
+    if ethernet.get_ethertype() == LLDP_ETHERTYPE {
+        parse_lldp(ethernet.payload(), source_mac, local_port)
+    } else if ethernet.get_destination() == CDP_MULTICAST {
+        // CDP uses 802.3 framing, the ethertype field holds the frame length
+        parse_cdp(ethernet.payload(), source_mac, local_port)
+    } else {
+        None
+    }
+}
+
+fn parse_lldp(
+    mut payload: &[u8],
+    source_mac: Option<MacAddress>,
+    local_port: &str,
+) -> Option<PhysicalLink> {
+    let mut chassis_id: Option<(u8, &[u8])> = None;
+    let mut port_id: Option<(u8, &[u8])> = None;
+    let mut port_description = None;
+    let mut system_name = None;
+    let mut management_address = None;
+
+    while payload.len() >= 2 {
+        // 7 bit type, 9 bit length
+        let tlv_type = payload[0] >> 1;
+        let length = (((payload[0] & 0x01) as usize) << 8) | payload[1] as usize;
+        let value = payload.get(2..2 + length)?;
+        payload = &payload[2 + length..];
+
+        match tlv_type {
+            LLDP_TLV_END => break,
+            LLDP_TLV_CHASSIS_ID => chassis_id = value.split_first().map(|(s, v)| (*s, v)),
+            LLDP_TLV_PORT_ID => port_id = value.split_first().map(|(s, v)| (*s, v)),
+            LLDP_TLV_PORT_DESC => port_description = text_from_bytes(value),
+            LLDP_TLV_SYS_NAME => system_name = text_from_bytes(value),
+            LLDP_TLV_MGMT_ADDR if management_address.is_none() => {
+                // Address string length (including the family byte), family, address
+                let (address_length, rest) = value.split_first()?;
+                let address = rest.get(..*address_length as usize)?;
+                management_address = ip_from_family_bytes(address);
+            }
+            _ => {}
+        }
+    }
+
+    // Chassis ID and port ID are mandatory
+    let (chassis_subtype, chassis_bytes) = chassis_id?;
+    let (port_subtype, port_bytes) = port_id?;
+
+    let chassis_mac = match chassis_subtype {
+        CHASSIS_ID_SUBTYPE_MAC_ADDRESS => mac_from_bytes(chassis_bytes),
+        _ => None,
+    };
+    let chassis_ip = match chassis_subtype {
+        CHASSIS_ID_SUBTYPE_NETWORK_ADDRESS => ip_from_family_bytes(chassis_bytes),
+        _ => None,
+    };
+    let port_mac = match port_subtype {
+        PORT_ID_SUBTYPE_MAC_ADDRESS => mac_from_bytes(port_bytes),
+        _ => None,
+    };
+
+    let remote_chassis_id = chassis_mac
+        .map(|mac| mac.to_string())
+        .or_else(|| chassis_ip.map(|ip| ip.to_string()))
+        .or_else(|| text_from_bytes(chassis_bytes))?;
+
+    let remote_port = port_description.or_else(|| {
+        port_mac
+            .map(|mac| mac.to_string())
+            .or_else(|| text_from_bytes(port_bytes))
+    });
+
+    Some(PhysicalLink {
+        local_port: local_port.to_string(),
+        speed_mbps: None,
+        is_up: true,
+        remote_chassis_id,
+        // The chassis MAC is usually the switch's management MAC, the frame's source
+        // is only the advertising port
+        remote_mac_address: chassis_mac.or(source_mac),
+        remote_port,
+        remote_system_name: system_name,
+        remote_ip_address: management_address.or(chassis_ip),
+    })
+}
+
+fn parse_cdp(
+    payload: &[u8],
+    source_mac: Option<MacAddress>,
+    local_port: &str,
+) -> Option<PhysicalLink> {
+    // SNAP header, then version, TTL and checksum
+    let mut payload = payload.strip_prefix(CDP_SNAP_HEADER.as_slice())?.get(4..)?;
+
+    let mut device_id = None;
+    let mut port_id = None;
+    let mut management_address = None;
+    let mut address = None;
+
+    while payload.len() >= 4 {
+        let tlv_type = u16::from_be_bytes([payload[0], payload[1]]);
+        // Length includes the 4 byte header
+        let length = u16::from_be_bytes([payload[2], payload[3]]) as usize;
+        if length < 4 {
+            break;
+        }
+        let value = payload.get(4..length)?;
+        payload = &payload[length..];
+
+        match tlv_type {
+            CDP_TLV_DEVICE_ID => device_id = text_from_bytes(value),
+            CDP_TLV_PORT_ID => port_id = text_from_bytes(value),
+            CDP_TLV_ADDRESSES => address = parse_cdp_addresses(value),
+            CDP_TLV_MGMT_ADDRESSES => management_address = parse_cdp_addresses(value),
+            _ => {}
+        }
+    }
+
+    let device_id = device_id?;
+
+    Some(PhysicalLink {
+        local_port: local_port.to_string(),
+        speed_mbps: None,
+        is_up: true,
+        remote_chassis_id: device_id.clone(),
+        remote_mac_address: source_mac,
+        remote_port: port_id,
+        remote_system_name: Some(device_id),
+        remote_ip_address: management_address.or(address),
+    })
+}
+
+/// First IP in a CDP address list: a 4 byte count, then per address the protocol
+/// type, protocol length, protocol, a 2 byte address length and the address
+fn parse_cdp_addresses(value: &[u8]) -> Option<IpAddr> {
+    let count = u32::from_be_bytes(value.get(..4)?.try_into().ok()?);
+    let mut rest = &value[4..];
+
+    for _ in 0..count {
+        let protocol_length = *rest.get(1)? as usize;
+        let protocol = rest.get(2..2 + protocol_length)?;
+        rest = &rest[2 + protocol_length..];
+
+        let address_length = u16::from_be_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+        let address = rest.get(2..2 + address_length)?;
+        rest = &rest[2 + address_length..];
+
+        let ip = match protocol {
+            CDP_PROTOCOL_IPV4 => <[u8; 4]>::try_from(address).ok().map(IpAddr::from),
+            CDP_PROTOCOL_IPV6 => <[u8; 16]>::try_from(address).ok().map(IpAddr::from),
+            _ => None,
+        };
+        if ip.is_some() {
+            return ip;
+        }
+    }
+
+    None
+}
+
+/// Decode an address prefixed with its IANA address family
+fn ip_from_family_bytes(bytes: &[u8]) -> Option<IpAddr> {
+    let (family, address) = bytes.split_first()?;
+    match *family {
+        ADDRESS_FAMILY_IPV4 => <[u8; 4]>::try_from(address).ok().map(IpAddr::from),
+        ADDRESS_FAMILY_IPV6 => <[u8; 16]>::try_from(address).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
+
+fn mac_from_bytes(bytes: &[u8]) -> Option<MacAddress> {
+    let octets: [u8; 6] = bytes.try_into().ok()?;
+    (octets != [0; 6]).then(|| MacAddress::new(octets))
+}
+
+fn text_from_bytes(bytes: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(bytes)
+        .trim_matches(char::from(0))
+        .trim()
+        .to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWN_MAC: MacAddr = MacAddr(0x02, 0x00, 0x00, 0x00, 0x00, 0x01);
+    const SWITCH_PORT_MAC: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x0c];
+
+    fn lldp_tlv(tlv_type: u8, value: &[u8]) -> Vec<u8> {
+        let header = ((tlv_type as u16) << 9) | value.len() as u16;
+        [header.to_be_bytes().as_slice(), value].concat()
+    }
+
+    fn cdp_tlv(tlv_type: u16, value: &[u8]) -> Vec<u8> {
+        let length = (value.len() + 4) as u16;
+        [
+            tlv_type.to_be_bytes().as_slice(),
+            length.to_be_bytes().as_slice(),
+            value,
+        ]
+        .concat()
+    }
+
+    fn frame(destination: [u8; 6], ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        [
+            destination.as_slice(),
+            SWITCH_PORT_MAC.as_slice(),
+            ethertype.to_be_bytes().as_slice(),
+            payload,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_parse_lldp_frame() {
+        let payload = [
+            lldp_tlv(
+                LLDP_TLV_CHASSIS_ID,
+                &[4, 0x00, 0x11, 0x22, 0x33, 0x44, 0x00],
+            ),
+            lldp_tlv(LLDP_TLV_PORT_ID, b"\x05gi12"),
+            lldp_tlv(3, &[0x00, 0x78]),
+            lldp_tlv(LLDP_TLV_PORT_DESC, b"GigabitEthernet1/0/12"),
+            lldp_tlv(LLDP_TLV_SYS_NAME, b"core-sw1.example.com"),
+            lldp_tlv(
+                LLDP_TLV_MGMT_ADDR,
+                &[5, 1, 192, 168, 1, 2, 2, 0, 0, 0, 1, 0],
+            ),
+            lldp_tlv(LLDP_TLV_END, &[]),
+        ]
+        .concat();
+        let frame = frame([0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e], 0x88cc, &payload);
+
+        let link = parse_frame(&frame, OWN_MAC, "eth0").unwrap();
+
+        assert_eq!(link.local_port, "eth0");
+        assert_eq!(link.remote_chassis_id, "00:11:22:33:44:00");
+        assert_eq!(
+            link.remote_mac_address,
+            Some(MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x00]))
+        );
+        assert_eq!(link.remote_port.as_deref(), Some("GigabitEthernet1/0/12"));
+        assert_eq!(
+            link.remote_system_name.as_deref(),
+            Some("core-sw1.example.com")
+        );
+        assert_eq!(link.remote_ip_address, Some("192.168.1.2".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_cdp_frame() {
+        let addresses = [
+            1u32.to_be_bytes().as_slice(),
+            &[1, 1, 0xcc],
+            4u16.to_be_bytes().as_slice(),
+            &[10, 0, 0, 2],
+        ]
+        .concat();
+        let tlvs = [
+            cdp_tlv(CDP_TLV_DEVICE_ID, b"access-sw2"),
+            cdp_tlv(CDP_TLV_ADDRESSES, &addresses),
+            cdp_tlv(CDP_TLV_PORT_ID, b"FastEthernet0/3"),
+        ]
+        .concat();
+        let payload = [CDP_SNAP_HEADER.as_slice(), &[2, 180, 0, 0], &tlvs].concat();
+        let frame = frame(
+            [0x01, 0x00, 0x0c, 0xcc, 0xcc, 0xcc],
+            payload.len() as u16,
+            &payload,
+        );
+
+        let link = parse_frame(&frame, OWN_MAC, "eth1").unwrap();
+
+        assert_eq!(link.local_port, "eth1");
+        assert_eq!(link.remote_chassis_id, "access-sw2");
+        assert_eq!(link.remote_system_name.as_deref(), Some("access-sw2"));
+        assert_eq!(link.remote_port.as_deref(), Some("FastEthernet0/3"));
+        assert_eq!(link.remote_ip_address, Some("10.0.0.2".parse().unwrap()));
+        assert_eq!(
+            link.remote_mac_address,
+            Some(MacAddress::new(SWITCH_PORT_MAC))
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_other_frames() {
+        let arp = frame([0xff; 6], 0x0806, &[0; 28]);
+        assert!(parse_frame(&arp, OWN_MAC, "eth0").is_none());
+
+        // LLDP without the mandatory port ID
+        let payload = lldp_tlv(LLDP_TLV_CHASSIS_ID, b"\x07switch");
+        let lldp = frame([0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e], 0x88cc, &payload);
+        assert!(parse_frame(&lldp, OWN_MAC, "eth0").is_none());
+    }
+}
@@ -0,0 +1,116 @@
+//! Link-Local Multicast Name Resolution reverse lookups (RFC 4795).
+//!
+//! Windows answers LLMNR on UDP 5355 even when nothing registered a PTR record in
+//! DNS, so a unicast reverse query to the host itself usually names it.
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::Result;
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, RData, RecordType};
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+pub const LLMNR_TIMEOUT: Duration = Duration::from_millis(600);
+const LLMNR_PORT: u16 = 5355;
+
+/// Ask a host for its own name. Returns `None` if it doesn't answer.
+pub async fn reverse_lookup(ip: IpAddr) -> Result<Option<String>> {
+    let bind_addr: SocketAddr = match ip {
+        IpAddr::V4(_) => "0.0.0.0:0".parse()?,
+        IpAddr::V6(_) => "[::]:0".parse()?,
+    };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    let target = SocketAddr::new(ip, LLMNR_PORT);
+
+    let transaction_id = fastrand::u16(..);
+    socket
+        .send_to(&reverse_query(transaction_id, ip)?, target)
+        .await?;
+
+    let mut buffer = [0u8; 1024];
+    let result = timeout(LLMNR_TIMEOUT, async {
+        loop {
+            let (length, from) = socket.recv_from(&mut buffer).await?;
+            if from.ip() == ip {
+                return Ok::<_, anyhow::Error>(length);
+            }
+        }
+    })
+    .await;
+
+    match result {
+        Ok(Ok(length)) => Ok(parse_reverse_answer(&buffer[..length], transaction_id)),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+fn reverse_query(transaction_id: u16, ip: IpAddr) -> Result<Vec<u8>> {
+    let mut message = Message::new();
+    message
+        .set_id(transaction_id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .add_query(Query::query(Name::from(ip), RecordType::PTR));
+
+    Ok(message.to_vec()?)
+}
+
+fn parse_reverse_answer(packet: &[u8], transaction_id: u16) -> Option<String> {
+    let message = Message::from_vec(packet).ok()?;
+
+    if message.id() != transaction_id || message.message_type() != MessageType::Response {
+        return None;
+    }
+
+    message
+        .answers()
+        .iter()
+        .find_map(|record| match record.data() {
+            RData::PTR(ptr) => Some(ptr.0.to_utf8().trim_end_matches('.').to_string()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_proto::rr::Record;
+    use hickory_proto::rr::rdata::PTR;
+
+    #[test]
+    fn test_parse_reverse_answer() {
+        let ip: IpAddr = "192.168.1.20".parse().unwrap();
+
+        let mut message = Message::new();
+        message
+            .set_id(42)
+            .set_message_type(MessageType::Response)
+            .add_answer(Record::from_rdata(
+                Name::from(ip),
+                30,
+                RData::PTR(PTR(Name::from_utf8("DESKTOP-4F2K9.").unwrap())),
+            ));
+        let packet = message.to_vec().unwrap();
+
+        // Names come off the wire lowercased
+        assert_eq!(
+            parse_reverse_answer(&packet, 42).as_deref(),
+            Some("desktop-4f2k9")
+        );
+        assert_eq!(parse_reverse_answer(&packet, 43), None);
+    }
+
+    #[test]
+    fn test_reverse_query_asks_for_ptr() {
+        let ip: IpAddr = "10.0.0.5".parse().unwrap();
+        let packet = reverse_query(7, ip).unwrap();
+        let message = Message::from_vec(&packet).unwrap();
+
+        let query = &message.queries()[0];
+        assert_eq!(query.query_type(), RecordType::PTR);
+        assert_eq!(query.name().to_utf8(), "5.0.0.10.in-addr.arpa.");
+    }
+}
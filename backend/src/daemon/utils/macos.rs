@@ -169,4 +169,37 @@ impl DaemonUtils for MacOsDaemonUtils {
 
         Ok(None)
     }
+
+    async fn get_ipv6_neighbors(
+        &self,
+        interface_name: &str,
+    ) -> Result<Vec<(std::net::Ipv6Addr, Option<MacAddress>)>, Error> {
+        use tokio::process::Command;
+
+        let output = Command::new("ndp").args(["-an"]).output().await?;
+
+        if !output.status.success() {
+            return Err(anyhow!("ndp command failed with status: {}", output.status));
+        }
+
+        // Parse macOS ndp output: "2001:db8::5   0:11:22:33:44:55   en0 23h59m58s S R"
+        // Scoped addresses carry their zone ("fe80::1%en0"), unresolved ones show "(incomplete)"
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let neighbors = output_str
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                if tokens.len() < 3 || tokens[2] != interface_name {
+                    return None;
+                }
+                let address = tokens[0].split('%').next()?;
+                let ip: std::net::Ipv6Addr = address.parse().ok()?;
+                let mac = self.parse_macos_mac_address(tokens[1]).ok();
+                Some((ip, mac))
+            })
+            .collect();
+
+        Ok(neighbors)
+    }
 }
@@ -0,0 +1,410 @@
+//! DNS-SD service discovery over multicast DNS.
+//!
+//! Devices like Chromecasts, speakers, printers and ESPHome nodes often have few
+//! open TCP ports but announce everything they offer over mDNS. Browsing a link:
+//!
+//! 1. Ask for `_services._dns-sd._udp.local` to learn which service types exist
+//! 2. Ask for each service type to learn its instances
+//! 3. Ask for the SRV/TXT records of instances that didn't come with them, then
+//!    the addresses of SRV targets that didn't either
+//!
+//! Queries are sent from an ephemeral port, which makes responders answer with
+//! unicast (RFC 6762 section 6.7) so we never have to join the multicast group.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::Result;
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, RData, RecordType};
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::UdpSocket;
+use tokio::time::{Instant, timeout_at};
+
+use crate::server::ports::r#impl::base::PortType;
+use crate::server::services::r#impl::endpoints::MdnsResponse;
+
+const MDNS_GROUP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 251)), 5353);
+const SERVICE_TYPES_QUERY: &str = "_services._dns-sd._udp.local.";
+
+/// How long to collect answers after each round of queries
+const ROUND_WAIT: Duration = Duration::from_millis(1500);
+/// Questions per query, keeps queries well inside a single datagram
+const QUESTIONS_PER_QUERY: usize = 16;
+
+/// Every record seen while browsing, keyed by lowercased name without the trailing dot
+#[derive(Debug, Default)]
+struct MdnsRecords {
+    /// Service type or meta query -> instance names / service types
+    pointers: HashMap<String, HashSet<String>>,
+    /// Instance -> (target, port)
+    services: HashMap<String, (String, u16)>,
+    /// Instance -> TXT strings
+    texts: HashMap<String, Vec<String>>,
+    /// Host name -> addresses
+    addresses: HashMap<String, Vec<IpAddr>>,
+    /// Name -> address of the responder that answered for it
+    responders: HashMap<String, IpAddr>,
+}
+
+impl MdnsRecords {
+    fn ingest(&mut self, message: &Message, responder: IpAddr) {
+        for record in message.answers().iter().chain(message.additionals()) {
+            let name = normalize(record.name());
+            self.responders.entry(name.clone()).or_insert(responder);
+
+            match record.data() {
+                RData::PTR(ptr) => {
+                    // Instances without an SRV record are attributed to whoever announced them
+                    let instance = normalize(&ptr.0);
+                    self.responders.entry(instance.clone()).or_insert(responder);
+                    self.pointers.entry(name).or_default().insert(instance);
+                }
+                RData::SRV(srv) => {
+                    self.services
+                        .insert(name, (normalize(srv.target()), srv.port()));
+                }
+                RData::TXT(txt) => {
+                    let strings = txt
+                        .txt_data()
+                        .iter()
+                        .map(|s| String::from_utf8_lossy(s).to_string())
+                        .collect();
+                    self.texts.insert(name, strings);
+                }
+                RData::A(a) => self.add_address(name, IpAddr::V4(a.0)),
+                RData::AAAA(aaaa) => self.add_address(name, IpAddr::V6(aaaa.0)),
+                _ => {}
+            }
+        }
+    }
+
+    fn add_address(&mut self, name: String, address: IpAddr) {
+        let addresses = self.addresses.entry(name).or_default();
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    fn service_types(&self) -> Vec<String> {
+        self.pointers
+            .get(SERVICE_TYPES_QUERY.trim_end_matches('.'))
+            .map(|types| types.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn instances(&self, service_type: &str) -> Vec<String> {
+        self.pointers
+            .get(service_type)
+            .map(|instances| instances.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Resolve every instance of every service type to the address it runs on
+    fn into_responses(self, service_types: &[String]) -> HashMap<IpAddr, Vec<MdnsResponse>> {
+        let mut responses: HashMap<IpAddr, Vec<MdnsResponse>> = HashMap::new();
+
+        for service_type in service_types {
+            for instance in self.instances(service_type) {
+                let service = self.services.get(&instance);
+
+                // Prefer the SRV target's IPv4 address, responders can answer for other hosts
+                let address = service
+                    .and_then(|(target, _)| self.addresses.get(target))
+                    .and_then(|addresses| {
+                        addresses
+                            .iter()
+                            .find(|a| a.is_ipv4())
+                            .or(addresses.first())
+                            .copied()
+                    })
+                    .or_else(|| self.responders.get(&instance).copied());
+
+                let Some(address) = address else {
+                    continue;
+                };
+
+                responses.entry(address).or_default().push(to_response(
+                    service_type,
+                    &instance,
+                    service,
+                    &self.texts,
+                ));
+            }
+        }
+
+        responses
+    }
+}
+
+fn to_response(
+    service_type: &str,
+    instance: &str,
+    service: Option<&(String, u16)>,
+    texts: &HashMap<String, Vec<String>>,
+) -> MdnsResponse {
+    let service_type_name = service_type.trim_end_matches(".local");
+
+    let instance_name = instance
+        .strip_suffix(service_type)
+        .map(|name| name.trim_end_matches('.'))
+        .unwrap_or(instance)
+        .to_string();
+
+    let port_type = service.map(|(_, port)| {
+        if service_type_name.ends_with("._udp") {
+            PortType::new_udp(*port)
+        } else {
+            PortType::new_tcp(*port)
+        }
+    });
+
+    let txt = texts
+        .get(instance)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (entry.clone(), String::new()),
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    MdnsResponse {
+        service_type: service_type_name.to_string(),
+        instance_name,
+        hostname: service.map(|(target, _)| target.clone()),
+        port_type,
+        txt,
+    }
+}
+
+/// Browse every DNS-SD service advertised on the link attached to `source_ip`,
+/// grouped by the address each service runs on.
+///
+/// # Arguments
+/// * `source_ip` - IPv4 address of the interface to browse from
+pub async fn browse(source_ip: Ipv4Addr) -> Result<HashMap<IpAddr, Vec<MdnsResponse>>> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_multicast_if_v4(&source_ip)?;
+    socket.set_multicast_loop_v4(false)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::new(IpAddr::V4(source_ip), 0).into())?;
+    let socket = UdpSocket::from_std(socket.into())?;
+
+    let mut records = MdnsRecords::default();
+
+    let meta_query = Name::from_ascii(SERVICE_TYPES_QUERY)?;
+    query(&socket, &[(meta_query, RecordType::PTR)], &mut records).await?;
+
+    let service_types = records.service_types();
+    if service_types.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let questions: Vec<(Name, RecordType)> = service_types
+        .iter()
+        .filter_map(|t| Some((fqdn(t)?, RecordType::PTR)))
+        .collect();
+    query(&socket, &questions, &mut records).await?;
+
+    // Most responders include SRV, TXT and addresses with the PTR answer, only ask for what's missing
+    let instances: Vec<String> = service_types
+        .iter()
+        .flat_map(|t| records.instances(t))
+        .collect();
+    let questions: Vec<(Name, RecordType)> = instances
+        .iter()
+        .flat_map(|instance| {
+            let missing_srv = !records.services.contains_key(instance);
+            let missing_txt = !records.texts.contains_key(instance);
+            [
+                missing_srv.then_some(RecordType::SRV),
+                missing_txt.then_some(RecordType::TXT),
+            ]
+            .into_iter()
+            .flatten()
+            .filter_map(|record_type| Some((fqdn(instance)?, record_type)))
+        })
+        .collect();
+    if !questions.is_empty() {
+        query(&socket, &questions, &mut records).await?;
+    }
+
+    let targets: HashSet<&String> = records
+        .services
+        .values()
+        .map(|(target, _)| target)
+        .filter(|target| !records.addresses.contains_key(*target))
+        .collect();
+    let questions: Vec<(Name, RecordType)> = targets
+        .into_iter()
+        .filter_map(|target| Some((fqdn(target)?, RecordType::A)))
+        .collect();
+    if !questions.is_empty() {
+        query(&socket, &questions, &mut records).await?;
+    }
+
+    let responses = records.into_responses(&service_types);
+
+    tracing::debug!(
+        source_ip = %source_ip,
+        service_types = service_types.len(),
+        instances = instances.len(),
+        hosts = responses.len(),
+        "mDNS browse complete"
+    );
+
+    Ok(responses)
+}
+
+/// Send the questions to the mDNS group and collect every answer that arrives
+/// within [`ROUND_WAIT`]
+async fn query(
+    socket: &UdpSocket,
+    questions: &[(Name, RecordType)],
+    records: &mut MdnsRecords,
+) -> Result<()> {
+    for chunk in questions.chunks(QUESTIONS_PER_QUERY) {
+        let mut message = Message::new();
+        message
+            .set_id(0)
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .set_recursion_desired(false);
+        for (name, record_type) in chunk {
+            message.add_query(Query::query(name.clone(), *record_type));
+        }
+
+        socket.send_to(&message.to_vec()?, MDNS_GROUP).await?;
+    }
+
+    let deadline = Instant::now() + ROUND_WAIT;
+    let mut buffer = vec![0u8; 9000];
+
+    while let Ok(received) = timeout_at(deadline, socket.recv_from(&mut buffer)).await {
+        let (length, responder) = received?;
+
+        match Message::from_vec(&buffer[..length]) {
+            Ok(message) if message.message_type() == MessageType::Response => {
+                records.ingest(&message, responder.ip());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::trace!(responder = %responder, error = %e, "Ignoring malformed mDNS packet");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn normalize(name: &Name) -> String {
+    // Joined from the raw labels, to_utf8 would escape the spaces in instance names
+    name.iter()
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(".")
+        .to_lowercase()
+}
+
+/// Instance names are free-form UTF-8 ("Living Room TV"), so labels are taken as raw
+/// bytes rather than parsed as a host name
+fn fqdn(name: &str) -> Option<Name> {
+    Name::from_labels(name.split('.').map(str::as_bytes)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_proto::rr::Record;
+    use hickory_proto::rr::rdata::{A, PTR, SRV, TXT};
+
+    fn name(s: &str) -> Name {
+        fqdn(s.trim_end_matches('.')).unwrap()
+    }
+
+    fn record(owner: &str, rdata: RData) -> Record {
+        Record::from_rdata(name(owner), 120, rdata)
+    }
+
+    #[test]
+    fn test_browse_records_resolve_to_responses() {
+        let responder: IpAddr = "192.168.1.40".parse().unwrap();
+
+        let mut meta = Message::new();
+        meta.set_message_type(MessageType::Response);
+        meta.add_answer(record(
+            SERVICE_TYPES_QUERY,
+            RData::PTR(PTR(name("_googlecast._tcp.local."))),
+        ));
+
+        let mut answer = Message::new();
+        answer.set_message_type(MessageType::Response);
+        answer.add_answer(record(
+            "_googlecast._tcp.local.",
+            RData::PTR(PTR(name("Living Room TV._googlecast._tcp.local."))),
+        ));
+        answer.add_additional(record(
+            "Living Room TV._googlecast._tcp.local.",
+            RData::SRV(SRV::new(0, 0, 8009, name("Chromecast-1a2b3c.local."))),
+        ));
+        answer.add_additional(record(
+            "Living Room TV._googlecast._tcp.local.",
+            RData::TXT(TXT::new(vec![
+                "md=Chromecast".to_string(),
+                "fn=Living Room TV".to_string(),
+            ])),
+        ));
+        answer.add_additional(record(
+            "Chromecast-1a2b3c.local.",
+            RData::A(A::new(192, 168, 1, 41)),
+        ));
+
+        let mut records = MdnsRecords::default();
+        records.ingest(&meta, responder);
+        records.ingest(&answer, responder);
+
+        let service_types = records.service_types();
+        assert_eq!(service_types, vec!["_googlecast._tcp.local".to_string()]);
+
+        let responses = records.into_responses(&service_types);
+        let expected_ip: IpAddr = "192.168.1.41".parse().unwrap();
+        let response = &responses[&expected_ip][0];
+
+        assert_eq!(response.service_type, "_googlecast._tcp");
+        assert_eq!(response.instance_name, "living room tv");
+        assert_eq!(
+            response.hostname.as_deref(),
+            Some("chromecast-1a2b3c.local")
+        );
+        assert_eq!(response.port_type, Some(PortType::new_tcp(8009)));
+        assert_eq!(
+            response.txt.get("md").map(String::as_str),
+            Some("Chromecast")
+        );
+    }
+
+    #[test]
+    fn test_instances_without_srv_fall_back_to_responder() {
+        let responder: IpAddr = "192.168.1.50".parse().unwrap();
+
+        let mut answer = Message::new();
+        answer.set_message_type(MessageType::Response);
+        answer.add_answer(record(
+            "_esphomelib._tcp.local.",
+            RData::PTR(PTR(name("kitchen-sensor._esphomelib._tcp.local."))),
+        ));
+
+        let mut records = MdnsRecords::default();
+        records.ingest(&answer, responder);
+
+        let responses = records.into_responses(&["_esphomelib._tcp.local".to_string()]);
+        let response = &responses[&responder][0];
+
+        assert_eq!(response.service_type, "_esphomelib._tcp");
+        assert_eq!(response.instance_name, "kitchen-sensor");
+        assert_eq!(response.port_type, None);
+    }
+}
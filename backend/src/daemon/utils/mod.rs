@@ -1,6 +1,25 @@
 pub mod arp;
 pub mod base;
+pub mod docker_endpoint;
+pub mod docker_labels;
+pub mod docker_swarm;
+pub mod icmp;
+pub mod libvirt;
 pub mod linux;
+pub mod lldp;
+pub mod llmnr;
 pub mod macos;
+pub mod mdns;
+pub mod ndp;
+pub mod netbios;
+pub mod passive;
+pub mod proxmox;
+pub mod reverse_dns;
+pub mod reverse_proxy;
+pub mod rtt;
 pub mod scanner;
+pub mod snmp;
+pub mod ssdp;
+pub mod syn_scan;
+pub mod tls;
 pub mod windows;
@@ -0,0 +1,488 @@
+//! IPv6 host discovery using the Neighbor Discovery Protocol.
+//!
+//! IPv6 subnets are far too large to sweep address by address, so instead of
+//! probing every address we ask the link who is there:
+//!
+//! 1. Harvest the OS neighbor cache (passed in by the caller)
+//! 2. Send an ICMPv6 echo request to the all-nodes group (`ff02::1`) from each
+//!    of our global addresses, so replies come back from the responders' global
+//!    (SLAAC, privacy or DHCPv6) addresses rather than their link-local ones
+//! 3. Send Neighbor Solicitations for cached neighbors that have no link-layer
+//!    address yet
+//!
+//! Every echo reply, Neighbor Advertisement and Neighbor Solicitation carrying
+//! a source link-layer address seen during the scan window is recorded.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use cidr::IpCidr;
+use mac_address::MacAddress;
+use pnet::datalink::{self, Channel, NetworkInterface};
+use pnet::packet::Packet;
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket, MutableEthernetPacket};
+use pnet::packet::icmpv6::{
+    self, Icmpv6Code, Icmpv6Packet, Icmpv6Type, Icmpv6Types, MutableIcmpv6Packet,
+};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv6::{Ipv6Packet, MutableIpv6Packet};
+use pnet::util::MacAddr;
+
+/// Wait time after each round of probes before sending the next one
+pub const ROUND_WAIT: Duration = Duration::from_secs(2);
+/// Extra receive time after the final round
+pub const POST_SCAN_RECEIVE: Duration = Duration::from_secs(3);
+
+/// All-nodes link-local multicast group
+const ALL_NODES: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1);
+/// Identifier used on our echo requests so replies to other tools are ignored
+const ECHO_IDENTIFIER: u16 = 0x5343;
+
+/// NDP option types (RFC 4861 section 4.6)
+const OPTION_SOURCE_LL_ADDR: u8 = 1;
+const OPTION_TARGET_LL_ADDR: u8 = 2;
+
+/// Result of NDP scanning a single address
+#[derive(Debug, Clone)]
+pub struct NdpScanResult {
+    pub ip: Ipv6Addr,
+    pub mac: MacAddress,
+}
+
+/// Discover IPv6 neighbors on a single link.
+///
+/// Blocks for the duration of the scan, so call from a blocking context.
+///
+/// # Arguments
+/// * `interface` - Network interface attached to the link
+/// * `source_mac` - MAC address of `interface`
+/// * `targets` - IPv6 subnets on this link; only addresses inside them are returned
+/// * `cached_neighbors` - Entries from the OS neighbor cache for this interface
+/// * `retries` - Number of additional probe rounds (0 = single round)
+/// * `rate_pps` - Maximum packets per second
+pub fn scan_link(
+    interface: &NetworkInterface,
+    source_mac: MacAddress,
+    targets: &[IpCidr],
+    cached_neighbors: Vec<(Ipv6Addr, Option<MacAddress>)>,
+    retries: u32,
+    rate_pps: u32,
+) -> Result<Vec<NdpScanResult>> {
+    let in_targets = |ip: &Ipv6Addr| targets.iter().any(|c| c.contains(&IpAddr::V6(*ip)));
+
+    let own_ips: Vec<Ipv6Addr> = interface
+        .ips
+        .iter()
+        .filter_map(|ip_net| match ip_net.ip() {
+            IpAddr::V6(ip) => Some(ip),
+            IpAddr::V4(_) => None,
+        })
+        .collect();
+
+    // Echo from each global address on the link so responders answer from theirs
+    let echo_sources: Vec<Ipv6Addr> = own_ips.iter().copied().filter(&in_targets).collect();
+    // Solicitations are conventionally sent from the link-local address
+    let Some(solicit_source) = own_ips
+        .iter()
+        .copied()
+        .find(is_link_local)
+        .or_else(|| echo_sources.first().copied())
+    else {
+        return Err(anyhow!(
+            "No IPv6 address on interface {}, skipping NDP scan",
+            interface.name
+        ));
+    };
+
+    let mut found: HashMap<Ipv6Addr, MacAddress> = HashMap::new();
+    let mut unresolved: Vec<Ipv6Addr> = Vec::new();
+    for (ip, mac) in cached_neighbors {
+        if !in_targets(&ip) || own_ips.contains(&ip) {
+            continue;
+        }
+        match mac {
+            Some(mac) => {
+                found.insert(ip, mac);
+            }
+            None => unresolved.push(ip),
+        }
+    }
+
+    let config = pnet::datalink::Config {
+        read_timeout: Some(Duration::from_millis(50)),
+        read_buffer_size: 65536,
+        write_buffer_size: 65536,
+        ..Default::default()
+    };
+
+    let (mut tx, mut rx) = match datalink::channel(interface, config)? {
+        Channel::Ethernet(tx, rx) => (tx, rx),
+        _ => return Err(anyhow!("Unsupported channel type")),
+    };
+
+    let source_mac_pnet = MacAddr::from(source_mac.bytes());
+    let send_delay = Duration::from_micros(1_000_000 / rate_pps.max(1) as u64);
+    let total_rounds = 1 + retries;
+
+    tracing::debug!(
+        interface = %interface.name,
+        echo_sources = echo_sources.len(),
+        cached = found.len(),
+        unresolved = unresolved.len(),
+        total_rounds,
+        "Starting NDP scan"
+    );
+
+    let mut receive_until = |deadline: Instant, found: &mut HashMap<Ipv6Addr, MacAddress>| {
+        while Instant::now() < deadline {
+            let Ok(packet) = rx.next() else {
+                continue;
+            };
+            if let Some((ip, mac)) = parse_neighbor_packet(packet, source_mac_pnet)
+                && in_targets(&ip)
+                && !own_ips.contains(&ip)
+                && found.insert(ip, mac).is_none()
+            {
+                tracing::debug!(ip = %ip, mac = %mac, "NDP: Host discovered");
+            }
+        }
+    };
+
+    for round in 1..=total_rounds {
+        let mut packets: Vec<Vec<u8>> = echo_sources
+            .iter()
+            .map(|src| build_echo_request(source_mac_pnet, *src, round as u16))
+            .collect();
+        packets.extend(
+            unresolved
+                .iter()
+                .filter(|ip| !found.contains_key(ip))
+                .map(|ip| build_neighbor_solicit(source_mac_pnet, solicit_source, *ip)),
+        );
+
+        for packet in &packets {
+            if let Some(Err(e)) = tx.send_to(packet, None) {
+                tracing::warn!(interface = %interface.name, error = %e, "Failed to send NDP probe");
+            }
+            // Keep reading while rate limiting so replies don't pile up in the buffer
+            receive_until(Instant::now() + send_delay, &mut found);
+        }
+
+        receive_until(Instant::now() + ROUND_WAIT, &mut found);
+        tracing::debug!(round, total_found = found.len(), "NDP round complete");
+    }
+
+    receive_until(Instant::now() + POST_SCAN_RECEIVE, &mut found);
+
+    Ok(found
+        .into_iter()
+        .map(|(ip, mac)| NdpScanResult { ip, mac })
+        .collect())
+}
+
+fn is_link_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+/// Solicited-node multicast address for a target (RFC 4291 section 2.7.1)
+fn solicited_node_multicast(target: Ipv6Addr) -> Ipv6Addr {
+    let o = target.octets();
+    Ipv6Addr::new(
+        0xff02,
+        0,
+        0,
+        0,
+        0,
+        1,
+        0xff00 | o[13] as u16,
+        u16::from_be_bytes([o[14], o[15]]),
+    )
+}
+
+/// Ethernet multicast address for an IPv6 multicast group (RFC 2464 section 7)
+fn multicast_mac(group: Ipv6Addr) -> MacAddr {
+    let o = group.octets();
+    MacAddr::new(0x33, 0x33, o[12], o[13], o[14], o[15])
+}
+
+fn build_icmpv6_frame(
+    source_mac: MacAddr,
+    source_ip: Ipv6Addr,
+    destination_ip: Ipv6Addr,
+    hop_limit: u8,
+    icmp_type: Icmpv6Type,
+    body: &[u8],
+) -> Vec<u8> {
+    let mut icmp_buffer = vec![0u8; 4 + body.len()];
+    {
+        let mut icmp = MutableIcmpv6Packet::new(&mut icmp_buffer).unwrap();
+        icmp.set_icmpv6_type(icmp_type);
+        icmp.set_icmpv6_code(Icmpv6Code::new(0));
+        icmp.set_payload(body);
+        let checksum = icmpv6::checksum(&icmp.to_immutable(), &source_ip, &destination_ip);
+        icmp.set_checksum(checksum);
+    }
+
+    let mut ipv6_buffer = vec![0u8; 40 + icmp_buffer.len()];
+    {
+        let mut ipv6 = MutableIpv6Packet::new(&mut ipv6_buffer).unwrap();
+        ipv6.set_version(6);
+        ipv6.set_payload_length(icmp_buffer.len() as u16);
+        ipv6.set_next_header(IpNextHeaderProtocols::Icmpv6);
+        ipv6.set_hop_limit(hop_limit);
+        ipv6.set_source(source_ip);
+        ipv6.set_destination(destination_ip);
+        ipv6.set_payload(&icmp_buffer);
+    }
+
+    let mut ethernet_buffer = vec![0u8; 14 + ipv6_buffer.len()];
+    {
+        let mut ethernet = MutableEthernetPacket::new(&mut ethernet_buffer).unwrap();
+        ethernet.set_destination(multicast_mac(destination_ip));
+        ethernet.set_source(source_mac);
+        ethernet.set_ethertype(EtherTypes::Ipv6);
+        ethernet.set_payload(&ipv6_buffer);
+    }
+
+    ethernet_buffer
+}
+
+fn build_echo_request(source_mac: MacAddr, source_ip: Ipv6Addr, sequence: u16) -> Vec<u8> {
+    let mut body = Vec::with_capacity(4);
+    body.extend_from_slice(&ECHO_IDENTIFIER.to_be_bytes());
+    body.extend_from_slice(&sequence.to_be_bytes());
+
+    build_icmpv6_frame(
+        source_mac,
+        source_ip,
+        ALL_NODES,
+        1,
+        Icmpv6Types::EchoRequest,
+        &body,
+    )
+}
+
+fn build_neighbor_solicit(source_mac: MacAddr, source_ip: Ipv6Addr, target: Ipv6Addr) -> Vec<u8> {
+    // Reserved (4) + target address (16) + source link-layer address option (8)
+    let mut body = Vec::with_capacity(28);
+    body.extend_from_slice(&[0u8; 4]);
+    body.extend_from_slice(&target.octets());
+    body.extend_from_slice(&[OPTION_SOURCE_LL_ADDR, 1]);
+    body.extend_from_slice(&source_mac.octets());
+
+    build_icmpv6_frame(
+        source_mac,
+        source_ip,
+        solicited_node_multicast(target),
+        255,
+        Icmpv6Types::NeighborSolicit,
+        &body,
+    )
+}
+
+/// Find a link-layer address option of the given type in an NDP options block
+fn find_ll_addr_option(mut options: &[u8], option_type: u8) -> Option<MacAddress> {
+    while options.len() >= 8 {
+        let length = options[1] as usize * 8;
+        if length == 0 || length > options.len() {
+            return None;
+        }
+        if options[0] == option_type && length >= 8 {
+            let mut mac = [0u8; 6];
+            mac.copy_from_slice(&options[2..8]);
+            return Some(MacAddress::new(mac));
+        }
+        options = &options[length..];
+    }
+    None
+}
+
+/// Extract an (address, MAC) pair from an echo reply, Neighbor Advertisement
+/// or Neighbor Solicitation frame. Frames sent by `own_mac` are ignored.
+fn parse_neighbor_packet(packet: &[u8], own_mac: MacAddr) -> Option<(Ipv6Addr, MacAddress)> {
+    let ethernet = EthernetPacket::new(packet)?;
+    if ethernet.get_ethertype() != EtherTypes::Ipv6 || ethernet.get_source() == own_mac {
+        return None;
+    }
+
+    let ipv6 = Ipv6Packet::new(ethernet.payload())?;
+    if ipv6.get_next_header() != IpNextHeaderProtocols::Icmpv6 {
+        return None;
+    }
+
+    let icmp = Icmpv6Packet::new(ipv6.payload())?;
+    let body = icmp.payload();
+    let frame_mac = MacAddress::new(ethernet.get_source().octets());
+
+    match icmp.get_icmpv6_type() {
+        Icmpv6Types::EchoReply => {
+            if body.len() < 2 || u16::from_be_bytes([body[0], body[1]]) != ECHO_IDENTIFIER {
+                return None;
+            }
+            Some((ipv6.get_source(), frame_mac))
+        }
+        Icmpv6Types::NeighborAdvert => {
+            if body.len() < 20 {
+                return None;
+            }
+            let mut target = [0u8; 16];
+            target.copy_from_slice(&body[4..20]);
+            let mac = find_ll_addr_option(&body[20..], OPTION_TARGET_LL_ADDR).unwrap_or(frame_mac);
+            Some((Ipv6Addr::from(target), mac))
+        }
+        Icmpv6Types::NeighborSolicit => {
+            // Duplicate address detection probes come from :: and say nothing about the sender
+            let source = ipv6.get_source();
+            if source.is_unspecified() || body.len() < 20 {
+                return None;
+            }
+            let mac = find_ll_addr_option(&body[20..], OPTION_SOURCE_LL_ADDR)?;
+            Some((source, mac))
+        }
+        _ => None,
+    }
+}
+
+/// Parse `ip -6 neigh show` output into (address, MAC) pairs.
+/// Entries without a link-layer address (INCOMPLETE, FAILED) are returned with `None`.
+pub fn parse_ip_neigh_output(output: &str) -> Vec<(Ipv6Addr, Option<MacAddress>)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let ip: Ipv6Addr = tokens.next()?.parse().ok()?;
+            let mac = tokens
+                .skip_while(|t| *t != "lladdr")
+                .nth(1)
+                .and_then(|m| m.parse::<MacAddress>().ok());
+            Some((ip, mac))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWN_MAC: MacAddr = MacAddr(0x00, 0x11, 0x22, 0x33, 0x44, 0x55);
+    const PEER_MAC: MacAddr = MacAddr(0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF);
+
+    #[test]
+    fn test_solicited_node_multicast() {
+        let target: Ipv6Addr = "2001:db8::1234:5678".parse().unwrap();
+        let expected: Ipv6Addr = "ff02::1:ff34:5678".parse().unwrap();
+        assert_eq!(solicited_node_multicast(target), expected);
+        assert_eq!(
+            multicast_mac(expected),
+            MacAddr::new(0x33, 0x33, 0xff, 0x34, 0x56, 0x78)
+        );
+    }
+
+    #[test]
+    fn test_build_neighbor_solicit_creates_valid_packet() {
+        let source_ip: Ipv6Addr = "fe80::211:22ff:fe33:4455".parse().unwrap();
+        let target: Ipv6Addr = "2001:db8::1234:5678".parse().unwrap();
+
+        let packet = build_neighbor_solicit(OWN_MAC, source_ip, target);
+        assert_eq!(packet.len(), 14 + 40 + 32);
+
+        let eth = EthernetPacket::new(&packet).unwrap();
+        assert_eq!(eth.get_ethertype(), EtherTypes::Ipv6);
+        assert_eq!(
+            eth.get_destination(),
+            multicast_mac(solicited_node_multicast(target))
+        );
+
+        let ipv6 = Ipv6Packet::new(eth.payload()).unwrap();
+        assert_eq!(ipv6.get_hop_limit(), 255);
+        assert_eq!(ipv6.get_destination(), solicited_node_multicast(target));
+
+        let icmp = Icmpv6Packet::new(ipv6.payload()).unwrap();
+        assert_eq!(icmp.get_icmpv6_type(), Icmpv6Types::NeighborSolicit);
+        assert_eq!(
+            icmp.get_checksum(),
+            icmpv6::checksum(&icmp, &source_ip, &solicited_node_multicast(target))
+        );
+        assert_eq!(&icmp.payload()[4..20], &target.octets());
+        assert_eq!(
+            find_ll_addr_option(&icmp.payload()[20..], OPTION_SOURCE_LL_ADDR),
+            Some(MacAddress::new(OWN_MAC.octets()))
+        );
+    }
+
+    #[test]
+    fn test_parse_neighbor_advert_extracts_target() {
+        let target: Ipv6Addr = "2001:db8::a8bb:ccff:fedd:eeff".parse().unwrap();
+        let mut body = vec![0x60, 0, 0, 0];
+        body.extend_from_slice(&target.octets());
+        body.extend_from_slice(&[OPTION_TARGET_LL_ADDR, 1]);
+        body.extend_from_slice(&PEER_MAC.octets());
+
+        let packet = build_icmpv6_frame(
+            PEER_MAC,
+            target,
+            "fe80::1".parse().unwrap(),
+            255,
+            Icmpv6Types::NeighborAdvert,
+            &body,
+        );
+
+        let (ip, mac) = parse_neighbor_packet(&packet, OWN_MAC).unwrap();
+        assert_eq!(ip, target);
+        assert_eq!(mac.bytes(), PEER_MAC.octets());
+    }
+
+    #[test]
+    fn test_parse_echo_reply_uses_frame_source() {
+        let source: Ipv6Addr = "2001:db8::5".parse().unwrap();
+        let mut body = ECHO_IDENTIFIER.to_be_bytes().to_vec();
+        body.extend_from_slice(&1u16.to_be_bytes());
+
+        let packet = build_icmpv6_frame(
+            PEER_MAC,
+            source,
+            "2001:db8::1".parse().unwrap(),
+            64,
+            Icmpv6Types::EchoReply,
+            &body,
+        );
+
+        let (ip, mac) = parse_neighbor_packet(&packet, OWN_MAC).unwrap();
+        assert_eq!(ip, source);
+        assert_eq!(mac.bytes(), PEER_MAC.octets());
+    }
+
+    #[test]
+    fn test_parse_ignores_own_and_dad_packets() {
+        let target: Ipv6Addr = "2001:db8::5".parse().unwrap();
+
+        // Our own solicitation
+        let own = build_neighbor_solicit(OWN_MAC, "fe80::1".parse().unwrap(), target);
+        assert!(parse_neighbor_packet(&own, OWN_MAC).is_none());
+
+        // Duplicate address detection from the unspecified address
+        let dad = build_neighbor_solicit(PEER_MAC, Ipv6Addr::UNSPECIFIED, target);
+        assert!(parse_neighbor_packet(&dad, OWN_MAC).is_none());
+    }
+
+    #[test]
+    fn test_parse_ip_neigh_output() {
+        let output = "\
+2001:db8::5 lladdr aa:bb:cc:dd:ee:ff REACHABLE
+fe80::1 lladdr 00:11:22:33:44:55 router STALE
+2001:db8::9 FAILED
+not-an-address lladdr aa:bb:cc:dd:ee:ff STALE
+";
+        let entries = parse_ip_neigh_output(output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].0, "2001:db8::5".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(
+            entries[0].1.map(|m| m.bytes()),
+            Some([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF])
+        );
+        assert!(entries[1].1.is_some());
+        assert_eq!(entries[2].1, None);
+    }
+}
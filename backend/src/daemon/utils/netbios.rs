@@ -0,0 +1,231 @@
+//! NetBIOS Node Status queries (RFC 1002 section 4.2.17).
+//!
+//! Windows hosts and Samba servers answer a node status request on UDP 137 with
+//! every name they have registered. That gives us the computer name when there is
+//! no PTR record, plus the workgroup or domain the host belongs to and whether it
+//! is a domain controller.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+use crate::server::services::r#impl::endpoints::NetbiosResponse;
+
+pub const NETBIOS_TIMEOUT: Duration = Duration::from_millis(600);
+const NETBIOS_PORT: u16 = 137;
+
+const NBSTAT: u16 = 0x0021;
+const CLASS_IN: u16 = 0x0001;
+const GROUP_NAME_FLAG: u16 = 0x8000;
+
+/// Workstation / file server name, or the workgroup when registered as a group
+const SUFFIX_WORKSTATION: u8 = 0x00;
+/// Domain controllers register the domain name with this suffix as a group
+const SUFFIX_DOMAIN_CONTROLLERS: u8 = 0x1c;
+
+/// Ask a host for its registered NetBIOS names. Returns `None` if it doesn't answer.
+pub async fn node_status(ip: Ipv4Addr) -> Result<Option<NetbiosResponse>> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    let target = SocketAddr::from((ip, NETBIOS_PORT));
+
+    let transaction_id = fastrand::u16(..);
+    socket
+        .send_to(&node_status_request(transaction_id), target)
+        .await?;
+
+    let mut buffer = [0u8; 1024];
+    let result = timeout(NETBIOS_TIMEOUT, async {
+        loop {
+            let (length, from) = socket.recv_from(&mut buffer).await?;
+            if from.ip() == target.ip() {
+                return Ok::<_, anyhow::Error>(length);
+            }
+        }
+    })
+    .await;
+
+    match result {
+        Ok(Ok(length)) => parse_node_status(&buffer[..length], transaction_id).map(Some),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Node status request for the wildcard name "*"
+fn node_status_request(transaction_id: u16) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(50);
+    packet.extend_from_slice(&transaction_id.to_be_bytes());
+    // Flags, 1 question, no answer/authority/additional records
+    packet.extend_from_slice(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    let mut name = [0u8; 16];
+    name[0] = b'*';
+    packet.push(32);
+    for byte in name {
+        packet.push(b'A' + (byte >> 4));
+        packet.push(b'A' + (byte & 0x0f));
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&NBSTAT.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    packet
+}
+
+fn parse_node_status(packet: &[u8], transaction_id: u16) -> Result<NetbiosResponse> {
+    let read_u16 = |offset: usize| -> Result<u16> {
+        packet
+            .get(offset..offset + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(|| anyhow!("Truncated node status response"))
+    };
+
+    if read_u16(0)? != transaction_id {
+        return Err(anyhow!("Node status response has the wrong transaction ID"));
+    }
+    if read_u16(6)? == 0 {
+        return Err(anyhow!("Node status response has no answers"));
+    }
+
+    // Answer name is either a compression pointer or the full encoded name
+    let mut offset = 12;
+    match packet.get(offset) {
+        Some(length) if length & 0xc0 == 0xc0 => offset += 2,
+        Some(_) => {
+            while let Some(&length) = packet.get(offset) {
+                offset += 1;
+                if length == 0 {
+                    break;
+                }
+                offset += length as usize;
+            }
+        }
+        None => return Err(anyhow!("Truncated node status response")),
+    }
+
+    if read_u16(offset)? != NBSTAT {
+        return Err(anyhow!("Answer is not a node status record"));
+    }
+    // Type, class, TTL and RDLENGTH
+    offset += 10;
+
+    let name_count = *packet
+        .get(offset)
+        .ok_or_else(|| anyhow!("Truncated node status response"))? as usize;
+    offset += 1;
+
+    let mut response = NetbiosResponse::default();
+
+    for _ in 0..name_count {
+        let Some(entry) = packet.get(offset..offset + 18) else {
+            break;
+        };
+        offset += 18;
+
+        let name = String::from_utf8_lossy(&entry[..15]).trim_end().to_string();
+        let suffix = entry[15];
+        let is_group = u16::from_be_bytes([entry[16], entry[17]]) & GROUP_NAME_FLAG != 0;
+
+        if name.is_empty() {
+            continue;
+        }
+
+        match (suffix, is_group) {
+            (SUFFIX_WORKSTATION, false) if response.name.is_none() => response.name = Some(name),
+            (SUFFIX_WORKSTATION, true) if response.workgroup.is_none() => {
+                response.workgroup = Some(name)
+            }
+            (SUFFIX_DOMAIN_CONTROLLERS, true) => {
+                response.is_domain_controller = true;
+                response.workgroup = Some(name);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_entry(name: &str, suffix: u8, group: bool) -> Vec<u8> {
+        let mut entry = format!("{:<15}", name).into_bytes();
+        entry.push(suffix);
+        entry.extend_from_slice(&if group { [0x84, 0x00] } else { [0x04, 0x00] });
+        entry
+    }
+
+    fn response(transaction_id: u16, entries: &[Vec<u8>]) -> Vec<u8> {
+        let request = node_status_request(transaction_id);
+
+        let mut packet = transaction_id.to_be_bytes().to_vec();
+        packet.extend_from_slice(&[0x84, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        // Echo the question name as the answer name
+        packet.extend_from_slice(&request[12..46]);
+        packet.extend_from_slice(&NBSTAT.to_be_bytes());
+        packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+        packet.extend_from_slice(&[0, 0, 0, 0]);
+        let rdata_length = 1 + entries.len() * 18 + 6;
+        packet.extend_from_slice(&(rdata_length as u16).to_be_bytes());
+        packet.push(entries.len() as u8);
+        for entry in entries {
+            packet.extend_from_slice(entry);
+        }
+        packet.extend_from_slice(&[0x00, 0x15, 0x5d, 0x01, 0x02, 0x03]);
+        packet
+    }
+
+    #[test]
+    fn test_request_encodes_wildcard_name() {
+        let request = node_status_request(0x1234);
+
+        assert_eq!(request.len(), 50);
+        assert_eq!(request[12], 32);
+        // "*" is 0x2a, encoded as 'C' 'K'
+        assert_eq!(&request[13..15], b"CK");
+        assert_eq!(&request[15..17], b"AA");
+    }
+
+    #[test]
+    fn test_parse_domain_controller() {
+        let packet = response(
+            7,
+            &[
+                name_entry("DC01", 0x00, false),
+                name_entry("CORP", 0x00, true),
+                name_entry("CORP", 0x1c, true),
+                name_entry("DC01", 0x20, false),
+            ],
+        );
+
+        let response = parse_node_status(&packet, 7).unwrap();
+
+        assert_eq!(response.name.as_deref(), Some("DC01"));
+        assert_eq!(response.workgroup.as_deref(), Some("CORP"));
+        assert!(response.is_domain_controller);
+    }
+
+    #[test]
+    fn test_parse_workstation() {
+        let packet = response(
+            9,
+            &[
+                name_entry("NAS", 0x00, false),
+                name_entry("WORKGROUP", 0x00, true),
+            ],
+        );
+
+        let response = parse_node_status(&packet, 9).unwrap();
+
+        assert_eq!(response.name.as_deref(), Some("NAS"));
+        assert_eq!(response.workgroup.as_deref(), Some("WORKGROUP"));
+        assert!(!response.is_domain_controller);
+
+        assert!(parse_node_status(&packet, 10).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+//! Passive host discovery from broadcast and multicast traffic.
+//!
+//! Some networks (medical devices, OT equipment) can't tolerate port scans, but
+//! every host on them still talks on the link:
+//!
+//! - ARP requests and replies pair an IPv4 address with a MAC
+//! - DHCP requests carry the client's hostname (option 12) and vendor class
+//!   (option 60), plus the address it is asking for
+//! - mDNS announcements carry the names a host answers to
+//!
+//! Nothing is ever sent, the listener only reads frames that reach the interface.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use dhcproto::Decoder;
+use dhcproto::v4::{self, Decodable};
+use hickory_proto::op::{Message, MessageType};
+use hickory_proto::rr::RData;
+use mac_address::MacAddress;
+use pnet::datalink::{self, Channel, NetworkInterface};
+use pnet::packet::Packet;
+use pnet::packet::arp::ArpPacket;
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::udp::UdpPacket;
+use pnet::util::MacAddr;
+
+use crate::server::shared::types::entities::HostnameSource;
+
+const DHCP_SERVER_PORT: u16 = 67;
+const DHCP_CLIENT_PORT: u16 = 68;
+const MDNS_PORT: u16 = 5353;
+
+/// Everything heard about one MAC address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObservedHost {
+    pub mac: MacAddress,
+    /// Latest IPv4 address seen for the MAC
+    pub ip: Option<Ipv4Addr>,
+    pub hostname: Option<(String, HostnameSource)>,
+    /// DHCP vendor class identifier, ie "MSFT 5.0" or "android-dhcp-14"
+    pub vendor_class: Option<String>,
+}
+
+impl ObservedHost {
+    fn new(mac: MacAddress) -> Self {
+        Self {
+            mac,
+            ip: None,
+            hostname: None,
+            vendor_class: None,
+        }
+    }
+
+    fn merge(&mut self, observation: Observation) {
+        if let Some(ip) = observation.ip {
+            self.ip = Some(ip);
+        }
+        if let Some(vendor_class) = observation.vendor_class {
+            self.vendor_class = Some(vendor_class);
+        }
+        // The name a client puts in its own DHCP request beats whatever it announces
+        match (&self.hostname, observation.hostname) {
+            (Some((_, HostnameSource::DhcpLease)), Some((_, HostnameSource::Mdns))) => {}
+            (_, Some(hostname)) => self.hostname = Some(hostname),
+            (_, None) => {}
+        }
+    }
+}
+
+/// What a single frame told us about its sender
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Observation {
+    ip: Option<Ipv4Addr>,
+    hostname: Option<(String, HostnameSource)>,
+    vendor_class: Option<String>,
+}
+
+/// A background listener for ARP, DHCP and mDNS traffic on one interface.
+/// Stops when finished or dropped.
+pub struct PassiveListener {
+    interface_name: String,
+    stop: Arc<AtomicBool>,
+    hosts: Arc<Mutex<HashMap<MacAddress, ObservedHost>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Start listening for host traffic on `interface`
+pub fn listen(interface: &NetworkInterface) -> Result<PassiveListener> {
+    let own_mac = interface
+        .mac
+        .ok_or_else(|| anyhow!("Interface {} has no MAC address", interface.name))?;
+
+    let config = datalink::Config {
+        read_timeout: Some(Duration::from_millis(250)),
+        read_buffer_size: 65536,
+        ..Default::default()
+    };
+
+    let mut rx = match datalink::channel(interface, config)? {
+        Channel::Ethernet(_, rx) => rx,
+        _ => return Err(anyhow!("Unsupported channel type")),
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let hosts: Arc<Mutex<HashMap<MacAddress, ObservedHost>>> = Arc::new(Mutex::new(HashMap::new()));
+
+    let handle = {
+        let stop = stop.clone();
+        let hosts = hosts.clone();
+        let interface_name = interface.name.clone();
+
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let frame = match rx.next() {
+                    Ok(frame) => frame,
+                    // Read timeout, check the stop flag again
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+                        ) =>
+                    {
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            interface = %interface_name,
+                            error = %e,
+                            "Passive listener stopped after a capture error"
+                        );
+                        break;
+                    }
+                };
+
+                let Some((mac, observation)) = parse_frame(frame, own_mac) else {
+                    continue;
+                };
+
+                tracing::trace!(
+                    interface = %interface_name,
+                    mac = %mac,
+                    ip = ?observation.ip,
+                    hostname = ?observation.hostname,
+                    "Heard host"
+                );
+
+                hosts
+                    .lock()
+                    .unwrap()
+                    .entry(mac)
+                    .or_insert_with(|| ObservedHost::new(mac))
+                    .merge(observation);
+            }
+        })
+    };
+
+    tracing::debug!(interface = %interface.name, "Listening for ARP, DHCP and mDNS traffic");
+
+    Ok(PassiveListener {
+        interface_name: interface.name.clone(),
+        stop,
+        hosts,
+        handle: Some(handle),
+    })
+}
+
+impl PassiveListener {
+    /// Hosts heard so far
+    pub fn heard_count(&self) -> usize {
+        self.hosts.lock().unwrap().len()
+    }
+
+    /// Stop listening and return the hosts heard. Blocks while the listener thread
+    /// finishes its current read, so call from a blocking context.
+    pub fn finish(mut self) -> Vec<ObservedHost> {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            tracing::warn!(interface = %self.interface_name, "Passive listener thread panicked");
+        }
+
+        std::mem::take(&mut *self.hosts.lock().unwrap())
+            .into_values()
+            .collect()
+    }
+}
+
+impl Drop for PassiveListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Parse an Ethernet frame, returning the sender's MAC and what the frame says about it
+fn parse_frame(frame: &[u8], own_mac: MacAddr) -> Option<(MacAddress, Observation)> {
+    let ethernet = EthernetPacket::new(frame)?;
+    let source = ethernet.get_source();
+
+    if source == own_mac || source.is_broadcast() || source.is_multicast() {
+        return None;
+    }
+
+    let observation = match ethernet.get_ethertype() {
+        EtherTypes::Arp => parse_arp(ethernet.payload())?,
+        EtherTypes::Ipv4 => {
+            let ipv4 = Ipv4Packet::new(ethernet.payload())?;
+            if ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Udp {
+                return None;
+            }
+            let udp = UdpPacket::new(ipv4.payload())?;
+
+            match (udp.get_source(), udp.get_destination()) {
+                (DHCP_CLIENT_PORT, DHCP_SERVER_PORT) => {
+                    parse_dhcp_request(udp.payload(), &source.octets())?
+                }
+                (MDNS_PORT, MDNS_PORT) => parse_mdns(udp.payload(), ipv4.get_source())?,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some((MacAddress::new(source.octets()), observation))
+}
+
+/// ARP requests and replies both carry the sender's address. Probes (RFC 5227)
+/// come from 0.0.0.0 and are skipped.
+fn parse_arp(payload: &[u8]) -> Option<Observation> {
+    let arp = ArpPacket::new(payload)?;
+    let ip = arp.get_sender_proto_addr();
+
+    if ip.is_unspecified() {
+        return None;
+    }
+
+    Some(Observation {
+        ip: Some(ip),
+        ..Default::default()
+    })
+}
+
+/// Client to server DHCP messages. The address is the client's current one when it
+/// has one, otherwise the one it is asking for.
+fn parse_dhcp_request(payload: &[u8], source_mac: &[u8; 6]) -> Option<Observation> {
+    let message = v4::Message::decode(&mut Decoder::new(payload)).ok()?;
+
+    if message.opcode() != v4::Opcode::BootRequest || message.htype() != v4::HType::Eth {
+        return None;
+    }
+
+    // Relayed requests arrive from the relay's MAC, not the client's
+    if message.chaddr().get(..6) != Some(source_mac.as_slice()) {
+        return None;
+    }
+
+    let ciaddr = message.ciaddr();
+    let mut observation = Observation {
+        ip: (!ciaddr.is_unspecified()).then_some(ciaddr),
+        ..Default::default()
+    };
+
+    for (_, option) in message.opts().iter() {
+        match option {
+            v4::DhcpOption::Hostname(hostname) => {
+                let hostname = hostname.trim_end_matches('\0');
+                if !hostname.is_empty() {
+                    observation.hostname = Some((hostname.to_string(), HostnameSource::DhcpLease));
+                }
+            }
+            v4::DhcpOption::RequestedIpAddress(ip) if observation.ip.is_none() => {
+                observation.ip = Some(*ip);
+            }
+            v4::DhcpOption::ClassIdentifier(vendor_class) => {
+                observation.vendor_class = Some(String::from_utf8_lossy(vendor_class).to_string());
+            }
+            _ => {}
+        }
+    }
+
+    Some(observation)
+}
+
+/// mDNS responses. Only address records for the sender's own address are taken, a
+/// responder can answer on behalf of other hosts.
+fn parse_mdns(payload: &[u8], source_ip: Ipv4Addr) -> Option<Observation> {
+    let message = Message::from_vec(payload).ok()?;
+
+    if message.message_type() != MessageType::Response {
+        return None;
+    }
+
+    let hostname = message
+        .answers()
+        .iter()
+        .chain(message.additionals())
+        .find_map(|record| match record.data() {
+            RData::A(a) if a.0 == source_ip => {
+                Some(record.name().to_utf8().trim_end_matches('.').to_string())
+            }
+            _ => None,
+        })?;
+
+    Some(Observation {
+        ip: Some(source_ip),
+        hostname: Some((hostname, HostnameSource::Mdns)),
+        vendor_class: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dhcproto::Encodable;
+    use hickory_proto::rr::rdata::A;
+    use hickory_proto::rr::{Name, Record};
+
+    const CLIENT_MAC: [u8; 6] = [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+
+    fn dhcp_request(ciaddr: Ipv4Addr) -> Vec<u8> {
+        let mut message = v4::Message::default();
+        message
+            .set_opcode(v4::Opcode::BootRequest)
+            .set_htype(v4::HType::Eth)
+            .set_ciaddr(ciaddr)
+            .set_chaddr(&CLIENT_MAC);
+
+        let options = message.opts_mut();
+        options.insert(v4::DhcpOption::MessageType(v4::MessageType::Request));
+        options.insert(v4::DhcpOption::RequestedIpAddress(Ipv4Addr::new(
+            192, 168, 1, 42,
+        )));
+        options.insert(v4::DhcpOption::Hostname("infuse".to_string()));
+        options.insert(v4::DhcpOption::ClassIdentifier(b"MSFT 5.0".to_vec()));
+
+        let mut buf = Vec::new();
+        message
+            .encode(&mut dhcproto::Encoder::new(&mut buf))
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_parse_dhcp_request() {
+        let observation =
+            parse_dhcp_request(&dhcp_request(Ipv4Addr::UNSPECIFIED), &CLIENT_MAC).unwrap();
+
+        assert_eq!(observation.ip, Some(Ipv4Addr::new(192, 168, 1, 42)));
+        assert_eq!(
+            observation.hostname,
+            Some(("infuse".to_string(), HostnameSource::DhcpLease))
+        );
+        assert_eq!(observation.vendor_class.as_deref(), Some("MSFT 5.0"));
+
+        // A renewing client's current address wins over the requested one
+        let renewing = dhcp_request(Ipv4Addr::new(192, 168, 1, 7));
+        assert_eq!(
+            parse_dhcp_request(&renewing, &CLIENT_MAC).unwrap().ip,
+            Some(Ipv4Addr::new(192, 168, 1, 7))
+        );
+
+        // Relayed through another MAC
+        assert!(parse_dhcp_request(&renewing, &[0x02; 6]).is_none());
+    }
+
+    #[test]
+    fn test_parse_mdns_announcement() {
+        let source_ip = Ipv4Addr::new(192, 168, 1, 30);
+        let name = Name::from_utf8("pump-3.local.").unwrap();
+
+        let mut message = Message::new();
+        message
+            .set_message_type(MessageType::Response)
+            .add_answer(Record::from_rdata(
+                Name::from_utf8("printer.local.").unwrap(),
+                120,
+                RData::A(A(Ipv4Addr::new(192, 168, 1, 31))),
+            ))
+            .add_answer(Record::from_rdata(name, 120, RData::A(A(source_ip))));
+        let packet = message.to_vec().unwrap();
+
+        let observation = parse_mdns(&packet, source_ip).unwrap();
+
+        assert_eq!(observation.ip, Some(source_ip));
+        assert_eq!(
+            observation.hostname,
+            Some(("pump-3.local".to_string(), HostnameSource::Mdns))
+        );
+    }
+
+    #[test]
+    fn test_dhcp_hostname_beats_mdns() {
+        let mut host = ObservedHost::new(MacAddress::new(CLIENT_MAC));
+
+        host.merge(Observation {
+            ip: Some(Ipv4Addr::new(10, 0, 0, 5)),
+            hostname: Some(("infuse".to_string(), HostnameSource::DhcpLease)),
+            vendor_class: None,
+        });
+        host.merge(Observation {
+            ip: None,
+            hostname: Some(("infuse-1f2e.local".to_string(), HostnameSource::Mdns)),
+            vendor_class: None,
+        });
+
+        assert_eq!(host.ip, Some(Ipv4Addr::new(10, 0, 0, 5)));
+        assert_eq!(
+            host.hostname,
+            Some(("infuse".to_string(), HostnameSource::DhcpLease))
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Proxmox VE API client.
+//!
+//! Lists a cluster's nodes and the QEMU VMs and LXC containers on each of them
+//! using an API token. Guests don't report their MAC addresses anywhere in the
+//! listings, they're read from the `netN` entries of each guest's config.
+
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use mac_address::MacAddress;
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::server::hosts::r#impl::api::DiscoveredProxmoxGuest;
+use crate::server::networks::proxmox::ProxmoxCredential;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Every API response wraps its payload in `data`
+#[derive(Deserialize)]
+struct ProxmoxResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct ClusterStatusEntry {
+    #[serde(rename = "type")]
+    entry_type: String,
+    name: String,
+    ip: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GuestSummary {
+    /// A number for VMs, but some versions return a string for containers
+    vmid: Value,
+    name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GuestKind {
+    Qemu,
+    Lxc,
+}
+
+impl GuestKind {
+    fn path(&self) -> &'static str {
+        match self {
+            GuestKind::Qemu => "qemu",
+            GuestKind::Lxc => "lxc",
+        }
+    }
+}
+
+pub struct ProxmoxClient {
+    client: reqwest::Client,
+    credential: ProxmoxCredential,
+}
+
+impl ProxmoxClient {
+    pub fn new(credential: ProxmoxCredential) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .danger_accept_invalid_certs(credential.allow_self_signed_certs)
+            .build()
+            .map_err(|e| anyhow!("Could not build client {}", e))?;
+
+        Ok(Self { client, credential })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self
+            .client
+            .get(self.credential.api_url(path))
+            .header(AUTHORIZATION, self.credential.authorization_header())
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ProxmoxResponse<T>>()
+            .await?;
+
+        Ok(response.data)
+    }
+
+    /// Nodes in the cluster with their addresses. Standalone nodes report themselves too.
+    async fn nodes(&self) -> Result<Vec<(String, Vec<IpAddr>)>> {
+        let status: Vec<ClusterStatusEntry> = self.get("/cluster/status").await?;
+
+        // A node without an address in the cluster status is the one the URL points at
+        let url_ip = reqwest::Url::parse(&self.credential.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .and_then(|host| IpAddr::from_str(host.trim_matches(['[', ']'])).ok());
+
+        Ok(status
+            .into_iter()
+            .filter(|entry| entry.entry_type == "node")
+            .map(|entry| {
+                let ips = entry
+                    .ip
+                    .as_deref()
+                    .and_then(|ip| IpAddr::from_str(ip).ok())
+                    .or(url_ip)
+                    .into_iter()
+                    .collect();
+                (entry.name, ips)
+            })
+            .collect())
+    }
+
+    async fn guests(
+        &self,
+        node: &str,
+        node_ips: &[IpAddr],
+        kind: GuestKind,
+    ) -> Result<Vec<DiscoveredProxmoxGuest>> {
+        let summaries: Vec<GuestSummary> = self
+            .get(&format!("/nodes/{}/{}", node, kind.path()))
+            .await?;
+
+        let mut guests = Vec::new();
+        for summary in summaries {
+            let Some(vm_id) = parse_vmid(&summary.vmid) else {
+                continue;
+            };
+
+            let config: Map<String, Value> = match self
+                .get(&format!("/nodes/{}/{}/{}/config", node, kind.path(), vm_id))
+                .await
+            {
+                Ok(config) => config,
+                // A guest the token can't read, or one being migrated, shouldn't hide the rest
+                Err(e) => {
+                    tracing::warn!(node = %node, vm_id = %vm_id, error = %e, "Failed to read Proxmox guest config");
+                    continue;
+                }
+            };
+
+            guests.push(DiscoveredProxmoxGuest {
+                node: node.to_string(),
+                node_ips: node_ips.to_vec(),
+                vm_id,
+                vm_name: summary.name,
+                mac_addresses: config_mac_addresses(&config),
+            });
+        }
+
+        Ok(guests)
+    }
+
+    /// Every VM and container on every node of the cluster
+    pub async fn list_guests(&self) -> Result<Vec<DiscoveredProxmoxGuest>> {
+        let mut guests = Vec::new();
+
+        for (node, node_ips) in self.nodes().await? {
+            for kind in [GuestKind::Qemu, GuestKind::Lxc] {
+                match self.guests(&node, &node_ips, kind).await {
+                    Ok(mut node_guests) => guests.append(&mut node_guests),
+                    // Offline nodes fail their listings, the rest of the cluster is still useful
+                    Err(e) => {
+                        tracing::warn!(node = %node, kind = kind.path(), error = %e, "Failed to list Proxmox guests");
+                    }
+                }
+            }
+        }
+
+        Ok(guests)
+    }
+}
+
+fn parse_vmid(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) => Some(number.to_string()),
+        Value::String(string) if !string.is_empty() => Some(string.clone()),
+        _ => None,
+    }
+}
+
+/// MAC addresses from a guest config's `netN` entries. VMs put the MAC after the
+/// NIC model (`virtio=BC:24:11:...,bridge=vmbr0`), containers in `hwaddr=`.
+fn config_mac_addresses(config: &Map<String, Value>) -> Vec<MacAddress> {
+    let mut net_keys: Vec<&String> = config
+        .keys()
+        .filter(|key| {
+            key.strip_prefix("net")
+                .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    net_keys.sort();
+
+    net_keys
+        .into_iter()
+        .filter_map(|key| config.get(key)?.as_str())
+        .filter_map(|net| {
+            net.split(',')
+                .filter_map(|option| option.split_once('='))
+                .find_map(|(_, value)| MacAddress::from_str(value).ok())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_mac_addresses() {
+        let vm = json!({
+            "name": "web",
+            "net0": "virtio=BC:24:11:5E:7A:01,bridge=vmbr0,firewall=1",
+            "net1": "e1000=BC:24:11:5E:7A:02,bridge=vmbr1,tag=20",
+            "netboot": "not-a-nic",
+        });
+        let container = json!({
+            "hostname": "dns",
+            "net0": "name=eth0,bridge=vmbr0,hwaddr=BC:24:11:9C:00:10,ip=dhcp,type=veth",
+        });
+
+        assert_eq!(
+            config_mac_addresses(vm.as_object().unwrap()),
+            vec![
+                MacAddress::from_str("BC:24:11:5E:7A:01").unwrap(),
+                MacAddress::from_str("BC:24:11:5E:7A:02").unwrap(),
+            ]
+        );
+        assert_eq!(
+            config_mac_addresses(container.as_object().unwrap()),
+            vec![MacAddress::from_str("BC:24:11:9C:00:10").unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_parse_vmid() {
+        assert_eq!(parse_vmid(&json!(100)), Some("100".to_string()));
+        assert_eq!(parse_vmid(&json!("101")), Some("101".to_string()));
+        assert_eq!(parse_vmid(&json!(null)), None);
+    }
+}
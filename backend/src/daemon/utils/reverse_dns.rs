@@ -0,0 +1,132 @@
+//! Reverse (PTR) lookups for network discovery.
+//!
+//! Subnets with resolvers configured on their network are looked up through those
+//! servers with hickory, everything else goes through the OS resolver. Answers,
+//! including misses, are cached for the session so an address is only looked up once.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hickory_resolver::Resolver;
+use hickory_resolver::config::{
+    NameServerConfig, ResolverConfig, ResolverOpts, ServerOrderingStrategy,
+};
+use hickory_resolver::name_server::TokioConnectionProvider;
+use hickory_resolver::proto::xfer::Protocol;
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use crate::server::networks::dns::DnsResolver;
+
+/// How long each server gets to answer
+const LOOKUP_TIMEOUT: Duration = Duration::from_millis(800);
+const MAX_CONCURRENT_LOOKUPS: usize = 32;
+
+/// A subnet's configured servers and how long a lookup through them may take
+#[derive(Clone)]
+struct SubnetResolver {
+    resolver: Arc<Resolver<TokioConnectionProvider>>,
+    /// Long enough for hickory to fail over through every server when the first ones are down
+    lookup_timeout: Duration,
+}
+
+pub struct ReverseDns {
+    resolvers: Vec<DnsResolver>,
+    /// Resolver built for each subnet, None when the subnet uses the OS resolver
+    subnet_resolvers: Mutex<HashMap<Uuid, Option<SubnetResolver>>>,
+    cache: Mutex<HashMap<IpAddr, Option<String>>>,
+    permits: Semaphore,
+}
+
+impl ReverseDns {
+    pub fn new(resolvers: Vec<DnsResolver>) -> Self {
+        Self {
+            resolvers,
+            subnet_resolvers: Mutex::new(HashMap::new()),
+            cache: Mutex::new(HashMap::new()),
+            permits: Semaphore::new(MAX_CONCURRENT_LOOKUPS),
+        }
+    }
+
+    pub async fn lookup(&self, ip: IpAddr, subnet_id: &Uuid) -> Option<String> {
+        if let Some(cached) = self.cache.lock().unwrap().get(&ip) {
+            return cached.clone();
+        }
+
+        let hostname = {
+            let _permit = self.permits.acquire().await.ok()?;
+
+            match self.resolver_for_subnet(subnet_id) {
+                Some(SubnetResolver {
+                    resolver,
+                    lookup_timeout,
+                }) => match timeout(lookup_timeout, resolver.reverse_lookup(ip)).await {
+                    Ok(Ok(lookup)) => lookup
+                        .iter()
+                        .next()
+                        .map(|ptr| ptr.0.to_utf8().trim_end_matches('.').to_string()),
+                    Ok(Err(e)) => {
+                        tracing::trace!(ip = %ip, error = %e, "Reverse lookup failed");
+                        None
+                    }
+                    Err(_) => None,
+                },
+                None => timeout(
+                    LOOKUP_TIMEOUT,
+                    tokio::task::spawn_blocking(move || dns_lookup::lookup_addr(&ip)),
+                )
+                .await
+                .ok()
+                .and_then(|joined| joined.ok())
+                .and_then(|result| result.ok()),
+            }
+        };
+
+        self.cache.lock().unwrap().insert(ip, hostname.clone());
+        hostname
+    }
+
+    fn resolver_for_subnet(&self, subnet_id: &Uuid) -> Option<SubnetResolver> {
+        self.subnet_resolvers
+            .lock()
+            .unwrap()
+            .entry(*subnet_id)
+            .or_insert_with(|| {
+                let resolvers = DnsResolver::for_subnet(&self.resolvers, subnet_id);
+                if resolvers.is_empty() {
+                    return None;
+                }
+
+                let lookup_timeout = LOOKUP_TIMEOUT * resolvers.len() as u32;
+
+                let mut config = ResolverConfig::new();
+                for resolver in resolvers {
+                    config.add_name_server(NameServerConfig::new(
+                        SocketAddr::new(resolver.ip, resolver.port),
+                        Protocol::Udp,
+                    ));
+                }
+
+                let mut options = ResolverOpts::default();
+                options.timeout = LOOKUP_TIMEOUT;
+                options.attempts = 1;
+                // Subnet specific servers are listed first and should be asked first
+                options.server_ordering_strategy = ServerOrderingStrategy::UserProvidedOrder;
+                // Answers are cached per session above
+                options.cache_size = 0;
+
+                Some(SubnetResolver {
+                    resolver: Arc::new(
+                        Resolver::builder_with_config(config, TokioConnectionProvider::default())
+                            .with_options(options)
+                            .build(),
+                    ),
+                    lookup_timeout,
+                })
+            })
+            .clone()
+    }
+}
@@ -0,0 +1,613 @@
+//! Reverse proxy configuration.
+//!
+//! Reads the routes configured on reverse proxies found during discovery: Traefik
+//! through its API, Caddy through its admin API, and Nginx Proxy Manager through its
+//! API or, when configured, its sqlite database. Each route is reduced to the
+//! hostnames and path it matches and the upstream it forwards to.
+
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+#[cfg(feature = "npm-sqlite")]
+use sqlx::{
+    Connection,
+    sqlite::{SqliteConnectOptions, SqliteConnection},
+};
+use tokio::net::lookup_host;
+use uuid::Uuid;
+
+use crate::server::hosts::r#impl::api::{DiscoveredProxyRoute, HostResponse};
+use crate::server::services::definitions::{
+    caddy::Caddy, nginx_proxy_manager::NginxProxyManager, traefik::Traefik,
+};
+use crate::server::services::r#impl::base::Service;
+use crate::server::shared::types::metadata::HasId;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverseProxyKind {
+    Traefik,
+    Caddy,
+    NginxProxyManager,
+}
+
+impl ReverseProxyKind {
+    pub fn from_service(service: &Service) -> Option<Self> {
+        let id = service.base.service_definition.id();
+        match id {
+            _ if id == Traefik.id() => Some(ReverseProxyKind::Traefik),
+            _ if id == Caddy.id() => Some(ReverseProxyKind::Caddy),
+            _ if id == NginxProxyManager.id() => Some(ReverseProxyKind::NginxProxyManager),
+            _ => None,
+        }
+    }
+}
+
+/// A reverse proxy service and the address its API answers on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseProxyTarget {
+    pub service_id: Uuid,
+    pub kind: ReverseProxyKind,
+    pub address: SocketAddr,
+}
+
+/// The reverse proxies of a created host, addressed on the port their service was matched on
+pub fn reverse_proxy_targets(host: &HostResponse) -> Vec<ReverseProxyTarget> {
+    host.services
+        .iter()
+        .filter_map(|service| {
+            let kind = ReverseProxyKind::from_service(service)?;
+
+            let binding = service
+                .base
+                .bindings
+                .iter()
+                .find(|b| b.port_id().is_some())?;
+            let port = host
+                .ports
+                .iter()
+                .find(|p| Some(p.id) == binding.port_id())?;
+            let interface = match binding.interface_id() {
+                Some(interface_id) => host.interfaces.iter().find(|i| i.id == interface_id),
+                None => host.interfaces.first(),
+            }?;
+
+            Some(ReverseProxyTarget {
+                service_id: service.id,
+                kind,
+                address: SocketAddr::new(interface.base.ip_address, port.base.port_type.number()),
+            })
+        })
+        .collect()
+}
+
+/// A route as configured on the proxy, before its upstream is resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyRoute {
+    pub rule: String,
+    pub upstream_host: String,
+    pub upstream_port: u16,
+}
+
+#[derive(Deserialize)]
+struct TraefikRouter {
+    name: String,
+    #[serde(default)]
+    rule: String,
+    #[serde(default)]
+    service: String,
+    #[serde(default)]
+    provider: String,
+}
+
+#[derive(Deserialize)]
+struct TraefikService {
+    name: String,
+    #[serde(rename = "loadBalancer")]
+    load_balancer: Option<TraefikLoadBalancer>,
+}
+
+#[derive(Deserialize)]
+struct TraefikLoadBalancer {
+    #[serde(default)]
+    servers: Vec<TraefikServer>,
+}
+
+#[derive(Deserialize)]
+struct TraefikServer {
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NpmToken {
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct NpmProxyHost {
+    domain_names: Vec<String>,
+    forward_host: String,
+    forward_port: Value,
+    /// `true` on current versions, `1` on older ones
+    #[serde(default)]
+    enabled: Value,
+    #[serde(default)]
+    locations: Option<Vec<NpmLocation>>,
+}
+
+/// Custom location of a proxy host, forwarding a path somewhere else
+#[derive(Deserialize)]
+struct NpmLocation {
+    path: String,
+    forward_host: String,
+    forward_port: Value,
+}
+
+pub struct ReverseProxyClient {
+    client: reqwest::Client,
+    /// Nginx Proxy Manager API login, as (email, password)
+    npm_credentials: Option<(String, String)>,
+    npm_database: Option<PathBuf>,
+}
+
+impl ReverseProxyClient {
+    pub fn new(
+        npm_credentials: Option<(String, String)>,
+        npm_database: Option<PathBuf>,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|e| anyhow!("Could not build client {}", e))?;
+
+        Ok(Self {
+            client,
+            npm_credentials,
+            npm_database,
+        })
+    }
+
+    /// The proxy's routes with their upstreams resolved. Upstreams on loopback point
+    /// back at the proxy's own host.
+    pub async fn routes(&self, target: &ReverseProxyTarget) -> Result<Vec<DiscoveredProxyRoute>> {
+        let routes = match target.kind {
+            ReverseProxyKind::Traefik => self.traefik_routes(target.address).await?,
+            ReverseProxyKind::Caddy => self.caddy_routes(target.address).await?,
+            ReverseProxyKind::NginxProxyManager => self.npm_routes(target.address).await?,
+        };
+
+        let mut resolved = Vec::new();
+        for route in routes {
+            let upstream_ips = resolve_upstream(&route, target.address.ip()).await;
+            resolved.push(DiscoveredProxyRoute {
+                rule: route.rule,
+                upstream_host: route.upstream_host,
+                upstream_ips,
+                upstream_port: route.upstream_port,
+            });
+        }
+
+        Ok(resolved)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: String) -> Result<T> {
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    async fn traefik_routes(&self, address: SocketAddr) -> Result<Vec<ProxyRoute>> {
+        let (routers, services): (Vec<TraefikRouter>, Vec<TraefikService>) = tokio::try_join!(
+            self.get_json(format!("http://{}/api/http/routers", address)),
+            self.get_json(format!("http://{}/api/http/services", address))
+        )?;
+
+        Ok(traefik_routes(&routers, &services))
+    }
+
+    async fn caddy_routes(&self, address: SocketAddr) -> Result<Vec<ProxyRoute>> {
+        let config: Value = self.get_json(format!("http://{}/config/", address)).await?;
+
+        Ok(caddy_routes(&config))
+    }
+
+    async fn npm_routes(&self, address: SocketAddr) -> Result<Vec<ProxyRoute>> {
+        let proxy_hosts = match (&self.npm_credentials, &self.npm_database) {
+            (Some((email, password)), _) => {
+                let token: NpmToken = self
+                    .client
+                    .post(format!("http://{}/api/tokens", address))
+                    .json(&serde_json::json!({ "identity": email, "secret": password }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+
+                self.client
+                    .get(format!("http://{}/api/nginx/proxy-hosts", address))
+                    .bearer_auth(token.token)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Vec<NpmProxyHost>>()
+                    .await?
+            }
+            (None, Some(database)) => npm_database_proxy_hosts(database).await?,
+            (None, None) => {
+                return Err(anyhow!(
+                    "Nginx Proxy Manager needs npm_credentials or npm_database to read its proxy hosts"
+                ));
+            }
+        };
+
+        Ok(npm_routes(&proxy_hosts))
+    }
+}
+
+/// Proxy hosts from Nginx Proxy Manager's `database.sqlite`, opened read only
+#[cfg(feature = "npm-sqlite")]
+async fn npm_database_proxy_hosts(database: &Path) -> Result<Vec<NpmProxyHost>> {
+    let options = SqliteConnectOptions::new()
+        .filename(database)
+        .read_only(true);
+    let mut connection = SqliteConnection::connect_with(&options).await?;
+
+    let rows: Vec<(String, String, i64, bool, Option<String>)> = sqlx::query_as(
+        "SELECT domain_names, forward_host, forward_port, enabled, locations \
+         FROM proxy_host WHERE is_deleted = 0",
+    )
+    .fetch_all(&mut connection)
+    .await?;
+
+    connection.close().await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(
+            |(domain_names, forward_host, forward_port, enabled, locations)| {
+                Some(NpmProxyHost {
+                    domain_names: serde_json::from_str(&domain_names).ok()?,
+                    forward_host,
+                    forward_port: Value::from(forward_port),
+                    enabled: Value::Bool(enabled),
+                    locations: locations.and_then(|l| serde_json::from_str(&l).ok()),
+                })
+            },
+        )
+        .collect())
+}
+
+#[cfg(not(feature = "npm-sqlite"))]
+async fn npm_database_proxy_hosts(database: &Path) -> Result<Vec<NpmProxyHost>> {
+    Err(anyhow!(
+        "Reading {} needs a daemon built with the npm-sqlite feature",
+        database.display()
+    ))
+}
+
+async fn resolve_upstream(route: &ProxyRoute, proxy_ip: IpAddr) -> Vec<IpAddr> {
+    let ips = match IpAddr::from_str(&route.upstream_host) {
+        Ok(ip) => vec![ip],
+        Err(_) if route.upstream_host.eq_ignore_ascii_case("localhost") => vec![proxy_ip],
+        Err(_) => lookup_host((route.upstream_host.as_str(), route.upstream_port))
+            .await
+            .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+            .unwrap_or_default(),
+    };
+
+    ips.into_iter()
+        .map(|ip| if ip.is_loopback() { proxy_ip } else { ip })
+        .collect()
+}
+
+/// Hostnames and path a route matches, e.g. `app.example.com/api`
+fn route_rule(hosts: &[String], path: Option<&str>) -> String {
+    let hosts = if hosts.is_empty() {
+        "*".to_string()
+    } else {
+        hosts.join(", ")
+    };
+
+    match path {
+        Some(path) if path != "/" => format!("{}{}", hosts, path.trim_end_matches('*')),
+        _ => hosts,
+    }
+}
+
+fn traefik_routes(routers: &[TraefikRouter], services: &[TraefikService]) -> Vec<ProxyRoute> {
+    let matcher_regex = Regex::new(r"(Host|PathPrefix|Path)\(([^)]*)\)").unwrap();
+    let argument_regex = Regex::new(r"`([^`]*)`").unwrap();
+    let mut routes = Vec::new();
+
+    for router in routers {
+        // Routers refer to services of their own provider without the @provider suffix
+        let service_name = if router.service.contains('@') {
+            router.service.clone()
+        } else {
+            format!("{}@{}", router.service, router.provider)
+        };
+
+        let Some(service) = services.iter().find(|s| s.name == service_name) else {
+            tracing::debug!(router = %router.name, service = %service_name, "Traefik router has no load balanced service");
+            continue;
+        };
+
+        let mut hosts = Vec::new();
+        let mut path = None;
+        for matcher in matcher_regex.captures_iter(&router.rule) {
+            let mut args = argument_regex
+                .captures_iter(&matcher[2])
+                .map(|arg| arg[1].to_string());
+            match &matcher[1] {
+                "Host" => hosts.extend(args),
+                _ => path = path.or(args.next()),
+            }
+        }
+        let rule = route_rule(&hosts, path.as_deref());
+
+        for server in service
+            .load_balancer
+            .iter()
+            .flat_map(|lb| lb.servers.iter())
+        {
+            let Some(url) = server.url.as_deref().and_then(|url| Url::parse(url).ok()) else {
+                continue;
+            };
+            let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+                continue;
+            };
+
+            routes.push(ProxyRoute {
+                rule: rule.clone(),
+                upstream_host: host.trim_matches(['[', ']']).to_string(),
+                upstream_port: port,
+            });
+        }
+    }
+
+    routes
+}
+
+fn caddy_routes(config: &Value) -> Vec<ProxyRoute> {
+    let mut routes = Vec::new();
+
+    let servers = config
+        .pointer("/apps/http/servers")
+        .and_then(Value::as_object);
+
+    for server in servers.into_iter().flat_map(|servers| servers.values()) {
+        if let Some(server_routes) = server.get("routes").and_then(Value::as_array) {
+            collect_caddy_routes(server_routes, &[], None, &mut routes);
+        }
+    }
+
+    routes
+}
+
+/// Walk Caddy routes, `subroute` handlers nest routes that inherit the outer matchers
+fn collect_caddy_routes(
+    caddy_routes: &[Value],
+    outer_hosts: &[String],
+    outer_path: Option<&str>,
+    routes: &mut Vec<ProxyRoute>,
+) {
+    for route in caddy_routes {
+        let matchers = route.get("match").and_then(Value::as_array);
+        let strings = |key: &str| -> Vec<String> {
+            matchers
+                .into_iter()
+                .flatten()
+                .filter_map(|m| m.get(key).and_then(Value::as_array))
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        };
+
+        let hosts = strings("host");
+        let hosts = if hosts.is_empty() {
+            outer_hosts.to_vec()
+        } else {
+            hosts
+        };
+        let paths = strings("path");
+        let path = paths.first().map(String::as_str).or(outer_path);
+
+        for handler in route
+            .get("handle")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            match handler.get("handler").and_then(Value::as_str) {
+                Some("subroute") => {
+                    if let Some(nested) = handler.get("routes").and_then(Value::as_array) {
+                        collect_caddy_routes(nested, &hosts, path, routes);
+                    }
+                }
+                Some("reverse_proxy") => {
+                    let dials = handler
+                        .get("upstreams")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|u| u.get("dial").and_then(Value::as_str));
+
+                    for dial in dials {
+                        // Placeholders like {http.request.host} can't be resolved up front
+                        let Some((host, port)) = dial.rsplit_once(':') else {
+                            continue;
+                        };
+                        let Ok(port) = port.parse::<u16>() else {
+                            continue;
+                        };
+
+                        routes.push(ProxyRoute {
+                            rule: route_rule(&hosts, path),
+                            upstream_host: host.trim_matches(['[', ']']).to_string(),
+                            upstream_port: port,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn npm_routes(proxy_hosts: &[NpmProxyHost]) -> Vec<ProxyRoute> {
+    let port = |value: &Value| match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+
+    proxy_hosts
+        .iter()
+        .filter(|h| !matches!(&h.enabled, Value::Bool(false)) && h.enabled != 0)
+        .flat_map(|proxy_host| {
+            let root = port(&proxy_host.forward_port).map(|forward_port| ProxyRoute {
+                rule: route_rule(&proxy_host.domain_names, None),
+                upstream_host: proxy_host.forward_host.clone(),
+                upstream_port: forward_port,
+            });
+
+            let locations = proxy_host
+                .locations
+                .iter()
+                .flatten()
+                .filter_map(|location| {
+                    Some(ProxyRoute {
+                        rule: route_rule(&proxy_host.domain_names, Some(&location.path)),
+                        upstream_host: location.forward_host.clone(),
+                        upstream_port: port(&location.forward_port)?,
+                    })
+                });
+
+            root.into_iter().chain(locations).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn route(rule: &str, upstream_host: &str, upstream_port: u16) -> ProxyRoute {
+        ProxyRoute {
+            rule: rule.to_string(),
+            upstream_host: upstream_host.to_string(),
+            upstream_port,
+        }
+    }
+
+    #[test]
+    fn test_traefik_routes() {
+        let routers: Vec<TraefikRouter> = serde_json::from_value(json!([
+            {
+                "name": "shop@docker",
+                "rule": "Host(`shop.example.com`) && PathPrefix(`/api`)",
+                "service": "shop-api",
+                "provider": "docker"
+            },
+            {
+                "name": "dashboard@internal",
+                "rule": "PathPrefix(`/api`) || PathPrefix(`/dashboard`)",
+                "service": "api@internal",
+                "provider": "internal"
+            }
+        ]))
+        .unwrap();
+        let services: Vec<TraefikService> = serde_json::from_value(json!([
+            {
+                "name": "shop-api@docker",
+                "loadBalancer": { "servers": [{ "url": "http://172.18.0.3:8080" }] }
+            },
+            { "name": "api@internal" }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            traefik_routes(&routers, &services),
+            vec![route("shop.example.com/api", "172.18.0.3", 8080)]
+        );
+    }
+
+    #[test]
+    fn test_caddy_routes() {
+        let config = json!({
+            "apps": { "http": { "servers": { "srv0": { "routes": [
+                {
+                    "match": [{ "host": ["grafana.lan"] }],
+                    "handle": [{
+                        "handler": "subroute",
+                        "routes": [{
+                            "handle": [{
+                                "handler": "reverse_proxy",
+                                "upstreams": [{ "dial": "grafana:3000" }]
+                            }]
+                        }]
+                    }]
+                },
+                {
+                    "match": [{ "host": ["files.lan"], "path": ["/share/*"] }],
+                    "handle": [{
+                        "handler": "reverse_proxy",
+                        "upstreams": [{ "dial": "[fd00::5]:8080" }, { "dial": "{http.request.host}" }]
+                    }]
+                }
+            ] } } } }
+        });
+
+        assert_eq!(
+            caddy_routes(&config),
+            vec![
+                route("grafana.lan", "grafana", 3000),
+                route("files.lan/share/", "fd00::5", 8080),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_routes() {
+        let proxy_hosts: Vec<NpmProxyHost> = serde_json::from_value(json!([
+            {
+                "domain_names": ["jellyfin.example.com"],
+                "forward_host": "192.168.1.20",
+                "forward_port": 8096,
+                "enabled": true,
+                "locations": [{ "path": "/metrics", "forward_host": "192.168.1.21", "forward_port": "9100" }]
+            },
+            {
+                "domain_names": ["old.example.com"],
+                "forward_host": "192.168.1.30",
+                "forward_port": 80,
+                "enabled": 0
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            npm_routes(&proxy_hosts),
+            vec![
+                route("jellyfin.example.com", "192.168.1.20", 8096),
+                route("jellyfin.example.com/metrics", "192.168.1.21", 9100),
+            ]
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Per-host round-trip time estimates, used to size probe timeouts.
+//!
+//! Every host starts out with the scan profile's timeout. Once a sample comes in, from
+//! the ARP reply or the first TCP connect that succeeds, timeouts follow the smoothed
+//! RTT and its variance the way TCP's retransmission timer does (RFC 6298), which is
+//! also how nmap adapts its probe timeouts. LAN hosts then answer or time out in a
+//! fraction of the fixed timeout, and hosts behind a slow VPN get the time they need.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::server::daemons::r#impl::api::SubnetRttStats;
+
+/// Floor for a measured timeout, so scheduling jitter on a fast LAN doesn't cut probes short
+const MIN_TIMEOUT: Duration = Duration::from_millis(100);
+const MAX_TIMEOUT: Duration = Duration::from_secs(10);
+/// What UDP probes wait before the host has been measured
+pub const UDP_TIMEOUT: Duration = Duration::from_millis(2000);
+/// Time allowed on top of the network round trips for a server to answer a request
+const HTTP_PROCESSING: Duration = Duration::from_millis(250);
+const UDP_PROCESSING: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy)]
+struct RttEstimate {
+    srtt: Duration,
+    rttvar: Duration,
+    min: Duration,
+    max: Duration,
+    samples: usize,
+}
+
+/// Round-trip estimate for a single host, shared by every probe sent to it
+#[derive(Debug)]
+pub struct HostRtt {
+    initial_timeout: Duration,
+    estimate: Mutex<Option<RttEstimate>>,
+}
+
+impl HostRtt {
+    pub fn new(initial_timeout: Duration) -> Self {
+        Self {
+            initial_timeout,
+            estimate: Mutex::new(None),
+        }
+    }
+
+    pub fn record(&self, rtt: Duration) {
+        let mut estimate = self.estimate.lock().unwrap();
+
+        *estimate = Some(match *estimate {
+            None => RttEstimate {
+                srtt: rtt,
+                rttvar: rtt / 2,
+                min: rtt,
+                max: rtt,
+                samples: 1,
+            },
+            Some(e) => RttEstimate {
+                rttvar: e.rttvar * 3 / 4 + e.srtt.abs_diff(rtt) / 4,
+                srtt: e.srtt * 7 / 8 + rtt / 8,
+                min: e.min.min(rtt),
+                max: e.max.max(rtt),
+                samples: e.samples + 1,
+            },
+        });
+    }
+
+    /// Smoothed round-trip time, once the host has been measured
+    pub fn srtt(&self) -> Option<Duration> {
+        self.estimate.lock().unwrap().map(|e| e.srtt)
+    }
+
+    /// How long to wait for a single round trip, such as a TCP connect or SYN/ACK
+    pub fn connect_timeout(&self) -> Duration {
+        self.measured(1, Duration::ZERO)
+            .unwrap_or(self.initial_timeout)
+    }
+
+    /// How long to wait for an HTTP response: connect, TLS handshake and request
+    pub fn request_timeout(&self) -> Duration {
+        self.measured(3, HTTP_PROCESSING)
+            .unwrap_or(self.initial_timeout)
+    }
+
+    /// How long to wait for a UDP service to answer a query
+    pub fn udp_timeout(&self) -> Duration {
+        self.measured(2, UDP_PROCESSING).unwrap_or(UDP_TIMEOUT)
+    }
+
+    /// How long to wait for a DNS answer. The test lookup may have to be resolved
+    /// upstream, which the host's own RTT says nothing about, so it never drops below
+    /// the fixed UDP timeout.
+    pub fn dns_timeout(&self) -> Duration {
+        self.udp_timeout().max(UDP_TIMEOUT)
+    }
+
+    fn measured(&self, round_trips: u32, processing: Duration) -> Option<Duration> {
+        let estimate = (*self.estimate.lock().unwrap())?;
+        let rto = (estimate.srtt + estimate.rttvar * 4).max(MIN_TIMEOUT);
+
+        Some((rto * round_trips + processing).min(MAX_TIMEOUT))
+    }
+}
+
+/// Round-trip estimates for every host in a discovery, for per-subnet reporting
+pub struct RttTracker {
+    initial_timeout: Duration,
+    hosts: Mutex<HashMap<IpAddr, (Uuid, Arc<HostRtt>)>>,
+}
+
+impl RttTracker {
+    pub fn new(initial_timeout: Duration) -> Self {
+        Self {
+            initial_timeout,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn host(&self, ip: IpAddr, subnet_id: Uuid) -> Arc<HostRtt> {
+        self.hosts
+            .lock()
+            .unwrap()
+            .entry(ip)
+            .or_insert_with(|| (subnet_id, Arc::new(HostRtt::new(self.initial_timeout))))
+            .1
+            .clone()
+    }
+
+    /// Statistics over the measured hosts of each subnet. Hosts that never answered are
+    /// left out.
+    pub fn subnet_stats(&self) -> Vec<SubnetRttStats> {
+        let mut by_subnet: HashMap<Uuid, Vec<RttEstimate>> = HashMap::new();
+        for (subnet_id, host) in self.hosts.lock().unwrap().values() {
+            if let Some(estimate) = *host.estimate.lock().unwrap() {
+                by_subnet.entry(*subnet_id).or_default().push(estimate);
+            }
+        }
+
+        let mut stats: Vec<SubnetRttStats> = by_subnet
+            .into_iter()
+            .map(|(subnet_id, estimates)| {
+                let micros = |d: Duration| d.as_micros() as u64;
+                let avg =
+                    estimates.iter().map(|e| e.srtt).sum::<Duration>() / estimates.len() as u32;
+
+                SubnetRttStats {
+                    subnet_id,
+                    hosts: estimates.len(),
+                    samples: estimates.iter().map(|e| e.samples).sum(),
+                    min_us: micros(estimates.iter().map(|e| e.min).min().unwrap_or_default()),
+                    avg_us: micros(avg),
+                    max_us: micros(estimates.iter().map(|e| e.max).max().unwrap_or_default()),
+                }
+            })
+            .collect();
+
+        stats.sort_by_key(|s| s.subnet_id);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeouts_follow_measured_rtt() {
+        let initial = Duration::from_millis(800);
+        let host = HostRtt::new(initial);
+
+        // Unmeasured hosts keep the fixed timeouts
+        assert_eq!(host.connect_timeout(), initial);
+        assert_eq!(host.request_timeout(), initial);
+        assert_eq!(host.udp_timeout(), UDP_TIMEOUT);
+        assert_eq!(host.dns_timeout(), UDP_TIMEOUT);
+
+        // A fast LAN host drops to the floor
+        host.record(Duration::from_millis(1));
+        assert_eq!(host.connect_timeout(), MIN_TIMEOUT);
+        assert!(host.udp_timeout() < UDP_TIMEOUT);
+        // Except DNS, which may be waiting on an upstream resolver
+        assert_eq!(host.dns_timeout(), UDP_TIMEOUT);
+
+        // A slow remote host gets more than the fixed timeout
+        let remote = HostRtt::new(initial);
+        for _ in 0..5 {
+            remote.record(Duration::from_millis(700));
+        }
+        assert!(remote.connect_timeout() > initial);
+        assert!(remote.connect_timeout() < MAX_TIMEOUT);
+        assert_eq!(remote.srtt(), Some(Duration::from_millis(700)));
+    }
+
+    #[test]
+    fn test_subnet_stats() {
+        let tracker = RttTracker::new(Duration::from_millis(800));
+        let lan = Uuid::new_v4();
+        let remote = Uuid::new_v4();
+
+        tracker
+            .host("10.0.0.1".parse().unwrap(), lan)
+            .record(Duration::from_millis(2));
+        tracker
+            .host("10.0.0.2".parse().unwrap(), lan)
+            .record(Duration::from_millis(4));
+        // Never answered, so not counted
+        tracker.host("10.0.1.1".parse().unwrap(), remote);
+
+        let stats = tracker.subnet_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].subnet_id, lan);
+        assert_eq!(stats[0].hosts, 2);
+        assert_eq!(stats[0].min_us, 2000);
+        assert_eq!(stats[0].avg_us, 3000);
+        assert_eq!(stats[0].max_us, 4000);
+    }
+}
@@ -1,6 +1,11 @@
 use crate::daemon::discovery::types::base::DiscoveryCriticalError;
+use crate::daemon::utils::rtt::HostRtt;
+use crate::daemon::utils::snmp;
+use crate::daemon::utils::tls::{certificate_connector, grab_certificate, parse_certificate};
 use crate::server::services::r#impl::base::Service;
-use crate::server::services::r#impl::endpoints::{Endpoint, EndpointResponse};
+use crate::server::services::r#impl::endpoints::{
+    ApplicationProtocol, BannerResponse, CertificateResponse, Endpoint, EndpointResponse,
+};
 use anyhow::anyhow;
 use anyhow::{Error, Result};
 use cidr::IpCidr;
@@ -14,18 +19,24 @@ use hickory_resolver::name_server::TokioConnectionProvider;
 use hickory_resolver::proto::xfer::Protocol;
 use rand::{Rng, SeedableRng};
 use rsntp::AsyncSntpClient;
-use snmp2::{AsyncSession, Oid};
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::UdpSocket;
 use tokio::{net::TcpStream, time::timeout};
 use tokio_util::sync::CancellationToken;
 
+use crate::server::networks::snmp::SnmpCredential;
 use crate::server::ports::r#impl::base::{PortType, TransportProtocol};
 
 pub const SCAN_TIMEOUT: Duration = Duration::from_millis(800);
+/// How long to wait for a TCP service to send its banner
+pub const BANNER_TIMEOUT: Duration = Duration::from_millis(1500);
+/// Maximum number of banner bytes kept per port
+const MAX_BANNER_LENGTH: usize = 512;
 
 /// Generic batch scanner that maintains constant parallelism
 /// This is the core RustScan pattern extracted into a reusable function
@@ -116,17 +127,33 @@ pub async fn scan_ports_and_endpoints(
     cidr: IpCidr,
     gateway_ips: Vec<IpAddr>,
     tcp_ports_to_check: Vec<u16>,
-) -> Result<(Vec<PortType>, Vec<EndpointResponse>), Error> {
+    snmp_credentials: Vec<SnmpCredential>,
+) -> Result<
+    (
+        Vec<PortType>,
+        Vec<EndpointResponse>,
+        Vec<BannerResponse>,
+        Vec<CertificateResponse>,
+    ),
+    Error,
+> {
     if cancel.is_cancelled() {
         return Err(anyhow!("Operation cancelled"));
     }
 
     let mut open_ports = Vec::new();
     let mut endpoint_responses = Vec::new();
+    let rtt = Arc::new(HostRtt::new(SCAN_TIMEOUT));
 
     // Scan TCP ports with batching
-    let tcp_ports =
-        scan_tcp_ports(ip, cancel.clone(), port_scan_batch_size, tcp_ports_to_check).await?;
+    let tcp_ports = scan_tcp_ports(
+        ip,
+        cancel.clone(),
+        port_scan_batch_size,
+        tcp_ports_to_check,
+        rtt.clone(),
+    )
+    .await?;
 
     let use_https_ports: HashMap<u16, bool> =
         tcp_ports.iter().map(|(p, h)| (p.number(), *h)).collect();
@@ -139,8 +166,16 @@ pub async fn scan_ports_and_endpoints(
     }
 
     // Scan UDP ports with batching
-    let udp_ports =
-        scan_udp_ports(ip, cancel.clone(), port_scan_batch_size, cidr, gateway_ips).await?;
+    let udp_ports = scan_udp_ports(
+        ip,
+        cancel.clone(),
+        port_scan_batch_size,
+        cidr,
+        gateway_ips,
+        snmp_credentials,
+        rtt.clone(),
+    )
+    .await?;
     open_ports.extend(udp_ports);
 
     if cancel.is_cancelled() {
@@ -160,12 +195,33 @@ pub async fn scan_ports_and_endpoints(
         ip,
         cancel.clone(),
         Some(ports_to_check),
-        Some(use_https_ports),
+        Some(use_https_ports.clone()),
         port_scan_batch_size,
+        rtt.request_timeout(),
     )
     .await?;
     endpoint_responses.extend(endpoints);
 
+    let banner_ports = banner_ports(&tcp_ports, &endpoint_responses, &use_https_ports);
+    let banner_responses = scan_banners(
+        ip,
+        cancel.clone(),
+        banner_ports,
+        port_scan_batch_size,
+        rtt.connect_timeout(),
+    )
+    .await?;
+
+    let certificate_responses = scan_certificates(
+        ip,
+        cancel.clone(),
+        tls_ports(&tcp_ports, &endpoint_responses, &banner_responses),
+        port_scan_batch_size,
+        rtt.connect_timeout(),
+        rtt.request_timeout(),
+    )
+    .await?;
+
     // Add any ports that had endpoint responses but weren't in open_ports
     // This handles cases where we got HTTP response but port scan didn't detect it
     for endpoint_response in &endpoint_responses {
@@ -187,100 +243,124 @@ pub async fn scan_ports_and_endpoints(
         ip = %ip,
         open_ports = %open_ports.len(),
         endpoint_responses = %endpoint_responses.len(),
+        banner_responses = %banner_responses.len(),
+        certificate_responses = %certificate_responses.len(),
         "Host scan complete"
     );
 
-    Ok((open_ports, endpoint_responses))
+    Ok((
+        open_ports,
+        endpoint_responses,
+        banner_responses,
+        certificate_responses,
+    ))
 }
 
+/// Connect scan `tcp_ports_to_check`. Timeouts follow the host's measured round-trip
+/// time, and successful connects are fed back into it.
 pub async fn scan_tcp_ports(
     ip: IpAddr,
     cancel: CancellationToken,
     batch_size: usize,
     tcp_ports_to_check: Vec<u16>,
+    rtt: Arc<HostRtt>,
 ) -> Result<Vec<(PortType, bool)>, Error> {
     let ports: Vec<PortType> = tcp_ports_to_check
         .iter()
         .map(|p| PortType::new_tcp(*p))
         .collect();
 
-    let open_ports = batch_scan(ports.clone(), batch_size, cancel, move |port| async move {
-        let socket = SocketAddr::new(ip, port.number());
-
-        // Try connection with timeout, retry once on timeout for slow hosts
-        let mut attempts = 0;
-        let max_attempts = 2;
-
-        loop {
-            attempts += 1;
-            let start = std::time::Instant::now();
-
-            match timeout(SCAN_TIMEOUT, TcpStream::connect(socket)).await {
-                Ok(Ok(stream)) => {
-                    let connect_time = start.elapsed();
-
-                    // Try to peek at the connection to detect immediate disconnects
-                    let mut buf = [0u8; 1];
-                    let peek_result =
-                        timeout(Duration::from_millis(50), stream.peek(&mut buf)).await;
-
-                    let use_https = match peek_result {
-                        Ok(Ok(0)) => {
-                            // Port open - HTTPS (immediate close)"
-                            true
-                        }
-                        Ok(Ok(_)) => {
-                            // Port open - got bytes
-                            false
-                        }
-                        Ok(Err(_)) => {
-                            // Port open - peek error
-                            false
-                        }
-                        Err(_) => {
-                            // Port open - no immediate response
-                            false
-                        }
-                    };
-
-                    tracing::debug!(
-                        "Found open TCP port {}:{} (took {:?})",
-                        ip,
-                        port,
-                        connect_time
-                    );
-
-                    drop(stream);
-                    return Some((
-                        PortType::new_tcp(port.number()),
-                        use_https || port.is_https(),
-                    ));
-                }
-                Ok(Err(e)) => {
-                    if DiscoveryCriticalError::is_critical_error(e.to_string()) {
-                        tracing::error!("Critical error scanning {}:{}: {}", socket.ip(), port, e);
-                    }
-                    return None;
-                }
-                Err(_) => {
-                    let elapsed = start.elapsed();
+    let open_ports = batch_scan(ports.clone(), batch_size, cancel, move |port| {
+        let rtt = rtt.clone();
+        async move {
+            let socket = SocketAddr::new(ip, port.number());
+
+            // Try connection with timeout, retry once on timeout for slow hosts
+            let mut attempts = 0;
+            let max_attempts = 2;
+
+            loop {
+                attempts += 1;
+                let start = std::time::Instant::now();
+
+                match timeout(rtt.connect_timeout(), TcpStream::connect(socket)).await {
+                    Ok(Ok(stream)) => {
+                        let connect_time = start.elapsed();
+                        rtt.record(connect_time);
+
+                        // Try to peek at the connection to detect immediate disconnects
+                        let mut buf = [0u8; 1];
+                        let peek_result =
+                            timeout(Duration::from_millis(50), stream.peek(&mut buf)).await;
+
+                        let use_https = match peek_result {
+                            Ok(Ok(0)) => {
+                                // Port open - HTTPS (immediate close)"
+                                true
+                            }
+                            Ok(Ok(_)) => {
+                                // Port open - got bytes
+                                false
+                            }
+                            Ok(Err(_)) => {
+                                // Port open - peek error
+                                false
+                            }
+                            Err(_) => {
+                                // Port open - no immediate response
+                                false
+                            }
+                        };
 
-                    if attempts < max_attempts {
-                        tracing::trace!(
-                            "Port {}:{} timeout attempt {}/{} (took {:?}), retrying...",
+                        tracing::debug!(
+                            "Found open TCP port {}:{} (took {:?})",
                             ip,
                             port,
-                            attempts,
-                            max_attempts,
-                            elapsed
+                            connect_time
                         );
-                        // Small delay before retry
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                        continue;
-                    } else {
-                        tracing::trace!("Port {}:{} timeout after {} attempts", ip, port, attempts);
+
+                        drop(stream);
+                        return Some((
+                            PortType::new_tcp(port.number()),
+                            use_https || port.is_https(),
+                        ));
+                    }
+                    Ok(Err(e)) => {
+                        if DiscoveryCriticalError::is_critical_error(e.to_string()) {
+                            tracing::error!(
+                                "Critical error scanning {}:{}: {}",
+                                socket.ip(),
+                                port,
+                                e
+                            );
+                        }
                         return None;
                     }
+                    Err(_) => {
+                        let elapsed = start.elapsed();
+
+                        if attempts < max_attempts {
+                            tracing::trace!(
+                                "Port {}:{} timeout attempt {}/{} (took {:?}), retrying...",
+                                ip,
+                                port,
+                                attempts,
+                                max_attempts,
+                                elapsed
+                            );
+                            // Small delay before retry
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            continue;
+                        } else {
+                            tracing::trace!(
+                                "Port {}:{} timeout after {} attempts",
+                                ip,
+                                port,
+                                attempts
+                            );
+                            return None;
+                        }
+                    }
                 }
             }
         }
@@ -303,6 +383,8 @@ pub async fn scan_udp_ports(
     batch_size: usize,
     cidr: IpCidr,
     gateway_ips: Vec<IpAddr>,
+    snmp_credentials: Vec<SnmpCredential>,
+    rtt: Arc<HostRtt>,
 ) -> Result<Vec<PortType>, Error> {
     let discovery_ports = Service::all_discovery_ports();
     let ports: Vec<u16> = discovery_ports
@@ -315,33 +397,39 @@ pub async fn scan_udp_ports(
     let udp_batch_size = std::cmp::min(batch_size, 10);
 
     let is_gateway = gateway_ips.contains(&ip);
+    let snmp_credentials = Arc::new(snmp_credentials);
+    let probe_timeout = rtt.udp_timeout();
+    let dns_timeout = rtt.dns_timeout();
 
-    let open_ports = batch_scan(ports.clone(), udp_batch_size, cancel, |port| async move {
-        let result = match port {
-            53 => test_dns_service(ip).await,
-            123 => test_ntp_service(ip).await,
-            161 => test_snmp_service(ip).await,
-            67 => {
-                if is_gateway {
-                    test_dhcp_service(ip, &cidr).await
-                } else {
-                    Ok(None)
+    let open_ports = batch_scan(ports.clone(), udp_batch_size, cancel, |port| {
+        let snmp_credentials = snmp_credentials.clone();
+        async move {
+            let result = match port {
+                53 => test_dns_service(ip, dns_timeout).await,
+                123 => test_ntp_service(ip, probe_timeout).await,
+                161 => test_snmp_service(ip, &snmp_credentials, probe_timeout).await,
+                67 => {
+                    if is_gateway {
+                        test_dhcp_service(ip, &cidr).await
+                    } else {
+                        Ok(None)
+                    }
                 }
-            }
-            _ => Ok(None),
-        };
+                _ => Ok(None),
+            };
 
-        match result {
-            Ok(Some(detected_port)) => {
-                tracing::trace!("Found open UDP port {}:{}", ip, detected_port);
-                Some(PortType::new_udp(detected_port))
-            }
-            Ok(None) => None,
-            Err(e) => {
-                if DiscoveryCriticalError::is_critical_error(e.to_string()) {
-                    tracing::error!("Critical error scanning UDP {}:{}: {}", ip, port, e);
+            match result {
+                Ok(Some(detected_port)) => {
+                    tracing::trace!("Found open UDP port {}:{}", ip, detected_port);
+                    Some(PortType::new_udp(detected_port))
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    if DiscoveryCriticalError::is_critical_error(e.to_string()) {
+                        tracing::error!("Critical error scanning UDP {}:{}: {}", ip, port, e);
+                    }
+                    None
                 }
-                None
             }
         }
     })
@@ -357,17 +445,201 @@ pub async fn scan_udp_ports(
     Ok(open_ports)
 }
 
+/// Pick the open TCP ports worth reading a banner from.
+/// Ports that answered HTTP or look like TLS wait for the client to speak first, so they're skipped.
+pub fn banner_ports(
+    tcp_ports: &[PortType],
+    endpoint_responses: &[EndpointResponse],
+    use_https_ports: &HashMap<u16, bool>,
+) -> Vec<PortType> {
+    tcp_ports
+        .iter()
+        .filter(|p| p.protocol() == TransportProtocol::Tcp)
+        .filter(|p| {
+            !endpoint_responses
+                .iter()
+                .any(|r| r.endpoint.port_type.number() == p.number())
+        })
+        .filter(|p| !use_https_ports.get(&p.number()).copied().unwrap_or(false))
+        .copied()
+        .collect()
+}
+
+/// Read the banner of each TCP port.
+/// Services that speak first (SSH, SMTP, FTP, POP3, IMAP, MySQL...) are read passively,
+/// services that wait for the client get a small protocol-specific probe.
+pub async fn scan_banners(
+    ip: IpAddr,
+    cancel: CancellationToken,
+    tcp_ports: Vec<PortType>,
+    batch_size: usize,
+    connect_timeout: Duration,
+) -> Result<Vec<BannerResponse>, Error> {
+    let port_count = tcp_ports.len();
+
+    let banners = batch_scan(tcp_ports, batch_size, cancel, move |port| async move {
+        let banner = grab_banner(SocketAddr::new(ip, port.number()), connect_timeout).await?;
+
+        tracing::debug!(ip = %ip, port = %port, banner = %banner, "Read TCP banner");
+
+        Some(BannerResponse {
+            port_type: port,
+            banner,
+        })
+    })
+    .await;
+
+    tracing::debug!(
+        ip = %ip,
+        ports_scanned = %port_count,
+        responses = %banners.len(),
+        "TCP banners scanned"
+    );
+
+    Ok(banners)
+}
+
+/// Pick the open TCP ports worth attempting a TLS handshake on.
+/// A port that sent a plaintext banner or answered plain HTTP isn't speaking TLS.
+pub fn tls_ports(
+    tcp_ports: &[PortType],
+    endpoint_responses: &[EndpointResponse],
+    banner_responses: &[BannerResponse],
+) -> Vec<PortType> {
+    tcp_ports
+        .iter()
+        .filter(|p| p.protocol() == TransportProtocol::Tcp)
+        .filter(|p| {
+            !banner_responses
+                .iter()
+                .any(|r| r.port_type.number() == p.number())
+        })
+        .filter(|p| {
+            !endpoint_responses.iter().any(|r| {
+                r.endpoint.port_type.number() == p.number()
+                    && r.endpoint.protocol == ApplicationProtocol::Http
+            })
+        })
+        .copied()
+        .collect()
+}
+
+/// Read the certificate presented by each TCP port that completes a TLS handshake
+pub async fn scan_certificates(
+    ip: IpAddr,
+    cancel: CancellationToken,
+    tcp_ports: Vec<PortType>,
+    batch_size: usize,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> Result<Vec<CertificateResponse>, Error> {
+    let port_count = tcp_ports.len();
+    let connector = certificate_connector()?;
+
+    let certificates = batch_scan(tcp_ports, batch_size, cancel, move |port| {
+        let connector = connector.clone();
+        async move {
+            let der = grab_certificate(
+                &connector,
+                SocketAddr::new(ip, port.number()),
+                connect_timeout,
+                request_timeout,
+            )
+            .await?;
+
+            match parse_certificate(&der) {
+                Ok(certificate) => {
+                    tracing::debug!(
+                        ip = %ip,
+                        port = %port,
+                        subject = %certificate.subject,
+                        issuer = %certificate.issuer,
+                        "Read TLS certificate"
+                    );
+                    Some(CertificateResponse {
+                        port_type: port,
+                        certificate,
+                    })
+                }
+                Err(e) => {
+                    tracing::debug!(ip = %ip, port = %port, error = %e, "Unreadable TLS certificate");
+                    None
+                }
+            }
+        }
+    })
+    .await;
+
+    tracing::debug!(
+        ip = %ip,
+        ports_scanned = %port_count,
+        responses = %certificates.len(),
+        "TLS certificates scanned"
+    );
+
+    Ok(certificates)
+}
+
+/// Probe to send to services that wait for the client to speak first
+fn banner_probe(port: u16) -> Option<&'static [u8]> {
+    match port {
+        6379 => Some(b"PING\r\n"),     // Redis
+        11211 => Some(b"version\r\n"), // Memcached
+        _ => None,
+    }
+}
+
+async fn grab_banner(socket: SocketAddr, connect_timeout: Duration) -> Option<String> {
+    let mut stream = timeout(connect_timeout, TcpStream::connect(socket))
+        .await
+        .ok()?
+        .ok()?;
+
+    if let Some(probe) = banner_probe(socket.port()) {
+        timeout(connect_timeout, stream.write_all(probe))
+            .await
+            .ok()?
+            .ok()?;
+    }
+
+    // Hosts behind slow links wait at least their connect timeout for the banner
+    let mut buf = vec![0u8; MAX_BANNER_LENGTH];
+    let read = timeout(BANNER_TIMEOUT.max(connect_timeout), stream.read(&mut buf))
+        .await
+        .ok()?
+        .ok()?;
+
+    let banner = sanitize_banner(&buf[..read]);
+    (!banner.is_empty()).then_some(banner)
+}
+
+/// Render banner bytes as text. Binary handshakes (e.g. MySQL's) keep their
+/// readable parts, everything else becomes '.'
+fn sanitize_banner(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| match b {
+            b'\r' | b'\n' | b'\t' => ' ',
+            0x20..=0x7e => *b as char,
+            _ => '.',
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
 pub async fn scan_endpoints(
     ip: IpAddr,
     cancel: CancellationToken,
     filter_ports: Option<Vec<PortType>>,
     use_https_ports: Option<HashMap<u16, bool>>,
     batch_size: usize,
+    request_timeout: Duration,
 ) -> Result<Vec<EndpointResponse>, Error> {
     use std::collections::HashMap;
 
     let client = reqwest::Client::builder()
-        .timeout(SCAN_TIMEOUT)
+        .timeout(request_timeout)
         .danger_accept_invalid_certs(true)
         .build()
         .map_err(|e| anyhow!("Could not build client {}", e))?;
@@ -497,7 +769,7 @@ pub async fn scan_endpoints(
     Ok(responses)
 }
 
-pub async fn test_dns_service(ip: IpAddr) -> Result<Option<u16>, Error> {
+pub async fn test_dns_service(ip: IpAddr, probe_timeout: Duration) -> Result<Option<u16>, Error> {
     let mut config = ResolverConfig::new();
     let name_server = NameServerConfig::new(SocketAddr::new(ip, 53), Protocol::Udp);
     config.add_name_server(name_server);
@@ -505,27 +777,17 @@ pub async fn test_dns_service(ip: IpAddr) -> Result<Option<u16>, Error> {
     let resolver =
         Resolver::builder_with_config(config, TokioConnectionProvider::default()).build();
 
-    match timeout(
-        Duration::from_millis(2000),
-        resolver.lookup_ip("google.com"),
-    )
-    .await
-    {
+    match timeout(probe_timeout, resolver.lookup_ip("google.com")).await {
         Ok(Ok(_)) => Ok(Some(53)),
         _ => Ok(None),
     }
 }
 
-pub async fn test_ntp_service(ip: IpAddr) -> Result<Option<u16>, Error> {
+pub async fn test_ntp_service(ip: IpAddr, probe_timeout: Duration) -> Result<Option<u16>, Error> {
     let client = AsyncSntpClient::new();
     let server_addr = format!("{}:123", ip);
 
-    match timeout(
-        Duration::from_millis(2000),
-        client.synchronize(&server_addr),
-    )
-    .await
-    {
+    match timeout(probe_timeout, client.synchronize(&server_addr)).await {
         Ok(Ok(result)) => {
             // Validate that we got a meaningful time response
             if let Ok(datetime) = result.datetime().unix_timestamp() {
@@ -543,29 +805,15 @@ pub async fn test_ntp_service(ip: IpAddr) -> Result<Option<u16>, Error> {
     }
 }
 
-pub async fn test_snmp_service(ip: IpAddr) -> Result<Option<u16>, Error> {
-    let target = format!("{}:161", ip);
-    let community = b"public";
-
-    match AsyncSession::new_v2c(&target, community, 0).await {
-        Ok(mut session) => {
-            let sys_descr_oid = Oid::from(&[1, 3, 6, 1, 2, 1, 1, 1, 0])
-                .map_err(|e| anyhow!("Invalid Oid: {:?}", e))?;
-
-            match timeout(Duration::from_millis(2000), session.get(&sys_descr_oid)).await {
-                Ok(Ok(mut response)) => {
-                    if let Some(_varbind) = response.varbinds.next() {
-                        Ok(Some(161))
-                    } else {
-                        Ok(None)
-                    }
-                }
-                Ok(Err(_)) => Ok(None),
-                Err(_) => Ok(None),
-            }
-        }
-        Err(_) => Ok(None),
-    }
+/// Test if a host answers SNMP with any of the given credentials
+pub async fn test_snmp_service(
+    ip: IpAddr,
+    credentials: &[SnmpCredential],
+    probe_timeout: Duration,
+) -> Result<Option<u16>, Error> {
+    Ok(snmp::find_credential(ip, credentials, probe_timeout)
+        .await
+        .map(|_| 161))
 }
 
 /// Test if a host is running a DHCP server on port 67
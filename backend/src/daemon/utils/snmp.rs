@@ -0,0 +1,784 @@
+//! SNMP inventory walk.
+//!
+//! Hosts that answer SNMP get a deeper look than the `sysDescr` probe used to
+//! detect the service:
+//!
+//! 1. The system group (sysDescr, sysObjectID, sysName)
+//! 2. IF-MIB ifTable/ifXTable for port names, MACs, speeds and oper status
+//! 3. The IP address table, mapping addresses to interfaces
+//! 4. LLDP-MIB lldpRemTable, which tells us which device sits on which port
+//!
+//! Every table after the system group is optional; devices that don't
+//! implement a MIB simply contribute nothing from it.
+//!
+//! Sessions are opened with the network's credential profiles, tried in order.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use mac_address::MacAddress;
+#[cfg(feature = "snmp-v3")]
+use snmp2::v3;
+use snmp2::{AsyncSession, Oid, Value};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use crate::server::hosts::r#impl::links::PhysicalLink;
+#[cfg(feature = "snmp-v3")]
+use crate::server::networks::snmp::{SnmpAuthProtocol, SnmpPrivacyProtocol, SnmpSecurityLevel};
+use crate::server::networks::snmp::{SnmpCredential, SnmpVersion};
+
+/// How long to wait for each SNMP response
+pub const SNMP_TIMEOUT: Duration = Duration::from_millis(2000);
+
+const SNMP_PORT: u16 = 161;
+/// Rows requested per GETBULK
+const MAX_REPETITIONS: u32 = 25;
+/// Upper bound on rows read from a single table, protects against agents that loop
+const MAX_WALK_ROWS: usize = 5000;
+
+// SNMPv2-MIB system group
+const SYSTEM: &[u64] = &[1, 3, 6, 1, 2, 1, 1];
+const SYS_DESCR: u64 = 1;
+const SYS_OBJECT_ID: u64 = 2;
+const SYS_NAME: u64 = 5;
+
+// IF-MIB ifEntry
+const IF_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 2, 2, 1];
+const IF_DESCR: u64 = 2;
+const IF_SPEED: u64 = 5;
+const IF_PHYS_ADDRESS: u64 = 6;
+const IF_OPER_STATUS: u64 = 8;
+const IF_OPER_STATUS_UP: u64 = 1;
+
+// IF-MIB ifXEntry
+const IF_X_ENTRY: &[u64] = &[1, 3, 6, 1, 2, 1, 31, 1, 1, 1];
+const IF_NAME: u64 = 1;
+const IF_HIGH_SPEED: u64 = 15;
+
+// IP-MIB ipAdEntIfIndex, indexed by address
+const IP_AD_ENT_IF_INDEX: &[u64] = &[1, 3, 6, 1, 2, 1, 4, 20, 1, 2];
+
+// LLDP-MIB lldpLocPortEntry, indexed by local port number
+const LLDP_LOC_PORT_ENTRY: &[u64] = &[1, 0, 8802, 1, 1, 2, 1, 3, 7, 1];
+const LLDP_LOC_PORT_ID: u64 = 3;
+const LLDP_LOC_PORT_DESC: u64 = 4;
+
+// LLDP-MIB lldpRemEntry, indexed by time mark, local port number and remote index
+const LLDP_REM_ENTRY: &[u64] = &[1, 0, 8802, 1, 1, 2, 1, 4, 1, 1];
+const LLDP_REM_CHASSIS_ID_SUBTYPE: u64 = 4;
+const LLDP_REM_CHASSIS_ID: u64 = 5;
+const LLDP_REM_PORT_ID_SUBTYPE: u64 = 6;
+const LLDP_REM_PORT_ID: u64 = 7;
+const LLDP_REM_PORT_DESC: u64 = 8;
+const LLDP_REM_SYS_NAME: u64 = 9;
+
+// LLDP-MIB lldpRemManAddrIfSubtype, indexed by the lldpRemEntry index plus the address
+const LLDP_REM_MAN_ADDR_IF_SUBTYPE: &[u64] = &[1, 0, 8802, 1, 1, 2, 1, 4, 2, 1, 3];
+
+// LldpChassisIdSubtype / LldpPortIdSubtype values we decode
+const CHASSIS_ID_SUBTYPE_MAC_ADDRESS: u64 = 4;
+const CHASSIS_ID_SUBTYPE_NETWORK_ADDRESS: u64 = 5;
+const PORT_ID_SUBTYPE_MAC_ADDRESS: u64 = 3;
+
+// IANA address family numbers, used by LLDP network addresses
+const ADDRESS_FAMILY_IPV4: u64 = 1;
+const ADDRESS_FAMILY_IPV6: u64 = 2;
+
+/// Owned copy of an SNMP value, so rows outlive the response buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnmpValue {
+    Bytes(Vec<u8>),
+    Integer(i64),
+    Unsigned(u64),
+    ObjectIdentifier(String),
+    IpAddress(Ipv4Addr),
+    Other,
+}
+
+impl From<&Value<'_>> for SnmpValue {
+    fn from(value: &Value<'_>) -> Self {
+        match value {
+            Value::OctetString(bytes) => SnmpValue::Bytes(bytes.to_vec()),
+            Value::Integer(n) => SnmpValue::Integer(*n),
+            Value::Counter32(n) | Value::Unsigned32(n) | Value::Timeticks(n) => {
+                SnmpValue::Unsigned(*n as u64)
+            }
+            Value::Counter64(n) => SnmpValue::Unsigned(*n),
+            Value::ObjectIdentifier(oid) => SnmpValue::ObjectIdentifier(oid.to_id_string()),
+            Value::IpAddress(octets) => SnmpValue::IpAddress(Ipv4Addr::from(*octets)),
+            _ => SnmpValue::Other,
+        }
+    }
+}
+
+impl SnmpValue {
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            SnmpValue::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Display strings, with padding and trailing NULs some agents include removed
+    fn as_text(&self) -> Option<String> {
+        match self {
+            SnmpValue::Bytes(bytes) => {
+                let text = String::from_utf8_lossy(bytes)
+                    .trim_matches(|c: char| c.is_whitespace() || c == '\0')
+                    .to_string();
+                (!text.is_empty()).then_some(text)
+            }
+            SnmpValue::ObjectIdentifier(oid) => Some(oid.clone()),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            SnmpValue::Integer(n) => u64::try_from(*n).ok(),
+            SnmpValue::Unsigned(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Rows of a walked subtree, keyed by the OID arcs that follow the subtree root
+pub type SnmpRows = Vec<(Vec<u64>, SnmpValue)>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnmpSystem {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub object_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnmpInterface {
+    pub index: u64,
+    /// ifName, falling back to ifDescr
+    pub name: String,
+    pub mac_address: Option<MacAddress>,
+    pub speed_mbps: Option<u64>,
+    pub is_up: bool,
+    pub ip_addresses: Vec<IpAddr>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SnmpInventory {
+    /// Profile that answered, None for the built-in "public" fallback
+    pub credential_id: Option<Uuid>,
+    pub system: SnmpSystem,
+    pub interfaces: Vec<SnmpInterface>,
+    pub physical_links: Vec<PhysicalLink>,
+    /// Whether the LLDP neighbor table could be walked. Without it the links are
+    /// unknown rather than absent.
+    pub neighbors_read: bool,
+}
+
+impl SnmpInventory {
+    pub fn interface_for_ip(&self, ip: IpAddr) -> Option<&SnmpInterface> {
+        self.interfaces
+            .iter()
+            .find(|i| i.ip_addresses.contains(&ip))
+    }
+}
+
+/// Open a session with a credential profile. v3 sessions discover the agent's engine first.
+pub async fn open_session(ip: IpAddr, credential: &SnmpCredential) -> Result<AsyncSession> {
+    let target = SocketAddr::new(ip, SNMP_PORT);
+
+    let session = match &credential.version {
+        SnmpVersion::V1 { community } => {
+            AsyncSession::new_v1(target, community.as_bytes(), 0).await?
+        }
+        SnmpVersion::V2c { community } => {
+            AsyncSession::new_v2c(target, community.as_bytes(), 0).await?
+        }
+        #[cfg(feature = "snmp-v3")]
+        SnmpVersion::V3 {
+            username,
+            security_level,
+            auth_protocol,
+            auth_password,
+            privacy_protocol,
+            privacy_password,
+        } => {
+            let auth = match security_level {
+                SnmpSecurityLevel::NoAuthNoPriv => v3::Auth::NoAuthNoPriv,
+                SnmpSecurityLevel::AuthNoPriv => v3::Auth::AuthNoPriv,
+                SnmpSecurityLevel::AuthPriv => v3::Auth::AuthPriv {
+                    cipher: match privacy_protocol {
+                        SnmpPrivacyProtocol::Des => v3::Cipher::Des,
+                        SnmpPrivacyProtocol::Aes128 => v3::Cipher::Aes128,
+                        SnmpPrivacyProtocol::Aes192 => v3::Cipher::Aes192,
+                        SnmpPrivacyProtocol::Aes256 => v3::Cipher::Aes256,
+                    },
+                    privacy_password: privacy_password.as_bytes().to_vec(),
+                },
+            };
+
+            let security = v3::Security::new(username.as_bytes(), auth_password.as_bytes())
+                .with_auth_protocol(match auth_protocol {
+                    SnmpAuthProtocol::Md5 => v3::AuthProtocol::Md5,
+                    SnmpAuthProtocol::Sha1 => v3::AuthProtocol::Sha1,
+                    SnmpAuthProtocol::Sha224 => v3::AuthProtocol::Sha224,
+                    SnmpAuthProtocol::Sha256 => v3::AuthProtocol::Sha256,
+                    SnmpAuthProtocol::Sha384 => v3::AuthProtocol::Sha384,
+                    SnmpAuthProtocol::Sha512 => v3::AuthProtocol::Sha512,
+                })
+                .with_auth(auth);
+
+            let mut session = AsyncSession::new_v3(target, 0, security).await?;
+            timeout(SNMP_TIMEOUT, session.init())
+                .await
+                .map_err(|_| anyhow!("SNMPv3 engine discovery timed out"))?
+                .map_err(|e| anyhow!("SNMPv3 engine discovery failed: {:?}", e))?;
+            session
+        }
+        #[cfg(not(feature = "snmp-v3"))]
+        SnmpVersion::V3 { .. } => {
+            return Err(anyhow!(
+                "SNMPv3 credential '{}' needs a daemon built with the snmp-v3 feature",
+                credential.name
+            ));
+        }
+    };
+
+    Ok(session)
+}
+
+/// The open session, if the agent answers a sysDescr GET with this profile
+async fn probe(
+    ip: IpAddr,
+    credential: &SnmpCredential,
+    probe_timeout: Duration,
+) -> Option<AsyncSession> {
+    let mut session = open_session(ip, credential).await.ok()?;
+    let sys_descr_oid = Oid::from(&[1, 3, 6, 1, 2, 1, 1, 1, 0]).ok()?;
+
+    let answered = match timeout(probe_timeout, session.get(&sys_descr_oid)).await {
+        Ok(Ok(mut response)) => response.varbinds.next().is_some(),
+        _ => false,
+    };
+    answered.then_some(session)
+}
+
+/// First profile the agent answers to, in the order given, with the session that answered
+pub async fn find_credential(
+    ip: IpAddr,
+    credentials: &[SnmpCredential],
+    probe_timeout: Duration,
+) -> Option<(&SnmpCredential, AsyncSession)> {
+    for credential in credentials {
+        if let Some(session) = probe(ip, credential, probe_timeout).await {
+            return Some((credential, session));
+        }
+    }
+    None
+}
+
+/// Walk the system group, interfaces and LLDP neighbors of an SNMP agent,
+/// using the first profile it answers to
+pub async fn walk_inventory(ip: IpAddr, credentials: &[SnmpCredential]) -> Result<SnmpInventory> {
+    let Some((credential, mut session)) = find_credential(ip, credentials, SNMP_TIMEOUT).await
+    else {
+        return Err(anyhow!(
+            "None of the {} SNMP credential profiles were accepted",
+            credentials.len()
+        ));
+    };
+
+    // GETBULK doesn't exist in SNMPv1
+    let bulk = !matches!(credential.version, SnmpVersion::V1 { .. });
+
+    let system = parse_system(&walk(&mut session, SYSTEM, bulk).await?);
+
+    let if_rows = walk(&mut session, IF_ENTRY, bulk).await.unwrap_or_default();
+    let if_x_rows = walk(&mut session, IF_X_ENTRY, bulk)
+        .await
+        .unwrap_or_default();
+    let ip_rows = walk(&mut session, IP_AD_ENT_IF_INDEX, bulk)
+        .await
+        .unwrap_or_default();
+    let interfaces = parse_interfaces(&if_rows, &if_x_rows, &ip_rows);
+
+    let rem_rows = walk(&mut session, LLDP_REM_ENTRY, bulk).await;
+    let neighbors_read = rem_rows.is_ok();
+    let rem_rows = rem_rows.unwrap_or_default();
+
+    let physical_links = if rem_rows.is_empty() {
+        Vec::new()
+    } else {
+        let loc_port_rows = walk(&mut session, LLDP_LOC_PORT_ENTRY, bulk)
+            .await
+            .unwrap_or_default();
+        let man_addr_rows = walk(&mut session, LLDP_REM_MAN_ADDR_IF_SUBTYPE, bulk)
+            .await
+            .unwrap_or_default();
+        parse_lldp_neighbors(&rem_rows, &loc_port_rows, &man_addr_rows, &interfaces)
+    };
+
+    tracing::debug!(
+        ip = %ip,
+        credential = %credential.name,
+        sys_name = ?system.name,
+        sys_object_id = ?system.object_id,
+        interfaces = interfaces.len(),
+        neighbors = physical_links.len(),
+        "SNMP inventory walk complete"
+    );
+
+    Ok(SnmpInventory {
+        credential_id: (!credential.id.is_nil()).then_some(credential.id),
+        system,
+        interfaces,
+        physical_links,
+        neighbors_read,
+    })
+}
+
+/// Walk a subtree with GETBULK (GETNEXT for v1) until the agent returns an OID outside of it
+async fn walk(session: &mut AsyncSession, root: &[u64], bulk: bool) -> Result<SnmpRows> {
+    let mut rows = Vec::new();
+    let mut cursor = root.to_vec();
+
+    loop {
+        let request_oid =
+            Oid::from(cursor.as_slice()).map_err(|e| anyhow!("Invalid Oid: {:?}", e))?;
+
+        let response = if bulk {
+            timeout(
+                SNMP_TIMEOUT,
+                session.getbulk(&[&request_oid], 0, MAX_REPETITIONS),
+            )
+            .await
+        } else {
+            timeout(SNMP_TIMEOUT, session.getnext(&request_oid)).await
+        };
+
+        let response = response
+            .map_err(|_| anyhow!("SNMP walk of {:?} timed out", root))?
+            .map_err(|e| anyhow!("SNMP walk of {:?} failed: {:?}", root, e))?;
+
+        let mut advanced = false;
+
+        for (oid, value) in response.varbinds {
+            let Some(arcs) = oid.iter().map(|arcs| arcs.collect::<Vec<u64>>()) else {
+                return Ok(rows);
+            };
+
+            let end_of_subtree = !arcs.starts_with(root)
+                || arcs <= cursor // Agents must return increasing OIDs, stop if one doesn't
+                || matches!(
+                    value,
+                    Value::EndOfMibView | Value::NoSuchObject | Value::NoSuchInstance
+                );
+
+            if end_of_subtree {
+                return Ok(rows);
+            }
+
+            rows.push((arcs[root.len()..].to_vec(), SnmpValue::from(&value)));
+            cursor = arcs;
+            advanced = true;
+        }
+
+        if !advanced || rows.len() >= MAX_WALK_ROWS {
+            return Ok(rows);
+        }
+    }
+}
+
+fn parse_system(rows: &SnmpRows) -> SnmpSystem {
+    let scalar = |column: u64| {
+        rows.iter()
+            .find(|(arcs, _)| arcs.as_slice() == [column, 0])
+            .and_then(|(_, value)| value.as_text())
+    };
+
+    SnmpSystem {
+        name: scalar(SYS_NAME),
+        description: scalar(SYS_DESCR),
+        object_id: scalar(SYS_OBJECT_ID),
+    }
+}
+
+fn parse_interfaces(
+    if_rows: &SnmpRows,
+    if_x_rows: &SnmpRows,
+    ip_rows: &SnmpRows,
+) -> Vec<SnmpInterface> {
+    let mut interfaces: BTreeMap<u64, SnmpInterface> = BTreeMap::new();
+    let mut descriptions: HashMap<u64, String> = HashMap::new();
+    let mut if_speeds: HashMap<u64, u64> = HashMap::new();
+
+    for (arcs, value) in if_rows {
+        let [column, index] = arcs.as_slice() else {
+            continue;
+        };
+
+        let interface = interfaces.entry(*index).or_insert_with(|| SnmpInterface {
+            index: *index,
+            ..Default::default()
+        });
+
+        match *column {
+            IF_DESCR => {
+                if let Some(description) = value.as_text() {
+                    descriptions.insert(*index, description);
+                }
+            }
+            IF_SPEED => {
+                if let Some(speed) = value.as_u64() {
+                    if_speeds.insert(*index, speed);
+                }
+            }
+            IF_PHYS_ADDRESS => interface.mac_address = value.as_bytes().and_then(mac_from_bytes),
+            IF_OPER_STATUS => interface.is_up = value.as_u64() == Some(IF_OPER_STATUS_UP),
+            _ => {}
+        }
+    }
+
+    for (arcs, value) in if_x_rows {
+        let [column, index] = arcs.as_slice() else {
+            continue;
+        };
+        let Some(interface) = interfaces.get_mut(index) else {
+            continue;
+        };
+
+        match *column {
+            IF_NAME => {
+                if let Some(name) = value.as_text() {
+                    interface.name = name;
+                }
+            }
+            // ifHighSpeed is already in Mbps, zero means unknown
+            IF_HIGH_SPEED => interface.speed_mbps = value.as_u64().filter(|s| *s > 0),
+            _ => {}
+        }
+    }
+
+    for interface in interfaces.values_mut() {
+        if interface.name.is_empty() {
+            interface.name = descriptions
+                .remove(&interface.index)
+                .unwrap_or_else(|| format!("ifIndex {}", interface.index));
+        }
+
+        // ifSpeed is in bits per second and saturates at ~4.3 Gbps, only use it without ifHighSpeed
+        if interface.speed_mbps.is_none() {
+            interface.speed_mbps = if_speeds
+                .get(&interface.index)
+                .map(|bps| bps / 1_000_000)
+                .filter(|s| *s > 0);
+        }
+    }
+
+    for (arcs, value) in ip_rows {
+        let [a, b, c, d] = arcs.as_slice() else {
+            continue;
+        };
+        let (Ok(a), Ok(b), Ok(c), Ok(d)) = (
+            u8::try_from(*a),
+            u8::try_from(*b),
+            u8::try_from(*c),
+            u8::try_from(*d),
+        ) else {
+            continue;
+        };
+
+        if let Some(interface) = value.as_u64().and_then(|index| interfaces.get_mut(&index)) {
+            interface
+                .ip_addresses
+                .push(IpAddr::V4(Ipv4Addr::new(a, b, c, d)));
+        }
+    }
+
+    interfaces.into_values().collect()
+}
+
+#[derive(Default)]
+struct LldpRemote {
+    chassis_id_subtype: Option<u64>,
+    chassis_id: Option<SnmpValue>,
+    port_id_subtype: Option<u64>,
+    port_id: Option<SnmpValue>,
+    port_description: Option<String>,
+    system_name: Option<String>,
+    management_address: Option<IpAddr>,
+}
+
+fn parse_lldp_neighbors(
+    rem_rows: &SnmpRows,
+    loc_port_rows: &SnmpRows,
+    man_addr_rows: &SnmpRows,
+    interfaces: &[SnmpInterface],
+) -> Vec<PhysicalLink> {
+    // (local port number, remote index) -> remote system
+    let mut remotes: BTreeMap<(u64, u64), LldpRemote> = BTreeMap::new();
+
+    for (arcs, value) in rem_rows {
+        let [column, _time_mark, local_port, remote_index] = arcs.as_slice() else {
+            continue;
+        };
+
+        let remote = remotes.entry((*local_port, *remote_index)).or_default();
+
+        match *column {
+            LLDP_REM_CHASSIS_ID_SUBTYPE => remote.chassis_id_subtype = value.as_u64(),
+            LLDP_REM_CHASSIS_ID => remote.chassis_id = Some(value.clone()),
+            LLDP_REM_PORT_ID_SUBTYPE => remote.port_id_subtype = value.as_u64(),
+            LLDP_REM_PORT_ID => remote.port_id = Some(value.clone()),
+            LLDP_REM_PORT_DESC => remote.port_description = value.as_text(),
+            LLDP_REM_SYS_NAME => remote.system_name = value.as_text(),
+            _ => {}
+        }
+    }
+
+    // Index: time mark, local port, remote index, address subtype, address length, address
+    for (arcs, _) in man_addr_rows {
+        let [
+            _time_mark,
+            local_port,
+            remote_index,
+            subtype,
+            _length,
+            address @ ..,
+        ] = arcs.as_slice()
+        else {
+            continue;
+        };
+
+        if let Some(remote) = remotes.get_mut(&(*local_port, *remote_index))
+            && remote.management_address.is_none()
+        {
+            remote.management_address = ip_from_arcs(*subtype, address);
+        }
+    }
+
+    // Local port number -> port ID / description advertised for it
+    let mut local_port_names: HashMap<u64, Vec<String>> = HashMap::new();
+    for (arcs, value) in loc_port_rows {
+        let [column, local_port] = arcs.as_slice() else {
+            continue;
+        };
+        if matches!(*column, LLDP_LOC_PORT_ID | LLDP_LOC_PORT_DESC)
+            && let Some(name) = value.as_text()
+        {
+            local_port_names.entry(*local_port).or_default().push(name);
+        }
+    }
+
+    remotes
+        .into_iter()
+        .map(|((local_port, _), remote)| {
+            // lldpLocPortNum is often, but not always, the ifIndex. Match by name first.
+            let names = local_port_names.get(&local_port);
+            let local_interface = interfaces
+                .iter()
+                .find(|i| names.is_some_and(|names| names.contains(&i.name)))
+                .or_else(|| interfaces.iter().find(|i| i.index == local_port));
+
+            let local_port_name = local_interface
+                .map(|i| i.name.clone())
+                .or_else(|| names.and_then(|names| names.first().cloned()))
+                .unwrap_or_else(|| format!("Port {}", local_port));
+
+            let chassis_bytes = remote.chassis_id.as_ref().and_then(|v| v.as_bytes());
+            let port_bytes = remote.port_id.as_ref().and_then(|v| v.as_bytes());
+
+            let chassis_mac = match remote.chassis_id_subtype {
+                Some(CHASSIS_ID_SUBTYPE_MAC_ADDRESS) => chassis_bytes.and_then(mac_from_bytes),
+                _ => None,
+            };
+            let port_mac = match remote.port_id_subtype {
+                Some(PORT_ID_SUBTYPE_MAC_ADDRESS) => port_bytes.and_then(mac_from_bytes),
+                _ => None,
+            };
+            let chassis_ip = match remote.chassis_id_subtype {
+                Some(CHASSIS_ID_SUBTYPE_NETWORK_ADDRESS) => chassis_bytes.and_then(|bytes| {
+                    let (family, address) = bytes.split_first()?;
+                    let address: Vec<u64> = address.iter().map(|b| *b as u64).collect();
+                    ip_from_arcs(*family as u64, &address)
+                }),
+                _ => None,
+            };
+
+            let remote_chassis_id = chassis_mac
+                .map(|mac| mac.to_string())
+                .or_else(|| chassis_ip.map(|ip| ip.to_string()))
+                .or_else(|| remote.chassis_id.as_ref().and_then(|v| v.as_text()))
+                .unwrap_or_default();
+
+            let remote_port = remote.port_description.clone().or_else(|| {
+                port_mac
+                    .map(|mac| mac.to_string())
+                    .or_else(|| remote.port_id.as_ref().and_then(|v| v.as_text()))
+            });
+
+            PhysicalLink {
+                local_port: local_port_name,
+                speed_mbps: local_interface.and_then(|i| i.speed_mbps),
+                is_up: local_interface.is_none_or(|i| i.is_up),
+                remote_chassis_id,
+                // The port MAC is the neighbor's interface, prefer it over the chassis MAC
+                remote_mac_address: port_mac.or(chassis_mac),
+                remote_port,
+                remote_system_name: remote.system_name,
+                remote_ip_address: remote.management_address.or(chassis_ip),
+            }
+        })
+        .collect()
+}
+
+fn mac_from_bytes(bytes: &[u8]) -> Option<MacAddress> {
+    let octets: [u8; 6] = bytes.try_into().ok()?;
+    (octets != [0; 6]).then(|| MacAddress::new(octets))
+}
+
+/// Decode an address carried as OID arcs (one arc per byte) with an IANA address family
+fn ip_from_arcs(family: u64, arcs: &[u64]) -> Option<IpAddr> {
+    let bytes: Vec<u8> = arcs
+        .iter()
+        .map(|arc| u8::try_from(*arc).ok())
+        .collect::<Option<_>>()?;
+
+    match family {
+        ADDRESS_FAMILY_IPV4 => <[u8; 4]>::try_from(bytes.as_slice()).ok().map(IpAddr::from),
+        ADDRESS_FAMILY_IPV6 => <[u8; 16]>::try_from(bytes.as_slice())
+            .ok()
+            .map(IpAddr::from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> SnmpValue {
+        SnmpValue::Bytes(s.as_bytes().to_vec())
+    }
+
+    fn switch_interfaces() -> Vec<SnmpInterface> {
+        let if_rows: SnmpRows = vec![
+            (vec![IF_DESCR, 1], text("GigabitEthernet1/0/1")),
+            (vec![IF_DESCR, 2], text("Vlan10")),
+            (vec![IF_SPEED, 1], SnmpValue::Unsigned(1_000_000_000)),
+            (
+                vec![IF_PHYS_ADDRESS, 1],
+                SnmpValue::Bytes(vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x01]),
+            ),
+            (
+                vec![IF_PHYS_ADDRESS, 2],
+                SnmpValue::Bytes(vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x02]),
+            ),
+            (vec![IF_OPER_STATUS, 1], SnmpValue::Integer(1)),
+            (vec![IF_OPER_STATUS, 2], SnmpValue::Integer(2)),
+        ];
+        let if_x_rows: SnmpRows = vec![
+            (vec![IF_NAME, 1], text("Gi1/0/1")),
+            (vec![IF_HIGH_SPEED, 2], SnmpValue::Unsigned(0)),
+        ];
+        let ip_rows: SnmpRows = vec![(vec![192, 168, 10, 2], SnmpValue::Integer(2))];
+
+        parse_interfaces(&if_rows, &if_x_rows, &ip_rows)
+    }
+
+    #[test]
+    fn test_parse_system() {
+        let rows: SnmpRows = vec![
+            (vec![SYS_DESCR, 0], text("Cisco IOS Software\0")),
+            (
+                vec![SYS_OBJECT_ID, 0],
+                SnmpValue::ObjectIdentifier("1.3.6.1.4.1.9.1.2134".to_string()),
+            ),
+            (vec![SYS_NAME, 0], text("core-sw1")),
+        ];
+
+        let system = parse_system(&rows);
+        assert_eq!(system.name.as_deref(), Some("core-sw1"));
+        assert_eq!(system.description.as_deref(), Some("Cisco IOS Software"));
+        assert_eq!(system.object_id.as_deref(), Some("1.3.6.1.4.1.9.1.2134"));
+    }
+
+    #[test]
+    fn test_parse_interfaces() {
+        let interfaces = switch_interfaces();
+        assert_eq!(interfaces.len(), 2);
+
+        let port = &interfaces[0];
+        assert_eq!(port.name, "Gi1/0/1", "ifName should win over ifDescr");
+        assert_eq!(port.speed_mbps, Some(1000));
+        assert!(port.is_up);
+        assert_eq!(
+            port.mac_address,
+            Some(MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x01]))
+        );
+
+        let vlan = &interfaces[1];
+        assert_eq!(vlan.name, "Vlan10", "ifDescr is used without ifName");
+        assert_eq!(vlan.speed_mbps, None);
+        assert!(!vlan.is_up);
+        assert_eq!(
+            vlan.ip_addresses,
+            vec!["192.168.10.2".parse::<IpAddr>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_parse_lldp_neighbors() {
+        let interfaces = switch_interfaces();
+
+        let rem_rows: SnmpRows = vec![
+            (
+                vec![LLDP_REM_CHASSIS_ID_SUBTYPE, 0, 5, 1],
+                SnmpValue::Integer(CHASSIS_ID_SUBTYPE_MAC_ADDRESS as i64),
+            ),
+            (
+                vec![LLDP_REM_CHASSIS_ID, 0, 5, 1],
+                SnmpValue::Bytes(vec![0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x00]),
+            ),
+            (
+                vec![LLDP_REM_PORT_ID_SUBTYPE, 0, 5, 1],
+                SnmpValue::Integer(PORT_ID_SUBTYPE_MAC_ADDRESS as i64),
+            ),
+            (
+                vec![LLDP_REM_PORT_ID, 0, 5, 1],
+                SnmpValue::Bytes(vec![0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01]),
+            ),
+            (vec![LLDP_REM_PORT_DESC, 0, 5, 1], text("eth0")),
+            (vec![LLDP_REM_SYS_NAME, 0, 5, 1], text("nas.lan")),
+        ];
+        // lldpLocPortNum 5 is not the ifIndex, it's matched by name
+        let loc_port_rows: SnmpRows = vec![(vec![LLDP_LOC_PORT_ID, 5], text("Gi1/0/1"))];
+        let man_addr_rows: SnmpRows = vec![(
+            vec![0, 5, 1, ADDRESS_FAMILY_IPV4, 4, 192, 168, 1, 20],
+            SnmpValue::Integer(2),
+        )];
+
+        let links = parse_lldp_neighbors(&rem_rows, &loc_port_rows, &man_addr_rows, &interfaces);
+        assert_eq!(links.len(), 1);
+
+        let link = &links[0];
+        assert_eq!(link.local_port, "Gi1/0/1");
+        assert_eq!(link.speed_mbps, Some(1000));
+        assert!(link.is_up);
+        assert_eq!(link.remote_chassis_id, "AA:BB:CC:DD:EE:00");
+        assert_eq!(
+            link.remote_mac_address,
+            Some(MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01])),
+            "Port MAC should be preferred over the chassis MAC"
+        );
+        assert_eq!(link.remote_port.as_deref(), Some("eth0"));
+        assert_eq!(link.remote_system_name.as_deref(), Some("nas.lan"));
+        assert_eq!(
+            link.remote_ip_address,
+            Some("192.168.1.20".parse::<IpAddr>().unwrap())
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! SSDP search and UPnP device description parsing.
+//!
+//! TVs, media renderers, NAS boxes and home routers answer an SSDP `M-SEARCH` with
+//! a LOCATION header pointing at an XML device description. That description names
+//! the manufacturer and model outright, which is far more reliable than guessing
+//! from open ports.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use futures::future::join_all;
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::UdpSocket;
+use tokio::time::{Instant, timeout_at};
+
+use crate::server::ports::r#impl::base::PortType;
+use crate::server::services::r#impl::endpoints::UpnpResponse;
+
+const SSDP_GROUP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250)), 1900);
+
+/// Seconds responders may wait before answering, they pick a random delay up to this
+const SEARCH_MX: u64 = 2;
+/// How long to collect answers after searching
+const SEARCH_WAIT: Duration = Duration::from_secs(SEARCH_MX + 1);
+/// Timeout for fetching a single device description
+const DESCRIPTION_TIMEOUT: Duration = Duration::from_secs(3);
+/// Device descriptions are a few KB, a responder sending more than this isn't sending one
+const MAX_DESCRIPTION_LENGTH: usize = 64 * 1024;
+
+/// Search for UPnP devices on the link attached to `source_ip` and fetch the
+/// description of each, grouped by the address that answered.
+///
+/// # Arguments
+/// * `source_ip` - IPv4 address of the interface to search from
+/// * `accept` - Whether to fetch the description from a responder, nothing is sent to rejected ones
+pub async fn search<F: Future<Output = bool>>(
+    source_ip: Ipv4Addr,
+    accept: impl Fn(IpAddr) -> F,
+) -> Result<HashMap<IpAddr, Vec<UpnpResponse>>> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_multicast_if_v4(&source_ip)?;
+    socket.set_multicast_loop_v4(false)?;
+    socket.set_multicast_ttl_v4(2)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::new(IpAddr::V4(source_ip), 0).into())?;
+    let socket = UdpSocket::from_std(socket.into())?;
+
+    let search = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: ssdp:all\r\n\r\n",
+        SEARCH_MX
+    );
+    // UDP is lossy and responders are often asleep, a second search is cheap insurance
+    for _ in 0..2 {
+        socket.send_to(search.as_bytes(), SSDP_GROUP).await?;
+    }
+
+    // Devices answer once per service they offer, all pointing at the same description
+    let mut locations: HashSet<(IpAddr, String)> = HashSet::new();
+    let deadline = Instant::now() + SEARCH_WAIT;
+    let mut buffer = vec![0u8; 2048];
+
+    while let Ok(received) = timeout_at(deadline, socket.recv_from(&mut buffer)).await {
+        let (length, responder) = received?;
+
+        if let Some(location) = parse_location(&buffer[..length]) {
+            locations.insert((responder.ip(), location));
+        }
+    }
+
+    let mut accepted = Vec::with_capacity(locations.len());
+    for (responder, location) in locations {
+        if accept(responder).await {
+            accepted.push((responder, location));
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(DESCRIPTION_TIMEOUT)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| anyhow!("Could not build client {}", e))?;
+
+    let fetches = accepted.into_iter().map(|(responder, location)| {
+        let client = client.clone();
+        async move {
+            match fetch_description(&client, responder, &location).await {
+                Ok(response) => Some((responder, response)),
+                Err(e) => {
+                    tracing::debug!(responder = %responder, location = %location, error = %e, "Failed to fetch UPnP device description");
+                    None
+                }
+            }
+        }
+    });
+
+    let mut responses: HashMap<IpAddr, Vec<UpnpResponse>> = HashMap::new();
+    for (responder, response) in join_all(fetches).await.into_iter().flatten() {
+        responses.entry(responder).or_default().push(response);
+    }
+
+    tracing::debug!(
+        source_ip = %source_ip,
+        devices = responses.len(),
+        "SSDP search complete"
+    );
+
+    Ok(responses)
+}
+
+/// Pull the LOCATION header out of an SSDP search response
+fn parse_location(packet: &[u8]) -> Option<String> {
+    let mut headers = [httparse::EMPTY_HEADER; 32];
+    let mut response = httparse::Response::new(&mut headers);
+    response.parse(packet).ok()?;
+
+    if response.code != Some(200) {
+        return None;
+    }
+
+    response
+        .headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("location"))
+        .and_then(|h| std::str::from_utf8(h.value).ok())
+        .map(|location| location.trim().to_string())
+}
+
+async fn fetch_description(
+    client: &reqwest::Client,
+    responder: IpAddr,
+    location: &str,
+) -> Result<UpnpResponse> {
+    let url = url::Url::parse(location)?;
+
+    // Don't let a responder point us at some other host
+    let host_ip = url
+        .host_str()
+        .and_then(|host| host.trim_matches(['[', ']']).parse::<IpAddr>().ok());
+    if host_ip != Some(responder) {
+        return Err(anyhow!("Description is not hosted by the responder"));
+    }
+
+    let mut http_response = client.get(url.clone()).send().await?;
+    let mut body = Vec::new();
+    while let Some(chunk) = http_response.chunk().await? {
+        if body.len() + chunk.len() > MAX_DESCRIPTION_LENGTH {
+            return Err(anyhow!(
+                "Description is longer than {} bytes",
+                MAX_DESCRIPTION_LENGTH
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let mut response = parse_description(&String::from_utf8_lossy(&body))?;
+
+    response.location = location.to_string();
+    response.port_type = url.port_or_known_default().map(PortType::new_tcp);
+
+    Ok(response)
+}
+
+/// Read the root device's identity out of a UPnP device description
+fn parse_description(xml: &str) -> Result<UpnpResponse> {
+    let document = roxmltree::Document::parse(xml)?;
+
+    let device = document
+        .root_element()
+        .children()
+        .find(|n| n.has_tag_name("device"))
+        .ok_or_else(|| anyhow!("Description has no root device"))?;
+
+    let field = |name: &str| {
+        device
+            .children()
+            .find(|n| n.has_tag_name(name))
+            .and_then(|n| n.text())
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+
+    Ok(UpnpResponse {
+        device_type: field("deviceType"),
+        friendly_name: field("friendlyName"),
+        manufacturer: field("manufacturer"),
+        model_name: field("modelName"),
+        model_number: field("modelNumber"),
+        serial_number: field("serialNumber"),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        let packet = b"HTTP/1.1 200 OK\r\nCache-Control: max-age=3600\r\nST: roku:ecp\r\nLocation: http://192.168.1.60:8060/\r\nUSN: uuid:roku:ecp:X00000000000\r\n\r\n";
+        assert_eq!(
+            parse_location(packet).as_deref(),
+            Some("http://192.168.1.60:8060/")
+        );
+
+        let notify = b"NOTIFY * HTTP/1.1\r\nLOCATION: http://192.168.1.60:8060/\r\n\r\n";
+        assert_eq!(parse_location(notify), None);
+    }
+
+    #[test]
+    fn test_parse_description() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <specVersion><major>1</major><minor>0</minor></specVersion>
+  <device>
+    <deviceType>urn:roku-com:device:player:1-0</deviceType>
+    <friendlyName>Living Room Roku</friendlyName>
+    <manufacturer>Roku</manufacturer>
+    <modelName>Roku Ultra</modelName>
+    <modelNumber>4800X</modelNumber>
+    <serialNumber>X00000000000</serialNumber>
+    <deviceList>
+      <device>
+        <manufacturer>Someone Else</manufacturer>
+      </device>
+    </deviceList>
+  </device>
+</root>"#;
+
+        let response = parse_description(xml).unwrap();
+
+        assert_eq!(response.manufacturer.as_deref(), Some("Roku"));
+        assert_eq!(response.model_name.as_deref(), Some("Roku Ultra"));
+        assert_eq!(response.model_number.as_deref(), Some("4800X"));
+        assert_eq!(response.serial_number.as_deref(), Some("X00000000000"));
+        assert_eq!(response.friendly_name.as_deref(), Some("Living Room Roku"));
+        assert_eq!(
+            response.device_type.as_deref(),
+            Some("urn:roku-com:device:player:1-0")
+        );
+    }
+}
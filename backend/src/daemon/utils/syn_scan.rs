@@ -0,0 +1,351 @@
+//! Half-open (SYN) TCP port scanning over a raw socket.
+//!
+//! A SYN is sent to each port and the port is open if a SYN/ACK comes back, closed on
+//! RST. No connection is ever completed, so unlike connect scanning it doesn't hold a
+//! file descriptor per port and full-range sweeps are limited only by the send rate.
+//! The kernel answers the SYN/ACK with its own RST since no socket owns the port.
+//!
+//! One scanner is shared by every host in a discovery: a single receiver thread reads
+//! replies off the raw socket and hands them to whichever host scan is waiting on that
+//! address, and sends from all scans draw from the same packets-per-second budget.
+//!
+//! IPv4 and Linux only, and requires raw socket access (CAP_NET_RAW). macOS and the BSDs
+//! never hand inbound TCP segments to raw sockets, so every port would look filtered
+//! there. Callers fall back to
+//! [`scan_tcp_ports`](super::scanner::scan_tcp_ports) when [`SynScanner::new`] fails,
+//! and when [`SynScanner::scan`] does because the receiver thread has died.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Error, anyhow};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::tcp::{MutableTcpPacket, TcpFlags, TcpOption, ipv4_checksum};
+use pnet::transport::{
+    TransportChannelType, TransportProtocol, TransportReceiver, TransportSender, tcp_packet_iter,
+    transport_channel,
+};
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// TCP header with a single MSS option, which some stacks expect on a SYN
+const SYN_PACKET_LEN: usize = 24;
+/// Attempts per port, matching the connect scan's retry on timeout
+const MAX_ATTEMPTS: usize = 2;
+/// How far sending may fall behind before the unused budget is dropped, so a stalled
+/// task can catch up with a short burst without exceeding the rate for long
+const MAX_BURST: Duration = Duration::from_millis(10);
+const RECEIVE_POLL: Duration = Duration::from_millis(100);
+
+type Responses = Arc<Mutex<HashMap<Ipv4Addr, UnboundedSender<(u16, bool)>>>>;
+
+pub struct SynScanner {
+    sender: Mutex<TransportSender>,
+    /// Source port of every probe, replies are recognised by it
+    source_port: u16,
+    /// Mixed into sequence numbers so replies to other traffic are ignored
+    cookie: u32,
+    send_interval: Duration,
+    next_send: Mutex<Instant>,
+    /// Host scans waiting for replies, keyed by target
+    responses: Responses,
+    stop: Arc<AtomicBool>,
+    /// Set when the receiver thread stops on a socket error. Replies can no longer be
+    /// read, so every port would look filtered.
+    failed: Arc<AtomicBool>,
+}
+
+impl SynScanner {
+    /// Open the raw socket and start listening for replies. Fails without raw socket access.
+    pub fn new(rate_pps: u32) -> Result<Self, Error> {
+        if !cfg!(target_os = "linux") {
+            return Err(anyhow!("SYN scanning is only supported on Linux"));
+        }
+
+        let (sender, receiver) = transport_channel(
+            65536,
+            TransportChannelType::Layer4(TransportProtocol::Ipv4(IpNextHeaderProtocols::Tcp)),
+        )
+        .map_err(|e| anyhow!("Failed to open raw TCP socket: {}", e))?;
+
+        let scanner = Self {
+            sender: Mutex::new(sender),
+            source_port: fastrand::u16(40000..60000),
+            cookie: fastrand::u32(..),
+            send_interval: Duration::from_micros(1_000_000 / rate_pps.max(1) as u64),
+            next_send: Mutex::new(Instant::now()),
+            responses: Arc::new(Mutex::new(HashMap::new())),
+            stop: Arc::new(AtomicBool::new(false)),
+            failed: Arc::new(AtomicBool::new(false)),
+        };
+
+        let responses = scanner.responses.clone();
+        let stop = scanner.stop.clone();
+        let failed = scanner.failed.clone();
+        let (source_port, cookie) = (scanner.source_port, scanner.cookie);
+        std::thread::spawn(move || {
+            receive_replies(receiver, source_port, cookie, responses, stop, failed);
+        });
+
+        tracing::info!(
+            rate_pps,
+            source_port = scanner.source_port,
+            "SYN scanning available, using raw sockets for TCP port scans"
+        );
+
+        Ok(scanner)
+    }
+
+    /// SYN scan `ports` on `target`, returning the ones that answered with a SYN/ACK.
+    /// Fails once replies can't be received anymore, rather than reporting every port
+    /// as filtered.
+    pub async fn scan(
+        &self,
+        target: Ipv4Addr,
+        ports: &[u16],
+        scan_timeout: Duration,
+        cancel: CancellationToken,
+    ) -> Result<Vec<u16>, Error> {
+        if self.failed.load(Ordering::Relaxed) {
+            return Err(anyhow!("SYN scan receiver has stopped"));
+        }
+
+        let (tx, mut rx) = unbounded_channel();
+        self.responses.lock().unwrap().insert(target, tx);
+
+        let result = async {
+            let source = source_ip_for(target)?;
+            let requested: HashSet<u16> = ports.iter().copied().collect();
+            // port -> whether it answered with a SYN/ACK
+            let mut replies: HashMap<u16, bool> = HashMap::new();
+            let mut unanswered: Vec<u16> = ports.to_vec();
+
+            for _ in 0..MAX_ATTEMPTS {
+                for &port in &unanswered {
+                    if cancel.is_cancelled() {
+                        return Err(anyhow!("Operation cancelled"));
+                    }
+
+                    self.pace().await;
+                    self.send_syn(source, target, port)?;
+
+                    while let Ok((port, is_open)) = rx.try_recv() {
+                        if requested.contains(&port) {
+                            replies.entry(port).or_insert(is_open);
+                        }
+                    }
+                }
+
+                // Give the last probes of the round time to be answered
+                let deadline = Instant::now() + scan_timeout;
+                while let Ok(Some((port, is_open))) =
+                    tokio::time::timeout_at(deadline, rx.recv()).await
+                {
+                    if requested.contains(&port) {
+                        replies.entry(port).or_insert(is_open);
+                    }
+                }
+
+                // Filtered ports never answer, only they get a second probe
+                unanswered.retain(|p| !replies.contains_key(p));
+                if unanswered.is_empty() {
+                    break;
+                }
+            }
+
+            if self.failed.load(Ordering::Relaxed) {
+                return Err(anyhow!("SYN scan receiver stopped during the scan"));
+            }
+
+            let open: BTreeSet<u16> = replies
+                .into_iter()
+                .filter_map(|(port, is_open)| is_open.then_some(port))
+                .collect();
+
+            Ok(open.into_iter().collect())
+        }
+        .await;
+
+        self.responses.lock().unwrap().remove(&target);
+        result
+    }
+
+    /// Wait for the next send slot in the shared packets-per-second budget
+    async fn pace(&self) {
+        let slot = {
+            let mut next_send = self.next_send.lock().unwrap();
+            let earliest = Instant::now()
+                .checked_sub(MAX_BURST)
+                .unwrap_or_else(Instant::now);
+            let slot = (*next_send).max(earliest);
+            *next_send = slot + self.send_interval;
+            slot
+        };
+
+        tokio::time::sleep_until(slot).await;
+    }
+
+    fn send_syn(&self, source: Ipv4Addr, target: Ipv4Addr, port: u16) -> Result<(), Error> {
+        let mut buffer = [0u8; SYN_PACKET_LEN];
+        let packet = syn_packet(
+            &mut buffer,
+            source,
+            target,
+            self.source_port,
+            port,
+            sequence_number(self.cookie, target, port),
+        );
+
+        self.sender
+            .lock()
+            .unwrap()
+            .send_to(packet, IpAddr::V4(target))
+            .map_err(|e| anyhow!("Failed to send SYN to {}:{}: {}", target, port, e))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for SynScanner {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn receive_replies(
+    mut receiver: TransportReceiver,
+    source_port: u16,
+    cookie: u32,
+    responses: Responses,
+    stop: Arc<AtomicBool>,
+    failed: Arc<AtomicBool>,
+) {
+    let mut packets = tcp_packet_iter(&mut receiver);
+
+    while !stop.load(Ordering::Relaxed) {
+        let (packet, from) = match packets.next_with_timeout(RECEIVE_POLL) {
+            Ok(Some(reply)) => reply,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "SYN scan receiver stopped, falling back to connect scans"
+                );
+                failed.store(true, Ordering::Relaxed);
+                // Wake scans waiting on replies so they fail now rather than time out
+                responses.lock().unwrap().clear();
+                return;
+            }
+        };
+
+        let IpAddr::V4(from) = from else {
+            continue;
+        };
+
+        if packet.get_destination() != source_port {
+            continue;
+        }
+
+        let port = packet.get_source();
+        let flags = packet.get_flags();
+        let is_open = flags & TcpFlags::SYN != 0 && flags & TcpFlags::ACK != 0;
+        let is_closed = flags & TcpFlags::RST != 0;
+
+        // Replies acknowledge our sequence number + 1
+        let expected_ack = sequence_number(cookie, from, port).wrapping_add(1);
+        if !(is_open || is_closed) || packet.get_acknowledgement() != expected_ack {
+            continue;
+        }
+
+        if let Some(tx) = responses.lock().unwrap().get(&from) {
+            let _ = tx.send((port, is_open));
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn receive_replies(
+    _receiver: TransportReceiver,
+    _source_port: u16,
+    _cookie: u32,
+    _responses: Responses,
+    _stop: Arc<AtomicBool>,
+    _failed: Arc<AtomicBool>,
+) {
+}
+
+fn sequence_number(cookie: u32, target: Ipv4Addr, port: u16) -> u32 {
+    cookie ^ u32::from(target).rotate_left(16) ^ port as u32
+}
+
+/// Address the OS would send from to reach `target`. Connecting a UDP socket picks the
+/// route without sending anything.
+fn source_ip_for(target: Ipv4Addr) -> Result<Ipv4Addr, Error> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.connect((target, 9))?;
+
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(ip) => Ok(ip),
+        IpAddr::V6(ip) => Err(anyhow!("No IPv4 route to {} (got {})", target, ip)),
+    }
+}
+
+fn syn_packet(
+    buffer: &mut [u8; SYN_PACKET_LEN],
+    source: Ipv4Addr,
+    target: Ipv4Addr,
+    source_port: u16,
+    port: u16,
+    sequence: u32,
+) -> MutableTcpPacket<'_> {
+    let mut packet = MutableTcpPacket::new(buffer).expect("buffer fits a TCP header");
+    packet.set_source(source_port);
+    packet.set_destination(port);
+    packet.set_sequence(sequence);
+    packet.set_acknowledgement(0);
+    packet.set_data_offset((SYN_PACKET_LEN / 4) as u8);
+    packet.set_flags(TcpFlags::SYN);
+    packet.set_window(64240);
+    packet.set_urgent_ptr(0);
+    packet.set_options(&[TcpOption::mss(1460)]);
+
+    let checksum = ipv4_checksum(&packet.to_immutable(), &source, &target);
+    packet.set_checksum(checksum);
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pnet::packet::Packet;
+    use pnet::packet::tcp::TcpPacket;
+
+    #[test]
+    fn test_syn_packet() {
+        let source = Ipv4Addr::new(192, 168, 1, 10);
+        let target = Ipv4Addr::new(192, 168, 1, 20);
+        let sequence = sequence_number(0xdeadbeef, target, 8443);
+
+        let mut buffer = [0u8; SYN_PACKET_LEN];
+        let packet = syn_packet(&mut buffer, source, target, 45000, 8443, sequence);
+        let packet = TcpPacket::new(packet.packet()).unwrap();
+
+        assert_eq!(packet.get_destination(), 8443);
+        assert_eq!(packet.get_source(), 45000);
+        assert_eq!(packet.get_flags(), TcpFlags::SYN);
+        assert_eq!(packet.get_sequence(), sequence);
+        assert_eq!(
+            packet.get_checksum(),
+            ipv4_checksum(&packet, &source, &target)
+        );
+
+        // Replies from a different host or port don't match the probe
+        assert_ne!(sequence, sequence_number(0xdeadbeef, source, 8443));
+        assert_ne!(sequence, sequence_number(0xdeadbeef, target, 8444));
+    }
+}
@@ -0,0 +1,189 @@
+//! TLS certificate collection.
+//!
+//! Appliances and homelab services mostly serve self-signed or privately
+//! issued certificates, so the handshake accepts anything the server presents:
+//! we only want to read the leaf certificate, never to trust the connection.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{
+    CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature,
+};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{self, ClientConfig, DigitallySignedStruct, SignatureScheme};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+use x509_parser::time::ASN1Time;
+
+use crate::server::certificates::r#impl::base::CertificateDetails;
+
+/// Shortest wait for the TLS handshake once connected, slow hosts get their request timeout
+pub const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// Accepts any server certificate while still checking handshake signatures,
+/// so the peer has to actually hold the key for the certificate it presents
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Build a connector that completes a handshake with any certificate
+pub fn certificate_connector() -> Result<TlsConnector> {
+    let provider = Arc::new(ring::default_provider());
+
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+        .with_no_client_auth();
+
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Complete a TLS handshake and return the DER-encoded leaf certificate
+pub async fn grab_certificate(
+    connector: &TlsConnector,
+    socket: SocketAddr,
+    connect_timeout: Duration,
+    handshake_timeout: Duration,
+) -> Option<Vec<u8>> {
+    let stream = timeout(connect_timeout, TcpStream::connect(socket))
+        .await
+        .ok()?
+        .ok()?;
+
+    let server_name = ServerName::IpAddress(socket.ip().into());
+    let tls_stream = timeout(
+        handshake_timeout.max(TLS_HANDSHAKE_TIMEOUT),
+        connector.connect(server_name, stream),
+    )
+    .await
+    .ok()?
+    .ok()?;
+
+    let (_, connection) = tls_stream.get_ref();
+    connection
+        .peer_certificates()?
+        .first()
+        .map(|cert| cert.to_vec())
+}
+
+/// Read the fields we keep from a DER-encoded X.509 certificate
+pub fn parse_certificate(der: &[u8]) -> Result<CertificateDetails> {
+    let (_, cert) =
+        X509Certificate::from_der(der).map_err(|e| anyhow!("Invalid certificate: {}", e))?;
+
+    let sans = match cert.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                GeneralName::IPAddress(bytes) => ip_from_bytes(bytes).map(|ip| ip.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let validity = cert.validity();
+
+    Ok(CertificateDetails {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        sans,
+        fingerprint: hex::encode(Sha256::digest(der)),
+        not_before: asn1_to_datetime(validity.not_before)?,
+        not_after: asn1_to_datetime(validity.not_after)?,
+    })
+}
+
+fn ip_from_bytes(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => <[u8; 4]>::try_from(bytes).ok().map(IpAddr::from),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
+
+fn asn1_to_datetime(time: ASN1Time) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp(time.timestamp(), 0)
+        .ok_or_else(|| anyhow!("Certificate time out of range: {}", time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_self_signed_certificate() {
+        let pem = include_str!("../../tests/tls_certificate.pem");
+        let (_, pem) = x509_parser::pem::parse_x509_pem(pem.as_bytes()).unwrap();
+
+        let details = parse_certificate(&pem.contents).unwrap();
+
+        assert!(details.subject.contains("CN=pve.lan"));
+        assert!(details.issuer.contains("O=PVE Cluster Manager CA"));
+        assert!(details.is_self_signed());
+        assert_eq!(details.sans, vec!["pve.lan", "192.168.1.10"]);
+        assert_eq!(
+            details.fingerprint,
+            "c3c1fd75baf985ed4450ca9ff63cf86efb6f93c0f2d3d27ec6cb99939579369a"
+        );
+        assert!(details.not_before < details.not_after);
+    }
+}
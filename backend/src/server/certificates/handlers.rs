@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::server::certificates::{r#impl::base::Certificate, service::CertificateService};
+use crate::server::config::AppState;
+use crate::server::shared::handlers::query::HostChildQuery;
+use crate::server::shared::handlers::traits::CrudHandlers;
+use crate::server::shared::types::api::ApiResponse;
+
+impl CrudHandlers for Certificate {
+    type Service = CertificateService;
+    type FilterQuery = HostChildQuery;
+
+    fn get_service(state: &AppState) -> &Self::Service {
+        &state.services.certificate_service
+    }
+}
+
+mod generated {
+    use super::*;
+    crate::crud_get_all_handler!(Certificate, "certificates", "certificate");
+    crate::crud_get_by_id_handler!(Certificate, "certificates", "certificate");
+    crate::crud_delete_handler!(Certificate, "certificates", "certificate");
+    crate::crud_bulk_delete_handler!(Certificate, "certificates");
+}
+
+/// Certificates are reported by discovery, so there are no create/update routes
+pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(generated::get_all))
+        .routes(routes!(generated::get_by_id, generated::delete))
+        .routes(routes!(generated::bulk_delete))
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::hash::Hash;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::server::shared::entities::ChangeTriggersTopologyStaleness;
+
+/// How long before `not_after` a certificate is reported as expiring
+pub const CERTIFICATE_EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// What the daemon read from a TLS handshake
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize, ToSchema)]
+pub struct CertificateDetails {
+    /// Subject distinguished name, ie "CN=pve.local, O=PVE Cluster Node"
+    pub subject: String,
+    /// Issuer distinguished name
+    pub issuer: String,
+    /// DNS names and IP addresses from the subjectAltName extension
+    pub sans: Vec<String>,
+    /// SHA-256 of the DER-encoded certificate, lowercase hex
+    pub fingerprint: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+}
+
+impl CertificateDetails {
+    pub fn is_self_signed(&self) -> bool {
+        self.subject == self.issuer
+    }
+
+    /// Whether the certificate expires within the warning window of `at`
+    pub fn expires_soon(&self, at: DateTime<Utc>) -> bool {
+        self.not_after - at <= Duration::days(CERTIFICATE_EXPIRY_WARNING_DAYS)
+    }
+}
+
+/// The base data for a Certificate entity (everything except id, created_at, updated_at)
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Default, Validate, Serialize, Deserialize, ToSchema,
+)]
+pub struct CertificateBase {
+    pub port_id: Uuid,
+    pub host_id: Uuid,
+    pub network_id: Uuid,
+    #[serde(flatten)]
+    pub details: CertificateDetails,
+}
+
+impl CertificateBase {
+    /// Create a CertificateBase without host/network (will be set by server).
+    /// port_id refers to the hostless port the daemon sent alongside it.
+    pub fn new_hostless(port_id: Uuid, details: CertificateDetails) -> Self {
+        Self {
+            port_id,
+            host_id: Uuid::nil(),
+            network_id: Uuid::nil(),
+            details,
+        }
+    }
+}
+
+/// TLS certificate presented on a port. A port has at most one certificate.
+#[derive(Debug, Clone, Default, Validate, Serialize, Deserialize, ToSchema, Eq)]
+pub struct Certificate {
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub id: Uuid,
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub base: CertificateBase,
+}
+
+impl Hash for Certificate {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.base.hash(state);
+    }
+}
+
+impl PartialEq for Certificate {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+impl ChangeTriggersTopologyStaleness<Certificate> for Certificate {
+    fn triggers_staleness(&self, _other: Option<Certificate>) -> bool {
+        false // Certificates aren't drawn on the topology
+    }
+}
+
+impl Display for Certificate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (ID: {})", self.base.details.subject, self.id)
+    }
+}
+
+impl Certificate {
+    pub fn new(base: CertificateBase) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            base,
+        }
+    }
+
+    /// Create a Certificate for a port created during discovery before host assignment.
+    pub fn new_hostless(port_id: Uuid, details: CertificateDetails) -> Self {
+        Self::new(CertificateBase::new_hostless(port_id, details))
+    }
+
+    pub fn port_id(&self) -> Uuid {
+        self.base.port_id
+    }
+
+    /// Set the port_id, host_id and network_id once the server has resolved the port
+    pub fn with_port(mut self, port_id: Uuid, host_id: Uuid, network_id: Uuid) -> Self {
+        self.base.port_id = port_id;
+        self.base.host_id = host_id;
+        self.base.network_id = network_id;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::CertificateDetails;
+
+    #[test]
+    fn test_certificate_expiry_window() {
+        let now = Utc::now();
+        let mut details = CertificateDetails {
+            subject: "CN=pve".to_string(),
+            issuer: "CN=pve".to_string(),
+            not_before: now - Duration::days(300),
+            not_after: now + Duration::days(90),
+            ..Default::default()
+        };
+
+        assert!(details.is_self_signed());
+        assert!(!details.expires_soon(now));
+
+        details.not_after = now + Duration::days(10);
+        assert!(details.expires_soon(now));
+
+        details.not_after = now - Duration::days(1);
+        assert!(details.expires_soon(now), "Expired certificates count");
+    }
+}
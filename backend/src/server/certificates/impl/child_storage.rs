@@ -0,0 +1,15 @@
+use uuid::Uuid;
+
+use crate::server::{
+    certificates::r#impl::base::Certificate, shared::storage::child::ChildStorableEntity,
+};
+
+impl ChildStorableEntity for Certificate {
+    fn parent_column() -> &'static str {
+        "port_id"
+    }
+
+    fn parent_id(&self) -> Uuid {
+        self.base.port_id
+    }
+}
@@ -0,0 +1,3 @@
+pub mod base;
+mod child_storage; // ChildStorableEntity impl for Certificate - parent relationship only
+mod storage; // StorableEntity impl for Certificate - full CRUD infrastructure
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::server::{
+    certificates::r#impl::base::{Certificate, CertificateBase, CertificateDetails},
+    shared::{
+        entities::EntityDiscriminants,
+        storage::traits::{Entity, SqlValue, Storable},
+    },
+};
+
+impl Storable for Certificate {
+    type BaseData = CertificateBase;
+
+    fn table_name() -> &'static str {
+        "certificates"
+    }
+
+    fn new(base: Self::BaseData) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            base,
+        }
+    }
+
+    fn get_base(&self) -> Self::BaseData {
+        self.base.clone()
+    }
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+
+    fn set_created_at(&mut self, time: DateTime<Utc>) {
+        self.created_at = time;
+    }
+
+    fn to_params(&self) -> Result<(Vec<&'static str>, Vec<SqlValue>), anyhow::Error> {
+        let Self {
+            id,
+            created_at,
+            updated_at,
+            base:
+                CertificateBase {
+                    port_id,
+                    host_id,
+                    network_id,
+                    details:
+                        CertificateDetails {
+                            subject,
+                            issuer,
+                            sans,
+                            fingerprint,
+                            not_before,
+                            not_after,
+                        },
+                },
+        } = self.clone();
+
+        Ok((
+            vec![
+                "id",
+                "port_id",
+                "host_id",
+                "network_id",
+                "subject",
+                "issuer",
+                "sans",
+                "fingerprint",
+                "not_before",
+                "not_after",
+                "created_at",
+                "updated_at",
+            ],
+            vec![
+                SqlValue::Uuid(id),
+                SqlValue::Uuid(port_id),
+                SqlValue::Uuid(host_id),
+                SqlValue::Uuid(network_id),
+                SqlValue::String(subject),
+                SqlValue::String(issuer),
+                SqlValue::StringArray(sans),
+                SqlValue::String(fingerprint),
+                SqlValue::Timestamp(not_before),
+                SqlValue::Timestamp(not_after),
+                SqlValue::Timestamp(created_at),
+                SqlValue::Timestamp(updated_at),
+            ],
+        ))
+    }
+
+    fn from_row(row: &PgRow) -> Result<Self, anyhow::Error> {
+        Ok(Certificate {
+            id: row.get("id"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            base: CertificateBase {
+                port_id: row.get("port_id"),
+                host_id: row.get("host_id"),
+                network_id: row.get("network_id"),
+                details: CertificateDetails {
+                    subject: row.get("subject"),
+                    issuer: row.get("issuer"),
+                    sans: row.get("sans"),
+                    fingerprint: row.get("fingerprint"),
+                    not_before: row.get("not_before"),
+                    not_after: row.get("not_after"),
+                },
+            },
+        })
+    }
+}
+
+impl Entity for Certificate {
+    fn entity_type() -> EntityDiscriminants {
+        EntityDiscriminants::Certificate
+    }
+
+    fn entity_name_singular() -> &'static str {
+        "certificate"
+    }
+
+    fn entity_name_plural() -> &'static str {
+        "certificates"
+    }
+
+    fn network_id(&self) -> Option<Uuid> {
+        Some(self.base.network_id)
+    }
+
+    fn organization_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, time: DateTime<Utc>) {
+        self.updated_at = time;
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::{collections::HashMap, sync::Arc};
+use uuid::Uuid;
+
+use crate::server::{
+    auth::middleware::auth::AuthenticatedEntity,
+    certificates::r#impl::base::Certificate,
+    shared::{
+        events::{
+            bus::EventBus,
+            types::{EntityEvent, EntityOperation},
+        },
+        services::traits::{ChildCrudService, CrudService, EventBusService},
+        storage::generic::GenericPostgresStorage,
+    },
+    tags::entity_tags::EntityTagService,
+};
+
+pub struct CertificateService {
+    storage: Arc<GenericPostgresStorage<Certificate>>,
+    event_bus: Arc<EventBus>,
+}
+
+impl EventBusService<Certificate> for CertificateService {
+    fn event_bus(&self) -> &Arc<EventBus> {
+        &self.event_bus
+    }
+
+    fn get_network_id(&self, entity: &Certificate) -> Option<Uuid> {
+        Some(entity.base.network_id)
+    }
+
+    fn get_organization_id(&self, _entity: &Certificate) -> Option<Uuid> {
+        None
+    }
+
+    /// Re-observing the same certificate only bumps updated_at
+    fn suppress_logs(&self, current: Option<&Certificate>, updated: Option<&Certificate>) -> bool {
+        match (current, updated) {
+            (Some(current), Some(updated)) => current.base == updated.base,
+            _ => false,
+        }
+    }
+}
+
+impl CrudService<Certificate> for CertificateService {
+    fn storage(&self) -> &Arc<GenericPostgresStorage<Certificate>> {
+        &self.storage
+    }
+
+    fn entity_tag_service(&self) -> Option<&Arc<EntityTagService>> {
+        None
+    }
+}
+
+impl ChildCrudService<Certificate> for CertificateService {}
+
+impl CertificateService {
+    pub fn new(
+        storage: Arc<GenericPostgresStorage<Certificate>>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self { storage, event_bus }
+    }
+
+    /// Get the certificates for a specific port (alias for get_for_parent)
+    pub async fn get_for_port(&self, port_id: &Uuid) -> Result<Vec<Certificate>> {
+        self.get_for_parent(port_id).await
+    }
+
+    /// Get certificates for multiple ports (alias for get_for_parents)
+    pub async fn get_for_ports(
+        &self,
+        port_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Certificate>>> {
+        self.get_for_parents(port_ids).await
+    }
+
+    /// Store the certificate a port presented during discovery, replacing the previous one.
+    ///
+    /// Publishes `CertificateChanged` when the fingerprint differs from the stored certificate,
+    /// and `CertificateExpiring` the first time a certificate is observed inside the expiry window.
+    pub async fn record_observed(
+        &self,
+        certificate: Certificate,
+        authentication: AuthenticatedEntity,
+    ) -> Result<Certificate> {
+        let existing = self
+            .get_for_port(&certificate.base.port_id)
+            .await?
+            .into_iter()
+            .next();
+
+        let (saved, previous_fingerprint, already_expiring) = match existing {
+            Some(mut existing) => {
+                let previous = existing.base.details.fingerprint.clone();
+                let changed = previous != certificate.base.details.fingerprint;
+                let already_expiring =
+                    !changed && existing.base.details.expires_soon(existing.updated_at);
+
+                existing.base = certificate.base;
+                let saved = self.update(&mut existing, authentication.clone()).await?;
+
+                (saved, changed.then_some(previous), already_expiring)
+            }
+            None => (
+                self.create(certificate, authentication.clone()).await?,
+                None,
+                false,
+            ),
+        };
+
+        if let Some(previous_fingerprint) = previous_fingerprint {
+            tracing::info!(
+                port_id = %saved.base.port_id,
+                subject = %saved.base.details.subject,
+                "TLS certificate changed"
+            );
+
+            self.publish_certificate_event(
+                &saved,
+                EntityOperation::CertificateChanged,
+                serde_json::json!({
+                    "previous_fingerprint": previous_fingerprint,
+                    "fingerprint": saved.base.details.fingerprint,
+                }),
+                authentication.clone(),
+            )
+            .await?;
+        }
+
+        if !already_expiring && saved.base.details.expires_soon(Utc::now()) {
+            self.publish_certificate_event(
+                &saved,
+                EntityOperation::CertificateExpiring,
+                serde_json::json!({
+                    "not_after": saved.base.details.not_after,
+                }),
+                authentication,
+            )
+            .await?;
+        }
+
+        Ok(saved)
+    }
+
+    async fn publish_certificate_event(
+        &self,
+        certificate: &Certificate,
+        operation: EntityOperation,
+        mut metadata: serde_json::Value,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        metadata["port_id"] = serde_json::json!(certificate.base.port_id);
+        metadata["host_id"] = serde_json::json!(certificate.base.host_id);
+        metadata["subject"] = serde_json::json!(certificate.base.details.subject);
+
+        self.event_bus()
+            .publish_entity(EntityEvent {
+                id: Uuid::new_v4(),
+                entity_id: certificate.id,
+                network_id: self.get_network_id(certificate),
+                organization_id: self.get_organization_id(certificate),
+                entity_type: certificate.clone().into(),
+                operation,
+                timestamp: Utc::now(),
+                metadata,
+                authentication,
+            })
+            .await
+    }
+}
@@ -396,6 +396,7 @@ async fn register_daemon(
         virtualization: None,
         hidden: false,
         tags: Vec::new(),
+        physical_links: Vec::new(),
     });
 
     let host_response = state
@@ -533,6 +534,7 @@ async fn register_daemon(
     let network_discovery_type = DiscoveryType::Network {
         subnet_ids: None,
         host_naming_fallback: HostNamingFallback::BestService,
+        scan_profile_id: None,
     };
 
     let network_discovery = discovery_service
@@ -778,7 +780,7 @@ async fn receive_work_request(
         .pull_cancellation_for_daemon(&daemon_id)
         .await;
 
-    let next_session = sessions.first().cloned();
+    let mut next_session = sessions.first().cloned();
 
     service
         .receive_work_request(
@@ -790,5 +792,28 @@ async fn receive_work_request(
         )
         .await?;
 
+    // Credentials only go to the daemon, the stored session is what gets streamed to the UI
+    if let Some(session) = next_session.take() {
+        let discovery_service = &state.services.discovery_service;
+
+        match discovery_service.daemon_config_for_session(&session).await {
+            Ok(config) => {
+                next_session = Some(DiscoveryUpdatePayload {
+                    daemon_config: Some(Box::new(config)),
+                    ..session
+                });
+            }
+            // The daemon picks up whatever is queued behind it on its next poll
+            Err(e) => {
+                discovery_service
+                    .fail_session(session, e.to_string())
+                    .await
+                    .map_err(|e| {
+                        ApiError::internal_error(&format!("Failed to end discovery session: {}", e))
+                    })?;
+            }
+        }
+    }
+
     Ok(Json(ApiResponse::success((next_session, cancel))))
 }
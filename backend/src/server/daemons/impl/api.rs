@@ -9,7 +9,14 @@ use crate::{
             base::{Daemon, DaemonBase, DaemonMode},
             version::{DaemonVersionStatus, DeprecationWarning},
         },
-        discovery::r#impl::types::DiscoveryType,
+        discovery::r#impl::{dns_zones::DnsRecord, types::DiscoveryType},
+        networks::{
+            dns::DnsResolver,
+            proxmox::ProxmoxCredential,
+            scan_exclusions::{ScanExclusion, SkippedAddresses},
+            scan_profiles::ScanProfile,
+            snmp::SnmpCredentialSet,
+        },
     },
 };
 use chrono::{DateTime, Utc};
@@ -71,6 +78,8 @@ pub struct DaemonRegistrationResponse {
 pub struct DaemonDiscoveryRequest {
     pub session_id: Uuid,
     pub discovery_type: DiscoveryType,
+    #[serde(flatten)]
+    pub config: DaemonDiscoveryConfig,
 }
 
 impl From<DiscoveryUpdatePayload> for DaemonDiscoveryRequest {
@@ -78,10 +87,36 @@ impl From<DiscoveryUpdatePayload> for DaemonDiscoveryRequest {
         Self {
             session_id: payload.session_id,
             discovery_type: payload.discovery_type,
+            config: payload
+                .daemon_config
+                .map(|config| *config)
+                .unwrap_or_default(),
         }
     }
 }
 
+/// Network settings a daemon needs to run a discovery, including credentials. They're
+/// sent with the discovery request, or with the session handed to a polling daemon, and
+/// never stored on or streamed with sessions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct DaemonDiscoveryConfig {
+    /// SNMP credentials for network discovery
+    #[serde(default)]
+    pub snmp_credentials: SnmpCredentialSet,
+    /// Proxmox API tokens for Proxmox discovery
+    #[serde(default)]
+    pub proxmox_credentials: Vec<ProxmoxCredential>,
+    /// DNS servers for reverse lookups during network discovery
+    #[serde(default)]
+    pub dns_resolvers: Vec<DnsResolver>,
+    /// Ports and timings for network discovery, a full scan when unset
+    #[serde(default)]
+    pub scan_profile: Option<ScanProfile>,
+    /// Addresses, ranges and MACs network discovery must not probe
+    #[serde(default)]
+    pub scan_exclusions: Vec<ScanExclusion>,
+}
+
 /// Daemon discovery response (for immediate acknowledgment)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonDiscoveryResponse {
@@ -100,6 +135,40 @@ pub struct DiscoveryUpdatePayload {
     pub error: Option<String>,
     pub started_at: Option<DateTime<Utc>>,
     pub finished_at: Option<DateTime<Utc>>,
+    /// Only set on the copy handed to a polling daemon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(ignore)]
+    pub daemon_config: Option<Box<DaemonDiscoveryConfig>>,
+    /// Round-trip times measured by network discovery, per scanned subnet
+    #[serde(default)]
+    #[schema(required)]
+    pub rtt_stats: Vec<SubnetRttStats>,
+    /// Addresses network discovery left alone because of the network's scan exclusions
+    #[serde(default)]
+    #[schema(required)]
+    pub skipped: Vec<SkippedAddresses>,
+    /// MAC and vendor exclusions that couldn't be checked against some deep scanned hosts
+    /// because their MAC wasn't known
+    #[serde(default)]
+    #[schema(required)]
+    pub unenforced_exclusions: Vec<Uuid>,
+    /// Records a DNS zone transfer returned that point at an address with no discovered host
+    #[serde(default)]
+    #[schema(required)]
+    pub dangling_records: Vec<DnsRecord>,
+}
+
+/// Round-trip times of the hosts that answered in one subnet. Each host contributes its
+/// smoothed RTT to the average and its fastest and slowest samples to the range.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct SubnetRttStats {
+    pub subnet_id: Uuid,
+    /// Hosts with at least one measurement
+    pub hosts: usize,
+    pub samples: usize,
+    pub min_us: u64,
+    pub avg_us: u64,
+    pub max_us: u64,
 }
 
 impl DiscoveryUpdatePayload {
@@ -119,6 +188,11 @@ impl DiscoveryUpdatePayload {
             error: None,
             started_at: None,
             finished_at: None,
+            daemon_config: None,
+            rtt_stats: Vec::new(),
+            skipped: Vec::new(),
+            unenforced_exclusions: Vec::new(),
+            dangling_records: Vec::new(),
         }
     }
 
@@ -137,6 +211,11 @@ impl DiscoveryUpdatePayload {
             error: update.error,
             started_at: info.started_at,
             finished_at: update.finished_at,
+            daemon_config: None,
+            rtt_stats: Vec::new(),
+            skipped: Vec::new(),
+            unenforced_exclusions: Vec::new(),
+            dangling_records: Vec::new(),
         }
     }
 }
@@ -1,17 +1,27 @@
 use crate::server::{
     auth::middleware::permissions::{Authorized, IsDaemon, Member, Viewer},
     config::AppState,
-    daemons::r#impl::api::DiscoveryUpdatePayload,
+    daemons::r#impl::{api::DiscoveryUpdatePayload, base::Daemon},
     discovery::r#impl::{
         base::Discovery,
+        dhcp_leases::{
+            DhcpLeaseImportRequest, DhcpLeaseImportResponse, DhcpLeaseSource, lease_host,
+            parse_leases,
+        },
+        dns_zones::{DnsZoneImportRequest, DnsZoneImportResponse, DnsZoneSource, parse_zone_file},
         types::{DiscoveryType, RunType},
     },
     networks::r#impl::Network,
     shared::{
         handlers::traits::{create_handler, update_handler},
         services::traits::CrudService,
-        types::api::{ApiError, ApiErrorResponse, ApiResponse, ApiResult, EmptyApiResponse},
+        storage::filter::StorableFilter,
+        types::{
+            api::{ApiError, ApiErrorResponse, ApiResponse, ApiResult, EmptyApiResponse},
+            entities::DiscoveryMetadata,
+        },
     },
+    subnets::r#impl::base::Subnet,
 };
 use axum::{
     extract::{Path, State},
@@ -49,6 +59,8 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
         .routes(routes!(start_session))
         .routes(routes!(get_active_sessions))
         .routes(routes!(cancel_discovery))
+        .routes(routes!(import_dhcp_leases))
+        .routes(routes!(import_dns_zone))
         // Internal daemon endpoints
         .routes(routes!(receive_discovery_update))
         // SSE endpoint (internal - not well-supported by OpenAPI)
@@ -64,6 +76,7 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
     responses(
         (status = 200, description = "Discovery created successfully", body = ApiResponse<Discovery>),
         (status = 400, description = "Invalid subnet network", body = ApiErrorResponse),
+        (status = 400, description = "Unknown scan profile", body = ApiErrorResponse),
         (status = 400, description = "Can't create historical discovery", body = ApiErrorResponse),
     ),
      security(("user_api_key" = []), ("session" = []))
@@ -78,9 +91,8 @@ pub async fn create_discovery(
     }
 
     // Custom validation: Check if any subnets aren't on the same network as the discovery
-    #[allow(clippy::single_match)]
     match &discovery.base.discovery_type {
-        DiscoveryType::Network { subnet_ids, .. } => {
+        DiscoveryType::Network { subnet_ids, .. } | DiscoveryType::Passive { subnet_ids, .. } => {
             for subnet_id in subnet_ids.as_ref().unwrap_or(&vec![]) {
                 if let Some(subnet) = state.services.subnet_service.get_by_id(subnet_id).await?
                     && subnet.base.network_id != discovery.base.network_id
@@ -91,9 +103,30 @@ pub async fn create_discovery(
                 }
             }
         }
-        DiscoveryType::Docker { .. } | DiscoveryType::SelfReport { .. } => (),
+        DiscoveryType::DhcpLeases {
+            source: DhcpLeaseSource::Uploaded { .. },
+        } => {
+            return Err(ApiError::bad_request(
+                "Uploaded lease files are imported directly, they can't be run as a discovery",
+            ));
+        }
+        DiscoveryType::DnsZone {
+            source: DnsZoneSource::Uploaded { .. },
+        } => {
+            return Err(ApiError::bad_request(
+                "Uploaded zone files are imported directly, they can't be run as a discovery",
+            ));
+        }
+        DiscoveryType::Docker { .. }
+        | DiscoveryType::SelfReport { .. }
+        | DiscoveryType::Kubernetes { .. }
+        | DiscoveryType::Proxmox { .. }
+        | DiscoveryType::DhcpLeases { .. }
+        | DiscoveryType::DnsZone { .. } => (),
     }
 
+    validate_scan_profile(&state, &discovery).await?;
+
     // Delegate to generic handler (handles validation, auth checks, creation)
     create_handler::<Discovery>(State(state), auth, Json(discovery)).await
 }
@@ -108,6 +141,7 @@ pub async fn create_discovery(
     responses(
         (status = 200, description = "Discovery updated successfully", body = ApiResponse<Discovery>),
         (status = 400, description = "Invalid subnet network", body = ApiErrorResponse),
+        (status = 400, description = "Unknown scan profile", body = ApiErrorResponse),
         (status = 400, description = "Can't update historical discovery", body = ApiErrorResponse),
     ),
      security(("user_api_key" = []), ("session" = []))
@@ -122,9 +156,180 @@ pub async fn update_discovery(
         return Err(ApiError::discovery_historical_read_only());
     }
 
+    if let DiscoveryType::DhcpLeases {
+        source: DhcpLeaseSource::Uploaded { .. },
+    } = discovery.base.discovery_type
+    {
+        return Err(ApiError::bad_request(
+            "Uploaded lease files are imported directly, they can't be run as a discovery",
+        ));
+    }
+
+    if let DiscoveryType::DnsZone {
+        source: DnsZoneSource::Uploaded { .. },
+    } = discovery.base.discovery_type
+    {
+        return Err(ApiError::bad_request(
+            "Uploaded zone files are imported directly, they can't be run as a discovery",
+        ));
+    }
+
+    validate_scan_profile(&state, &discovery).await?;
+
     update_handler::<Discovery>(state, auth, id, discovery).await
 }
 
+/// A network discovery can only scan with one of its network's scan profiles
+async fn validate_scan_profile(state: &AppState, discovery: &Discovery) -> ApiResult<()> {
+    if let DiscoveryType::Network {
+        scan_profile_id: Some(scan_profile_id),
+        ..
+    } = &discovery.base.discovery_type
+    {
+        let network = state
+            .services
+            .network_service
+            .get_by_id(&discovery.base.network_id)
+            .await?;
+
+        if !network.is_some_and(|n| {
+            n.base
+                .scan_profiles
+                .iter()
+                .any(|p| p.id == *scan_profile_id)
+        }) {
+            return Err(ApiError::bad_request(&format!(
+                "Scan profile {} doesn't exist on the discovery's network",
+                scan_profile_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Import an uploaded DHCP lease file
+///
+/// Creates or updates a host for every lease inside one of the network's subnets,
+/// matching existing hosts by MAC address.
+#[utoipa::path(
+    post,
+    path = "/dhcp-leases/import",
+    tag = "discoveries",
+    request_body = DhcpLeaseImportRequest,
+    responses(
+        (status = 200, description = "Leases imported", body = ApiResponse<DhcpLeaseImportResponse>),
+        (status = 400, description = "Lease file couldn't be parsed", body = ApiErrorResponse),
+        (status = 404, description = "Daemon not found on the network", body = ApiErrorResponse),
+    ),
+     security(("user_api_key" = []), ("session" = []))
+)]
+async fn import_dhcp_leases(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<Member>,
+    Json(request): Json<DhcpLeaseImportRequest>,
+) -> ApiResult<Json<ApiResponse<DhcpLeaseImportResponse>>> {
+    if !auth.network_ids().contains(&request.network_id) {
+        return Err(ApiError::entity_access_denied::<Network>(
+            request.network_id,
+        ));
+    }
+
+    // Hosts are attributed to a daemon on the network, as if it had read the file itself
+    let daemon = state
+        .services
+        .daemon_service
+        .get_by_id(&request.daemon_id)
+        .await?
+        .filter(|d| d.base.network_id == request.network_id)
+        .ok_or_else(|| ApiError::entity_not_found::<Daemon>(request.daemon_id))?;
+
+    let leases = parse_leases(request.format, &request.contents)
+        .map_err(|e| ApiError::bad_request(&format!("Invalid lease file: {}", e)))?;
+
+    let subnets = state
+        .services
+        .subnet_service
+        .get_all(StorableFilter::<Subnet>::new().network_ids(&[request.network_id]))
+        .await?;
+
+    let entity = auth.into_entity();
+    let discovery_type = DiscoveryType::DhcpLeases {
+        source: DhcpLeaseSource::Uploaded {
+            format: request.format,
+        },
+    };
+
+    let mut response = DhcpLeaseImportResponse {
+        imported: 0,
+        skipped: 0,
+    };
+
+    for lease in &leases {
+        let Some(subnet) = subnets.iter().find(|s| s.base.cidr.contains(&lease.ip)) else {
+            response.skipped += 1;
+            continue;
+        };
+
+        let metadata = DiscoveryMetadata::new(discovery_type.clone(), daemon.id);
+        let (host, interface) = lease_host(lease, subnet, metadata);
+
+        state
+            .services
+            .host_service
+            .discover_host(host, vec![interface], vec![], vec![], entity.clone())
+            .await?;
+
+        response.imported += 1;
+    }
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// Import an uploaded DNS zone file
+///
+/// Names discovered interfaces, hosts and services after the zone's A, AAAA and
+/// CNAME records. Records pointing at addresses without a discovered host are
+/// returned as dangling.
+#[utoipa::path(
+    post,
+    path = "/dns-zones/import",
+    tag = "discoveries",
+    request_body = DnsZoneImportRequest,
+    responses(
+        (status = 200, description = "Zone imported", body = ApiResponse<DnsZoneImportResponse>),
+        (status = 400, description = "Zone file couldn't be parsed", body = ApiErrorResponse),
+    ),
+     security(("user_api_key" = []), ("session" = []))
+)]
+async fn import_dns_zone(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<Member>,
+    Json(request): Json<DnsZoneImportRequest>,
+) -> ApiResult<Json<ApiResponse<DnsZoneImportResponse>>> {
+    if !auth.network_ids().contains(&request.network_id) {
+        return Err(ApiError::entity_access_denied::<Network>(
+            request.network_id,
+        ));
+    }
+
+    let records = parse_zone_file(&request.zone, &request.contents)
+        .map_err(|e| ApiError::bad_request(&format!("Invalid zone file: {}", e)))?;
+
+    let response = state
+        .services
+        .host_service
+        .record_dns_names(
+            &request.network_id,
+            &request.zone,
+            records,
+            auth.into_entity(),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
 /// Receive discovery progress update from daemon
 ///
 /// Internal endpoint for daemons to report discovery progress.
@@ -7,7 +7,7 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::server::{
-    discovery::r#impl::types::{DiscoveryType, RunType},
+    discovery::r#impl::types::{DiscoveryType, RunType, validate_discovery_type},
     shared::entities::ChangeTriggersTopologyStaleness,
 };
 
@@ -15,6 +15,7 @@ use crate::server::{
     Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq, Default, ToSchema, Validate,
 )]
 pub struct DiscoveryBase {
+    #[validate(custom(function = "validate_discovery_type"))]
     pub discovery_type: DiscoveryType,
     pub run_type: RunType,
     pub name: String,
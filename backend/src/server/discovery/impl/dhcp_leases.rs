@@ -0,0 +1,402 @@
+//! DHCP lease parsing, shared by daemon lease discovery and lease files uploaded to the server.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use anyhow::{Result, anyhow};
+use mac_address::MacAddress;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::server::hosts::r#impl::base::{Host, HostBase};
+use crate::server::interfaces::r#impl::base::{Interface, InterfaceBase};
+use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource, HostnameSource};
+use crate::server::subnets::r#impl::base::Subnet;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, ToSchema)]
+pub enum DhcpLeaseFormat {
+    /// dnsmasq.leases
+    Dnsmasq,
+    /// ISC dhcpd.leases
+    IscDhcpd,
+    /// Kea memfile CSV (kea-leases4.csv)
+    Kea,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, ToSchema)]
+#[serde(tag = "type")]
+pub enum DhcpLeaseSource {
+    /// Lease file on the daemon's host
+    #[schema(title = "File")]
+    File {
+        format: DhcpLeaseFormat,
+        path: String,
+    },
+    /// Kea DHCPv4 control socket on the daemon's host
+    #[schema(title = "KeaControlSocket")]
+    KeaControlSocket { path: String },
+    /// Lease file uploaded to the server. Imported by the server, daemons can't run it.
+    #[schema(title = "Uploaded")]
+    Uploaded { format: DhcpLeaseFormat },
+}
+
+impl Default for DhcpLeaseSource {
+    fn default() -> Self {
+        Self::File {
+            format: DhcpLeaseFormat::Dnsmasq,
+            path: "/var/lib/misc/dnsmasq.leases".to_string(),
+        }
+    }
+}
+
+/// Lease file uploaded for import into a network
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DhcpLeaseImportRequest {
+    pub network_id: Uuid,
+    /// Daemon recorded on the imported hosts' discovery metadata, must be on the network
+    pub daemon_id: Uuid,
+    pub format: DhcpLeaseFormat,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DhcpLeaseImportResponse {
+    /// Leases that created or updated a host
+    pub imported: usize,
+    /// Leases for addresses outside every subnet on the network
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DhcpLease {
+    pub ip: IpAddr,
+    pub mac: MacAddress,
+    pub hostname: Option<String>,
+}
+
+/// Parse a lease file. When an address appears more than once the last entry wins,
+/// ISC dhcpd and Kea both append to their files rather than rewriting leases in place.
+pub fn parse_leases(format: DhcpLeaseFormat, contents: &str) -> Result<Vec<DhcpLease>> {
+    let leases = match format {
+        DhcpLeaseFormat::Dnsmasq => parse_dnsmasq(contents),
+        DhcpLeaseFormat::IscDhcpd => parse_isc_dhcpd(contents),
+        DhcpLeaseFormat::Kea => parse_kea_csv(contents)?,
+    };
+
+    Ok(latest_by_ip(leases))
+}
+
+/// Parse the response to a Kea `lease4-get-all` command, sent either straight to the
+/// DHCPv4 server's control socket or through the control agent (which wraps it in an array)
+pub fn parse_kea_command_response(response: &str) -> Result<Vec<DhcpLease>> {
+    let value: serde_json::Value = serde_json::from_str(response)?;
+    let value = match value {
+        serde_json::Value::Array(mut responses) if !responses.is_empty() => responses.remove(0),
+        other => other,
+    };
+
+    match value.get("result").and_then(|r| r.as_i64()) {
+        Some(0) => {}
+        // Command succeeded but there are no leases
+        Some(3) => return Ok(Vec::new()),
+        _ => {
+            return Err(anyhow!(
+                "Kea returned an error: {}",
+                value
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("unknown error")
+            ));
+        }
+    }
+
+    let leases = value
+        .pointer("/arguments/leases")
+        .and_then(|l| l.as_array())
+        .map(|leases| {
+            leases
+                .iter()
+                .filter_map(|lease| {
+                    let ip = lease.get("ip-address")?.as_str()?;
+                    let assigned = lease.get("state").and_then(|s| s.as_i64()).unwrap_or(0) == 0;
+                    let mac = lease
+                        .get("hw-address")
+                        .and_then(|m| m.as_str())
+                        .filter(|_| assigned);
+                    let hostname = lease.get("hostname").and_then(|h| h.as_str());
+
+                    lease_entry(ip, mac.and_then(|mac| lease_from_parts(ip, mac, hostname)))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(latest_by_ip(leases))
+}
+
+/// Build the host a lease describes, named after the lease hostname when it has one
+pub fn lease_host(
+    lease: &DhcpLease,
+    subnet: &Subnet,
+    mut metadata: DiscoveryMetadata,
+) -> (Host, Interface) {
+    metadata.hostname_source = lease.hostname.as_ref().map(|_| HostnameSource::DhcpLease);
+
+    let host = Host::new(HostBase {
+        name: lease
+            .hostname
+            .clone()
+            .unwrap_or_else(|| lease.ip.to_string()),
+        hostname: lease.hostname.clone(),
+        tags: Vec::new(),
+        network_id: subnet.base.network_id,
+        description: None,
+        source: EntitySource::Discovery {
+            metadata: vec![metadata],
+        },
+        virtualization: None,
+        hidden: false,
+        physical_links: Vec::new(),
+    });
+
+    let interface = Interface::new(InterfaceBase {
+        network_id: subnet.base.network_id,
+        host_id: Uuid::nil(), // Placeholder - server will set correct host_id
+        name: None,
+        subnet_id: subnet.id,
+        ip_address: lease.ip,
+        mac_address: Some(lease.mac),
+        position: 0,
+        dns_names: Vec::new(),
+    });
+
+    (host, interface)
+}
+
+/// `<expiry> <mac> <ip> <hostname|*> <client id|*>`, one lease per line. DHCPv6 leases
+/// carry an IAID instead of a MAC and are skipped.
+fn parse_dnsmasq(contents: &str) -> Vec<LeaseEntry> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, mac, ip, hostname, ..] => {
+                    lease_entry(ip, lease_from_parts(ip, mac, Some(hostname)))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// `lease <ip> { ... }` blocks. Only active leases belong to a client, free, expired,
+/// released, abandoned and backup ones don't.
+fn parse_isc_dhcpd(contents: &str) -> Vec<LeaseEntry> {
+    let mut entries = Vec::new();
+
+    let mut ip: Option<&str> = None;
+    let mut mac: Option<&str> = None;
+    let mut hostname: Option<&str> = None;
+    let mut active = false;
+
+    for line in contents.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("lease ")
+            && line.ends_with('{')
+        {
+            ip = rest.split_whitespace().next();
+            mac = None;
+            hostname = None;
+            active = false;
+        } else if line == "}" {
+            if let Some(ip) = ip.take() {
+                let lease = mac
+                    .take()
+                    .filter(|_| active)
+                    .and_then(|mac| lease_from_parts(ip, mac, hostname));
+                entries.extend(lease_entry(ip, lease));
+            }
+        } else if let Some(value) = line.strip_prefix("hardware ethernet ") {
+            mac = Some(value.trim_end_matches(';'));
+        } else if let Some(value) = line.strip_prefix("client-hostname ") {
+            hostname = Some(value.trim_end_matches(';').trim_matches('"'));
+        } else if let Some(value) = line.strip_prefix("binding state ") {
+            active = value.trim_end_matches(';') == "active";
+        }
+    }
+
+    entries
+}
+
+/// Kea memfile CSV with a header row. Only leases in the default (assigned) state count.
+fn parse_kea_csv(contents: &str) -> Result<Vec<LeaseEntry>> {
+    let mut lines = contents.lines();
+    let header: Vec<&str> = lines
+        .next()
+        .ok_or_else(|| anyhow!("Kea lease file is empty"))?
+        .split(',')
+        .collect();
+
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|c| c.trim() == name)
+            .ok_or_else(|| anyhow!("Kea lease file has no {} column", name))
+    };
+    let address = column("address")?;
+    let hwaddr = column("hwaddr")?;
+    let hostname = column("hostname")?;
+    let state = column("state").ok();
+
+    Ok(lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let ip = fields.get(address)?;
+
+            let assigned = state
+                .and_then(|state| fields.get(state))
+                .is_none_or(|s| s.trim() == "0");
+            if !assigned {
+                return lease_entry(ip, None);
+            }
+
+            // Kea escapes commas in values
+            let name = fields
+                .get(hostname)
+                .map(|h| h.replace("&#x2c", ","))
+                .filter(|h| !h.is_empty());
+
+            let lease = fields
+                .get(hwaddr)
+                .and_then(|mac| lease_from_parts(ip, mac, name.as_deref()));
+            lease_entry(ip, lease)
+        })
+        .collect())
+}
+
+fn lease_from_parts(ip: &str, mac: &str, hostname: Option<&str>) -> Option<DhcpLease> {
+    let ip: IpAddr = ip.trim().parse().ok()?;
+    let mac: MacAddress = mac.trim().parse().ok()?;
+
+    let hostname = hostname
+        .map(|h| h.trim().trim_end_matches('.'))
+        .filter(|h| !h.is_empty() && *h != "*")
+        .map(str::to_string);
+
+    Some(DhcpLease { ip, mac, hostname })
+}
+
+/// An address and the lease on it, `None` when the entry isn't an active lease
+type LeaseEntry = (IpAddr, Option<DhcpLease>);
+
+fn lease_entry(ip: &str, lease: Option<DhcpLease>) -> Option<LeaseEntry> {
+    Some((ip.trim().parse().ok()?, lease))
+}
+
+/// Keep the last entry for each address, dropping addresses whose last entry isn't an
+/// active lease so a later free or released entry retires an earlier one
+fn latest_by_ip(entries: Vec<LeaseEntry>) -> Vec<DhcpLease> {
+    let mut by_ip: HashMap<IpAddr, Option<DhcpLease>> = HashMap::new();
+    for (ip, lease) in entries {
+        by_ip.insert(ip, lease);
+    }
+
+    let mut leases: Vec<DhcpLease> = by_ip.into_values().flatten().collect();
+    leases.sort_by_key(|l| l.ip);
+    leases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dnsmasq() {
+        let contents = "1760781600 aa:bb:cc:dd:ee:01 192.168.1.20 laptop 01:aa:bb:cc:dd:ee:01\n\
+                        1760781600 aa:bb:cc:dd:ee:02 192.168.1.21 * *\n\
+                        duid 00:01:00:01:2c:1f:aa:bb:cc:dd:ee:ff\n\
+                        1760781600 1234 fd00::20 phone *\n";
+
+        let leases = parse_leases(DhcpLeaseFormat::Dnsmasq, contents).unwrap();
+
+        assert_eq!(leases.len(), 2);
+        assert_eq!(leases[0].hostname.as_deref(), Some("laptop"));
+        assert_eq!(leases[0].mac.to_string(), "AA:BB:CC:DD:EE:01");
+        assert_eq!(leases[1].hostname, None);
+    }
+
+    #[test]
+    fn test_parse_isc_dhcpd() {
+        let contents = r#"
+lease 192.168.1.30 {
+  starts 4 2026/10/15 10:00:00;
+  ends 4 2026/10/15 22:00:00;
+  binding state active;
+  next binding state free;
+  hardware ethernet aa:bb:cc:dd:ee:03;
+  client-hostname "printer";
+}
+lease 192.168.1.31 {
+  binding state free;
+  hardware ethernet aa:bb:cc:dd:ee:04;
+}
+lease 192.168.1.30 {
+  binding state active;
+  hardware ethernet aa:bb:cc:dd:ee:03;
+  client-hostname "office-printer";
+}
+lease 192.168.1.32 {
+  binding state active;
+  hardware ethernet aa:bb:cc:dd:ee:0a;
+}
+lease 192.168.1.32 {
+  binding state released;
+  hardware ethernet aa:bb:cc:dd:ee:0a;
+}
+lease 192.168.1.33 {
+  binding state expired;
+  hardware ethernet aa:bb:cc:dd:ee:0b;
+}
+"#;
+
+        let leases = parse_leases(DhcpLeaseFormat::IscDhcpd, contents).unwrap();
+
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].ip, "192.168.1.30".parse::<IpAddr>().unwrap());
+        assert_eq!(leases[0].hostname.as_deref(), Some("office-printer"));
+    }
+
+    #[test]
+    fn test_parse_kea_csv() {
+        let contents = "address,hwaddr,client_id,valid_lifetime,expire,subnet_id,fqdn_fwd,fqdn_rev,hostname,state,user_context\n\
+                        192.168.1.40,aa:bb:cc:dd:ee:05,,4000,1760781600,1,0,0,nas.lan.,0,\n\
+                        192.168.1.41,aa:bb:cc:dd:ee:06,,4000,1760781600,1,0,0,,1,\n\
+                        192.168.1.42,aa:bb:cc:dd:ee:09,,4000,1760781600,1,0,0,tablet,0,\n\
+                        192.168.1.42,aa:bb:cc:dd:ee:09,,0,1760781600,1,0,0,tablet,2,\n";
+
+        let leases = parse_leases(DhcpLeaseFormat::Kea, contents).unwrap();
+
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].hostname.as_deref(), Some("nas.lan"));
+    }
+
+    #[test]
+    fn test_parse_kea_command_response() {
+        let response = r#"[{"result": 0, "text": "2 IPv4 lease(s) found.", "arguments": {"leases": [
+            {"ip-address": "192.168.1.50", "hw-address": "aa:bb:cc:dd:ee:07", "hostname": "tv", "state": 0},
+            {"ip-address": "192.168.1.51", "hw-address": "aa:bb:cc:dd:ee:08", "hostname": "", "state": 2}
+        ]}}]"#;
+
+        let leases = parse_kea_command_response(response).unwrap();
+
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].hostname.as_deref(), Some("tv"));
+
+        let empty = r#"{"result": 3, "text": "0 IPv4 lease(s) found."}"#;
+        assert!(parse_kea_command_response(empty).unwrap().is_empty());
+
+        let error = r#"{"result": 1, "text": "unknown command"}"#;
+        assert!(parse_kea_command_response(error).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! DNS zone parsing, shared by daemon zone transfers and zone files uploaded to the server.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use anyhow::{Result, anyhow};
+use hickory_proto::rr::{Name, RData, Record};
+use hickory_proto::serialize::txt::Parser;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// CNAMEs pointing at CNAMEs are followed this many times before giving up
+const MAX_ALIAS_CHAIN: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, ToSchema)]
+#[serde(tag = "type")]
+pub enum DnsZoneSource {
+    /// Zone transfer (AXFR) from an authoritative server reachable from the daemon.
+    /// The server must allow transfers to the daemon's address.
+    #[schema(title = "Axfr")]
+    Axfr {
+        /// Address of the DNS server, port 53 unless one is given (ie "10.0.0.53:5353")
+        server: String,
+        zone: String,
+    },
+    /// Zone file uploaded to the server. Imported by the server, daemons can't run it.
+    #[schema(title = "Uploaded")]
+    Uploaded { zone: String },
+}
+
+impl Default for DnsZoneSource {
+    fn default() -> Self {
+        Self::Axfr {
+            server: "127.0.0.1".to_string(),
+            zone: String::new(),
+        }
+    }
+}
+
+impl DnsZoneSource {
+    pub fn zone(&self) -> &str {
+        match self {
+            DnsZoneSource::Axfr { zone, .. } | DnsZoneSource::Uploaded { zone } => zone,
+        }
+    }
+}
+
+/// Zone file uploaded for import into a network
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DnsZoneImportRequest {
+    pub network_id: Uuid,
+    /// Zone origin, used for names in the file that aren't fully qualified
+    pub zone: String,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DnsZoneImportResponse {
+    /// Records that named a discovered interface
+    pub matched: usize,
+    /// Records pointing at an address no discovered host has
+    pub dangling: Vec<DnsRecord>,
+}
+
+/// A name in the zone and one of the addresses it resolves to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+pub struct DnsRecord {
+    /// Fully qualified name, lowercase and without the trailing dot
+    pub name: String,
+    #[schema(value_type = String)]
+    pub ip: IpAddr,
+    /// The name is a CNAME, the address belongs to its target
+    pub alias: bool,
+}
+
+/// Parse a zone file in RFC 1035 master file format (as written by bind9 or
+/// `pdnsutil list-zone`)
+pub fn parse_zone_file(zone: &str, contents: &str) -> Result<Vec<DnsRecord>> {
+    let (_, record_sets) = Parser::new(contents, None, Some(zone_origin(zone)?))
+        .parse()
+        .map_err(|e| anyhow!("{}", e))?;
+
+    Ok(zone_records(record_sets.values().flat_map(|record_set| {
+        record_set.records_without_rrsigs()
+    })))
+}
+
+pub fn zone_origin(zone: &str) -> Result<Name> {
+    Name::from_ascii(format!("{}.", zone.trim_end_matches('.')))
+        .map_err(|e| anyhow!("Invalid zone name '{}': {}", zone, e))
+}
+
+/// Resolve a zone's A, AAAA and CNAME records to name/address pairs. CNAMEs are
+/// followed within the zone, aliases of names outside it can't be resolved and are
+/// left out, as are wildcards.
+pub fn zone_records<'a>(records: impl IntoIterator<Item = &'a Record>) -> Vec<DnsRecord> {
+    let mut addresses: BTreeMap<String, Vec<IpAddr>> = BTreeMap::new();
+    let mut aliases: BTreeMap<String, String> = BTreeMap::new();
+
+    for record in records {
+        let name = record_name(record.name());
+        if name.starts_with('*') {
+            continue;
+        }
+
+        match record.data() {
+            RData::A(a) => addresses.entry(name).or_default().push(IpAddr::V4(a.0)),
+            RData::AAAA(aaaa) => addresses.entry(name).or_default().push(IpAddr::V6(aaaa.0)),
+            RData::CNAME(cname) => {
+                aliases.insert(name, record_name(&cname.0));
+            }
+            _ => {}
+        }
+    }
+
+    let mut dns_records: Vec<DnsRecord> = addresses
+        .iter()
+        .flat_map(|(name, ips)| {
+            ips.iter().map(|ip| DnsRecord {
+                name: name.clone(),
+                ip: *ip,
+                alias: false,
+            })
+        })
+        .collect();
+
+    for (alias, target) in &aliases {
+        let mut target = target;
+        for _ in 0..MAX_ALIAS_CHAIN {
+            match aliases.get(target) {
+                Some(next) => target = next,
+                None => break,
+            }
+        }
+
+        for ip in addresses.get(target).into_iter().flatten() {
+            dns_records.push(DnsRecord {
+                name: alias.clone(),
+                ip: *ip,
+                alias: true,
+            });
+        }
+    }
+
+    dns_records.sort();
+    dns_records.dedup();
+    dns_records
+}
+
+/// Whether a name is the zone's apex or one of its subdomains
+pub fn in_zone(name: &str, zone: &str) -> bool {
+    let zone = zone.trim_end_matches('.').to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+    name == zone || name.ends_with(&format!(".{}", zone))
+}
+
+/// Whether the first label of a name refers to a service, ie "home-assistant.lan"
+/// names "Home Assistant"
+pub fn names_service(name: &str, service_name: &str) -> bool {
+    let label = name.split('.').next().unwrap_or_default();
+    let normalize = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+
+    let label = normalize(label);
+    !label.is_empty() && label == normalize(service_name)
+}
+
+fn record_name(name: &Name) -> String {
+    name.to_ascii().trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zone_file() {
+        let contents = r#"
+$TTL 3600
+@       IN SOA  ns1.example.lan. admin.example.lan. 1 7200 3600 1209600 3600
+@       IN NS   ns1
+ns1     IN A    10.0.0.53
+nas     IN A    10.0.0.20
+nas     IN AAAA fd00::20
+grafana IN CNAME monitor
+monitor IN CNAME nas
+www     IN CNAME example.com.
+*       IN A    10.0.0.99
+"#;
+
+        let records = parse_zone_file("example.lan", contents).unwrap();
+        let nas: IpAddr = "10.0.0.20".parse().unwrap();
+
+        assert!(records.contains(&DnsRecord {
+            name: "nas.example.lan".to_string(),
+            ip: nas,
+            alias: false,
+        }));
+        assert!(records.contains(&DnsRecord {
+            name: "grafana.example.lan".to_string(),
+            ip: nas,
+            alias: true,
+        }));
+        assert!(
+            records
+                .iter()
+                .any(|r| r.name == "nas.example.lan" && r.ip.is_ipv6())
+        );
+        assert!(!records.iter().any(|r| r.name.starts_with("www.")));
+        assert!(!records.iter().any(|r| r.name.starts_with('*')));
+        assert_eq!(records.len(), 7);
+    }
+
+    #[test]
+    fn test_in_zone() {
+        assert!(in_zone("nas.example.lan", "example.lan."));
+        assert!(in_zone("example.lan", "example.lan"));
+        assert!(!in_zone("nas.otherexample.lan", "example.lan"));
+        assert!(!in_zone("lan", "example.lan"));
+    }
+
+    #[test]
+    fn test_names_service() {
+        assert!(names_service(
+            "home-assistant.example.lan",
+            "Home Assistant"
+        ));
+        assert!(names_service("grafana.example.lan", "Grafana"));
+        assert!(!names_service("grafana01.example.lan", "Grafana"));
+    }
+}
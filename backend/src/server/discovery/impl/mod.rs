@@ -1,4 +1,6 @@
 pub mod base;
+pub mod dhcp_leases;
+pub mod dns_zones;
 pub mod handlers;
 pub mod storage;
 pub mod types;
@@ -5,7 +5,10 @@ use std::fmt::Display;
 use strum::{Display, EnumDiscriminants, EnumIter, IntoStaticStr};
 use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::ValidationError;
 
+use crate::server::discovery::r#impl::dhcp_leases::DhcpLeaseSource;
+use crate::server::discovery::r#impl::dns_zones::DnsZoneSource;
 use crate::server::shared::entities::EntityDiscriminants;
 use crate::server::{
     daemons::r#impl::api::DiscoveryUpdatePayload,
@@ -42,6 +45,10 @@ pub enum DiscoveryType {
         #[serde(default)]
         #[schema(required)]
         host_naming_fallback: HostNamingFallback,
+        /// Which of the network's scan profiles to probe hosts with, every port when unset
+        #[serde(default)]
+        #[schema(required)]
+        scan_profile_id: Option<Uuid>,
     },
     #[schema(title = "Docker")]
     Docker {
@@ -51,6 +58,61 @@ pub enum DiscoveryType {
         #[schema(required)]
         host_naming_fallback: HostNamingFallback,
     },
+    #[schema(title = "DhcpLeases")]
+    DhcpLeases { source: DhcpLeaseSource },
+    #[schema(title = "Kubernetes")]
+    Kubernetes {
+        /// Path to a kubeconfig on the daemon's host. The in-cluster service account
+        /// (or the default kubeconfig) is used when unset.
+        #[schema(required)]
+        kubeconfig_path: Option<String>,
+        /// Kubeconfig context to use instead of the current one
+        #[serde(default)]
+        #[schema(required)]
+        context: Option<String>,
+    },
+    /// Link VMs and LXC containers to their hypervisor through the Proxmox VE API
+    #[schema(title = "Proxmox")]
+    Proxmox {
+        /// Which of the network's Proxmox API tokens to use, all of them when unset
+        #[serde(default)]
+        #[schema(required)]
+        credential_ids: Option<Vec<Uuid>>,
+    },
+    /// Listen-only discovery for networks that can't tolerate scans. Nothing is transmitted.
+    #[schema(title = "Passive")]
+    Passive {
+        /// How long to listen for, in seconds. Between 10 seconds and 24 hours.
+        duration: u64,
+        #[schema(required)]
+        subnet_ids: Option<Vec<Uuid>>,
+    },
+    /// Name hosts and services after the A, AAAA and CNAME records of a DNS zone
+    #[schema(title = "DnsZone")]
+    DnsZone { source: DnsZoneSource },
+}
+
+/// Bounds on how long passive discovery listens. It holds the daemon's discovery slot
+/// for the whole time, so it can't be left running indefinitely.
+pub const PASSIVE_DURATION_SECS: std::ops::RangeInclusive<u64> = 10..=86_400;
+
+pub fn validate_discovery_type(discovery_type: &DiscoveryType) -> Result<(), ValidationError> {
+    if let DiscoveryType::Passive { duration, .. } = discovery_type
+        && !PASSIVE_DURATION_SECS.contains(duration)
+    {
+        let mut err = ValidationError::new("passive_duration");
+        err.message = Some(
+            format!(
+                "Passive discovery must listen for between {} seconds and {} hours",
+                PASSIVE_DURATION_SECS.start(),
+                PASSIVE_DURATION_SECS.end() / 3600
+            )
+            .into(),
+        );
+        return Err(err);
+    }
+
+    Ok(())
 }
 
 impl Default for DiscoveryType {
@@ -67,6 +129,11 @@ impl Display for DiscoveryType {
             DiscoveryType::SelfReport { .. } => write!(f, "Self Report"),
             DiscoveryType::Network { .. } => write!(f, "Network Discovery"),
             DiscoveryType::Docker { .. } => write!(f, "Docker Discovery"),
+            DiscoveryType::DhcpLeases { .. } => write!(f, "DHCP Lease Discovery"),
+            DiscoveryType::Passive { .. } => write!(f, "Passive Discovery"),
+            DiscoveryType::Kubernetes { .. } => write!(f, "Kubernetes Discovery"),
+            DiscoveryType::Proxmox { .. } => write!(f, "Proxmox Discovery"),
+            DiscoveryType::DnsZone { .. } => write!(f, "DNS Zone Discovery"),
         }
     }
 }
@@ -78,6 +145,9 @@ pub enum HostNamingFallback {
     Ip,
     #[default]
     BestService,
+    /// The name the host advertises over mDNS (ie "living-room-tv.local"), falling back
+    /// to the best service when it advertises nothing
+    Mdns,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
@@ -93,7 +163,9 @@ pub enum RunType {
     },
     #[schema(title = "Historical")]
     /// Historical discovery runs are created by the server and cannot be submitted via API
-    Historical { results: DiscoveryUpdatePayload },
+    Historical {
+        results: Box<DiscoveryUpdatePayload>,
+    },
     #[schema(title = "AdHoc")]
     AdHoc {
         #[serde(default)]
@@ -139,6 +211,21 @@ impl TypeMetadataProvider for DiscoveryType {
             DiscoveryType::SelfReport { .. } => {
                 "The daemon reports its own host configuration and network details"
             }
+            DiscoveryType::DhcpLeases { .. } => {
+                "Import hosts from a DHCP server's leases, including devices that are offline or firewalled"
+            }
+            DiscoveryType::Kubernetes { .. } => {
+                "Discover Kubernetes nodes, pods, services and ingresses through the cluster API"
+            }
+            DiscoveryType::Proxmox { .. } => {
+                "List Proxmox VE nodes, VMs and containers and link guests to their hypervisor"
+            }
+            DiscoveryType::Passive { .. } => {
+                "Listen for ARP, DHCP and mDNS traffic without sending anything, for networks that can't be scanned"
+            }
+            DiscoveryType::DnsZone { .. } => {
+                "Name discovered hosts and services from a DNS zone and flag records pointing at missing hosts"
+            }
         }
     }
 }
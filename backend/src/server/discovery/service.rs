@@ -1,7 +1,11 @@
 use crate::daemon::runtime::service::LOG_TARGET;
 use crate::server::auth::middleware::auth::AuthenticatedEntity;
 use crate::server::daemons::r#impl::base::DaemonMode;
-use crate::server::discovery::r#impl::types::RunType;
+use crate::server::discovery::r#impl::types::{DiscoveryType, RunType};
+use crate::server::interfaces::r#impl::base::Interface;
+use crate::server::networks::r#impl::Network;
+use crate::server::networks::snmp::{SnmpCredential, SnmpCredentialSet};
+use crate::server::ports::r#impl::base::Port;
 use crate::server::shared::entities::{ChangeTriggersTopologyStaleness, EntityDiscriminants};
 use crate::server::shared::events::bus::EventBus;
 use crate::server::shared::events::types::{EntityEvent, EntityOperation};
@@ -14,7 +18,10 @@ use anyhow::anyhow;
 use anyhow::{Error, Result};
 use async_trait::async_trait;
 use chrono::Utc;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use tokio::sync::{RwLock, broadcast};
 use tokio_cron_scheduler::{Job, JobScheduler};
 use uuid::Uuid;
@@ -23,7 +30,7 @@ use crate::server::discovery::r#impl::base::Discovery;
 use crate::{
     daemon::discovery::types::base::DiscoveryPhase,
     server::daemons::{
-        r#impl::api::{DaemonDiscoveryRequest, DiscoveryUpdatePayload},
+        r#impl::api::{DaemonDiscoveryConfig, DaemonDiscoveryRequest, DiscoveryUpdatePayload},
         service::DaemonService,
     },
 };
@@ -32,6 +39,9 @@ use crate::{
 pub struct DiscoveryService {
     discovery_storage: Arc<GenericPostgresStorage<Discovery>>,
     daemon_service: Arc<DaemonService>,
+    network_storage: Arc<GenericPostgresStorage<Network>>,
+    port_storage: Arc<GenericPostgresStorage<Port>>,
+    interface_storage: Arc<GenericPostgresStorage<Interface>>,
     sessions: RwLock<HashMap<Uuid, DiscoveryUpdatePayload>>, // session_id -> session state mapping
     daemon_sessions: RwLock<HashMap<Uuid, Vec<Uuid>>>,       // daemon_id -> session_id mapping
     daemon_pull_cancellations: RwLock<HashMap<Uuid, (bool, Uuid)>>, // daemon_id -> (boolean, session_id) mapping for pull mode cancellations of current session on daemon
@@ -70,6 +80,9 @@ impl DiscoveryService {
     pub async fn new(
         discovery_storage: Arc<GenericPostgresStorage<Discovery>>,
         daemon_service: Arc<DaemonService>,
+        network_storage: Arc<GenericPostgresStorage<Network>>,
+        port_storage: Arc<GenericPostgresStorage<Port>>,
+        interface_storage: Arc<GenericPostgresStorage<Interface>>,
         event_bus: Arc<EventBus>,
         entity_tag_service: Arc<EntityTagService>,
     ) -> Result<Arc<Self>> {
@@ -79,6 +92,9 @@ impl DiscoveryService {
         Ok(Arc::new(Self {
             discovery_storage,
             daemon_service,
+            network_storage,
+            port_storage,
+            interface_storage,
             sessions: RwLock::new(HashMap::new()),
             daemon_sessions: RwLock::new(HashMap::new()),
             daemon_pull_cancellations: RwLock::new(HashMap::new()),
@@ -90,6 +106,106 @@ impl DiscoveryService {
         }))
     }
 
+    /// Network settings a daemon needs to run a session, each only for the discovery types
+    /// that use them
+    pub async fn daemon_config_for_session(
+        &self,
+        session: &DiscoveryUpdatePayload,
+    ) -> Result<DaemonDiscoveryConfig> {
+        let Some(network) = self.network_storage.get_by_id(&session.network_id).await? else {
+            return Ok(DaemonDiscoveryConfig::default());
+        };
+
+        match &session.discovery_type {
+            DiscoveryType::Network {
+                scan_profile_id, ..
+            } => {
+                let scan_profile = scan_profile_id
+                    .map(|scan_profile_id| {
+                        network
+                            .base
+                            .scan_profiles
+                            .iter()
+                            .find(|p| p.id == scan_profile_id)
+                            .cloned()
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "Scan profile {} no longer exists on the network",
+                                    scan_profile_id
+                                )
+                            })
+                    })
+                    .transpose()?;
+
+                Ok(DaemonDiscoveryConfig {
+                    snmp_credentials: self
+                        .snmp_credential_set(session.network_id, network.base.snmp_credentials)
+                        .await?,
+                    dns_resolvers: network.base.dns_resolvers,
+                    scan_profile,
+                    scan_exclusions: network.base.scan_exclusions,
+                    ..Default::default()
+                })
+            }
+            DiscoveryType::Proxmox { credential_ids } => Ok(DaemonDiscoveryConfig {
+                proxmox_credentials: network
+                    .base
+                    .proxmox_credentials
+                    .into_iter()
+                    .filter(|c| {
+                        credential_ids
+                            .as_ref()
+                            .is_none_or(|ids| ids.contains(&c.id))
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            _ => Ok(DaemonDiscoveryConfig::default()),
+        }
+    }
+
+    /// The network's SNMP profiles, with the profile that answered at each known address
+    /// so it's tried first
+    async fn snmp_credential_set(
+        &self,
+        network_id: Uuid,
+        profiles: Vec<SnmpCredential>,
+    ) -> Result<SnmpCredentialSet> {
+        if profiles.is_empty() {
+            return Ok(SnmpCredentialSet::default());
+        }
+
+        // host_id -> credential that answered on one of its ports
+        let answered: HashMap<Uuid, Uuid> = self
+            .port_storage
+            .get_all(StorableFilter::<Port>::new().network_ids(&[network_id]))
+            .await?
+            .into_iter()
+            .filter_map(|port| {
+                port.base
+                    .snmp_credential_id
+                    .map(|credential_id| (port.base.host_id, credential_id))
+            })
+            .collect();
+
+        let mut known = BTreeMap::new();
+        if !answered.is_empty() {
+            let host_ids: Vec<Uuid> = answered.keys().copied().collect();
+            let interfaces = self
+                .interface_storage
+                .get_all(StorableFilter::<Interface>::new().host_ids(&host_ids))
+                .await?;
+
+            for interface in interfaces {
+                if let Some(credential_id) = answered.get(&interface.base.host_id) {
+                    known.insert(interface.base.ip_address, *credential_id);
+                }
+            }
+        }
+
+        Ok(SnmpCredentialSet { profiles, known })
+    }
+
     /// Expose stream to handler
     pub fn subscribe(&self) -> broadcast::Receiver<DiscoveryUpdatePayload> {
         self.update_tx.subscribe()
@@ -509,12 +625,18 @@ impl DiscoveryService {
 
         // Initiate session on daemon if none are running and daemon is push
         if !daemon_is_running_discovery && daemon_is_push {
+            let config = match self.daemon_config_for_session(&session_payload).await {
+                Ok(config) => config,
+                Err(e) => return self.fail_session(session_payload, e.to_string()).await,
+            };
+
             self.daemon_service
                 .send_discovery_request(
                     &discovery.base.daemon_id,
                     DaemonDiscoveryRequest {
                         discovery_type: discovery.base.discovery_type,
                         session_id,
+                        config,
                     },
                     authentication,
                 )
@@ -534,9 +656,11 @@ impl DiscoveryService {
 
         let mut sessions = self.sessions.write().await;
 
-        let mut last_updated = self.session_last_updated.write().await;
         // Track last update time
-        last_updated.insert(update.session_id, Utc::now());
+        self.session_last_updated
+            .write()
+            .await
+            .insert(update.session_id, Utc::now());
 
         // Auto-create session if it doesn't exist (handles server restarts during discovery)
         if let std::collections::hash_map::Entry::Vacant(e) = sessions.entry(update.session_id) {
@@ -591,7 +715,7 @@ impl DiscoveryService {
                     tags: Vec::new(),
                     discovery_type: session.discovery_type.clone(),
                     run_type: RunType::Historical {
-                        results: session.clone(),
+                        results: Box::new(session.clone()),
                     },
                 },
             };
@@ -625,7 +749,7 @@ impl DiscoveryService {
             }
 
             // Get next session info BEFORE trying to send request
-            let next_session_info = if let Some(daemon_sessions) = self
+            let next_session = if let Some(daemon_sessions) = self
                 .daemon_sessions
                 .write()
                 .await
@@ -639,7 +763,7 @@ impl DiscoveryService {
                     .and_then(|next_session_id| sessions.get_mut(next_session_id))
                     .map(|next_session| {
                         next_session.phase = DiscoveryPhase::Pending;
-                        (next_session.discovery_type.clone(), next_session.session_id)
+                        next_session.clone()
                     })
             } else {
                 None
@@ -660,17 +784,26 @@ impl DiscoveryService {
                 .map(|d| d.base.mode == DaemonMode::Push)
                 .unwrap_or(false);
 
-            if let Some((discovery_type, session_id)) = next_session_info
+            if let Some(next_session) = next_session
                 && daemon_is_push
             {
                 tracing::debug!("Starting next session");
 
+                let config = match self.daemon_config_for_session(&next_session).await {
+                    Ok(config) => config,
+                    Err(e) => {
+                        Box::pin(self.fail_session(next_session, e.to_string())).await?;
+                        return Ok(());
+                    }
+                };
+
                 self.daemon_service
                     .send_discovery_request(
                         &daemon_id,
                         DaemonDiscoveryRequest {
-                            discovery_type,
-                            session_id,
+                            discovery_type: next_session.discovery_type,
+                            session_id: next_session.session_id,
+                            config,
                         },
                         AuthenticatedEntity::System,
                     )
@@ -681,6 +814,26 @@ impl DiscoveryService {
         Ok(())
     }
 
+    /// End a session that can't be handed to its daemon, recording why
+    pub async fn fail_session(
+        &self,
+        mut session: DiscoveryUpdatePayload,
+        error: String,
+    ) -> Result<DiscoveryUpdatePayload, Error> {
+        tracing::warn!(
+            session_id = %session.session_id,
+            error = %error,
+            "Failing discovery session before it started"
+        );
+
+        session.phase = DiscoveryPhase::Failed;
+        session.error = Some(error);
+        session.finished_at = Some(Utc::now());
+        self.update_session(session.clone()).await?;
+
+        Ok(session)
+    }
+
     pub async fn cancel_session(
         &self,
         session_id: Uuid,
@@ -727,6 +880,11 @@ impl DiscoveryService {
                     started_at: session.started_at,
                     finished_at: Some(Utc::now()),
                     discovery_type: session.discovery_type,
+                    daemon_config: None,
+                    rtt_stats: Vec::new(),
+                    skipped: Vec::new(),
+                    unenforced_exclusions: Vec::new(),
+                    dangling_records: Vec::new(),
                 };
                 let _ = self.update_tx.send(cancelled_update);
 
@@ -785,6 +943,11 @@ impl DiscoveryService {
                                             started_at: session.started_at,
                                             finished_at: Some(Utc::now()),
                                             discovery_type: session.discovery_type.clone(),
+                                            daemon_config: None,
+                                            rtt_stats: Vec::new(),
+                                            skipped: Vec::new(),
+                                            unenforced_exclusions: Vec::new(),
+                                            dangling_records: Vec::new(),
                                         };
                                         let _ = self.update_tx.send(cancelled_update.clone());
 
@@ -800,7 +963,7 @@ impl DiscoveryService {
                                                 name: "Discovery Run (Cancellation Failed)".to_string(),
                                                 discovery_type: session.discovery_type.clone(),
                                                 run_type: RunType::Historical {
-                                                    results: cancelled_update,
+                                                    results: Box::new(cancelled_update),
                                                 },
                                             },
                                         };
@@ -1059,7 +1222,9 @@ impl DiscoveryService {
                         tags: Vec::new(),
                         name: "Discovery Run (Stalled)".to_string(),
                         discovery_type: session.discovery_type.clone(),
-                        run_type: RunType::Historical { results: session },
+                        run_type: RunType::Historical {
+                            results: Box::new(session),
+                        },
                     },
                 };
 
@@ -3,21 +3,23 @@ use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::server::auth::middleware::permissions::{Authorized, Member, Viewer};
+use crate::server::auth::middleware::permissions::{Authorized, IsDaemon, Member, Viewer};
 use crate::server::bindings::r#impl::base::Binding;
 use crate::server::config::AppState;
 use crate::server::groups::r#impl::base::Group;
+use crate::server::hosts::r#impl::api::DiscoveryProxyRoutesRequest;
 use crate::server::shared::handlers::ordering::OrderField;
 use crate::server::shared::handlers::query::{
     FilterQueryExtractor, OrderDirection, PaginationParams,
 };
-use crate::server::shared::handlers::traits::{create_handler, update_handler};
+use crate::server::shared::handlers::traits::{CrudHandlers, create_handler, update_handler};
 use crate::server::shared::services::traits::CrudService;
 use crate::server::shared::storage::filter::StorableFilter;
 use crate::server::shared::storage::traits::Storable;
 use crate::server::shared::types::api::{
     ApiError, ApiErrorResponse, ApiResponse, ApiResult, PaginatedApiResponse,
 };
+use crate::server::shared::validation::validate_entity;
 use std::sync::Arc;
 use utoipa::IntoParams;
 use utoipa_axum::{router::OpenApiRouter, routes};
@@ -125,6 +127,8 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
             generated::delete
         ))
         .routes(routes!(generated::bulk_delete))
+        .routes(routes!(create_group_discovery))
+        .routes(routes!(create_proxy_routes_discovery))
 }
 
 /// List all groups
@@ -262,3 +266,127 @@ async fn update_group(
     // Delegate to generic handler (handles validation, auth checks, update)
     update_handler::<Group>(State(state), auth, path, Json(group)).await
 }
+
+/// Internal endpoint for daemon group discovery
+///
+/// Used by daemons to report groups they inferred, such as request paths
+/// from ingress rules. A group from an earlier discovery run with the same
+/// name and type is updated instead of duplicated.
+#[utoipa::path(
+    post,
+    path = "/discovery",
+    tags = ["groups", "internal"],
+    request_body = Group,
+    responses(
+        (status = 200, description = "Group created or updated", body = ApiResponse<Group>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 403, description = "Daemon cannot create groups on other networks", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_group_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(group): Json<Group>,
+) -> ApiResult<Json<ApiResponse<Group>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    if group.base.network_id != daemon_network_id {
+        return Err(ApiError::forbidden(
+            "Daemon cannot create groups on networks it's not assigned to",
+        ));
+    }
+
+    if !group.base.source.is_from_discovery() {
+        return Err(ApiError::bad_request(
+            "Groups reported by daemons must have a discovery source",
+        ));
+    }
+
+    validate_entity(|| CrudHandlers::validate(&group), Group::entity_name())?;
+
+    for binding_id in &group.base.binding_ids {
+        let binding_id_filter = StorableFilter::<Binding>::new().entity_id(binding_id);
+
+        if let Some(binding) = state
+            .services
+            .binding_service
+            .get_one(binding_id_filter)
+            .await?
+            && binding.base.network_id != group.base.network_id
+        {
+            return Err(ApiError::bad_request(&format!(
+                "Group is on network {}, can't add binding which is on network {}",
+                group.base.network_id, binding.base.network_id
+            )));
+        }
+    }
+
+    let group = state
+        .services
+        .group_service
+        .discover_group(group, auth.into_entity())
+        .await?;
+
+    Ok(Json(ApiResponse::success(group)))
+}
+
+/// Internal endpoint for daemon reverse proxy routes
+///
+/// Used by daemons to report the routes read from a reverse proxy's
+/// configuration. Each route's upstream is matched to a discovered service by
+/// address and port, and recorded as a request path group from the proxy to
+/// that service. Groups are deduplicated like other discovered groups.
+#[utoipa::path(
+    post,
+    path = "/discovery/proxy-routes",
+    tags = ["groups", "internal"],
+    request_body = DiscoveryProxyRoutesRequest,
+    responses(
+        (status = 200, description = "Request path groups created or updated", body = ApiResponse<Vec<Group>>),
+        (status = 400, description = "Service is not a reverse proxy", body = ApiErrorResponse),
+        (status = 403, description = "Daemon has no network assignment", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_proxy_routes_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(request): Json<DiscoveryProxyRoutesRequest>,
+) -> ApiResult<Json<ApiResponse<Vec<Group>>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    if !request.source.is_from_discovery() {
+        return Err(ApiError::bad_request(
+            "Groups reported by daemons must have a discovery source",
+        ));
+    }
+
+    let route_groups = state
+        .services
+        .host_service
+        .proxy_route_groups(&daemon_network_id, request)
+        .await?;
+
+    let entity = auth.into_entity();
+    let mut groups = Vec::new();
+    for group in route_groups {
+        groups.push(
+            state
+                .services
+                .group_service
+                .discover_group(group, entity.clone())
+                .await?,
+        );
+    }
+
+    Ok(Json(ApiResponse::success(groups)))
+}
@@ -259,4 +259,36 @@ impl GroupService {
             entity_tag_service,
         }
     }
+
+    /// Create a group found by discovery, or update the one an earlier run created.
+    /// Discovered groups are matched by name and type, so user-created groups are
+    /// never touched.
+    pub async fn discover_group(
+        &self,
+        group: Group,
+        authentication: AuthenticatedEntity,
+    ) -> Result<Group> {
+        let filter = StorableFilter::<Group>::new()
+            .network_ids(&[group.base.network_id])
+            .name(group.base.name.clone());
+
+        let existing = self.get_all(filter).await?.into_iter().find(|g| {
+            g.base.group_type == group.base.group_type && g.base.source.is_from_discovery()
+        });
+
+        match existing {
+            Some(existing)
+                if existing.base.binding_ids == group.base.binding_ids
+                    && existing.base.description == group.base.description =>
+            {
+                Ok(existing)
+            }
+            Some(mut existing) => {
+                existing.base.binding_ids = group.base.binding_ids;
+                existing.base.description = group.base.description;
+                self.update(&mut existing, authentication).await
+            }
+            None => self.create(group, authentication).await,
+        }
+    }
 }
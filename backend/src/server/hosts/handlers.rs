@@ -1,5 +1,6 @@
 use crate::server::auth::middleware::auth::AuthenticatedEntity;
 use crate::server::auth::middleware::permissions::{Authorized, IsDaemon, Member, Or, Viewer};
+use crate::server::services::r#impl::base::Service;
 use crate::server::shared::entities::EntityDiscriminants;
 use crate::server::shared::extractors::Query;
 use crate::server::shared::handlers::ordering::OrderField;
@@ -16,8 +17,13 @@ use crate::server::shared::validation::{validate_network_access, validate_read_a
 use crate::server::{
     config::AppState,
     daemons::r#impl::base::Daemon,
+    discovery::r#impl::dns_zones::DnsZoneImportResponse,
     hosts::r#impl::{
-        api::{CreateHostRequest, DiscoveryHostRequest, HostResponse, UpdateHostRequest},
+        api::{
+            CreateHostRequest, DiscoveryDnsRecordsRequest, DiscoveryHostRequest,
+            DiscoveryLibvirtDomainsRequest, DiscoveryLinkNeighborsRequest,
+            DiscoveryProxmoxGuestsRequest, HostResponse, UpdateHostRequest,
+        },
         base::Host,
         legacy::{HostCreateRequestBody, HostCreateResponse, LegacyHostWithServicesResponse},
     },
@@ -149,6 +155,10 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
         .routes(routes!(bulk_delete_hosts))
         .routes(routes!(consolidate_hosts))
         .routes(routes!(create_host_discovery))
+        .routes(routes!(create_link_neighbors_discovery))
+        .routes(routes!(create_proxmox_guests_discovery))
+        .routes(routes!(create_libvirt_domains_discovery))
+        .routes(routes!(create_dns_records_discovery))
 }
 
 /// List all hosts
@@ -359,6 +369,7 @@ async fn create_host(
                 interfaces,
                 ports,
                 services,
+                ..
             } = discovery_request;
 
             let host_response = host_service
@@ -483,6 +494,8 @@ async fn create_host_discovery(
         interfaces,
         ports,
         services,
+        certificates,
+        neighbors_read,
     } = request;
 
     // Get daemon network_id from entity
@@ -498,13 +511,229 @@ async fn create_host_discovery(
         ));
     }
 
-    let host_response = host_service
-        .discover_host(host, interfaces, ports, services, auth.into_entity())
+    let authentication = auth.into_entity();
+    let discovered_ports = ports.clone();
+    let physical_links = host.base.physical_links.clone();
+    let tagged_services: Vec<Service> = services
+        .iter()
+        .filter(|s| !s.base.tags.is_empty())
+        .cloned()
+        .collect();
+
+    let mut host_response = host_service
+        .discover_host(host, interfaces, ports, services, authentication.clone())
+        .await?;
+
+    // Daemons aren't tied to an organization, so service tags (from container labels)
+    // are resolved against the organization owning the daemon's network
+    if !tagged_services.is_empty() {
+        let organization_id = state
+            .services
+            .network_service
+            .get_by_id(&daemon_network_id)
+            .await?
+            .map(|n| n.base.organization_id)
+            .ok_or_else(|| ApiError::bad_request("Daemon network not found"))?;
+
+        host_service
+            .record_discovered_service_tags(&mut host_response, &tagged_services, organization_id)
+            .await?;
+    }
+
+    if neighbors_read {
+        host_service
+            .record_discovered_physical_links(
+                &mut host_response,
+                physical_links,
+                authentication.clone(),
+            )
+            .await?;
+    }
+
+    host_service
+        .record_discovered_certificates(
+            &host_response,
+            &discovered_ports,
+            certificates,
+            authentication,
+        )
         .await?;
 
     Ok(Json(ApiResponse::success(host_response)))
 }
 
+/// Internal endpoint for daemon link neighbors
+///
+/// Used by daemons to report the switches they heard advertising themselves
+/// via LLDP or CDP. Each switch is upserted like a discovered host, and the
+/// links are recorded on the reporting daemon's host.
+#[utoipa::path(
+    post,
+    path = "/discovery/neighbors",
+    tags = ["hosts", "internal"],
+    request_body = DiscoveryLinkNeighborsRequest,
+    responses(
+        (status = 200, description = "Link neighbors recorded", body = EmptyApiResponse),
+        (status = 403, description = "Daemon cannot create hosts on other networks", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_link_neighbors_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(request): Json<DiscoveryLinkNeighborsRequest>,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    if request
+        .neighbors
+        .iter()
+        .any(|n| n.host.base.network_id != daemon_network_id)
+    {
+        return Err(ApiError::forbidden(
+            "Daemon cannot create hosts on networks it's not assigned to",
+        ));
+    }
+
+    let authentication = auth.into_entity();
+    let AuthenticatedEntity::Daemon { daemon_id, .. } = &authentication else {
+        return Err(ApiError::forbidden(
+            "Only daemons can report link neighbors",
+        ));
+    };
+
+    state
+        .services
+        .host_service
+        .record_link_neighbors(daemon_id, request.neighbors, authentication.clone())
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Internal endpoint for daemon Proxmox guests
+///
+/// Used by daemons to report the VMs and containers listed by the Proxmox VE
+/// API. Guests are matched to discovered hosts by MAC address and linked to
+/// the Proxmox VE service of their node.
+#[utoipa::path(
+    post,
+    path = "/discovery/proxmox-guests",
+    tags = ["hosts", "internal"],
+    request_body = DiscoveryProxmoxGuestsRequest,
+    responses(
+        (status = 200, description = "Proxmox guests recorded", body = EmptyApiResponse),
+        (status = 403, description = "Daemon has no network assignment", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_proxmox_guests_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(request): Json<DiscoveryProxmoxGuestsRequest>,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    state
+        .services
+        .host_service
+        .record_proxmox_guests(&daemon_network_id, request.guests, auth.into_entity())
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Internal endpoint for daemon libvirt domains
+///
+/// Used by daemons running on a libvirt/KVM hypervisor to report the domains
+/// defined on it. Domains are matched to discovered hosts by MAC address and
+/// linked to the Libvirt service of the daemon's host.
+#[utoipa::path(
+    post,
+    path = "/discovery/libvirt-domains",
+    tags = ["hosts", "internal"],
+    request_body = DiscoveryLibvirtDomainsRequest,
+    responses(
+        (status = 200, description = "Libvirt domains recorded", body = EmptyApiResponse),
+        (status = 403, description = "Daemon has no network assignment", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_libvirt_domains_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(request): Json<DiscoveryLibvirtDomainsRequest>,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    state
+        .services
+        .host_service
+        .record_libvirt_domains(
+            &daemon_network_id,
+            &request.service_id,
+            request.domains,
+            auth.into_entity(),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Internal endpoint for daemon DNS zone records
+///
+/// Used by daemons to report the A, AAAA and CNAME records transferred from a
+/// DNS zone. Records are matched to discovered interfaces by address and used to
+/// name them, their hosts and their services. Records pointing at addresses
+/// without a discovered host are returned as dangling.
+#[utoipa::path(
+    post,
+    path = "/discovery/dns-records",
+    tags = ["hosts", "internal"],
+    request_body = DiscoveryDnsRecordsRequest,
+    responses(
+        (status = 200, description = "DNS records recorded", body = ApiResponse<DnsZoneImportResponse>),
+        (status = 403, description = "Daemon has no network assignment", body = ApiErrorResponse),
+    ),
+    security(("daemon_api_key" = []))
+)]
+async fn create_dns_records_discovery(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<IsDaemon>,
+    Json(request): Json<DiscoveryDnsRecordsRequest>,
+) -> ApiResult<Json<ApiResponse<DnsZoneImportResponse>>> {
+    let daemon_network_id = auth
+        .network_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| ApiError::forbidden("Daemon has no network assignment"))?;
+
+    let response = state
+        .services
+        .host_service
+        .record_dns_names(
+            &daemon_network_id,
+            &request.zone,
+            request.records,
+            auth.into_entity(),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
 /// Consolidate hosts
 ///
 /// Merges all interfaces, ports, and services from `other_host` into
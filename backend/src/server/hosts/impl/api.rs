@@ -8,8 +8,11 @@ use validator::Validate;
 
 use crate::server::{
     bindings::r#impl::base::{Binding, BindingBase, BindingType},
+    certificates::r#impl::base::Certificate,
+    discovery::r#impl::dns_zones::DnsRecord,
     hosts::r#impl::{
         base::{Host, HostBase},
+        links::PhysicalLink,
         virtualization::HostVirtualization,
     },
     interfaces::r#impl::base::{Interface, InterfaceBase},
@@ -52,6 +55,149 @@ pub struct DiscoveryHostRequest {
     pub interfaces: Vec<Interface>,
     pub ports: Vec<Port>,
     pub services: Vec<Service>,
+    /// TLS certificates, keyed to `ports` by port_id
+    #[serde(default)]
+    pub certificates: Vec<Certificate>,
+    /// The run read the host's LLDP neighbor table, so `host.physical_links` is complete
+    /// and links missing from it have been unplugged
+    #[serde(default)]
+    pub neighbors_read: bool,
+}
+
+/// A switch a daemon heard advertising itself via LLDP or CDP on one of its own interfaces
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveredLinkNeighbor {
+    /// The daemon's end of the link, recorded on the daemon's host
+    pub link: PhysicalLink,
+    /// The switch, created or merged like any other discovered host
+    pub host: Host,
+    /// Empty unless the advertised management address is in a known subnet
+    pub interfaces: Vec<Interface>,
+}
+
+/// Request type for daemons reporting the switches they're plugged into
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveryLinkNeighborsRequest {
+    pub neighbors: Vec<DiscoveredLinkNeighbor>,
+}
+
+/// A QEMU VM or LXC container listed by the Proxmox VE API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveredProxmoxGuest {
+    /// Name of the Proxmox node the guest runs on
+    pub node: String,
+    /// Addresses of that node, used to find its Proxmox VE service
+    #[schema(value_type = Vec<String>)]
+    pub node_ips: Vec<IpAddr>,
+    pub vm_id: String,
+    #[schema(required)]
+    pub vm_name: Option<String>,
+    /// MAC addresses of the guest's network devices, matched against discovered interfaces
+    #[schema(value_type = Vec<String>)]
+    pub mac_addresses: Vec<MacAddress>,
+}
+
+/// Request type for daemons reporting the guests of a Proxmox VE cluster
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveryProxmoxGuestsRequest {
+    pub guests: Vec<DiscoveredProxmoxGuest>,
+}
+
+/// A domain defined on the libvirt hypervisor of the daemon's host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveredLibvirtDomain {
+    pub name: String,
+    pub uuid: Uuid,
+    /// MAC addresses of the domain's network interfaces, matched against discovered interfaces
+    #[schema(value_type = Vec<String>)]
+    pub mac_addresses: Vec<MacAddress>,
+}
+
+/// Request type for daemons reporting the libvirt domains of the host they run on
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveryLibvirtDomainsRequest {
+    /// The Libvirt service on the daemon's host
+    pub service_id: Uuid,
+    pub domains: Vec<DiscoveredLibvirtDomain>,
+}
+
+/// A route read from a reverse proxy's configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveredProxyRoute {
+    /// Hostnames and path the route matches, e.g. `app.example.com/api`
+    pub rule: String,
+    /// Upstream host as configured on the proxy
+    pub upstream_host: String,
+    /// Addresses the daemon resolved the upstream host to. Empty if it couldn't, the
+    /// host is then matched by hostname or name.
+    #[schema(value_type = Vec<String>)]
+    pub upstream_ips: Vec<IpAddr>,
+    pub upstream_port: u16,
+}
+
+impl DiscoveredProxyRoute {
+    /// Binding of the service listening on the upstream port of the host the upstream
+    /// resolves to. Hosts with an interface on one of `preferred_subnet_ids` win when
+    /// several have the upstream address.
+    pub fn backend_binding(
+        &self,
+        hosts: &[HostResponse],
+        preferred_subnet_ids: &[Uuid],
+    ) -> Option<Uuid> {
+        let mut candidates: Vec<(&HostResponse, &Interface)> = hosts
+            .iter()
+            .flat_map(|host| host.interfaces.iter().map(move |i| (host, i)))
+            .filter(|(host, interface)| {
+                if self.upstream_ips.is_empty() {
+                    host.hostname
+                        .as_deref()
+                        .is_some_and(|h| h.eq_ignore_ascii_case(&self.upstream_host))
+                        || host.name.eq_ignore_ascii_case(&self.upstream_host)
+                } else {
+                    self.upstream_ips.contains(&interface.base.ip_address)
+                }
+            })
+            .collect();
+
+        candidates.sort_by_key(|(_, interface)| {
+            !preferred_subnet_ids.contains(&interface.base.subnet_id)
+        });
+
+        candidates.into_iter().find_map(|(host, interface)| {
+            let port = host.ports.iter().find(|p| {
+                p.base.port_type.number() == self.upstream_port
+                    && p.base.port_type.protocol() == TransportProtocol::Tcp
+            })?;
+
+            host.services
+                .iter()
+                .flat_map(|s| s.base.bindings.iter())
+                .find(|b| {
+                    b.port_id() == Some(port.id)
+                        && b.interface_id().is_none_or(|id| id == interface.id)
+                })
+                .map(|b| b.id)
+        })
+    }
+}
+
+/// Request type for daemons reporting the routes configured on a reverse proxy
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveryProxyRoutesRequest {
+    /// The reverse proxy service the routes were read from
+    pub service_id: Uuid,
+    pub routes: Vec<DiscoveredProxyRoute>,
+    /// Source recorded on the request path groups built from the routes
+    pub source: EntitySource,
+}
+
+/// Request type for daemons reporting the address records of a DNS zone
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveryDnsRecordsRequest {
+    /// The zone the records were transferred from. Names from an earlier transfer of
+    /// the same zone that are no longer in it are removed.
+    pub zone: String,
+    pub records: Vec<DnsRecord>,
 }
 
 // =============================================================================
@@ -96,6 +242,7 @@ impl InterfaceInput {
                 mac_address: self.mac_address,
                 name: self.name,
                 position: self.position.unwrap_or(0),
+                dns_names: Vec::new(),
             },
         }
     }
@@ -143,6 +290,7 @@ impl PortInput {
                     number: self.number,
                     protocol: self.protocol,
                 }),
+                snmp_credential_id: None,
             },
         }
     }
@@ -387,6 +535,8 @@ pub struct HostResponse {
     pub virtualization: Option<HostVirtualization>,
     pub hidden: bool,
     pub tags: Vec<Uuid>,
+    #[serde(default)]
+    pub physical_links: Vec<PhysicalLink>,
 
     // Hydrated children (fetched by service layer)
     pub interfaces: Vec<Interface>,
@@ -411,6 +561,7 @@ impl HostResponse {
             virtualization,
             hidden,
             tags,
+            physical_links,
             interfaces: _,
             ports: _,
             services: _,
@@ -429,6 +580,7 @@ impl HostResponse {
                 virtualization: virtualization.clone(),
                 hidden: *hidden,
                 tags: tags.clone(),
+                physical_links: physical_links.clone(),
             },
         }
     }
@@ -460,6 +612,7 @@ impl HostResponse {
             virtualization,
             hidden,
             tags,
+            physical_links,
         } = base;
 
         Self {
@@ -474,6 +627,7 @@ impl HostResponse {
             virtualization,
             hidden,
             tags,
+            physical_links,
             interfaces,
             ports,
             services,
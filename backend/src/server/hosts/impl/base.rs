@@ -1,3 +1,4 @@
+use crate::server::hosts::r#impl::links::PhysicalLink;
 use crate::server::hosts::r#impl::virtualization::HostVirtualization;
 use crate::server::shared::entities::ChangeTriggersTopologyStaleness;
 use crate::server::shared::types::api::deserialize_empty_string_as_none;
@@ -32,6 +33,10 @@ pub struct HostBase {
     #[serde(default)]
     #[schema(required)]
     pub tags: Vec<Uuid>,
+    /// Cabling to neighboring devices, reported by the host itself (LLDP over SNMP)
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub physical_links: Vec<PhysicalLink>,
 }
 
 impl Default for HostBase {
@@ -45,6 +50,7 @@ impl Default for HostBase {
             virtualization: None,
             hidden: false,
             tags: Vec::new(),
+            physical_links: Vec::new(),
         }
     }
 }
@@ -102,6 +108,7 @@ impl ChangeTriggersTopologyStaleness<Host> for Host {
             self.base.hostname != other_host.base.hostname
                 || self.base.virtualization != other_host.base.virtualization
                 || self.base.hidden != other_host.base.hidden
+                || self.base.physical_links != other_host.base.physical_links
         } else {
             true
         }
@@ -123,6 +123,7 @@ impl LegacyInterface {
                 mac_address: self.mac_address,
                 name: self.name,
                 position: 0,
+                dns_names: Vec::new(),
             },
         }
     }
@@ -161,6 +162,7 @@ impl LegacyPort {
                 network_id,
                 host_id,
                 port_type,
+                snmp_credential_id: None,
             },
         }
     }
@@ -331,6 +333,7 @@ impl LegacyHostWithServicesRequest {
                 virtualization: None,
                 hidden: host.hidden,
                 tags: host.tags,
+                physical_links: Vec::new(),
             },
         };
 
@@ -339,6 +342,8 @@ impl LegacyHostWithServicesRequest {
             interfaces,
             ports,
             services,
+            certificates: Vec::new(),
+            neighbors_read: false,
         }
     }
 }
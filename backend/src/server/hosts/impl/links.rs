@@ -0,0 +1,65 @@
+use mac_address::MacAddress;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use utoipa::ToSchema;
+
+use crate::server::{hosts::r#impl::base::Host, interfaces::r#impl::base::Interface};
+
+/// A cable between one of this host's ports and a neighboring device, read from the
+/// host's LLDP neighbor table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct PhysicalLink {
+    /// Name of the local port, ie "Gi1/0/12"
+    pub local_port: String,
+    /// Speed of the local port in Mbps
+    #[schema(required)]
+    pub speed_mbps: Option<u64>,
+    /// Whether the local port is operationally up
+    pub is_up: bool,
+    /// Chassis ID advertised by the neighbor, usually a MAC address
+    pub remote_chassis_id: String,
+    #[schema(value_type = Option<String>, required)]
+    pub remote_mac_address: Option<MacAddress>,
+    /// Port ID or description advertised by the neighbor
+    #[schema(required)]
+    pub remote_port: Option<String>,
+    #[schema(required)]
+    pub remote_system_name: Option<String>,
+    /// Management address advertised by the neighbor
+    #[schema(value_type = Option<String>, required)]
+    pub remote_ip_address: Option<IpAddr>,
+}
+
+impl PhysicalLink {
+    /// Whether the neighbor end of this link is the given interface, by the neighbor's
+    /// port MAC, its chassis ID when that is a MAC, or its management address
+    pub fn matches_interface(&self, interface: &Interface) -> bool {
+        let chassis_mac = self.remote_chassis_id.parse::<MacAddress>().ok();
+        let mac_matches = interface
+            .base
+            .mac_address
+            .is_some_and(|mac| self.remote_mac_address == Some(mac) || chassis_mac == Some(mac));
+        let ip_matches = self.remote_ip_address == Some(interface.base.ip_address);
+
+        mac_matches || ip_matches
+    }
+
+    /// Whether the neighbor's advertised system name is the given host. Neighbors without
+    /// one are named after their chassis ID.
+    pub fn matches_host_name(&self, host: &Host) -> bool {
+        let system_name = self
+            .remote_system_name
+            .as_ref()
+            .unwrap_or(&self.remote_chassis_id);
+
+        // Neighbors often advertise an FQDN, hosts are usually named by their short name
+        let short_name = system_name.split('.').next().unwrap_or(system_name);
+
+        [Some(&host.base.name), host.base.hostname.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|name| {
+                name.eq_ignore_ascii_case(system_name) || name.eq_ignore_ascii_case(short_name)
+            })
+    }
+}
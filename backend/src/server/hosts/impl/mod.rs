@@ -2,5 +2,6 @@ pub mod api;
 pub mod base;
 pub mod handlers;
 pub mod legacy;
+pub mod links;
 pub mod storage;
 pub mod virtualization;
@@ -6,6 +6,7 @@ use uuid::Uuid;
 use crate::server::{
     hosts::r#impl::{
         base::{Host, HostBase},
+        links::PhysicalLink,
         virtualization::HostVirtualization,
     },
     shared::{
@@ -68,6 +69,7 @@ impl Storable for Host {
                     source,
                     virtualization,
                     tags: _, // Stored in entity_tags junction table
+                    physical_links,
                 },
         } = self.clone();
 
@@ -83,6 +85,7 @@ impl Storable for Host {
                 "hostname",
                 "hidden",
                 "virtualization",
+                "physical_links",
             ],
             vec![
                 SqlValue::Uuid(id),
@@ -95,6 +98,7 @@ impl Storable for Host {
                 SqlValue::OptionalString(hostname),
                 SqlValue::Bool(hidden),
                 SqlValue::OptionalHostVirtualization(virtualization),
+                SqlValue::JsonValue(serde_json::to_value(&physical_links)?),
             ],
         ))
     }
@@ -107,6 +111,9 @@ impl Storable for Host {
         let virtualization: Option<HostVirtualization> =
             serde_json::from_value(row.get::<serde_json::Value, _>("virtualization"))
                 .map_err(|e| anyhow::anyhow!("Failed to deserialize virtualization: {}", e))?;
+        let physical_links: Vec<PhysicalLink> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("physical_links"))
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize physical_links: {}", e))?;
 
         Ok(Host {
             id: row.get("id"),
@@ -121,6 +128,7 @@ impl Storable for Host {
                 hidden: row.get("hidden"),
                 virtualization,
                 tags: Vec::new(), // Hydrated from entity_tags junction table
+                physical_links,
             },
         })
     }
@@ -19,6 +19,33 @@ use crate::server::shared::{
 pub enum HostVirtualization {
     #[schema(title = "Proxmox")]
     Proxmox(ProxmoxVirtualization),
+    #[schema(title = "Libvirt")]
+    Libvirt(LibvirtVirtualization),
+    #[schema(title = "Esxi")]
+    Esxi(EsxiVirtualization),
+    #[schema(title = "HyperV")]
+    HyperV(HyperVVirtualization),
+}
+
+impl HostVirtualization {
+    /// The hypervisor service the host runs under
+    pub fn service_id(&self) -> Uuid {
+        match self {
+            HostVirtualization::Proxmox(v) => v.service_id,
+            HostVirtualization::Libvirt(v) => v.service_id,
+            HostVirtualization::Esxi(v) => v.service_id,
+            HostVirtualization::HyperV(v) => v.service_id,
+        }
+    }
+
+    pub fn vm_name(&self) -> Option<&str> {
+        match self {
+            HostVirtualization::Proxmox(v) => v.vm_name.as_deref(),
+            HostVirtualization::Libvirt(v) => v.vm_name.as_deref(),
+            HostVirtualization::Esxi(v) => v.vm_name.as_deref(),
+            HostVirtualization::HyperV(v) => v.vm_name.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
@@ -28,6 +55,31 @@ pub struct ProxmoxVirtualization {
     pub service_id: Uuid,
 }
 
+#[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct LibvirtVirtualization {
+    /// Domain name
+    pub vm_name: Option<String>,
+    /// Domain UUID
+    pub vm_id: Option<String>,
+    pub service_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct EsxiVirtualization {
+    pub vm_name: Option<String>,
+    /// Managed object ID, ie "vm-42"
+    pub vm_id: Option<String>,
+    pub service_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct HyperVVirtualization {
+    pub vm_name: Option<String>,
+    /// VM GUID
+    pub vm_id: Option<String>,
+    pub service_id: Uuid,
+}
+
 impl HasId for HostVirtualization {
     fn id(&self) -> &'static str {
         self.into()
@@ -45,10 +97,20 @@ impl EntityMetadataProvider for HostVirtualization {
 
 impl TypeMetadataProvider for HostVirtualization {
     fn name(&self) -> &'static str {
-        "Proxmox"
+        match self {
+            HostVirtualization::Proxmox(_) => "Proxmox",
+            HostVirtualization::Libvirt(_) => "Libvirt/KVM",
+            HostVirtualization::Esxi(_) => "VMware ESXi",
+            HostVirtualization::HyperV(_) => "Hyper-V",
+        }
     }
 
     fn description(&self) -> &'static str {
-        "A host running as a Proxmox VM"
+        match self {
+            HostVirtualization::Proxmox(_) => "A host running as a Proxmox VM",
+            HostVirtualization::Libvirt(_) => "A host running as a libvirt/KVM domain",
+            HostVirtualization::Esxi(_) => "A host running as a VMware ESXi VM",
+            HostVirtualization::HyperV(_) => "A host running as a Hyper-V VM",
+        }
     }
 }
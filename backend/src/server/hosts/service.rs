@@ -1,17 +1,31 @@
 use crate::server::{
     auth::middleware::auth::AuthenticatedEntity,
     bindings::r#impl::base::{Binding, BindingType},
+    certificates::{r#impl::base::Certificate, service::CertificateService},
     daemons::{r#impl::base::Daemon, service::DaemonService},
+    discovery::r#impl::dns_zones::{DnsRecord, DnsZoneImportResponse, in_zone, names_service},
+    groups::r#impl::{
+        base::{Group, GroupBase},
+        types::{GroupType, GroupTypeDiscriminants},
+    },
     hosts::r#impl::{
         api::{
-            BindingInput, ConflictBehavior, CreateHostRequest, HostResponse, InterfaceInput,
-            PortInput, ServiceInput, UpdateHostRequest,
+            BindingInput, ConflictBehavior, CreateHostRequest, DiscoveredLibvirtDomain,
+            DiscoveredLinkNeighbor, DiscoveredProxmoxGuest, DiscoveredProxyRoute,
+            DiscoveryProxyRoutesRequest, HostResponse, InterfaceInput, PortInput, ServiceInput,
+            UpdateHostRequest,
         },
         base::{Host, HostBase},
+        links::PhysicalLink,
+        virtualization::{HostVirtualization, LibvirtVirtualization, ProxmoxVirtualization},
     },
     interfaces::{r#impl::base::Interface, service::InterfaceService},
     ports::{r#impl::base::Port, service::PortService},
-    services::{r#impl::base::Service, service::ServiceService},
+    services::{
+        definitions::{libvirt::Libvirt, proxmox::Proxmox},
+        r#impl::{base::Service, categories::ServiceCategory, definitions::ServiceDefinition},
+        service::ServiceService,
+    },
     shared::{
         entities::{ChangeTriggersTopologyStaleness, EntityDiscriminants},
         events::{
@@ -28,6 +42,7 @@ use crate::server::{
         types::{
             api::ValidationError,
             entities::{EntitySource, EntitySourceDiscriminants},
+            metadata::{EntityMetadataProvider, HasId},
         },
     },
     tags::entity_tags::EntityTagService,
@@ -35,7 +50,7 @@ use crate::server::{
 use anyhow::{Error, Result, anyhow};
 use async_trait::async_trait;
 use chrono::Utc;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, net::IpAddr, sync::Arc};
 use strum::IntoDiscriminant;
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -44,6 +59,7 @@ pub struct HostService {
     storage: Arc<GenericPostgresStorage<Host>>,
     interface_service: Arc<InterfaceService>,
     port_service: Arc<PortService>,
+    certificate_service: Arc<CertificateService>,
     service_service: Arc<ServiceService>,
     daemon_service: Arc<DaemonService>,
     host_locks: Arc<Mutex<HashMap<Uuid, Arc<Mutex<()>>>>>,
@@ -211,10 +227,12 @@ impl CrudService<Host> for HostService {
 }
 
 impl HostService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         storage: Arc<GenericPostgresStorage<Host>>,
         interface_service: Arc<InterfaceService>,
         port_service: Arc<PortService>,
+        certificate_service: Arc<CertificateService>,
         service_service: Arc<ServiceService>,
         daemon_service: Arc<DaemonService>,
         event_bus: Arc<EventBus>,
@@ -224,6 +242,7 @@ impl HostService {
             storage,
             interface_service,
             port_service,
+            certificate_service,
             service_service,
             daemon_service,
             host_locks: Arc::new(Mutex::new(HashMap::new())),
@@ -449,6 +468,7 @@ impl HostService {
             virtualization,
             hidden,
             tags,
+            physical_links: Vec::new(),
         };
         let host = Host::new(host_base);
 
@@ -588,14 +608,21 @@ impl HostService {
                 }
 
                 // MAC fallback: find by (host_id, mac_address) when subnet differs
-                // This handles cases where subnet_id changed between discovery runs
-                if let Some(mac) = &interface.base.mac_address {
+                // This handles cases where subnet_id changed between discovery runs.
+                // IPv6 is excluded: a single MAC legitimately carries several IPv6
+                // addresses (SLAAC, privacy) alongside its IPv4 address.
+                if let Some(mac) = &interface.base.mac_address
+                    && interface.base.ip_address.is_ipv4()
+                {
                     let mac_filter = StorableFilter::<Interface>::new()
                         .host_id(&interface.base.host_id)
                         .mac_address(mac);
                     let existing_by_mac: Vec<Interface> =
                         self.interface_service.get_all(mac_filter).await?;
-                    if let Some(existing_iface) = existing_by_mac.into_iter().next() {
+                    if let Some(existing_iface) = existing_by_mac
+                        .into_iter()
+                        .find(|i| i.base.ip_address.is_ipv4())
+                    {
                         tracing::debug!(
                             interface_ip = %interface.base.ip_address,
                             interface_mac = %mac,
@@ -625,20 +652,32 @@ impl HostService {
 
             if matches!(conflict_behavior, ConflictBehavior::Upsert) {
                 // Check if port already exists by ID
-                if let Some(existing_port) = self.port_service.get_by_id(&port_with_host.id).await?
-                {
-                    created_ports.push(existing_port);
-                    continue;
-                }
+                let mut existing_port = self.port_service.get_by_id(&port_with_host.id).await?;
 
                 // Check by unique constraint (host_id, port_number, protocol)
-                let existing_ports = self.port_service.get_for_host(&created_host.id).await?;
-                let port_config = port_with_host.base.port_type.config();
-                if let Some(existing_port) = existing_ports.into_iter().find(|p| {
-                    let existing_config = p.base.port_type.config();
-                    existing_config.number == port_config.number
-                        && existing_config.protocol == port_config.protocol
-                }) {
+                if existing_port.is_none() {
+                    let existing_ports = self.port_service.get_for_host(&created_host.id).await?;
+                    let port_config = port_with_host.base.port_type.config();
+                    existing_port = existing_ports.into_iter().find(|p| {
+                        let existing_config = p.base.port_type.config();
+                        existing_config.number == port_config.number
+                            && existing_config.protocol == port_config.protocol
+                    });
+                }
+
+                if let Some(mut existing_port) = existing_port {
+                    // Remember the SNMP credential that answered most recently
+                    let snmp_credential_id = port_with_host.base.snmp_credential_id;
+                    if snmp_credential_id.is_some()
+                        && existing_port.base.snmp_credential_id != snmp_credential_id
+                    {
+                        existing_port.base.snmp_credential_id = snmp_credential_id;
+                        existing_port = self
+                            .port_service
+                            .update(&mut existing_port, authentication.clone())
+                            .await?;
+                    }
+
                     created_ports.push(existing_port);
                     continue;
                 }
@@ -851,6 +890,8 @@ impl HostService {
                 virtualization,
                 hidden,
                 tags: tags.clone(),
+                // Discovery owns physical links, users can't edit them
+                physical_links: existing.base.physical_links,
             },
         };
 
@@ -1125,6 +1166,615 @@ impl HostService {
         .await
     }
 
+    /// Apply the tags a daemon reported for discovered services. Services are upserted
+    /// without them since daemons have no organization to validate tags against.
+    pub async fn record_discovered_service_tags(
+        &self,
+        host_response: &mut HostResponse,
+        tagged_services: &[Service],
+        organization_id: Uuid,
+    ) -> Result<()> {
+        for tagged_service in tagged_services {
+            let Some(service) = host_response.services.iter_mut().find(|s| {
+                s.base.virtualization == tagged_service.base.virtualization
+                    && s.base.service_definition.id() == tagged_service.base.service_definition.id()
+            }) else {
+                continue;
+            };
+
+            for tag_id in &tagged_service.base.tags {
+                if service.base.tags.contains(tag_id) {
+                    continue;
+                }
+
+                self.entity_tag_service
+                    .add_tag(
+                        service.id,
+                        EntityDiscriminants::Service,
+                        *tag_id,
+                        organization_id,
+                    )
+                    .await?;
+                service.base.tags.push(*tag_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace a discovered host's physical links with the ones from a run that read its
+    /// neighbor table, so links that disappeared from the table are dropped.
+    pub async fn record_discovered_physical_links(
+        &self,
+        host_response: &mut HostResponse,
+        physical_links: Vec<PhysicalLink>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        if host_response.physical_links == physical_links {
+            return Ok(());
+        }
+
+        let mut host = host_response.to_host();
+        host.base.physical_links = physical_links;
+        let updated = self.update(&mut host, authentication).await?;
+
+        host_response.physical_links = updated.base.physical_links;
+        host_response.updated_at = updated.updated_at;
+
+        Ok(())
+    }
+
+    /// Store the TLS certificates reported alongside a discovered host.
+    /// Certificates reference the daemon's port IDs, which may have been deduplicated
+    /// against existing ports, so they're resolved by number + protocol.
+    pub async fn record_discovered_certificates(
+        &self,
+        host_response: &HostResponse,
+        discovered_ports: &[Port],
+        certificates: Vec<Certificate>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        for certificate in certificates {
+            let Some(port_config) = discovered_ports
+                .iter()
+                .find(|p| p.id == certificate.base.port_id)
+                .map(|p| p.base.port_type.config())
+            else {
+                tracing::warn!(
+                    port_id = %certificate.base.port_id,
+                    "Discovered certificate references a port that wasn't reported, skipping"
+                );
+                continue;
+            };
+
+            let Some(port) = host_response
+                .ports
+                .iter()
+                .find(|p| p.base.port_type.config() == port_config)
+            else {
+                continue;
+            };
+
+            let certificate =
+                certificate.with_port(port.id, host_response.id, host_response.network_id);
+
+            self.certificate_service
+                .record_observed(certificate, authentication.clone())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Record the switches a daemon heard advertising themselves on its interfaces.
+    /// Switches are upserted like any discovered host, except ones without a management
+    /// address in a known subnet: those have no interfaces to match on, so an existing
+    /// host with the advertised name is used instead. The links replace any of the
+    /// daemon host's links on the same local ports.
+    pub async fn record_link_neighbors(
+        &self,
+        daemon_id: &Uuid,
+        neighbors: Vec<DiscoveredLinkNeighbor>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        let daemon = self
+            .daemon_service
+            .get_by_id(daemon_id)
+            .await?
+            .ok_or_else(|| anyhow!("Daemon '{}' not found", daemon_id))?;
+        let daemon_host_id = daemon.base.host_id;
+
+        let filter = StorableFilter::<Host>::new().network_ids(&[daemon.base.network_id]);
+        let existing_hosts = self.get_all(filter).await?;
+        let host_ids: Vec<Uuid> = existing_hosts.iter().map(|h| h.id).collect();
+        let interfaces_by_host = self.interface_service.get_for_hosts(&host_ids).await?;
+
+        let mut links = Vec::new();
+        for DiscoveredLinkNeighbor {
+            link,
+            host,
+            interfaces,
+        } in neighbors
+        {
+            // Neighbors without a management address can't be merged by interface, so
+            // find them by MAC before falling back to their name
+            let known = interfaces.is_empty()
+                && (interfaces_by_host.iter().any(|(host_id, interfaces)| {
+                    *host_id != daemon_host_id
+                        && interfaces.iter().any(|i| link.matches_interface(i))
+                }) || existing_hosts
+                    .iter()
+                    .any(|h| h.id != daemon_host_id && link.matches_host_name(h)));
+
+            if !known {
+                self.discover_host(
+                    host,
+                    interfaces,
+                    Vec::new(),
+                    Vec::new(),
+                    authentication.clone(),
+                )
+                .await?;
+            }
+
+            links.push(link);
+        }
+
+        let Some(mut daemon_host) = self.get_by_id(&daemon_host_id).await? else {
+            tracing::warn!(
+                daemon_id = %daemon_id,
+                host_id = %daemon_host_id,
+                "Daemon host not found, not recording link neighbors"
+            );
+            return Ok(());
+        };
+
+        let mut physical_links: Vec<_> = daemon_host
+            .base
+            .physical_links
+            .iter()
+            .filter(|existing| !links.iter().any(|l| l.local_port == existing.local_port))
+            .cloned()
+            .collect();
+        physical_links.extend(links);
+
+        if physical_links != daemon_host.base.physical_links {
+            daemon_host.base.physical_links = physical_links;
+            self.update(&mut daemon_host, authentication).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Link Proxmox guests to the Proxmox VE service of the node they run on. Guests are
+    /// matched to hosts by MAC address, and nodes by the addresses the cluster reports for
+    /// them. Guests on nodes without a discovered Proxmox VE service are skipped.
+    pub async fn record_proxmox_guests(
+        &self,
+        network_id: &Uuid,
+        guests: Vec<DiscoveredProxmoxGuest>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        let hosts = self
+            .get_all(StorableFilter::<Host>::new().network_ids(&[*network_id]))
+            .await?;
+        let host_ids: Vec<Uuid> = hosts.iter().map(|h| h.id).collect();
+        let interfaces_by_host = self.interface_service.get_for_hosts(&host_ids).await?;
+
+        let proxmox_services: Vec<Service> = self
+            .service_service
+            .get_all(StorableFilter::<Service>::new().network_ids(&[*network_id]))
+            .await?
+            .into_iter()
+            .filter(|s| s.base.service_definition.id() == Proxmox.id())
+            .collect();
+
+        let host_with_interface = |matches: &dyn Fn(&Interface) -> bool| {
+            hosts.iter().find(|h| {
+                interfaces_by_host
+                    .get(&h.id)
+                    .is_some_and(|interfaces| interfaces.iter().any(matches))
+            })
+        };
+
+        let mut linked = 0;
+        let mut unmatched = 0;
+
+        for guest in guests {
+            let Some(node_host) =
+                host_with_interface(&|i| guest.node_ips.contains(&i.base.ip_address))
+            else {
+                unmatched += 1;
+                continue;
+            };
+            let Some(proxmox_service) = proxmox_services
+                .iter()
+                .find(|s| s.base.host_id == node_host.id)
+            else {
+                tracing::debug!(node = %guest.node, host_id = %node_host.id, "Proxmox node has no Proxmox VE service, skipping its guests");
+                unmatched += 1;
+                continue;
+            };
+            let Some(guest_host) = host_with_interface(&|i| {
+                i.base
+                    .mac_address
+                    .is_some_and(|mac| guest.mac_addresses.contains(&mac))
+            }) else {
+                unmatched += 1;
+                continue;
+            };
+
+            if guest_host.id == node_host.id {
+                continue;
+            }
+
+            let virtualization = Some(HostVirtualization::Proxmox(ProxmoxVirtualization {
+                vm_name: guest.vm_name.clone(),
+                vm_id: Some(guest.vm_id.clone()),
+                service_id: proxmox_service.id,
+            }));
+
+            linked += 1;
+            if guest_host.base.virtualization != virtualization {
+                let mut guest_host = guest_host.clone();
+                guest_host.base.virtualization = virtualization;
+                self.update(&mut guest_host, authentication.clone()).await?;
+            }
+        }
+
+        tracing::info!(
+            network_id = %network_id,
+            linked,
+            unmatched,
+            "Recorded Proxmox guests"
+        );
+
+        Ok(())
+    }
+
+    /// Link libvirt domains to the Libvirt service of the host they're defined on. Domains
+    /// are matched to hosts by MAC address; domains without a discovered host are skipped.
+    pub async fn record_libvirt_domains(
+        &self,
+        network_id: &Uuid,
+        service_id: &Uuid,
+        domains: Vec<DiscoveredLibvirtDomain>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<()> {
+        let libvirt_service = self
+            .service_service
+            .get_by_id(service_id)
+            .await?
+            .filter(|s| s.base.network_id == *network_id)
+            .ok_or_else(|| anyhow!("Service '{}' not found", service_id))?;
+
+        if libvirt_service.base.service_definition.id() != Libvirt.id() {
+            return Err(ValidationError::new(format!(
+                "Service '{}' is not a Libvirt service",
+                service_id
+            ))
+            .into());
+        }
+
+        let hosts = self
+            .get_all(StorableFilter::<Host>::new().network_ids(&[*network_id]))
+            .await?;
+        let host_ids: Vec<Uuid> = hosts.iter().map(|h| h.id).collect();
+        let interfaces_by_host = self.interface_service.get_for_hosts(&host_ids).await?;
+
+        let mut linked = 0;
+        let mut unmatched = 0;
+
+        for domain in domains {
+            let Some(guest_host) = hosts.iter().find(|h| {
+                h.id != libvirt_service.base.host_id
+                    && interfaces_by_host.get(&h.id).is_some_and(|interfaces| {
+                        interfaces.iter().any(|i| {
+                            i.base
+                                .mac_address
+                                .is_some_and(|mac| domain.mac_addresses.contains(&mac))
+                        })
+                    })
+            }) else {
+                unmatched += 1;
+                continue;
+            };
+
+            let virtualization = Some(HostVirtualization::Libvirt(LibvirtVirtualization {
+                vm_name: Some(domain.name.clone()),
+                vm_id: Some(domain.uuid.to_string()),
+                service_id: libvirt_service.id,
+            }));
+
+            linked += 1;
+            if guest_host.base.virtualization != virtualization {
+                let mut guest_host = guest_host.clone();
+                guest_host.base.virtualization = virtualization;
+                self.update(&mut guest_host, authentication.clone()).await?;
+            }
+        }
+
+        tracing::info!(
+            network_id = %network_id,
+            service_id = %service_id,
+            linked,
+            unmatched,
+            "Recorded libvirt domains"
+        );
+
+        Ok(())
+    }
+
+    /// Name interfaces, hosts and services after the records of a DNS zone. Each record
+    /// is matched to the interface with its address, which keeps every name in the zone
+    /// that points at it. A host without a hostname takes the first A/AAAA name of its
+    /// interfaces, and a service still named after its definition takes the name that
+    /// refers to it (ie "grafana.example.lan" for Grafana). Records whose address has no
+    /// discovered host are returned as dangling.
+    pub async fn record_dns_names(
+        &self,
+        network_id: &Uuid,
+        zone: &str,
+        records: Vec<DnsRecord>,
+        authentication: AuthenticatedEntity,
+    ) -> Result<DnsZoneImportResponse> {
+        let hosts = self
+            .get_all(StorableFilter::<Host>::new().network_ids(&[*network_id]))
+            .await?;
+        let host_ids: Vec<Uuid> = hosts.iter().map(|h| h.id).collect();
+        let interfaces_by_host = self.interface_service.get_for_hosts(&host_ids).await?;
+        let services = self
+            .service_service
+            .get_all(StorableFilter::<Service>::new().network_ids(&[*network_id]))
+            .await?;
+
+        let mut interfaces_by_ip: HashMap<IpAddr, Vec<&Interface>> = HashMap::new();
+        for interface in interfaces_by_host.values().flatten() {
+            interfaces_by_ip
+                .entry(interface.base.ip_address)
+                .or_default()
+                .push(interface);
+        }
+
+        let mut response = DnsZoneImportResponse::default();
+        let mut records_by_interface: HashMap<Uuid, Vec<&DnsRecord>> = HashMap::new();
+        let mut ambiguous = 0;
+
+        for record in &records {
+            match interfaces_by_ip.get(&record.ip).map(Vec::as_slice) {
+                // Addresses like Docker bridge IPs repeat across hosts, there's no telling
+                // which one the record names
+                Some([first, rest @ ..])
+                    if rest.iter().any(|i| i.base.host_id != first.base.host_id) =>
+                {
+                    ambiguous += 1;
+                }
+                Some([interface, ..]) => {
+                    response.matched += 1;
+                    records_by_interface
+                        .entry(interface.id)
+                        .or_default()
+                        .push(record);
+                }
+                Some([]) | None => response.dangling.push(record.clone()),
+            }
+        }
+
+        for host in &hosts {
+            let Some(interfaces) = interfaces_by_host.get(&host.id) else {
+                continue;
+            };
+
+            let mut host_records: Vec<(Uuid, &DnsRecord)> = Vec::new();
+
+            for interface in interfaces {
+                let interface_records = records_by_interface
+                    .get(&interface.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                // Names from other zones stay, names from an earlier import of this zone
+                // are replaced
+                let mut dns_names: Vec<String> = interface
+                    .base
+                    .dns_names
+                    .iter()
+                    .filter(|name| !in_zone(name, zone))
+                    .cloned()
+                    .chain(interface_records.iter().map(|r| r.name.clone()))
+                    .collect();
+                dns_names.sort();
+                dns_names.dedup();
+
+                if dns_names != interface.base.dns_names {
+                    let mut interface = interface.clone();
+                    interface.base.dns_names = dns_names;
+                    self.interface_service
+                        .update(&mut interface, authentication.clone())
+                        .await?;
+                }
+
+                host_records.extend(interface_records.iter().map(|r| (interface.id, *r)));
+            }
+
+            if host.base.hostname.is_none()
+                && let Some((_, record)) = host_records.iter().find(|(_, r)| !r.alias)
+            {
+                let mut host = host.clone();
+                if interfaces
+                    .iter()
+                    .any(|i| host.base.name == i.base.ip_address.to_string())
+                {
+                    host.base.name = record.name.clone();
+                }
+                host.base.hostname = Some(record.name.clone());
+                self.update(&mut host, authentication.clone()).await?;
+            }
+
+            for service in services.iter().filter(|s| s.base.host_id == host.id) {
+                let definition_name = ServiceDefinition::name(&service.base.service_definition);
+                if service.base.name != definition_name {
+                    continue;
+                }
+
+                let Some((_, record)) = host_records.iter().find(|(interface_id, record)| {
+                    names_service(&record.name, definition_name)
+                        && service
+                            .base
+                            .bindings
+                            .iter()
+                            .any(|b| b.interface_id().is_none_or(|id| id == *interface_id))
+                }) else {
+                    continue;
+                };
+
+                let mut service = service.clone();
+                service.base.name = record.name.clone();
+                self.service_service
+                    .update(&mut service, authentication.clone())
+                    .await?;
+            }
+        }
+
+        tracing::info!(
+            network_id = %network_id,
+            zone = %zone,
+            matched = response.matched,
+            ambiguous,
+            dangling = response.dangling.len(),
+            "Recorded DNS zone names"
+        );
+
+        Ok(response)
+    }
+
+    /// Build request path groups from the routes of a reverse proxy: the proxy's
+    /// listening port binding followed by the bindings of the services a route's
+    /// upstreams resolve to. Upstreams that aren't a discovered service are skipped.
+    pub async fn proxy_route_groups(
+        &self,
+        network_id: &Uuid,
+        request: DiscoveryProxyRoutesRequest,
+    ) -> Result<Vec<Group>> {
+        let proxy_service = self
+            .service_service
+            .get_by_id(&request.service_id)
+            .await?
+            .filter(|s| s.base.network_id == *network_id)
+            .ok_or_else(|| anyhow!("Service '{}' not found", request.service_id))?;
+
+        if ServiceDefinition::category(&proxy_service.base.service_definition)
+            != ServiceCategory::ReverseProxy
+        {
+            return Err(ValidationError::new(format!(
+                "Service '{}' is not a reverse proxy",
+                request.service_id
+            ))
+            .into());
+        }
+
+        let hosts = self
+            .get_all_host_responses(StorableFilter::<Host>::new().network_ids(&[*network_id]))
+            .await?;
+
+        // Requests enter through the proxy's port, prefer HTTPS then HTTP over admin ports
+        let proxy_ports: Vec<&Port> = hosts
+            .iter()
+            .filter(|h| h.id == proxy_service.base.host_id)
+            .flat_map(|h| h.ports.iter())
+            .collect();
+        let Some(entry_binding) = proxy_service
+            .base
+            .bindings
+            .iter()
+            .filter_map(|b| {
+                let port = proxy_ports.iter().find(|p| b.port_id() == Some(p.id))?;
+                Some((b, port.base.port_type.number()))
+            })
+            .min_by_key(|(_, number)| match number {
+                443 => 0,
+                80 => 1,
+                _ => 2,
+            })
+            .map(|(b, _)| b)
+        else {
+            return Ok(Vec::new());
+        };
+
+        // Docker bridge addresses repeat across Docker hosts, prefer upstreams on the proxy's own subnets
+        let proxy_subnet_ids: Vec<Uuid> = hosts
+            .iter()
+            .filter(|h| h.id == proxy_service.base.host_id)
+            .flat_map(|h| h.interfaces.iter().map(|i| i.base.subnet_id))
+            .collect();
+
+        // Load-balanced routes are reported once per upstream, they make up one group
+        let mut routes_by_rule: Vec<(&str, Vec<&DiscoveredProxyRoute>)> = Vec::new();
+        for route in &request.routes {
+            match routes_by_rule
+                .iter_mut()
+                .find(|(rule, _)| *rule == route.rule)
+            {
+                Some((_, routes)) => routes.push(route),
+                None => routes_by_rule.push((&route.rule, vec![route])),
+            }
+        }
+
+        let mut groups = Vec::new();
+        let mut unmatched = 0;
+
+        for (rule, routes) in routes_by_rule {
+            let mut binding_ids = vec![entry_binding.id];
+            let mut upstreams = Vec::new();
+
+            for route in routes {
+                let Some(backend_binding) = route.backend_binding(&hosts, &proxy_subnet_ids) else {
+                    unmatched += 1;
+                    continue;
+                };
+
+                if !binding_ids.contains(&backend_binding) {
+                    binding_ids.push(backend_binding);
+                    upstreams.push(format!("{}:{}", route.upstream_host, route.upstream_port));
+                }
+            }
+
+            if upstreams.is_empty() {
+                continue;
+            }
+
+            groups.push(Group {
+                base: GroupBase {
+                    name: format!("{} {}", proxy_service.base.name, rule),
+                    network_id: *network_id,
+                    description: Some(format!(
+                        "{} routing {} to {}",
+                        proxy_service.base.name,
+                        rule,
+                        upstreams.join(", ")
+                    )),
+                    group_type: GroupType::RequestPath,
+                    binding_ids,
+                    source: request.source.clone(),
+                    color: GroupTypeDiscriminants::RequestPath.color(),
+                    edge_style: Default::default(),
+                    tags: Vec::new(),
+                },
+                ..Default::default()
+            });
+        }
+
+        tracing::info!(
+            network_id = %network_id,
+            service_id = %request.service_id,
+            routes = request.routes.len(),
+            unmatched,
+            "Resolved reverse proxy routes"
+        );
+
+        Ok(groups)
+    }
+
     /// Find an existing host that matches based on interface data (MAC address or subnet+IP).
     pub async fn find_matching_host_by_interfaces(
         &self,
@@ -1200,6 +1850,15 @@ impl HostService {
             existing_host.base.hostname = new_host_data.base.hostname;
         }
 
+        // Latest neighbor table wins. An empty list is unknown rather than unplugged, runs
+        // that read the table clear stale links via record_discovered_physical_links.
+        if !new_host_data.base.physical_links.is_empty()
+            && existing_host.base.physical_links != new_host_data.base.physical_links
+        {
+            has_updates = true;
+            existing_host.base.physical_links = new_host_data.base.physical_links;
+        }
+
         // Merge entity source metadata
         existing_host.base.source = match (existing_host.base.source, new_host_data.base.source) {
             (
@@ -1369,6 +2028,18 @@ impl HostService {
                 self.port_service
                     .update(&mut transferred, authentication.clone())
                     .await?;
+
+                // Certificates follow their port
+                for mut certificate in self
+                    .certificate_service
+                    .get_for_port(&other_port.id)
+                    .await?
+                {
+                    certificate.base.host_id = destination_host.id;
+                    self.certificate_service
+                        .update(&mut certificate, authentication.clone())
+                        .await?;
+                }
                 tracing::debug!(
                     port_id = %other_port.id,
                     port = %other_config.number,
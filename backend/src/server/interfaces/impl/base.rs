@@ -29,6 +29,10 @@ pub struct InterfaceBase {
     /// Position of this interface in the host's interface list (for ordering)
     #[serde(default)]
     pub position: i32,
+    /// Names from imported DNS zones (A, AAAA and CNAME records) pointing at this address
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub dns_names: Vec<String>,
 }
 
 impl Default for InterfaceBase {
@@ -41,6 +45,7 @@ impl Default for InterfaceBase {
             mac_address: None,
             name: None,
             position: 0,
+            dns_names: Vec::new(),
         }
     }
 }
@@ -59,6 +64,7 @@ impl InterfaceBase {
             mac_address: None,
             name: Some(subnet.base.name.clone()),
             position: 0,
+            dns_names: Vec::new(),
         }
     }
 }
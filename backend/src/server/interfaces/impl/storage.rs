@@ -70,6 +70,7 @@ impl Storable for Interface {
                     mac_address,
                     name,
                     position,
+                    dns_names,
                 },
         } = self.clone();
 
@@ -83,6 +84,7 @@ impl Storable for Interface {
                 "mac_address",
                 "name",
                 "position",
+                "dns_names",
                 "created_at",
                 "updated_at",
             ],
@@ -95,6 +97,7 @@ impl Storable for Interface {
                 SqlValue::OptionalMacAddress(mac_address),
                 SqlValue::OptionalString(name),
                 SqlValue::I32(position),
+                SqlValue::StringArray(dns_names),
                 SqlValue::Timestamp(created_at),
                 SqlValue::Timestamp(updated_at),
             ],
@@ -125,6 +128,7 @@ impl Storable for Interface {
                 mac_address,
                 name: row.get("name"),
                 position: row.get("position"),
+                dns_names: row.get("dns_names"),
             },
         })
     }
@@ -161,6 +165,8 @@ impl Entity for Interface {
 
     fn preserve_immutable_fields(&mut self, existing: &Self) {
         self.created_at = existing.created_at;
+        // Only set by DNS zone imports
+        self.base.dns_names = existing.base.dns_names.clone();
     }
 }
 
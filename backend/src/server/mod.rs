@@ -1,6 +1,7 @@
 pub mod auth;
 pub mod billing;
 pub mod bindings;
+pub mod certificates;
 pub mod config;
 pub mod daemon_api_keys;
 pub mod daemons;
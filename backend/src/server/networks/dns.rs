@@ -0,0 +1,135 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::server::interfaces::r#impl::base::Interface;
+use crate::server::services::r#impl::{
+    base::Service, categories::ServiceCategory, definitions::ServiceDefinition,
+};
+
+/// A DNS server discovery sends reverse (PTR) lookups to instead of the daemon's
+/// own resolver, for daemons whose resolv.conf doesn't know the network's names
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq, Eq, Hash, ToSchema)]
+pub struct DnsResolver {
+    #[serde(default = "Uuid::new_v4")]
+    #[schema(required)]
+    pub id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[schema(value_type = String)]
+    pub ip: IpAddr,
+    #[serde(default = "default_dns_port")]
+    #[schema(required)]
+    pub port: u16,
+    /// Subnets whose addresses are looked up through this resolver. Empty means every
+    /// subnet in the network.
+    #[serde(default)]
+    #[schema(required)]
+    pub subnet_ids: Vec<Uuid>,
+}
+
+fn default_dns_port() -> u16 {
+    53
+}
+
+impl DnsResolver {
+    pub fn applies_to_subnet(&self, subnet_id: &Uuid) -> bool {
+        self.subnet_ids.is_empty() || self.subnet_ids.contains(subnet_id)
+    }
+
+    /// Resolvers to use for a subnet, in order. Resolvers configured for the subnet
+    /// itself come before the network-wide ones.
+    pub fn for_subnet<'a>(resolvers: &'a [DnsResolver], subnet_id: &Uuid) -> Vec<&'a DnsResolver> {
+        let (specific, network_wide): (Vec<_>, Vec<_>) = resolvers
+            .iter()
+            .filter(|r| r.applies_to_subnet(subnet_id))
+            .partition(|r| !r.subnet_ids.is_empty());
+
+        specific.into_iter().chain(network_wide).collect()
+    }
+}
+
+/// DNS servers discovery has found on the network that aren't configured as resolvers yet
+pub fn resolver_candidates(
+    services: &[Service],
+    interfaces: &[Interface],
+    configured: &[DnsResolver],
+) -> Vec<DnsResolver> {
+    let mut candidates: Vec<DnsResolver> = Vec::new();
+
+    for service in services {
+        if !matches!(
+            ServiceDefinition::category(&service.base.service_definition),
+            ServiceCategory::DNS | ServiceCategory::AdBlock
+        ) {
+            continue;
+        }
+
+        // A binding without an interface listens on all of the host's interfaces
+        let addresses = interfaces.iter().filter(|i| {
+            i.base.host_id == service.base.host_id
+                && service
+                    .base
+                    .bindings
+                    .iter()
+                    .any(|b| b.interface_id().is_none_or(|id| id == i.id))
+        });
+
+        for interface in addresses {
+            let ip = interface.base.ip_address;
+            if configured.iter().chain(&candidates).any(|r| r.ip == ip) {
+                continue;
+            }
+
+            candidates.push(DnsResolver {
+                id: Uuid::new_v4(),
+                name: service.base.name.clone(),
+                ip,
+                port: default_dns_port(),
+                subnet_ids: Vec::new(),
+            });
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver(ip: &str, subnet_ids: Vec<Uuid>) -> DnsResolver {
+        DnsResolver {
+            id: Uuid::new_v4(),
+            name: ip.to_string(),
+            ip: ip.parse().unwrap(),
+            port: 53,
+            subnet_ids,
+        }
+    }
+
+    #[test]
+    fn test_subnet_resolvers_come_first() {
+        let office = Uuid::new_v4();
+        let lab = Uuid::new_v4();
+        let resolvers = vec![
+            resolver("10.0.0.53", Vec::new()),
+            resolver("10.1.0.53", vec![office]),
+            resolver("10.2.0.53", vec![lab]),
+        ];
+
+        let office_resolvers: Vec<String> = DnsResolver::for_subnet(&resolvers, &office)
+            .iter()
+            .map(|r| r.ip.to_string())
+            .collect();
+
+        assert_eq!(office_resolvers, vec!["10.1.0.53", "10.0.0.53"]);
+        assert_eq!(
+            DnsResolver::for_subnet(&resolvers, &Uuid::new_v4()).len(),
+            1
+        );
+    }
+}
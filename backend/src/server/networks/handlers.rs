@@ -5,9 +5,16 @@ use crate::server::shared::handlers::traits::{
 use crate::server::{
     auth::middleware::{
         features::{CreateNetworkFeature, RequireFeature},
-        permissions::{Admin, Authorized, Member},
+        permissions::{Admin, Authorized, Member, Viewer},
+    },
+    interfaces::r#impl::base::Interface,
+    networks::dns::{DnsResolver, resolver_candidates},
+    services::r#impl::base::Service,
+    shared::{
+        services::traits::CrudService,
+        storage::filter::StorableFilter,
+        types::api::{ApiError, ApiErrorResponse, EmptyApiResponse},
     },
-    shared::types::api::{ApiErrorResponse, EmptyApiResponse},
 };
 use crate::server::{
     config::AppState,
@@ -36,6 +43,7 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
             delete_network
         ))
         .routes(routes!(bulk_delete_networks))
+        .routes(routes!(get_dns_resolver_candidates))
 }
 
 /// Create a new network
@@ -135,3 +143,52 @@ async fn bulk_delete_networks(
 ) -> ApiResult<Json<ApiResponse<BulkDeleteResponse>>> {
     bulk_delete_handler::<Network>(state, auth.into_permission::<Member>(), json).await
 }
+
+/// List DNS resolver candidates
+///
+/// DNS servers discovery found on the network (DNS Server, Unbound, Pi-Hole, ...)
+/// that aren't configured as resolvers yet, one per address they listen on.
+#[utoipa::path(
+    get,
+    path = "/{id}/dns-resolver-candidates",
+    tag = "networks",
+    params(("id" = Uuid, Path, description = "Network ID")),
+    responses(
+        (status = 200, description = "Candidate resolvers", body = ApiResponse<Vec<DnsResolver>>),
+        (status = 404, description = "Network not found", body = ApiErrorResponse),
+    ),
+     security(("user_api_key" = []), ("session" = []))
+)]
+async fn get_dns_resolver_candidates(
+    State(state): State<Arc<AppState>>,
+    auth: Authorized<Viewer>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ApiResponse<Vec<DnsResolver>>>> {
+    if !auth.network_ids().contains(&id) {
+        return Err(ApiError::entity_access_denied::<Network>(id));
+    }
+
+    let network = state
+        .services
+        .network_service
+        .get_by_id(&id)
+        .await?
+        .ok_or_else(|| ApiError::entity_not_found::<Network>(id))?;
+
+    let services = state
+        .services
+        .service_service
+        .get_all(StorableFilter::<Service>::new().network_ids(&[id]))
+        .await?;
+    let interfaces = state
+        .services
+        .interface_service
+        .get_all(StorableFilter::<Interface>::new().network_ids(&[id]))
+        .await?;
+
+    Ok(Json(ApiResponse::success(resolver_candidates(
+        &services,
+        &interfaces,
+        &network.base.dns_resolvers,
+    ))))
+}
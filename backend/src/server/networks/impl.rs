@@ -2,7 +2,10 @@ use std::fmt::Display;
 
 use crate::server::{
     config::AppState,
-    networks::service::NetworkService,
+    networks::{
+        dns::DnsResolver, proxmox::ProxmoxCredential, scan_exclusions::ScanExclusion,
+        scan_profiles::ScanProfile, service::NetworkService, snmp::SnmpCredential,
+    },
     shared::{
         entities::{ChangeTriggersTopologyStaleness, EntityDiscriminants},
         handlers::{query::NoFilterQuery, traits::CrudHandlers},
@@ -28,6 +31,31 @@ pub struct NetworkBase {
     #[serde(default)]
     #[schema(required)]
     pub tags: Vec<Uuid>,
+    /// SNMP credential profiles, tried in order during network discovery
+    #[serde(default)]
+    #[schema(required)]
+    #[validate(nested)]
+    pub snmp_credentials: Vec<SnmpCredential>,
+    /// Proxmox VE API tokens, used by Proxmox discovery to link guests to their hypervisor
+    #[serde(default)]
+    #[schema(required)]
+    #[validate(nested)]
+    pub proxmox_credentials: Vec<ProxmoxCredential>,
+    /// DNS servers for reverse lookups during network discovery, instead of the daemon's own
+    #[serde(default)]
+    #[schema(required)]
+    #[validate(nested)]
+    pub dns_resolvers: Vec<DnsResolver>,
+    /// Port sets and timings network discoveries can scan with
+    #[serde(default)]
+    #[schema(required)]
+    #[validate(nested)]
+    pub scan_profiles: Vec<ScanProfile>,
+    /// Addresses, ranges and MACs network discovery never probes
+    #[serde(default)]
+    #[schema(required)]
+    #[validate(nested)]
+    pub scan_exclusions: Vec<ScanExclusion>,
 }
 
 impl NetworkBase {
@@ -36,6 +64,11 @@ impl NetworkBase {
             name: "My Network".to_string(),
             organization_id,
             tags: Vec::new(),
+            snmp_credentials: Vec::new(),
+            proxmox_credentials: Vec::new(),
+            dns_resolvers: Vec::new(),
+            scan_profiles: Vec::new(),
+            scan_exclusions: Vec::new(),
         }
     }
 }
@@ -72,6 +105,18 @@ impl CrudHandlers for Network {
     fn get_service(state: &AppState) -> &Self::Service {
         &state.services.network_service
     }
+
+    fn redact(&mut self) {
+        // Credential secrets only ever leave the server in discovery requests to a daemon
+        self.base
+            .snmp_credentials
+            .iter_mut()
+            .for_each(SnmpCredential::redact_secrets);
+        self.base
+            .proxmox_credentials
+            .iter_mut()
+            .for_each(ProxmoxCredential::redact_secrets);
+    }
 }
 
 impl ChangeTriggersTopologyStaleness<Network> for Network {
@@ -127,22 +172,54 @@ impl Storable for Network {
                     name,
                     organization_id,
                     tags: _, // Stored in entity_tags junction table
+                    snmp_credentials,
+                    proxmox_credentials,
+                    dns_resolvers,
+                    scan_profiles,
+                    scan_exclusions,
                 },
         } = self.clone();
 
         Ok((
-            vec!["id", "created_at", "updated_at", "name", "organization_id"],
+            vec![
+                "id",
+                "created_at",
+                "updated_at",
+                "name",
+                "organization_id",
+                "snmp_credentials",
+                "proxmox_credentials",
+                "dns_resolvers",
+                "scan_profiles",
+                "scan_exclusions",
+            ],
             vec![
                 SqlValue::Uuid(id),
                 SqlValue::Timestamp(created_at),
                 SqlValue::Timestamp(updated_at),
                 SqlValue::String(name),
                 SqlValue::Uuid(organization_id),
+                SqlValue::JsonValue(serde_json::to_value(&snmp_credentials)?),
+                SqlValue::JsonValue(serde_json::to_value(&proxmox_credentials)?),
+                SqlValue::JsonValue(serde_json::to_value(&dns_resolvers)?),
+                SqlValue::JsonValue(serde_json::to_value(&scan_profiles)?),
+                SqlValue::JsonValue(serde_json::to_value(&scan_exclusions)?),
             ],
         ))
     }
 
     fn from_row(row: &PgRow) -> Result<Self, anyhow::Error> {
+        let snmp_credentials: Vec<SnmpCredential> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("snmp_credentials"))?;
+        let proxmox_credentials: Vec<ProxmoxCredential> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("proxmox_credentials"))?;
+        let dns_resolvers: Vec<DnsResolver> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("dns_resolvers"))?;
+        let scan_profiles: Vec<ScanProfile> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("scan_profiles"))?;
+        let scan_exclusions: Vec<ScanExclusion> =
+            serde_json::from_value(row.get::<serde_json::Value, _>("scan_exclusions"))?;
+
         Ok(Network {
             id: row.get("id"),
             created_at: row.get("created_at"),
@@ -151,6 +228,11 @@ impl Storable for Network {
                 name: row.get("name"),
                 organization_id: row.get("organization_id"),
                 tags: Vec::new(), // Hydrated from entity_tags junction table
+                snmp_credentials,
+                proxmox_credentials,
+                dns_resolvers,
+                scan_profiles,
+                scan_exclusions,
             },
         })
     }
@@ -192,4 +274,14 @@ impl Entity for Network {
     fn set_tags(&mut self, tags: Vec<Uuid>) {
         self.base.tags = tags;
     }
+
+    fn preserve_immutable_fields(&mut self, existing: &Self) {
+        // Credential secrets are write-only, blank ones in an update keep the stored value
+        for credential in &mut self.base.snmp_credentials {
+            credential.keep_stored_secrets(&existing.base.snmp_credentials);
+        }
+        for credential in &mut self.base.proxmox_credentials {
+            credential.keep_stored_secrets(&existing.base.proxmox_credentials);
+        }
+    }
 }
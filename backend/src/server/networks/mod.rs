@@ -1,3 +1,8 @@
+pub mod dns;
 pub mod handlers;
 pub mod r#impl;
+pub mod proxmox;
+pub mod scan_exclusions;
+pub mod scan_profiles;
 pub mod service;
+pub mod snmp;
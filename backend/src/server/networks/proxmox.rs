@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// A Proxmox VE API token discovery uses to list the cluster's nodes and guests
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq, Eq, Hash, ToSchema)]
+pub struct ProxmoxCredential {
+    #[serde(default = "Uuid::new_v4")]
+    #[schema(required)]
+    pub id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Base URL of any node in the cluster, ie "https://pve.local:8006"
+    #[validate(url)]
+    pub url: String,
+    /// Token ID in the form "user@realm!token"
+    #[validate(custom(function = "validate_token_id"))]
+    pub token_id: String,
+    /// Write-only: returned blank, leave blank in an update to keep the stored value.
+    /// Must be entered again when the URL changes.
+    #[validate(length(min = 1))]
+    pub token_secret: String,
+    /// Proxmox ships with a self-signed certificate
+    #[serde(default)]
+    #[schema(required)]
+    pub allow_self_signed_certs: bool,
+}
+
+impl ProxmoxCredential {
+    /// Value of the Authorization header for API token requests
+    pub fn authorization_header(&self) -> String {
+        format!("PVEAPIToken={}={}", self.token_id, self.token_secret)
+    }
+
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}/api2/json{}", self.url.trim_end_matches('/'), path)
+    }
+
+    /// Blank the token secret before the credential goes back to a client
+    pub fn redact_secrets(&mut self) {
+        self.token_secret.clear();
+    }
+
+    /// Fill a secret an update left blank from the stored credential with the same ID and
+    /// URL. Pointing the credential at another server leaves it blank, so validation asks
+    /// for the secret again instead of sending the stored token somewhere new.
+    pub fn keep_stored_secrets(&mut self, stored: &[ProxmoxCredential]) {
+        if !self.token_secret.is_empty() {
+            return;
+        }
+        if let Some(existing) = stored.iter().find(|c| c.id == self.id && c.url == self.url) {
+            self.token_secret = existing.token_secret.clone();
+        }
+    }
+}
+
+fn validate_token_id(token_id: &str) -> Result<(), ValidationError> {
+    let valid = token_id
+        .split_once('!')
+        .is_some_and(|(user, token)| user.contains('@') && !token.is_empty());
+
+    if !valid {
+        let mut err = ValidationError::new("proxmox_token_id");
+        err.message = Some("Token ID must look like user@realm!token".into());
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credential(token_id: &str) -> ProxmoxCredential {
+        ProxmoxCredential {
+            id: Uuid::new_v4(),
+            name: "pve".to_string(),
+            url: "https://pve.local:8006/".to_string(),
+            token_id: token_id.to_string(),
+            token_secret: "6d3b6b1e-4c1f-4f5e-9d8a-2b7c5e1f0a9d".to_string(),
+            allow_self_signed_certs: true,
+        }
+    }
+
+    #[test]
+    fn test_token_id_validation() {
+        assert!(credential("scanopy@pve!discovery").validate().is_ok());
+        assert!(credential("scanopy@pve").validate().is_err());
+        assert!(credential("scanopy!discovery").validate().is_err());
+    }
+
+    #[test]
+    fn test_request_formatting() {
+        let credential = credential("scanopy@pve!discovery");
+
+        assert_eq!(
+            credential.authorization_header(),
+            "PVEAPIToken=scanopy@pve!discovery=6d3b6b1e-4c1f-4f5e-9d8a-2b7c5e1f0a9d"
+        );
+        assert_eq!(
+            credential.api_url("/cluster/status"),
+            "https://pve.local:8006/api2/json/cluster/status"
+        );
+    }
+
+    #[test]
+    fn test_blank_secret_kept_only_for_same_url() {
+        let stored = credential("scanopy@pve!discovery");
+
+        let mut redacted = stored.clone();
+        redacted.redact_secrets();
+        redacted.keep_stored_secrets(std::slice::from_ref(&stored));
+        assert_eq!(redacted, stored);
+
+        let mut moved = stored.clone();
+        moved.redact_secrets();
+        moved.url = "https://attacker.example:8006".to_string();
+        moved.keep_stored_secrets(std::slice::from_ref(&stored));
+        assert!(moved.token_secret.is_empty());
+        assert!(moved.validate().is_err());
+    }
+}
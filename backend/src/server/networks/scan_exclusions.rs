@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+
+use cidr::IpCidr;
+use mac_address::MacAddress;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// Addresses listed per exclusion in a session's skipped summary, the count keeps going
+const MAX_LISTED_ADDRESSES: usize = 100;
+
+/// A device or range network discovery never probes, for hosts that misbehave when
+/// scanned (old PLCs, fragile printers, honeypots)
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq, Eq, Hash, ToSchema)]
+pub struct ScanExclusion {
+    #[serde(default = "Uuid::new_v4")]
+    #[schema(required)]
+    pub id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(custom(function = "validate_exclusion_target"))]
+    pub target: ExclusionTarget,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(tag = "type")]
+pub enum ExclusionTarget {
+    Ip {
+        #[schema(value_type = String)]
+        ip: IpAddr,
+    },
+    Cidr {
+        #[schema(value_type = String)]
+        cidr: IpCidr,
+    },
+    /// Matched once ARP or the OS neighbor table has the host's MAC, so it holds when the
+    /// host's IP changes
+    Mac {
+        #[schema(value_type = String)]
+        mac: MacAddress,
+    },
+    /// Vendor prefix, the first three octets of the MAC, ie "00:80:F4"
+    MacOui { oui: String },
+}
+
+impl ScanExclusion {
+    pub fn matches_ip(&self, ip: IpAddr) -> bool {
+        match &self.target {
+            ExclusionTarget::Ip { ip: excluded } => *excluded == ip,
+            ExclusionTarget::Cidr { cidr } => cidr.contains(&ip),
+            ExclusionTarget::Mac { .. } | ExclusionTarget::MacOui { .. } => false,
+        }
+    }
+
+    /// Whether the exclusion can only be checked once the host's MAC is known
+    pub fn is_by_mac(&self) -> bool {
+        matches!(
+            self.target,
+            ExclusionTarget::Mac { .. } | ExclusionTarget::MacOui { .. }
+        )
+    }
+
+    pub fn matches_mac(&self, mac: MacAddress) -> bool {
+        match &self.target {
+            ExclusionTarget::Mac { mac: excluded } => *excluded == mac,
+            ExclusionTarget::MacOui { oui } => {
+                parse_oui(oui).is_some_and(|oui| mac.bytes()[..3] == oui)
+            }
+            ExclusionTarget::Ip { .. } | ExclusionTarget::Cidr { .. } => false,
+        }
+    }
+
+    /// First exclusion covering the address, or the MAC when it's known
+    pub fn find(
+        exclusions: &[ScanExclusion],
+        ip: IpAddr,
+        mac: Option<MacAddress>,
+    ) -> Option<&ScanExclusion> {
+        exclusions
+            .iter()
+            .find(|e| e.matches_ip(ip) || mac.is_some_and(|mac| e.matches_mac(mac)))
+    }
+}
+
+/// Addresses a discovery session skipped because of one exclusion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct SkippedAddresses {
+    pub exclusion_id: Uuid,
+    pub count: usize,
+    /// The first addresses skipped, a CIDR exclusion can cover far more than are listed
+    #[schema(value_type = Vec<String>)]
+    pub addresses: Vec<IpAddr>,
+}
+
+/// A session's skipped addresses. Discovery can run into the same address more than
+/// once (sweeps, mDNS and SSDP responders, deep scans), each counts once per exclusion.
+#[derive(Debug, Default)]
+pub struct SkippedLog {
+    summaries: Vec<SkippedAddresses>,
+    seen: HashSet<(Uuid, IpAddr)>,
+}
+
+impl SkippedLog {
+    pub fn record(&mut self, exclusion_id: Uuid, ip: IpAddr) {
+        if !self.seen.insert((exclusion_id, ip)) {
+            return;
+        }
+
+        let index = match self
+            .summaries
+            .iter()
+            .position(|s| s.exclusion_id == exclusion_id)
+        {
+            Some(index) => index,
+            None => {
+                self.summaries.push(SkippedAddresses {
+                    exclusion_id,
+                    count: 0,
+                    addresses: Vec::new(),
+                });
+                self.summaries.len() - 1
+            }
+        };
+
+        let entry = &mut self.summaries[index];
+        entry.count += 1;
+        if entry.addresses.len() < MAX_LISTED_ADDRESSES {
+            entry.addresses.push(ip);
+        }
+    }
+
+    pub fn summaries(&self) -> &[SkippedAddresses] {
+        &self.summaries
+    }
+
+    /// Addresses skipped across every exclusion
+    pub fn total(&self) -> usize {
+        self.seen.len()
+    }
+}
+
+/// Parse an OUI like "00:80:F4", "00-80-f4" or "0080F4"
+fn parse_oui(oui: &str) -> Option<[u8; 3]> {
+    let hex: String = oui
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.'))
+        .collect();
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let mut bytes = [0u8; 3];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn validate_exclusion_target(target: &ExclusionTarget) -> Result<(), ValidationError> {
+    let ExclusionTarget::MacOui { oui } = target else {
+        return Ok(());
+    };
+
+    if parse_oui(oui).is_some() {
+        return Ok(());
+    }
+
+    let mut err = ValidationError::new("mac_oui");
+    err.message = Some(format!("'{}' is not a MAC vendor prefix like 00:80:F4", oui).into());
+    Err(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusion(target: ExclusionTarget) -> ScanExclusion {
+        ScanExclusion {
+            id: Uuid::new_v4(),
+            name: "fragile".to_string(),
+            target,
+        }
+    }
+
+    #[test]
+    fn test_exclusion_matching() {
+        let plc: MacAddress = "00:80:F4:12:34:56".parse().unwrap();
+        let printer: MacAddress = "3C:2A:F4:00:00:01".parse().unwrap();
+        let exclusions = vec![
+            exclusion(ExclusionTarget::Cidr {
+                cidr: "10.0.5.0/24".parse().unwrap(),
+            }),
+            exclusion(ExclusionTarget::Ip {
+                ip: "10.0.0.9".parse().unwrap(),
+            }),
+            exclusion(ExclusionTarget::MacOui {
+                oui: "00-80-f4".to_string(),
+            }),
+        ];
+
+        let find = |ip: &str, mac| ScanExclusion::find(&exclusions, ip.parse().unwrap(), mac);
+        assert_eq!(find("10.0.5.77", None).unwrap().id, exclusions[0].id);
+        assert_eq!(find("10.0.0.9", None).unwrap().id, exclusions[1].id);
+        // The PLC is caught by its vendor prefix at whatever address it has
+        assert_eq!(find("10.0.0.50", Some(plc)).unwrap().id, exclusions[2].id);
+        assert!(find("10.0.0.50", Some(printer)).is_none());
+        assert!(find("10.0.0.50", None).is_none());
+    }
+
+    #[test]
+    fn test_oui_validation() {
+        let valid = |oui: &str| {
+            exclusion(ExclusionTarget::MacOui {
+                oui: oui.to_string(),
+            })
+            .validate()
+            .is_ok()
+        };
+
+        assert!(valid("00:80:F4"));
+        assert!(valid("0080f4"));
+        assert!(!valid("00:80"));
+        assert!(!valid("GG:80:F4"));
+    }
+
+    #[test]
+    fn test_skipped_addresses() {
+        let id = Uuid::new_v4();
+        let mut skipped = SkippedLog::default();
+
+        for i in 0..=MAX_LISTED_ADDRESSES {
+            skipped.record(id, IpAddr::from([10, 0, 0, i as u8]));
+        }
+        // Seen again, both listed and past the listed ones
+        skipped.record(id, "10.0.0.0".parse().unwrap());
+        skipped.record(id, IpAddr::from([10, 0, 0, MAX_LISTED_ADDRESSES as u8]));
+
+        let summaries = skipped.summaries();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].count, MAX_LISTED_ADDRESSES + 1);
+        assert_eq!(summaries[0].addresses.len(), MAX_LISTED_ADDRESSES);
+        assert_eq!(skipped.total(), MAX_LISTED_ADDRESSES + 1);
+    }
+}
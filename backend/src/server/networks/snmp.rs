@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// A set of SNMP credentials discovery tries against hosts answering on UDP 161.
+/// Profiles are tried in the order they're configured on the network.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq, Eq, Hash, ToSchema)]
+pub struct SnmpCredential {
+    /// Stable ID, recorded on the ports this profile answered for
+    #[serde(default = "Uuid::new_v4")]
+    #[schema(required)]
+    pub id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Subnets this profile is tried on. Empty means every subnet in the network.
+    #[serde(default)]
+    #[schema(required)]
+    pub subnet_ids: Vec<Uuid>,
+    #[serde(flatten)]
+    #[validate(custom(function = "validate_snmp_version"))]
+    pub version: SnmpVersion,
+}
+
+impl SnmpCredential {
+    /// What discovery falls back to on networks without any profiles
+    pub fn default_public() -> Self {
+        Self {
+            id: Uuid::nil(),
+            name: "public".to_string(),
+            subnet_ids: Vec::new(),
+            version: SnmpVersion::V2c {
+                community: "public".to_string(),
+            },
+        }
+    }
+
+    pub fn applies_to_subnet(&self, subnet_id: &Uuid) -> bool {
+        self.subnet_ids.is_empty() || self.subnet_ids.contains(subnet_id)
+    }
+
+    /// Blank the community and passwords before the profile goes back to a client
+    pub fn redact_secrets(&mut self) {
+        match &mut self.version {
+            SnmpVersion::V1 { community } | SnmpVersion::V2c { community } => community.clear(),
+            SnmpVersion::V3 {
+                auth_password,
+                privacy_password,
+                ..
+            } => {
+                auth_password.clear();
+                privacy_password.clear();
+            }
+        }
+    }
+
+    /// Fill secrets an update left blank from the stored profile with the same ID and version
+    pub fn keep_stored_secrets(&mut self, stored: &[SnmpCredential]) {
+        let Some(existing) = stored.iter().find(|p| p.id == self.id) else {
+            return;
+        };
+
+        match (&mut self.version, &existing.version) {
+            (SnmpVersion::V1 { community }, SnmpVersion::V1 { community: stored })
+            | (SnmpVersion::V2c { community }, SnmpVersion::V2c { community: stored }) => {
+                keep_if_blank(community, stored)
+            }
+            (
+                SnmpVersion::V3 {
+                    auth_password,
+                    privacy_password,
+                    ..
+                },
+                SnmpVersion::V3 {
+                    auth_password: stored_auth,
+                    privacy_password: stored_privacy,
+                    ..
+                },
+            ) => {
+                keep_if_blank(auth_password, stored_auth);
+                keep_if_blank(privacy_password, stored_privacy);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn keep_if_blank(secret: &mut String, stored: &str) {
+    if secret.is_empty() {
+        *secret = stored.to_string();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Display, ToSchema)]
+#[serde(tag = "version")]
+pub enum SnmpVersion {
+    V1 {
+        /// Write-only: returned blank, leave blank in an update to keep the stored value
+        community: String,
+    },
+    V2c {
+        /// Write-only: returned blank, leave blank in an update to keep the stored value
+        community: String,
+    },
+    /// Needs a daemon built with the `snmp-v3` feature, as the released binaries and images are.
+    /// Daemons built without it skip these profiles.
+    V3 {
+        username: String,
+        #[serde(default)]
+        security_level: SnmpSecurityLevel,
+        #[serde(default)]
+        auth_protocol: SnmpAuthProtocol,
+        /// Required for AuthNoPriv and AuthPriv. Write-only, like the community.
+        #[serde(default)]
+        auth_password: String,
+        #[serde(default)]
+        privacy_protocol: SnmpPrivacyProtocol,
+        /// Required for AuthPriv. Write-only, like the community.
+        #[serde(default)]
+        privacy_password: String,
+    },
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Display, ToSchema,
+)]
+pub enum SnmpSecurityLevel {
+    NoAuthNoPriv,
+    AuthNoPriv,
+    #[default]
+    AuthPriv,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Display, ToSchema,
+)]
+pub enum SnmpAuthProtocol {
+    Md5,
+    Sha1,
+    Sha224,
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Display, ToSchema,
+)]
+pub enum SnmpPrivacyProtocol {
+    Des,
+    #[default]
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+fn validate_snmp_version(version: &SnmpVersion) -> Result<(), ValidationError> {
+    match version {
+        SnmpVersion::V1 { community } | SnmpVersion::V2c { community } => {
+            if community.is_empty() {
+                return Err(snmp_error("snmp_community", "Community is required"));
+            }
+        }
+        SnmpVersion::V3 {
+            username,
+            security_level,
+            auth_password,
+            privacy_password,
+            ..
+        } => {
+            if username.is_empty() {
+                return Err(snmp_error("snmp_username", "Username is required"));
+            }
+            // USM keys are derived from the passwords, which must be at least 8 characters
+            let needs_auth = *security_level != SnmpSecurityLevel::NoAuthNoPriv;
+            let needs_privacy = *security_level == SnmpSecurityLevel::AuthPriv;
+            if needs_auth && auth_password.len() < 8 {
+                return Err(snmp_error(
+                    "snmp_auth_password",
+                    "Auth password must be at least 8 characters",
+                ));
+            }
+            if needs_privacy && privacy_password.len() < 8 {
+                return Err(snmp_error(
+                    "snmp_privacy_password",
+                    "Privacy password must be at least 8 characters",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn snmp_error(code: &'static str, message: &'static str) -> ValidationError {
+    let mut err = ValidationError::new(code);
+    err.message = Some(message.into());
+    err
+}
+
+/// SNMP credentials sent to a daemon with a network discovery request
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct SnmpCredentialSet {
+    #[serde(default)]
+    pub profiles: Vec<SnmpCredential>,
+    /// Profile that last answered at each address, tried before the others
+    #[serde(default)]
+    #[schema(value_type = BTreeMap<String, Uuid>)]
+    pub known: BTreeMap<IpAddr, Uuid>,
+}
+
+impl SnmpCredentialSet {
+    /// Profiles to try against a host, the one that answered last time first.
+    /// Networks without any profiles keep probing v2c "public".
+    pub fn candidates(&self, ip: IpAddr, subnet_id: &Uuid) -> Vec<SnmpCredential> {
+        if self.profiles.is_empty() {
+            return vec![SnmpCredential::default_public()];
+        }
+
+        let known_id = self.known.get(&ip);
+
+        let mut candidates: Vec<SnmpCredential> = self
+            .profiles
+            .iter()
+            .filter(|p| p.applies_to_subnet(subnet_id))
+            .cloned()
+            .collect();
+
+        if let Some(position) = candidates.iter().position(|p| Some(&p.id) == known_id) {
+            let known = candidates.remove(position);
+            candidates.insert(0, known);
+        }
+
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn community(name: &str, subnet_ids: Vec<Uuid>) -> SnmpCredential {
+        SnmpCredential {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            subnet_ids,
+            version: SnmpVersion::V2c {
+                community: name.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_candidates_order_and_subnet_scope() {
+        let subnet_id = Uuid::new_v4();
+        let other_subnet_id = Uuid::new_v4();
+        let ip: IpAddr = "192.168.1.10".parse().unwrap();
+
+        let everywhere = community("everywhere", Vec::new());
+        let scoped = community("scoped", vec![subnet_id]);
+        let elsewhere = community("elsewhere", vec![other_subnet_id]);
+
+        let mut set = SnmpCredentialSet {
+            profiles: vec![everywhere.clone(), scoped.clone(), elsewhere],
+            known: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            set.candidates(ip, &subnet_id),
+            vec![everywhere.clone(), scoped.clone()]
+        );
+
+        set.known.insert(ip, scoped.id);
+        assert_eq!(set.candidates(ip, &subnet_id), vec![scoped, everywhere]);
+    }
+
+    #[test]
+    fn test_candidates_default_to_public() {
+        let set = SnmpCredentialSet::default();
+        let candidates = set.candidates("10.0.0.1".parse().unwrap(), &Uuid::new_v4());
+
+        assert_eq!(candidates, vec![SnmpCredential::default_public()]);
+    }
+
+    #[test]
+    fn test_v3_validation() {
+        let mut credential = SnmpCredential {
+            id: Uuid::new_v4(),
+            name: "switches".to_string(),
+            subnet_ids: Vec::new(),
+            version: SnmpVersion::V3 {
+                username: "scanopy".to_string(),
+                security_level: SnmpSecurityLevel::AuthPriv,
+                auth_protocol: SnmpAuthProtocol::Sha256,
+                auth_password: "authpassword".to_string(),
+                privacy_protocol: SnmpPrivacyProtocol::Aes128,
+                privacy_password: "short".to_string(),
+            },
+        };
+        assert!(credential.validate().is_err());
+
+        if let SnmpVersion::V3 {
+            privacy_password, ..
+        } = &mut credential.version
+        {
+            *privacy_password = "privpassword".to_string();
+        }
+        assert!(credential.validate().is_ok());
+    }
+
+    #[test]
+    fn test_blank_secrets_keep_stored_value() {
+        let stored = community("stored", Vec::new());
+
+        let mut redacted = stored.clone();
+        redacted.redact_secrets();
+        assert_eq!(
+            redacted.version,
+            SnmpVersion::V2c {
+                community: String::new()
+            }
+        );
+
+        redacted.keep_stored_secrets(std::slice::from_ref(&stored));
+        assert_eq!(redacted, stored);
+
+        let mut changed = community("changed", Vec::new());
+        changed.id = stored.id;
+        changed.keep_stored_secrets(std::slice::from_ref(&stored));
+        assert_eq!(
+            changed.version,
+            SnmpVersion::V2c {
+                community: "changed".to_string()
+            }
+        );
+    }
+}
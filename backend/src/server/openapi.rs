@@ -160,6 +160,7 @@ Resources are scoped to your **organization** and **network(s)**:
         (name = "discoveries", description = "Network discovery operations. Trigger and monitor scans that detect hosts, services, and network topology."),
         (name = "github", description = "GitHub integration endpoints."),
         (name = "ports", description = "Ports that have been scanned and found open on a host"),
+        (name = "certificates", description = "TLS certificates presented on a port during discovery. Discovery replaces a port's certificate when it changes."),
         (name = "bindings", description = "
             ## Binding Types
             - **Interface binding**: Service is present at an interface (IP address) without a specific port.
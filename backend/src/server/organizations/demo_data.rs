@@ -174,6 +174,11 @@ fn generate_networks(organization_id: Uuid, tags: &[Tag], now: DateTime<Utc>) ->
                 name: "Headquarters".to_string(),
                 organization_id,
                 tags: production_tag.into_iter().collect(),
+                snmp_credentials: vec![],
+                proxmox_credentials: vec![],
+                dns_resolvers: vec![],
+                scan_profiles: vec![],
+                scan_exclusions: vec![],
             },
         },
         Network {
@@ -184,6 +189,11 @@ fn generate_networks(organization_id: Uuid, tags: &[Tag], now: DateTime<Utc>) ->
                 name: "Cloud Infrastructure".to_string(),
                 organization_id,
                 tags: production_tag.into_iter().collect(),
+                snmp_credentials: vec![],
+                proxmox_credentials: vec![],
+                dns_resolvers: vec![],
+                scan_profiles: vec![],
+                scan_exclusions: vec![],
             },
         },
         Network {
@@ -194,6 +204,11 @@ fn generate_networks(organization_id: Uuid, tags: &[Tag], now: DateTime<Utc>) ->
                 name: "Remote Office - Denver".to_string(),
                 organization_id,
                 tags: vec![],
+                snmp_credentials: vec![],
+                proxmox_credentials: vec![],
+                dns_resolvers: vec![],
+                scan_profiles: vec![],
+                scan_exclusions: vec![],
             },
         },
         Network {
@@ -204,6 +219,11 @@ fn generate_networks(organization_id: Uuid, tags: &[Tag], now: DateTime<Utc>) ->
                 name: "Client: Riverside Medical".to_string(),
                 organization_id,
                 tags: managed_client_tag.into_iter().collect(),
+                snmp_credentials: vec![],
+                proxmox_credentials: vec![],
+                dns_resolvers: vec![],
+                scan_profiles: vec![],
+                scan_exclusions: vec![],
             },
         },
     ]
@@ -443,6 +463,7 @@ fn create_host(
             mac_address: None,
             name: Some("eth0".to_string()),
             position: 0,
+            dns_names: Vec::new(),
         },
     };
     let host = Host {
@@ -458,6 +479,7 @@ fn create_host(
             virtualization: None,
             hidden: false,
             tags,
+            physical_links: Vec::new(),
         },
     };
     (host, interface)
@@ -43,6 +43,10 @@ pub struct PortBase {
     #[serde(flatten)]
     #[schema(required)]
     pub port_type: PortType,
+    /// SNMP credential profile that last answered on this port, tried first on later scans
+    #[serde(default)]
+    #[schema(read_only, required)]
+    pub snmp_credential_id: Option<Uuid>,
 }
 
 impl PortBase {
@@ -51,6 +55,7 @@ impl PortBase {
             host_id,
             network_id,
             port_type,
+            snmp_credential_id: None,
         }
     }
 
@@ -60,6 +65,7 @@ impl PortBase {
             host_id: Uuid::nil(),
             network_id: Uuid::nil(),
             port_type,
+            snmp_credential_id: None,
         }
     }
 }
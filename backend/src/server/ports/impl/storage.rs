@@ -60,6 +60,7 @@ impl Storable for Port {
                 "port_number",
                 "protocol",
                 "port_type",
+                "snmp_credential_id",
                 "created_at",
                 "updated_at",
             ],
@@ -70,6 +71,7 @@ impl Storable for Port {
                 SqlValue::I32(config.number as i32),
                 SqlValue::String(protocol.to_string()),
                 SqlValue::String(port_type),
+                SqlValue::OptionalUuid(self.base.snmp_credential_id),
                 SqlValue::Timestamp(self.created_at),
                 SqlValue::Timestamp(self.updated_at),
             ],
@@ -84,6 +86,7 @@ impl Storable for Port {
         let updated_at: DateTime<Utc> = row.get("updated_at");
         let port_number: i32 = row.get("port_number");
         let protocol: String = row.get("protocol");
+        let snmp_credential_id: Option<Uuid> = row.get("snmp_credential_id");
 
         let protocol = match protocol.as_str() {
             "Tcp" => TransportProtocol::Tcp,
@@ -114,6 +117,7 @@ impl Storable for Port {
                 host_id,
                 network_id,
                 port_type,
+                snmp_credential_id,
             },
         })
     }
@@ -147,6 +151,12 @@ impl Entity for Port {
     fn set_updated_at(&mut self, time: DateTime<Utc>) {
         self.updated_at = time;
     }
+
+    fn preserve_immutable_fields(&mut self, existing: &Self) {
+        self.created_at = existing.created_at;
+        // Discovery owns the SNMP credential, users can't edit it
+        self.base.snmp_credential_id = existing.base.snmp_credential_id;
+    }
 }
 
 impl Port {
@@ -18,10 +18,16 @@ impl ServiceDefinition for ActiveDirectory {
         ServiceCategory::IdentityAndAccess
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::AllOf(vec![
-            Pattern::Port(PortType::Ldap),
-            Pattern::Port(PortType::Samba),
-            Pattern::Port(PortType::Kerberos),
+        Pattern::AnyOf(vec![
+            Pattern::AllOf(vec![
+                Pattern::Port(PortType::Ldap),
+                Pattern::Port(PortType::Samba),
+                Pattern::Port(PortType::Kerberos),
+            ]),
+            Pattern::AllOf(vec![
+                Pattern::Port(PortType::Samba),
+                Pattern::NetbiosDomainController,
+            ]),
         ])
     }
     fn logo_url(&self) -> &'static str {
@@ -22,9 +22,15 @@ impl ServiceDefinition for AmazonEcho {
     }
 
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::AllOf(vec![
-            Pattern::MacVendor(Vendor::AMAZON),
-            Pattern::Port(PortType::new_tcp(40317)),
+        Pattern::AnyOf(vec![
+            Pattern::UpnpDevice {
+                manufacturer: "Amazon",
+                model: Some("Echo"),
+            },
+            Pattern::AllOf(vec![
+                Pattern::MacVendor(Vendor::AMAZON),
+                Pattern::Port(PortType::new_tcp(40317)),
+            ]),
         ])
     }
 
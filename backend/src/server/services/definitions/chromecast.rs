@@ -21,10 +21,13 @@ impl ServiceDefinition for ChromecastDevice {
     }
 
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::AllOf(vec![
-            Pattern::MacVendor(Vendor::GOOGLE),
-            Pattern::Port(PortType::new_tcp(8008)),
-            Pattern::Port(PortType::new_tcp(8009)),
+        Pattern::AnyOf(vec![
+            Pattern::MdnsService("_googlecast._tcp"),
+            Pattern::AllOf(vec![
+                Pattern::MacVendor(Vendor::GOOGLE),
+                Pattern::Port(PortType::new_tcp(8008)),
+                Pattern::Port(PortType::new_tcp(8009)),
+            ]),
         ])
     }
 
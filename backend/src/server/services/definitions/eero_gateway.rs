@@ -18,7 +18,16 @@ impl ServiceDefinition for EeroGateway {
     }
 
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::AllOf(vec![Pattern::MacVendor(Vendor::EERO), Pattern::IsGateway])
+        Pattern::AllOf(vec![
+            Pattern::AnyOf(vec![
+                Pattern::MacVendor(Vendor::EERO),
+                Pattern::UpnpDevice {
+                    manufacturer: "eero",
+                    model: None,
+                },
+            ]),
+            Pattern::IsGateway,
+        ])
     }
 
     fn logo_url(&self) -> &'static str {
@@ -18,8 +18,12 @@ impl ServiceDefinition for ESPHome {
         ServiceCategory::HomeAutomation
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        // ESPHome API port
-        Pattern::Port(PortType::new_tcp(6052))
+        Pattern::AnyOf(vec![
+            // Nodes advertise their native API (port 6053)
+            Pattern::MdnsService("_esphomelib._tcp"),
+            // ESPHome dashboard port
+            Pattern::Port(PortType::new_tcp(6052)),
+        ])
     }
     fn logo_url(&self) -> &'static str {
         "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/esphome.svg"
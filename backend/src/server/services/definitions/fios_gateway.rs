@@ -20,7 +20,13 @@ impl ServiceDefinition for FiosGateway {
 
     fn discovery_pattern(&self) -> Pattern<'_> {
         Pattern::AllOf(vec![
-            Pattern::Endpoint(PortType::Http, "/#/login/", "fios", None),
+            Pattern::AnyOf(vec![
+                Pattern::Endpoint(PortType::Http, "/#/login/", "fios", None),
+                Pattern::UpnpDevice {
+                    manufacturer: "Verizon",
+                    model: None,
+                },
+            ]),
             Pattern::IsGateway,
         ])
     }
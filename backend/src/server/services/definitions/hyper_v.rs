@@ -0,0 +1,31 @@
+use crate::server::ports::r#impl::base::PortType;
+use crate::server::services::definitions::{ServiceDefinitionFactory, create_service};
+use crate::server::services::r#impl::categories::ServiceCategory;
+use crate::server::services::r#impl::definitions::ServiceDefinition;
+use crate::server::services::r#impl::patterns::Pattern;
+
+#[derive(Default, Clone, Eq, PartialEq, Hash)]
+pub struct HyperV;
+
+impl ServiceDefinition for HyperV {
+    fn name(&self) -> &'static str {
+        "Hyper-V"
+    }
+    fn description(&self) -> &'static str {
+        "Windows hypervisor from Microsoft"
+    }
+    fn category(&self) -> ServiceCategory {
+        ServiceCategory::Virtualization
+    }
+
+    fn discovery_pattern(&self) -> Pattern<'_> {
+        // VM Connect, served by the Hyper-V Virtual Machine Management service
+        Pattern::Port(PortType::new_tcp(2179))
+    }
+
+    fn logo_url(&self) -> &'static str {
+        "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/microsoft-hyper-v.svg"
+    }
+}
+
+inventory::submit!(ServiceDefinitionFactory::new(create_service::<HyperV>));
@@ -0,0 +1,63 @@
+use crate::server::services::definitions::{ServiceDefinitionFactory, create_service};
+use crate::server::services::r#impl::base::DiscoverySessionServiceMatchParams;
+use crate::server::services::r#impl::categories::ServiceCategory;
+use crate::server::services::r#impl::definitions::ServiceDefinition;
+use crate::server::services::r#impl::patterns::{MatchConfidence, Pattern};
+use crate::server::services::r#impl::virtualization::ServiceVirtualization;
+
+#[derive(Default, Clone, Eq, PartialEq, Hash)]
+pub struct KubernetesPod;
+
+impl ServiceDefinition for KubernetesPod {
+    fn name(&self) -> &'static str {
+        "Kubernetes Pod"
+    }
+    fn description(&self) -> &'static str {
+        "A generic Kubernetes workload"
+    }
+    fn category(&self) -> ServiceCategory {
+        ServiceCategory::Virtualization
+    }
+
+    fn discovery_pattern(&self) -> Pattern<'_> {
+        Pattern::AllOf(vec![
+            Pattern::KubernetesPod,
+            Pattern::Custom(
+                |p: &DiscoverySessionServiceMatchParams| {
+                    // If there's a matched service for the same workload, the pod was already detected as a non-generic service
+                    let Some(ServiceVirtualization::Kubernetes(pod)) =
+                        p.baseline_params.virtualization
+                    else {
+                        return false;
+                    };
+
+                    p.service_params
+                        .matched_services
+                        .iter()
+                        .all(|s| match &s.base.virtualization {
+                            Some(ServiceVirtualization::Kubernetes(other)) => {
+                                other.identity() != pod.identity()
+                            }
+                            _ => true,
+                        })
+                },
+                |_| Vec::new(),
+                "No other services for this pod's workload have been matched",
+                "A service for this pod's workload has already been matched",
+                MatchConfidence::Low,
+            ),
+        ])
+    }
+
+    fn is_generic(&self) -> bool {
+        true
+    }
+
+    fn logo_url(&self) -> &'static str {
+        "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/kubernetes.svg"
+    }
+}
+
+inventory::submit!(ServiceDefinitionFactory::new(
+    create_service::<KubernetesPod>
+));
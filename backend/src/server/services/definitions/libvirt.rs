@@ -0,0 +1,35 @@
+use crate::server::ports::r#impl::base::PortType;
+use crate::server::services::definitions::{ServiceDefinitionFactory, create_service};
+use crate::server::services::r#impl::categories::ServiceCategory;
+use crate::server::services::r#impl::definitions::ServiceDefinition;
+use crate::server::services::r#impl::patterns::Pattern;
+
+#[derive(Default, Clone, Eq, PartialEq, Hash)]
+pub struct Libvirt;
+
+impl ServiceDefinition for Libvirt {
+    fn name(&self) -> &'static str {
+        "Libvirt"
+    }
+    fn description(&self) -> &'static str {
+        "Virtualization API and daemon for KVM/QEMU hosts"
+    }
+    fn category(&self) -> ServiceCategory {
+        ServiceCategory::Virtualization
+    }
+
+    fn discovery_pattern(&self) -> Pattern<'_> {
+        // libvirtd only listens on TCP when remote access is enabled,
+        // the daemon's self report finds it through the local socket otherwise
+        Pattern::AnyOf(vec![
+            Pattern::Port(PortType::new_tcp(16509)),
+            Pattern::Port(PortType::new_tcp(16514)),
+        ])
+    }
+
+    fn logo_url(&self) -> &'static str {
+        "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/qemu.svg"
+    }
+}
+
+inventory::submit!(ServiceDefinitionFactory::new(create_service::<Libvirt>));
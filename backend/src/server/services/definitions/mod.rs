@@ -162,12 +162,17 @@ pub mod zwave_js;
 pub mod docker_container;
 pub mod docker_daemon;
 pub mod docker_swarm;
+pub mod hyper_v;
 pub mod kubernetes;
+pub mod kubernetes_pod;
+pub mod libvirt;
 pub mod nomad;
 pub mod openshift;
+pub mod podman;
 pub mod portainer;
 pub mod proxmox;
 pub mod rancher;
+pub mod vmware_esxi;
 
 // ============= APPLICATION SERVICES =============
 
@@ -18,7 +18,11 @@ impl ServiceDefinition for MySQL {
         ServiceCategory::Database
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::Port(PortType::MySql)
+        Pattern::AnyOf(vec![
+            // Handshake advertises the auth plugin (mysql_native_password, caching_sha2_password)
+            Pattern::Banner(PortType::MySql, "_password"),
+            Pattern::Port(PortType::MySql),
+        ])
     }
     fn logo_url(&self) -> &'static str {
         "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/mysql.svg"
@@ -21,7 +21,10 @@ impl ServiceDefinition for PfSense {
     fn discovery_pattern(&self) -> Pattern<'_> {
         Pattern::AllOf(vec![
             Pattern::Port(PortType::Ssh),
-            Pattern::Endpoint(PortType::Http, "/", "pfsense", None),
+            Pattern::AnyOf(vec![
+                Pattern::Endpoint(PortType::Http, "/", "pfsense", None),
+                Pattern::TlsSubject(Some(PortType::Https), "pfSense webConfigurator Self-Signed"),
+            ]),
         ])
     }
 
@@ -21,6 +21,7 @@ impl ServiceDefinition for Proxmox {
     fn discovery_pattern(&self) -> Pattern<'_> {
         Pattern::AnyOf(vec![
             Pattern::Endpoint(PortType::new_tcp(8006), "/", "proxmox", None),
+            Pattern::TlsSubject(Some(PortType::new_tcp(8006)), "PVE Cluster Manager CA"),
             Pattern::Port(PortType::new_tcp(8006)),
         ])
     }
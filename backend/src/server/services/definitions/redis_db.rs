@@ -18,7 +18,12 @@ impl ServiceDefinition for RedisDB {
         ServiceCategory::Database
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::Port(PortType::Redis)
+        Pattern::AnyOf(vec![
+            // Reply to the PING probe, or an auth challenge when a password is set
+            Pattern::Banner(PortType::Redis, "+PONG"),
+            Pattern::Banner(PortType::Redis, "-NOAUTH"),
+            Pattern::Port(PortType::Redis),
+        ])
     }
     fn logo_url(&self) -> &'static str {
         "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/redis.svg"
@@ -21,9 +21,15 @@ impl ServiceDefinition for RokuDevice {
     }
 
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::AllOf(vec![
-            Pattern::MacVendor(Vendor::ROKU),
-            Pattern::Port(PortType::new_tcp(8060)),
+        Pattern::AnyOf(vec![
+            Pattern::UpnpDevice {
+                manufacturer: "Roku",
+                model: None,
+            },
+            Pattern::AllOf(vec![
+                Pattern::MacVendor(Vendor::ROKU),
+                Pattern::Port(PortType::new_tcp(8060)),
+            ]),
         ])
     }
 
@@ -18,7 +18,13 @@ impl ServiceDefinition for Samba {
         ServiceCategory::Storage
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::Port(PortType::Samba)
+        Pattern::AnyOf(vec![
+            Pattern::Port(PortType::Samba),
+            Pattern::AllOf(vec![
+                Pattern::Port(PortType::Samba),
+                Pattern::NetbiosWorkgroup,
+            ]),
+        ])
     }
     fn is_generic(&self) -> bool {
         true
@@ -18,7 +18,11 @@ impl ServiceDefinition for Ssh {
         ServiceCategory::NetworkCore
     }
     fn discovery_pattern(&self) -> Pattern<'_> {
-        Pattern::Port(PortType::Ssh)
+        Pattern::AnyOf(vec![
+            // Protocol version exchange, e.g. "SSH-2.0-OpenSSH_9.6"
+            Pattern::Banner(PortType::Ssh, "SSH-"),
+            Pattern::Port(PortType::Ssh),
+        ])
     }
     fn is_generic(&self) -> bool {
         true
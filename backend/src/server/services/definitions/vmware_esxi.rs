@@ -0,0 +1,42 @@
+use crate::server::ports::r#impl::base::PortType;
+use crate::server::services::definitions::{ServiceDefinitionFactory, create_service};
+use crate::server::services::r#impl::categories::ServiceCategory;
+use crate::server::services::r#impl::definitions::ServiceDefinition;
+use crate::server::services::r#impl::patterns::Pattern;
+
+#[derive(Default, Clone, Eq, PartialEq, Hash)]
+pub struct VmwareEsxi;
+
+impl ServiceDefinition for VmwareEsxi {
+    fn name(&self) -> &'static str {
+        "VMware ESXi"
+    }
+    fn description(&self) -> &'static str {
+        "Bare-metal hypervisor from VMware"
+    }
+    fn category(&self) -> ServiceCategory {
+        ServiceCategory::Virtualization
+    }
+
+    fn discovery_pattern(&self) -> Pattern<'_> {
+        // The SOAP SDK or a VMware certificate alongside the console and authentication daemon
+        Pattern::AllOf(vec![
+            Pattern::Port(PortType::new_tcp(902)),
+            Pattern::AnyOf(vec![
+                Pattern::Endpoint(
+                    PortType::Https,
+                    "/sdk/vimServiceVersions.xml",
+                    "urn:vim25",
+                    None,
+                ),
+                Pattern::TlsSubject(Some(PortType::Https), "VMware"),
+            ]),
+        ])
+    }
+
+    fn logo_url(&self) -> &'static str {
+        "https://cdn.jsdelivr.net/gh/homarr-labs/dashboard-icons/svg/vmware-esxi.svg"
+    }
+}
+
+inventory::submit!(ServiceDefinitionFactory::new(create_service::<VmwareEsxi>));
@@ -5,7 +5,10 @@ use crate::server::ports::r#impl::base::{Port, PortType};
 use crate::server::services::definitions::ServiceDefinitionRegistry;
 use crate::server::services::r#impl::definitions::ServiceDefinitionExt;
 use crate::server::services::r#impl::definitions::{DefaultServiceDefinition, ServiceDefinition};
-use crate::server::services::r#impl::endpoints::{Endpoint, EndpointResponse};
+use crate::server::services::r#impl::endpoints::{
+    BannerResponse, CertificateResponse, Endpoint, EndpointResponse, MdnsResponse, NetbiosResponse,
+    UpnpResponse,
+};
 use crate::server::services::r#impl::patterns::{MatchConfidence, MatchReason};
 use crate::server::services::r#impl::virtualization::{
     DockerVirtualization, ServiceVirtualization,
@@ -108,6 +111,11 @@ pub struct ServiceMatchBaselineParams<'a> {
     pub interface: &'a Interface,
     pub all_ports: &'a Vec<PortType>,
     pub endpoint_responses: &'a Vec<EndpointResponse>,
+    pub banner_responses: &'a Vec<BannerResponse>,
+    pub certificate_responses: &'a Vec<CertificateResponse>,
+    pub mdns_responses: &'a Vec<MdnsResponse>,
+    pub upnp_responses: &'a Vec<UpnpResponse>,
+    pub netbios_response: &'a Option<NetbiosResponse>,
     pub virtualization: &'a Option<ServiceVirtualization>,
 }
 
@@ -158,16 +166,25 @@ impl PartialEq for Service {
         // === GENERIC SERVICE EQUALITY ===
         // All possible permutations of generic services on the same host:
 
+        // Kubernetes workloads on the same node are the same service
+        if let (
+            Some(ServiceVirtualization::Kubernetes(self_kv)),
+            Some(ServiceVirtualization::Kubernetes(other_kv)),
+        ) = (&self.base.virtualization, &other.base.virtualization)
+        {
+            return self_kv.identity() == other_kv.identity();
+        }
+
         // Extract virtualization info
-        let self_docker = self.base.virtualization.as_ref().map(|v| {
-            let ServiceVirtualization::Docker(dv) = v;
-            dv
-        });
+        let self_docker = match &self.base.virtualization {
+            Some(ServiceVirtualization::Docker(dv)) => Some(dv),
+            _ => None,
+        };
 
-        let other_docker = other.base.virtualization.as_ref().map(|v| {
-            let ServiceVirtualization::Docker(dv) = v;
-            dv
-        });
+        let other_docker = match &other.base.virtualization {
+            Some(ServiceVirtualization::Docker(dv)) => Some(dv),
+            _ => None,
+        };
 
         // Extract port IDs from bindings
         let self_port_ids: std::collections::HashSet<_> = self
@@ -391,12 +408,15 @@ impl Service {
             let mut name = service_definition.name().to_string();
 
             if ServiceDefinitionExt::is_generic(&service_definition) {
-                if let Some(ServiceVirtualization::Docker(DockerVirtualization {
-                    container_name: Some(c_name),
-                    ..
-                })) = virtualization
-                {
-                    name = c_name.clone()
+                match virtualization {
+                    Some(ServiceVirtualization::Docker(DockerVirtualization {
+                        container_name: Some(c_name),
+                        ..
+                    })) => name = c_name.clone(),
+                    Some(ServiceVirtualization::Kubernetes(kv)) => {
+                        name = kv.workload.clone().unwrap_or_else(|| kv.pod.clone())
+                    }
+                    _ => {}
                 }
 
                 // Confidence not applicable for generic services
@@ -1,6 +1,10 @@
 use crate::server::services::definitions::ServiceDefinitionRegistry;
 use crate::server::services::definitions::docker_daemon::Docker;
+use crate::server::services::definitions::hyper_v::HyperV;
+use crate::server::services::definitions::libvirt::Libvirt;
+use crate::server::services::definitions::podman::Podman;
 use crate::server::services::definitions::proxmox::Proxmox;
+use crate::server::services::definitions::vmware_esxi::VmwareEsxi;
 use crate::server::services::r#impl::categories::ServiceCategory;
 use crate::server::services::r#impl::patterns::Pattern;
 use crate::server::shared::types::metadata::TypeMetadataProvider;
@@ -135,8 +139,14 @@ impl ServiceDefinitionExt for Box<dyn ServiceDefinition> {
     fn manages_virtualization(&self) -> Option<&'static str> {
         let id = self.id();
         match id {
-            _ if id == Proxmox.id() => Some("vms"),
-            _ if id == Docker.id() => Some("containers"),
+            _ if id == Proxmox.id()
+                || id == Libvirt.id()
+                || id == VmwareEsxi.id()
+                || id == HyperV.id() =>
+            {
+                Some("vms")
+            }
+            _ if id == Docker.id() || id == Podman.id() => Some("containers"),
             _ => None,
         }
     }
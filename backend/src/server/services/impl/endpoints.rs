@@ -1,6 +1,7 @@
+use crate::server::certificates::r#impl::base::CertificateDetails;
 use crate::server::ports::r#impl::base::PortType;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::{fmt::Display, net::IpAddr};
 use strum::IntoDiscriminant;
@@ -53,6 +54,113 @@ pub struct EndpointResponse {
     pub status: u16,
 }
 
+/// First bytes a TCP service sent after connecting, or its reply to a protocol probe
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BannerResponse {
+    pub port_type: PortType,
+    pub banner: String,
+}
+
+impl Display for BannerResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let banner_length = self.banner.len().min(40);
+        write!(
+            f,
+            "Banner from port {} -- {}...",
+            self.port_type,
+            &self.banner[0..banner_length]
+        )
+    }
+}
+
+/// Certificate a port presented during a TLS handshake
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CertificateResponse {
+    pub port_type: PortType,
+    pub certificate: CertificateDetails,
+}
+
+impl Display for CertificateResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Certificate from port {} -- Subject {}; Issuer {}",
+            self.port_type, self.certificate.subject, self.certificate.issuer
+        )
+    }
+}
+
+/// A service instance a host advertised over multicast DNS
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MdnsResponse {
+    /// DNS-SD service type without the domain, ie "_googlecast._tcp"
+    pub service_type: String,
+    /// Instance name, ie "Living Room TV"
+    pub instance_name: String,
+    /// SRV target, ie "Chromecast-1a2b3c.local"
+    pub hostname: Option<String>,
+    /// Port from the SRV record
+    pub port_type: Option<PortType>,
+    pub txt: BTreeMap<String, String>,
+}
+
+impl Display for MdnsResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mDNS service {} -- Instance {}; Host {:?}; Port {:?}",
+            self.service_type, self.instance_name, self.hostname, self.port_type
+        )
+    }
+}
+
+/// Names a host registered over NetBIOS, from a node status query
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct NetbiosResponse {
+    /// Computer name
+    pub name: Option<String>,
+    /// Workgroup, or domain if the host is domain joined
+    pub workgroup: Option<String>,
+    /// Whether the host registered itself as a domain controller for the domain
+    pub is_domain_controller: bool,
+}
+
+impl Display for NetbiosResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "NetBIOS name {:?} -- Workgroup {:?}; Domain controller {}",
+            self.name, self.workgroup, self.is_domain_controller
+        )
+    }
+}
+
+/// A UPnP device that answered an SSDP search, with its device description
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct UpnpResponse {
+    /// URL of the device description XML, from the LOCATION header
+    pub location: String,
+    /// Port the device description was served from
+    pub port_type: Option<PortType>,
+    /// ie "urn:schemas-upnp-org:device:MediaRenderer:1"
+    pub device_type: Option<String>,
+    pub friendly_name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model_name: Option<String>,
+    pub model_number: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+impl Display for UpnpResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UPnP device {} -- Manufacturer {:?}; Model {:?}; Name {:?}",
+            self.location, self.manufacturer, self.model_name, self.friendly_name
+        )
+    }
+}
+
 impl Display for EndpointResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let body_length = self.body.len().min(20);
@@ -172,6 +172,35 @@ pub enum Pattern<'a> {
     /// status_code: optional, defaults to 200..300 (any ok or redirect)
     Header(Option<PortType>, &'a str, &'a str, Option<Range<u16>>),
 
+    /// Whether a TCP service identified itself in its banner
+    /// PortType: port the banner was read from
+    /// &str - String to match on in the banner (case-insensitive)
+    Banner(PortType, &'a str),
+
+    /// Whether a TLS certificate's subject or issuer contains a string. Useful for appliances
+    /// that ship a default self-signed certificate.
+    /// PortType: If provided, only check the certificate from the specific port. Otherwise, use any port.
+    /// &str - String to match on in the subject or issuer (case-insensitive)
+    TlsSubject(Option<PortType>, &'a str),
+
+    /// Whether the host advertised a DNS-SD service type over multicast DNS
+    /// &str - Service type without the domain, ie "_googlecast._tcp" (case-insensitive)
+    MdnsService(&'a str),
+
+    /// Whether the host answered an SSDP search with a matching UPnP device description
+    /// manufacturer: &str - String to match on in the manufacturer (case-insensitive)
+    /// model: optional, string to match on in the model name or number (case-insensitive)
+    UpnpDevice {
+        manufacturer: &'a str,
+        model: Option<&'a str>,
+    },
+
+    /// Whether the host reported a workgroup or domain in a NetBIOS node status response
+    NetbiosWorkgroup,
+
+    /// Whether the host registered itself as a domain controller over NetBIOS
+    NetbiosDomainController,
+
     /// Whether the subnet that the host was found on matches a subnet type
     SubnetIsType(SubnetType),
 
@@ -197,6 +226,9 @@ pub enum Pattern<'a> {
     /// Whether the host is a docker container
     DockerContainer,
 
+    /// Whether the service is running in a Kubernetes pod
+    KubernetesPod,
+
     /// No match pattern (only added manually or by the system)
     None,
 }
@@ -234,6 +266,25 @@ impl PartialEq for Pattern<'_> {
             ) => {
                 port_a == port_b && header_a == header_b && value_a == value_b && range_a == range_b
             }
+            (Pattern::Banner(port_a, match_a), Pattern::Banner(port_b, match_b)) => {
+                port_a == port_b && match_a == match_b
+            }
+            (Pattern::TlsSubject(port_a, match_a), Pattern::TlsSubject(port_b, match_b)) => {
+                port_a == port_b && match_a == match_b
+            }
+            (Pattern::MdnsService(a), Pattern::MdnsService(b)) => a == b,
+            (
+                Pattern::UpnpDevice {
+                    manufacturer: manufacturer_a,
+                    model: model_a,
+                },
+                Pattern::UpnpDevice {
+                    manufacturer: manufacturer_b,
+                    model: model_b,
+                },
+            ) => manufacturer_a == manufacturer_b && model_a == model_b,
+            (Pattern::NetbiosWorkgroup, Pattern::NetbiosWorkgroup) => true,
+            (Pattern::NetbiosDomainController, Pattern::NetbiosDomainController) => true,
             (Pattern::SubnetIsType(a), Pattern::SubnetIsType(b)) => a == b,
             (Pattern::IsGateway, Pattern::IsGateway) => true,
             (Pattern::MacVendor(a), Pattern::MacVendor(b)) => a == b,
@@ -249,6 +300,7 @@ impl PartialEq for Pattern<'_> {
                     && conf_a == conf_b
             }
             (Pattern::DockerContainer, Pattern::DockerContainer) => true,
+            (Pattern::KubernetesPod, Pattern::KubernetesPod) => true,
             (Pattern::None, Pattern::None) => true,
             _ => false,
         }
@@ -311,6 +363,42 @@ impl Display for Pattern<'_> {
                     )
                 }
             }
+            Pattern::Banner(port_base, match_string) => write!(
+                f,
+                "Banner from <ip>:{} contains \"{}\"",
+                port_base.number(),
+                match_string
+            ),
+            Pattern::TlsSubject(port_base, match_string) => {
+                let ip_str = if let Some(port_base) = port_base {
+                    format!("<ip>:{}", port_base.number())
+                } else {
+                    "<ip>".to_string()
+                };
+                write!(
+                    f,
+                    "TLS certificate from {} has subject or issuer containing \"{}\"",
+                    ip_str, match_string
+                )
+            }
+            Pattern::MdnsService(service_type) => {
+                write!(f, "Host advertises {} over mDNS", service_type)
+            }
+            Pattern::UpnpDevice {
+                manufacturer,
+                model,
+            } => match model {
+                Some(model) => write!(
+                    f,
+                    "Host is a UPnP device made by {} with model {}",
+                    manufacturer, model
+                ),
+                None => write!(f, "Host is a UPnP device made by {}", manufacturer),
+            },
+            Pattern::NetbiosWorkgroup => write!(f, "Host reports a NetBIOS workgroup or domain"),
+            Pattern::NetbiosDomainController => {
+                write!(f, "Host is registered as a domain controller over NetBIOS")
+            }
             Pattern::SubnetIsType(subnet_type) => write!(f, "Subnet is type {:?}", subnet_type),
             Pattern::IsGateway => write!(
                 f,
@@ -321,6 +409,7 @@ impl Display for Pattern<'_> {
                 write!(f, "A custom match pattern evaluated at runtime")
             }
             Pattern::DockerContainer => write!(f, "Service is running in a docker container"),
+            Pattern::KubernetesPod => write!(f, "Service is running in a Kubernetes pod"),
             Pattern::None => write!(f, "No match pattern provided"),
         }
     }
@@ -345,6 +434,11 @@ impl Pattern<'_> {
             subnet,
             interface,
             endpoint_responses,
+            banner_responses,
+            certificate_responses,
+            mdns_responses,
+            upnp_responses,
+            netbios_response,
             virtualization,
             ..
         } = baseline_params;
@@ -547,6 +641,216 @@ impl Pattern<'_> {
                 }
             }
 
+            Pattern::Banner(port_base, expected_match_string) => {
+                let match_result = banner_responses.iter().find(|actual| {
+                    // Compare number + protocol, named port types won't match new_tcp(n)
+                    actual.port_type.number() == port_base.number()
+                        && actual.port_type.protocol() == port_base.protocol()
+                        && actual
+                            .banner
+                            .to_lowercase()
+                            .contains(&expected_match_string.to_lowercase())
+                });
+
+                match match_result {
+                    Some(response) => Ok(MatchResult {
+                        ports: vec![response.port_type],
+                        endpoint: None,
+                        mac_vendor: None,
+                        details: MatchDetails {
+                            reason: MatchReason::Reason(format!(
+                                "Banner from {}:{} contained \"{}\"",
+                                interface.base.ip_address,
+                                port_base.number(),
+                                expected_match_string
+                            )),
+                            confidence: MatchConfidence::High,
+                        },
+                    }),
+                    None => Err(anyhow!(
+                        "Could not find a banner on port {} containing {}",
+                        port_base.number(),
+                        expected_match_string
+                    )),
+                }
+            }
+
+            Pattern::TlsSubject(port_base, expected_match_string) => {
+                let expected = expected_match_string.to_lowercase();
+
+                let match_result = certificate_responses.iter().find(|actual| {
+                    // Compare number + protocol, named port types won't match new_tcp(n)
+                    let is_same_port = port_base.is_none_or(|port_base| {
+                        actual.port_type.number() == port_base.number()
+                            && actual.port_type.protocol() == port_base.protocol()
+                    });
+
+                    is_same_port
+                        && (actual
+                            .certificate
+                            .subject
+                            .to_lowercase()
+                            .contains(&expected)
+                            || actual.certificate.issuer.to_lowercase().contains(&expected))
+                });
+
+                match match_result {
+                    Some(response) => Ok(MatchResult {
+                        ports: vec![response.port_type],
+                        endpoint: None,
+                        mac_vendor: None,
+                        details: MatchDetails {
+                            reason: MatchReason::Reason(format!(
+                                "TLS certificate from {}:{} has subject or issuer containing \"{}\"",
+                                interface.base.ip_address,
+                                response.port_type.number(),
+                                expected_match_string
+                            )),
+                            confidence: MatchConfidence::High,
+                        },
+                    }),
+                    None => Err(anyhow!(
+                        "Could not find a TLS certificate with subject or issuer containing {}",
+                        expected_match_string
+                    )),
+                }
+            }
+
+            Pattern::MdnsService(service_type) => {
+                let match_result = mdns_responses
+                    .iter()
+                    .find(|actual| actual.service_type.eq_ignore_ascii_case(service_type));
+
+                match match_result {
+                    Some(response) => {
+                        // Only bind the advertised port if the scan found it open
+                        let ports = response
+                            .port_type
+                            .and_then(|advertised| {
+                                unbound_ports.iter().find(|p| {
+                                    p.number() == advertised.number()
+                                        && p.protocol() == advertised.protocol()
+                                })
+                            })
+                            .into_iter()
+                            .copied()
+                            .collect();
+
+                        Ok(MatchResult {
+                            ports,
+                            endpoint: None,
+                            mac_vendor: None,
+                            details: MatchDetails {
+                                reason: MatchReason::Reason(format!(
+                                    "{} advertises {} over mDNS as \"{}\"",
+                                    interface.base.ip_address,
+                                    response.service_type,
+                                    response.instance_name
+                                )),
+                                confidence: MatchConfidence::High,
+                            },
+                        })
+                    }
+                    None => Err(anyhow!(
+                        "Host does not advertise {} over mDNS",
+                        service_type
+                    )),
+                }
+            }
+
+            Pattern::UpnpDevice {
+                manufacturer,
+                model,
+            } => {
+                let contains = |value: &Option<String>, needle: &str| {
+                    value
+                        .as_ref()
+                        .is_some_and(|v| v.to_lowercase().contains(&needle.to_lowercase()))
+                };
+
+                let match_result = upnp_responses.iter().find(|actual| {
+                    contains(&actual.manufacturer, manufacturer)
+                        && model.is_none_or(|model| {
+                            contains(&actual.model_name, model)
+                                || contains(&actual.model_number, model)
+                        })
+                });
+
+                match match_result {
+                    Some(response) => {
+                        // Bind the port serving the device description if the scan found it open
+                        let ports = response
+                            .port_type
+                            .and_then(|described| {
+                                unbound_ports.iter().find(|p| {
+                                    p.number() == described.number()
+                                        && p.protocol() == described.protocol()
+                                })
+                            })
+                            .into_iter()
+                            .copied()
+                            .collect();
+
+                        Ok(MatchResult {
+                            ports,
+                            endpoint: None,
+                            mac_vendor: None,
+                            details: MatchDetails {
+                                reason: MatchReason::Reason(format!(
+                                    "{} describes itself over UPnP as {} {}",
+                                    interface.base.ip_address,
+                                    response.manufacturer.as_deref().unwrap_or_default(),
+                                    response.model_name.as_deref().unwrap_or_default()
+                                )),
+                                confidence: MatchConfidence::High,
+                            },
+                        })
+                    }
+                    None => Err(anyhow!("No UPnP device description matched {}", self)),
+                }
+            }
+
+            Pattern::NetbiosWorkgroup => match netbios_response
+                .as_ref()
+                .and_then(|response| response.workgroup.as_ref())
+            {
+                Some(workgroup) => Ok(MatchResult {
+                    ports: vec![],
+                    endpoint: None,
+                    mac_vendor: None,
+                    details: MatchDetails {
+                        reason: MatchReason::Reason(format!(
+                            "{} is a member of workgroup/domain {}",
+                            interface.base.ip_address, workgroup
+                        )),
+                        confidence: MatchConfidence::Medium,
+                    },
+                }),
+                None => Err(anyhow!("Host did not report a NetBIOS workgroup")),
+            },
+
+            Pattern::NetbiosDomainController => match netbios_response
+                .as_ref()
+                .filter(|response| response.is_domain_controller)
+            {
+                Some(response) => Ok(MatchResult {
+                    ports: vec![],
+                    endpoint: None,
+                    mac_vendor: None,
+                    details: MatchDetails {
+                        reason: MatchReason::Reason(format!(
+                            "{} is a domain controller for {}",
+                            interface.base.ip_address,
+                            response.workgroup.as_deref().unwrap_or("an unnamed domain")
+                        )),
+                        confidence: MatchConfidence::High,
+                    },
+                }),
+                None => Err(anyhow!(
+                    "Host is not registered as a NetBIOS domain controller"
+                )),
+            },
+
             Pattern::MacVendor(vendor_string) => {
                 if let Some(mac) = interface.base.mac_address {
                     let Ok(oui_db) = Oui::default() else {
@@ -841,6 +1145,21 @@ impl Pattern<'_> {
                 _ => Err(anyhow!("Service is not running in a docker container")),
             },
 
+            Pattern::KubernetesPod => match virtualization {
+                Some(ServiceVirtualization::Kubernetes(..)) => Ok(MatchResult {
+                    ports: vec![],
+                    endpoint: None,
+                    mac_vendor: None,
+                    details: MatchDetails {
+                        reason: MatchReason::Reason(
+                            "Service is running in a Kubernetes pod".to_string(),
+                        ),
+                        confidence: MatchConfidence::Low,
+                    },
+                }),
+                _ => Err(anyhow!("Service is not running in a Kubernetes pod")),
+            },
+
             Pattern::None => Err(anyhow!("No match pattern provided")),
         }
     }
@@ -850,7 +1169,9 @@ impl Pattern<'_> {
     /// There's logic to add any endpoint-specific ports into scanning in scan_ports_and_endpoints and the docker discovery equivalent
     pub fn ports(&self) -> Vec<PortType> {
         match self {
-            Pattern::Port(port) => vec![*port],
+            Pattern::Port(port) | Pattern::Banner(port, _) | Pattern::TlsSubject(Some(port), _) => {
+                vec![*port]
+            }
             Pattern::AnyOf(patterns) | Pattern::AllOf(patterns) => {
                 patterns.iter().flat_map(|p| p.ports().to_vec()).collect()
             }
@@ -899,9 +1220,10 @@ impl Pattern<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::net::IpAddr;
 
+    use crate::server::certificates::r#impl::base::CertificateDetails;
     use crate::server::discovery::r#impl::types::{DiscoveryType, HostNamingFallback};
     use crate::server::services::r#impl::base::Service;
     use crate::server::services::r#impl::virtualization::ServiceVirtualization;
@@ -920,7 +1242,10 @@ mod tests {
                         ServiceMatchServiceParams,
                     },
                     definitions::ServiceDefinition,
-                    endpoints::{Endpoint, EndpointResponse},
+                    endpoints::{
+                        BannerResponse, CertificateResponse, Endpoint, EndpointResponse,
+                        MdnsResponse, NetbiosResponse, UpnpResponse,
+                    },
                     patterns::Pattern,
                 },
             },
@@ -939,6 +1264,11 @@ mod tests {
         discovery_type: DiscoveryType,
         gateway_ips: Vec<IpAddr>,
         endpoint_responses: Vec<EndpointResponse>,
+        banner_responses: Vec<BannerResponse>,
+        certificate_responses: Vec<CertificateResponse>,
+        mdns_responses: Vec<MdnsResponse>,
+        upnp_responses: Vec<UpnpResponse>,
+        netbios_response: Option<NetbiosResponse>,
         virtualization: Option<ServiceVirtualization>,
         matched_services: Vec<Service>,
     }
@@ -969,9 +1299,44 @@ mod tests {
                 discovery_type: DiscoveryType::Network {
                     subnet_ids: None,
                     host_naming_fallback: HostNamingFallback::BestService,
+                    scan_profile_id: None,
                 },
                 gateway_ips: vec![],
                 endpoint_responses,
+                banner_responses: vec![BannerResponse {
+                    port_type: PortType::Ssh,
+                    banner: "SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13".to_string(),
+                }],
+                certificate_responses: vec![CertificateResponse {
+                    port_type: PortType::new_tcp(8006),
+                    certificate: CertificateDetails {
+                        subject: "O=PVE Cluster Manager CA, CN=pve.lan".to_string(),
+                        issuer: "O=PVE Cluster Manager CA, CN=pve.lan".to_string(),
+                        ..Default::default()
+                    },
+                }],
+                mdns_responses: vec![MdnsResponse {
+                    service_type: "_googlecast._tcp".to_string(),
+                    instance_name: "Living Room TV".to_string(),
+                    hostname: Some("Chromecast-1a2b3c.local".to_string()),
+                    port_type: Some(PortType::new_tcp(8009)),
+                    txt: BTreeMap::from([("md".to_string(), "Chromecast".to_string())]),
+                }],
+                upnp_responses: vec![UpnpResponse {
+                    location: "http://192.168.1.100:8060/".to_string(),
+                    port_type: Some(PortType::new_tcp(8060)),
+                    device_type: Some("urn:roku-com:device:player:1-0".to_string()),
+                    friendly_name: Some("Living Room Roku".to_string()),
+                    manufacturer: Some("Roku".to_string()),
+                    model_name: Some("Roku Ultra".to_string()),
+                    model_number: Some("4800X".to_string()),
+                    serial_number: Some("X00000000000".to_string()),
+                }],
+                netbios_response: Some(NetbiosResponse {
+                    name: Some("DC01".to_string()),
+                    workgroup: Some("CORP".to_string()),
+                    is_domain_controller: true,
+                }),
                 virtualization: None,
                 matched_services: vec![],
             }
@@ -1006,6 +1371,11 @@ mod tests {
                 interface: &self.interface,
                 all_ports,
                 endpoint_responses: &self.endpoint_responses,
+                banner_responses: &self.banner_responses,
+                certificate_responses: &self.certificate_responses,
+                mdns_responses: &self.mdns_responses,
+                upnp_responses: &self.upnp_responses,
+                netbios_response: &self.netbios_response,
                 virtualization: &self.virtualization,
             }
         }
@@ -1118,4 +1488,181 @@ mod tests {
             "OR pattern should not match when no conditions met"
         );
     }
+
+    #[test]
+    fn test_pattern_banner_matching() {
+        let ctx = TestContext::new();
+
+        let ports = vec![PortType::Ssh];
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        let result = Pattern::Banner(PortType::Ssh, "ssh-2.0").matches(&params);
+        assert!(
+            result.is_ok(),
+            "Banner pattern should match case-insensitively"
+        );
+        assert_eq!(result.unwrap().ports, vec![PortType::Ssh]);
+
+        let result = Pattern::Banner(PortType::Ssh, "dropbear").matches(&params);
+        assert!(
+            result.is_err(),
+            "Banner pattern should not match a different banner"
+        );
+
+        let result = Pattern::Banner(PortType::new_tcp(2222), "SSH-").matches(&params);
+        assert!(
+            result.is_err(),
+            "Banner pattern should not match a banner from another port"
+        );
+    }
+
+    #[test]
+    fn test_pattern_tls_subject_matching() {
+        let ctx = TestContext::new();
+
+        let ports = vec![PortType::new_tcp(8006)];
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        let result =
+            Pattern::TlsSubject(Some(PortType::new_tcp(8006)), "pve cluster").matches(&params);
+        assert!(
+            result.is_ok(),
+            "TlsSubject pattern should match case-insensitively"
+        );
+        assert_eq!(result.unwrap().ports, vec![PortType::new_tcp(8006)]);
+
+        let result = Pattern::TlsSubject(None, "PVE Cluster Manager CA").matches(&params);
+        assert!(
+            result.is_ok(),
+            "TlsSubject pattern without a port should match a certificate on any port"
+        );
+
+        let result = Pattern::TlsSubject(None, "pfSense").matches(&params);
+        assert!(
+            result.is_err(),
+            "TlsSubject pattern should not match a different subject"
+        );
+
+        let result = Pattern::TlsSubject(Some(PortType::Https), "PVE").matches(&params);
+        assert!(
+            result.is_err(),
+            "TlsSubject pattern should not match a certificate from another port"
+        );
+    }
+
+    #[test]
+    fn test_pattern_mdns_service_matching() {
+        let ctx = TestContext::new();
+
+        let ports = vec![PortType::new_tcp(8009)];
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        let result = Pattern::MdnsService("_GoogleCast._tcp").matches(&params);
+        assert!(
+            result.is_ok(),
+            "MdnsService pattern should match case-insensitively"
+        );
+        assert_eq!(result.unwrap().ports, vec![PortType::new_tcp(8009)]);
+
+        let result = Pattern::MdnsService("_sonos._tcp").matches(&params);
+        assert!(
+            result.is_err(),
+            "MdnsService pattern should not match a service type that wasn't advertised"
+        );
+
+        // Advertised port wasn't found open, match without binding it
+        let no_ports = vec![];
+        let params = ctx.create_params_with_ports(&baseline, &no_ports);
+        let result = Pattern::MdnsService("_googlecast._tcp").matches(&params);
+        assert!(result.is_ok_and(|r| r.ports.is_empty()));
+    }
+
+    #[test]
+    fn test_pattern_upnp_device_matching() {
+        let ctx = TestContext::new();
+
+        let ports = vec![PortType::new_tcp(8060)];
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        let result = Pattern::UpnpDevice {
+            manufacturer: "roku",
+            model: None,
+        }
+        .matches(&params);
+        assert!(
+            result.is_ok(),
+            "UpnpDevice pattern should match manufacturer case-insensitively"
+        );
+        assert_eq!(result.unwrap().ports, vec![PortType::new_tcp(8060)]);
+
+        let result = Pattern::UpnpDevice {
+            manufacturer: "Roku",
+            model: Some("4800"),
+        }
+        .matches(&params);
+        assert!(
+            result.is_ok(),
+            "UpnpDevice pattern should match on the model number"
+        );
+
+        let result = Pattern::UpnpDevice {
+            manufacturer: "Roku",
+            model: Some("Express"),
+        }
+        .matches(&params);
+        assert!(
+            result.is_err(),
+            "UpnpDevice pattern should not match a different model"
+        );
+
+        let result = Pattern::UpnpDevice {
+            manufacturer: "Synology",
+            model: None,
+        }
+        .matches(&params);
+        assert!(
+            result.is_err(),
+            "UpnpDevice pattern should not match a different manufacturer"
+        );
+    }
+
+    #[test]
+    fn test_pattern_netbios_matching() {
+        let mut ctx = TestContext::new();
+
+        let ports = vec![PortType::Samba];
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        let result = Pattern::NetbiosDomainController.matches(&params);
+        assert!(
+            result.is_ok_and(|r| r.details.reason_string().contains("CORP")),
+            "NetbiosDomainController should match and name the domain"
+        );
+        assert!(Pattern::NetbiosWorkgroup.matches(&params).is_ok());
+
+        ctx.netbios_response = Some(NetbiosResponse {
+            name: Some("NAS".to_string()),
+            workgroup: Some("WORKGROUP".to_string()),
+            is_domain_controller: false,
+        });
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        assert!(
+            Pattern::NetbiosDomainController.matches(&params).is_err(),
+            "NetbiosDomainController should not match a member server"
+        );
+        assert!(Pattern::NetbiosWorkgroup.matches(&params).is_ok());
+
+        ctx.netbios_response = None;
+        let baseline = ctx.create_baseline_params(&ports);
+        let params = ctx.create_params_with_ports(&baseline, &ports);
+
+        assert!(Pattern::NetbiosWorkgroup.matches(&params).is_err());
+    }
 }
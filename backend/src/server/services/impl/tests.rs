@@ -353,7 +353,10 @@ fn check_port_usage(
     service_name: &str,
 ) {
     match pattern {
-        Pattern::Port(port_base) | Pattern::Endpoint(port_base, .., None) => {
+        Pattern::Port(port_base)
+        | Pattern::Banner(port_base, _)
+        | Pattern::TlsSubject(Some(port_base), _)
+        | Pattern::Endpoint(port_base, .., None) => {
             if let PortType::Custom(_) = port_base {
                 if let Some(named_constant) = well_known_ports.get(&port_base) {
                     panic!(
@@ -30,6 +30,8 @@ use crate::server::shared::{
 pub enum ServiceVirtualization {
     #[schema(title = "Docker")]
     Docker(DockerVirtualization),
+    #[schema(title = "Kubernetes")]
+    Kubernetes(KubernetesVirtualization),
 }
 
 #[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
@@ -39,6 +41,25 @@ pub struct DockerVirtualization {
     pub service_id: Uuid,
 }
 
+#[derive(Debug, Clone, Serialize, Validate, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub struct KubernetesVirtualization {
+    pub namespace: String,
+    pub pod: String,
+    /// Deployment, StatefulSet or DaemonSet that owns the pod, if any
+    pub workload: Option<String>,
+}
+
+impl KubernetesVirtualization {
+    /// Pods are replaced on every rollout, so services are identified by their workload
+    /// and only fall back to the pod for unmanaged pods
+    pub fn identity(&self) -> (&str, &str) {
+        (
+            &self.namespace,
+            self.workload.as_deref().unwrap_or(&self.pod),
+        )
+    }
+}
+
 impl HasId for ServiceVirtualization {
     fn id(&self) -> &'static str {
         self.into()
@@ -56,10 +77,16 @@ impl EntityMetadataProvider for ServiceVirtualization {
 
 impl TypeMetadataProvider for ServiceVirtualization {
     fn name(&self) -> &'static str {
-        "Docker"
+        match self {
+            ServiceVirtualization::Docker(_) => "Docker",
+            ServiceVirtualization::Kubernetes(_) => "Kubernetes",
+        }
     }
 
     fn description(&self) -> &'static str {
-        "A service running in a docker container"
+        match self {
+            ServiceVirtualization::Docker(_) => "A service running in a docker container",
+            ServiceVirtualization::Kubernetes(_) => "A service running in a Kubernetes pod",
+        }
     }
 }
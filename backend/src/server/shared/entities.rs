@@ -1,4 +1,5 @@
 use crate::server::bindings::r#impl::base::Binding;
+use crate::server::certificates::r#impl::base::Certificate;
 use crate::server::interfaces::r#impl::base::Interface;
 use crate::server::invites::r#impl::base::Invite;
 use crate::server::ports::r#impl::base::Port;
@@ -89,6 +90,7 @@ pub enum Entity {
     Port(Port),
     Binding(Binding),
     Interface(Interface),
+    Certificate(Certificate),
 
     Subnet(Subnet),
     Group(Group),
@@ -124,6 +126,7 @@ impl EntityMetadataProvider for EntityDiscriminants {
             EntityDiscriminants::Interface => Color::Cyan,
             EntityDiscriminants::Port => Color::Cyan,
             EntityDiscriminants::Binding => Color::Purple,
+            EntityDiscriminants::Certificate => Color::Emerald,
 
             EntityDiscriminants::Subnet => Color::Orange,
             EntityDiscriminants::Group => Color::Rose,
@@ -150,6 +153,7 @@ impl EntityMetadataProvider for EntityDiscriminants {
             EntityDiscriminants::Interface => Icon::Binary,
             EntityDiscriminants::Port => Icon::EthernetPort,
             EntityDiscriminants::Binding => Icon::Link,
+            EntityDiscriminants::Certificate => Icon::ShieldCheck,
             EntityDiscriminants::Subnet => Icon::Network,
             EntityDiscriminants::Group => Icon::Group,
             EntityDiscriminants::Topology => Icon::ChartBarStacked,
@@ -237,6 +241,12 @@ impl From<Binding> for Entity {
     }
 }
 
+impl From<Certificate> for Entity {
+    fn from(value: Certificate) -> Self {
+        Self::Certificate(value)
+    }
+}
+
 impl From<Interface> for Entity {
     fn from(value: Interface) -> Self {
         Self::Interface(value)
@@ -337,11 +337,18 @@ pub enum EntityOperation {
     Deleted,
     DiscoveryStarted,
     DiscoveryCancelled,
+    CertificateChanged,
+    CertificateExpiring,
 }
 
 impl EntityOperation {
     fn log_level(&self) -> EventLogLevel {
-        EventLogLevel::Info
+        match self {
+            EntityOperation::CertificateChanged | EntityOperation::CertificateExpiring => {
+                EventLogLevel::Warn
+            }
+            _ => EventLogLevel::Info,
+        }
     }
 }
 
@@ -7,16 +7,17 @@ use crate::server::shared::types::api::ApiResponse;
 use crate::server::shared::types::metadata::{__path_get_metadata_registry, get_metadata_registry};
 use crate::server::{
     auth::handlers as auth_handlers, billing::handlers as billing_handlers,
-    bindings::handlers as binding_handlers, config::AppState,
-    daemon_api_keys::handlers as daemon_api_key_handlers, daemons::handlers as daemon_handlers,
-    discovery::handlers as discovery_handlers, groups::handlers as group_handlers,
-    hosts::handlers as host_handlers, interfaces::handlers as interface_handlers,
-    invites::handlers as invite_handlers, metrics::handlers as metrics_handlers,
-    networks::handlers as network_handlers, organizations::handlers as organization_handlers,
-    ports::handlers as port_handlers, services::handlers as service_handlers,
-    shares::handlers as share_handlers, subnets::handlers as subnet_handlers,
-    tags::handlers as tag_handlers, topology::handlers as topology_handlers,
-    user_api_keys::handlers as user_api_key_handlers, users::handlers as user_handlers,
+    bindings::handlers as binding_handlers, certificates::handlers as certificate_handlers,
+    config::AppState, daemon_api_keys::handlers as daemon_api_key_handlers,
+    daemons::handlers as daemon_handlers, discovery::handlers as discovery_handlers,
+    groups::handlers as group_handlers, hosts::handlers as host_handlers,
+    interfaces::handlers as interface_handlers, invites::handlers as invite_handlers,
+    metrics::handlers as metrics_handlers, networks::handlers as network_handlers,
+    organizations::handlers as organization_handlers, ports::handlers as port_handlers,
+    services::handlers as service_handlers, shares::handlers as share_handlers,
+    subnets::handlers as subnet_handlers, tags::handlers as tag_handlers,
+    topology::handlers as topology_handlers, user_api_keys::handlers as user_api_key_handlers,
+    users::handlers as user_handlers,
 };
 use axum::Json;
 use axum::Router;
@@ -83,6 +84,10 @@ fn create_billed_openapi_routes() -> OpenApiRouter<Arc<AppState>> {
         .nest("/api/v1/tags", tag_handlers::create_router())
         .nest("/api/v1/ports", port_handlers::create_router())
         .nest("/api/v1/bindings", binding_handlers::create_router())
+        .nest(
+            "/api/v1/certificates",
+            certificate_handlers::create_router(),
+        )
         // API key routes (versioned)
         .nest("/api/v1/auth/keys", user_api_key_handlers::create_router())
         .nest(
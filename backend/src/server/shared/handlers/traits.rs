@@ -54,6 +54,11 @@ where
     fn validate(&self) -> Result<(), String> {
         validator::Validate::validate(self).map_err(|e| e.to_string())
     }
+
+    /// Blank write-only fields (e.g., credentials) before the entity is returned to a client
+    fn redact(&mut self) {
+        // Default: no-op
+    }
 }
 
 /// Create a standard CRUD router
@@ -99,7 +104,7 @@ where
         organization_id,
     )?;
 
-    let created = service
+    let mut created = service
         .create(entity, auth.into_entity())
         .await
         .map_err(|e| {
@@ -116,6 +121,8 @@ where
             api_error
         })?;
 
+    created.redact();
+
     Ok(Json(ApiResponse::success(created)))
 }
 
@@ -153,7 +160,7 @@ where
     let service = T::get_service(&state);
 
     // Use paginated query to get items and total count
-    let result = service.get_paginated(filter).await.map_err(|e| {
+    let mut result = service.get_paginated(filter).await.map_err(|e| {
         tracing::error!(
             entity_type = T::table_name(),
             user_id = ?user_id,
@@ -163,6 +170,8 @@ where
         ApiError::internal_error(&e.to_string())
     })?;
 
+    result.items.iter_mut().for_each(T::redact);
+
     // Get effective pagination values for response metadata
     let limit = pagination.effective_limit().unwrap_or(0);
     let offset = pagination.effective_offset();
@@ -192,7 +201,7 @@ where
     let user_id = auth.user_id();
 
     let service = T::get_service(&state);
-    let entity = service
+    let mut entity = service
         .get_by_id(&id)
         .await
         .map_err(|e| {
@@ -222,6 +231,8 @@ where
         organization_id,
     )?;
 
+    entity.redact();
+
     Ok(Json(ApiResponse::success(entity)))
 }
 
@@ -288,7 +299,7 @@ where
         organization_id,
     )?;
 
-    let updated = service
+    let mut updated = service
         .update(&mut entity, auth.into_entity())
         .await
         .map_err(|e| {
@@ -306,6 +317,8 @@ where
             api_error
         })?;
 
+    updated.redact();
+
     Ok(Json(ApiResponse::success(updated)))
 }
 
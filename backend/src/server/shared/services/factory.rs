@@ -2,6 +2,7 @@ use crate::server::{
     auth::{oidc::OidcService, service::AuthService},
     billing::service::{BillingService, BillingServiceParams},
     bindings::service::BindingService,
+    certificates::service::CertificateService,
     config::ServerConfig,
     daemon_api_keys::service::DaemonApiKeyService,
     daemons::service::DaemonService,
@@ -64,6 +65,7 @@ pub struct ServiceFactory {
     pub entity_tag_service: Arc<EntityTagService>,
     pub port_service: Arc<PortService>,
     pub binding_service: Arc<BindingService>,
+    pub certificate_service: Arc<CertificateService>,
 }
 
 impl ServiceFactory {
@@ -136,10 +138,18 @@ impl ServiceFactory {
             event_bus.clone(),
         ));
 
+        let certificate_service = Arc::new(CertificateService::new(
+            storage.certificates.clone(),
+            event_bus.clone(),
+        ));
+
         // Already implements Arc internally due to scheduler + sessions
         let discovery_service = DiscoveryService::new(
             storage.discovery.clone(),
             daemon_service.clone(),
+            storage.networks.clone(),
+            storage.ports.clone(),
+            storage.interfaces.clone(),
             event_bus.clone(),
             entity_tag_service.clone(),
         )
@@ -163,6 +173,7 @@ impl ServiceFactory {
             storage.hosts.clone(),
             interface_service.clone(),
             port_service.clone(),
+            certificate_service.clone(),
             service_service.clone(),
             daemon_service.clone(),
             event_bus.clone(),
@@ -320,6 +331,7 @@ impl ServiceFactory {
             entity_tag_service,
             port_service,
             binding_service,
+            certificate_service,
         })
     }
 }
@@ -5,14 +5,15 @@ use tower_sessions::{Expiry, SessionManagerLayer};
 use tower_sessions_sqlx_store::PostgresStore;
 
 use crate::server::{
-    bindings::r#impl::base::Binding, daemon_api_keys::r#impl::base::DaemonApiKey,
-    daemons::r#impl::base::Daemon, discovery::r#impl::base::Discovery, groups::r#impl::base::Group,
-    hosts::r#impl::base::Host, interfaces::r#impl::base::Interface, invites::r#impl::base::Invite,
-    networks::r#impl::Network, organizations::r#impl::base::Organization,
-    ports::r#impl::base::Port, services::r#impl::base::Service,
-    shared::storage::generic::GenericPostgresStorage, shares::r#impl::base::Share,
-    subnets::r#impl::base::Subnet, tags::r#impl::base::Tag, topology::types::base::Topology,
-    user_api_keys::r#impl::base::UserApiKey, users::r#impl::base::User,
+    bindings::r#impl::base::Binding, certificates::r#impl::base::Certificate,
+    daemon_api_keys::r#impl::base::DaemonApiKey, daemons::r#impl::base::Daemon,
+    discovery::r#impl::base::Discovery, groups::r#impl::base::Group, hosts::r#impl::base::Host,
+    interfaces::r#impl::base::Interface, invites::r#impl::base::Invite, networks::r#impl::Network,
+    organizations::r#impl::base::Organization, ports::r#impl::base::Port,
+    services::r#impl::base::Service, shared::storage::generic::GenericPostgresStorage,
+    shares::r#impl::base::Share, subnets::r#impl::base::Subnet, tags::r#impl::base::Tag,
+    topology::types::base::Topology, user_api_keys::r#impl::base::UserApiKey,
+    users::r#impl::base::User,
 };
 
 pub struct StorageFactory {
@@ -36,6 +37,7 @@ pub struct StorageFactory {
     pub tags: Arc<GenericPostgresStorage<Tag>>,
     pub ports: Arc<GenericPostgresStorage<Port>>,
     pub bindings: Arc<GenericPostgresStorage<Binding>>,
+    pub certificates: Arc<GenericPostgresStorage<Certificate>>,
 }
 
 pub async fn create_session_store(
@@ -83,6 +85,7 @@ impl StorageFactory {
             tags: Arc::new(GenericPostgresStorage::new(pool.clone())),
             ports: Arc::new(GenericPostgresStorage::new(pool.clone())),
             bindings: Arc::new(GenericPostgresStorage::new(pool.clone())),
+            certificates: Arc::new(GenericPostgresStorage::new(pool.clone())),
         })
     }
 }
@@ -89,6 +89,7 @@ pub fn create_remote_host(
         source: EntitySource::System,
         virtualization: None,
         hidden: false,
+        physical_links: Vec::new(),
     };
 
     let host = Host::new(base);
@@ -128,6 +129,7 @@ pub fn create_internet_connectivity_host(
         source: EntitySource::System,
         virtualization: None,
         hidden: false,
+        physical_links: Vec::new(),
     };
 
     let host = Host::new(base);
@@ -167,6 +169,7 @@ pub fn create_public_dns_host(
         source: EntitySource::System,
         virtualization: None,
         hidden: false,
+        physical_links: Vec::new(),
     };
 
     let host = Host::new(base);
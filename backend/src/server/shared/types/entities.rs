@@ -46,27 +46,55 @@ pub struct DiscoveryMetadata {
     pub discovery_type: DiscoveryType,
     pub daemon_id: Uuid,
     pub date: DateTime<Utc>,
+    /// Where the host's hostname came from, if discovery found one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname_source: Option<HostnameSource>,
+    /// Found by listening only, nothing was sent to the host
+    #[serde(default)]
+    pub passive: bool,
 }
 
 impl DiscoveryMetadata {
     pub fn new(discovery_type: DiscoveryType, daemon_id: Uuid) -> Self {
         Self {
+            passive: matches!(discovery_type, DiscoveryType::Passive { .. }),
             discovery_type,
             daemon_id,
             date: Utc::now(),
+            hostname_source: None,
         }
     }
 }
 
+/// How discovery resolved a host's hostname
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, ToSchema)]
+pub enum HostnameSource {
+    /// Reverse DNS (PTR record)
+    Dns,
+    /// NetBIOS node status
+    Netbios,
+    /// LLMNR reverse query
+    Llmnr,
+    /// SNMP sysName
+    Snmp,
+    /// Hostname the client sent with its DHCP request
+    DhcpLease,
+    /// Name the host announced over mDNS
+    Mdns,
+}
+
 impl Default for DiscoveryMetadata {
     fn default() -> Self {
         Self {
             discovery_type: DiscoveryType::Network {
                 subnet_ids: None,
                 host_naming_fallback: HostNamingFallback::BestService,
+                scan_profile_id: None,
             },
             daemon_id: Uuid::new_v4(),
             date: Utc::now(),
+            hostname_source: None,
+            passive: false,
         }
     }
 }
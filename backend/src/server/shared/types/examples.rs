@@ -97,6 +97,11 @@ pub fn network() -> Network {
             name: "Home Network".to_string(),
             organization_id: ids::ORGANIZATION,
             tags: vec![],
+            snmp_credentials: vec![],
+            proxmox_credentials: vec![],
+            dns_resolvers: vec![],
+            scan_profiles: vec![],
+            scan_exclusions: vec![],
         },
     }
 }
@@ -116,6 +121,7 @@ pub fn host() -> Host {
             virtualization: None,
             hidden: false,
             tags: vec![],
+            physical_links: Vec::new(),
         },
     }
 }
@@ -152,6 +158,7 @@ pub fn interface() -> Interface {
             mac_address: Some(MacAddress::new([0xDE, 0xAD, 0xBE, 0xEF, 0x12, 0x34])),
             name: Some("eth0".to_string()),
             position: 0,
+            dns_names: Vec::new(),
         },
     }
 }
@@ -166,6 +173,7 @@ pub fn port() -> Port {
             host_id: ids::HOST,
             network_id: ids::NETWORK,
             port_type: PortType::Http,
+            snmp_credential_id: None,
         },
     }
 }
@@ -331,6 +339,7 @@ pub fn discovery() -> Discovery {
             discovery_type: DiscoveryType::Network {
                 subnet_ids: Some(vec![ids::SUBNET]),
                 host_naming_fallback: Default::default(),
+                scan_profile_id: None,
             },
             run_type: RunType::AdHoc {
                 last_run: Some(example_timestamp()),
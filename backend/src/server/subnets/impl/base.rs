@@ -8,7 +8,7 @@ use crate::server::shared::types::api::deserialize_empty_string_as_none;
 use crate::server::shared::types::entities::{DiscoveryMetadata, EntitySource};
 use crate::server::subnets::r#impl::types::SubnetType;
 use chrono::{DateTime, Utc};
-use cidr::{IpCidr, Ipv4Cidr};
+use cidr::{IpCidr, Ipv4Cidr, Ipv6Cidr};
 use pnet::ipnetwork::IpNetwork;
 use serde::de::Error as DeError;
 use serde::{Deserialize, Serialize};
@@ -107,8 +107,17 @@ impl Subnet {
     ) -> Option<Self> {
         let subnet_type = SubnetType::from_interface_name(&interface_name);
 
-        match ip_network {
-            IpNetwork::V6(_) => None,
+        let cidr = match ip_network {
+            IpNetwork::V6(ipv6_network) => {
+                // Link-local addresses aren't routable and single addresses (/128) have no
+                // neighbors to discover, so neither maps to a useful subnet
+                let is_link_local = (ipv6_network.ip().segments()[0] & 0xffc0) == 0xfe80;
+                if is_link_local || ipv6_network.prefix() == 128 {
+                    return None;
+                }
+
+                IpCidr::V6(Ipv6Cidr::new(ipv6_network.network(), ipv6_network.prefix()).ok()?)
+            }
             IpNetwork::V4(ipv4_network) => {
                 let (network_addr, prefix_len) = match (&subnet_type, ipv4_network.prefix()) {
                     // VPN tunnels with /32 -> expand to /24
@@ -124,21 +133,21 @@ impl Subnet {
                     _ => (ipv4_network.network(), ipv4_network.prefix()),
                 };
 
-                let cidr = IpCidr::V4(Ipv4Cidr::new(network_addr, prefix_len).ok()?);
-
-                Some(Subnet::new(SubnetBase {
-                    cidr,
-                    network_id,
-                    description: None,
-                    tags: Vec::new(),
-                    name: cidr.to_string(),
-                    subnet_type,
-                    source: EntitySource::Discovery {
-                        metadata: vec![DiscoveryMetadata::new(discovery_type.clone(), daemon_id)],
-                    },
-                }))
+                IpCidr::V4(Ipv4Cidr::new(network_addr, prefix_len).ok()?)
             }
-        }
+        };
+
+        Some(Subnet::new(SubnetBase {
+            cidr,
+            network_id,
+            description: None,
+            tags: Vec::new(),
+            name: cidr.to_string(),
+            subnet_type,
+            source: EntitySource::Discovery {
+                metadata: vec![DiscoveryMetadata::new(discovery_type.clone(), daemon_id)],
+            },
+        }))
     }
 
     pub fn has_interface_with_service(
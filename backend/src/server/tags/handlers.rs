@@ -1,4 +1,6 @@
-use crate::server::auth::middleware::permissions::{Admin, Authorized, Member, Viewer};
+use crate::server::auth::middleware::permissions::{
+    Admin, Authorized, IsDaemon, Member, Or, Viewer,
+};
 use crate::server::shared::entities::{EntityDiscriminants, is_entity_taggable};
 use crate::server::shared::handlers::ordering::OrderField;
 use crate::server::shared::handlers::query::{
@@ -126,6 +128,8 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
 /// List all tags
 ///
 /// Returns all tags in the authenticated user's organization.
+/// Daemons get the tags of the organization owning their network, to resolve
+/// tag names from container labels.
 /// Supports pagination via `limit` and `offset` query parameters,
 /// and ordering via `group_by`, `order_by`, and `order_direction`.
 #[utoipa::path(
@@ -136,18 +140,26 @@ pub fn create_router() -> OpenApiRouter<Arc<AppState>> {
     responses(
         (status = 200, description = "List of tags", body = PaginatedApiResponse<Tag>),
     ),
-    security(("user_api_key" = []), ("session" = []))
+    security(("user_api_key" = []), ("session" = []), ("daemon_api_key" = []))
 )]
 async fn get_all_tags(
     State(state): State<Arc<AppState>>,
-    auth: Authorized<Viewer>,
+    auth: Authorized<Or<Viewer, IsDaemon>>,
     crate::server::shared::extractors::Query(query): crate::server::shared::extractors::Query<
         TagFilterQuery,
     >,
 ) -> ApiResult<Json<PaginatedApiResponse<Tag>>> {
-    let organization_id = auth
-        .organization_id()
-        .ok_or_else(|| ApiError::forbidden("Organization context required"))?;
+    let organization_id = match (auth.organization_id(), auth.network_ids().first()) {
+        (Some(organization_id), _) => organization_id,
+        (None, Some(network_id)) if auth.daemon_id().is_some() => state
+            .services
+            .network_service
+            .get_by_id(network_id)
+            .await?
+            .map(|n| n.base.organization_id)
+            .ok_or_else(|| ApiError::forbidden("Organization context required"))?,
+        _ => return Err(ApiError::forbidden("Organization context required")),
+    };
 
     let base_filter = StorableFilter::<Tag>::new().organization_id(&organization_id);
 
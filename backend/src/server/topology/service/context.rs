@@ -3,7 +3,7 @@ use uuid::Uuid;
 use crate::server::{
     bindings::r#impl::base::Binding,
     groups::r#impl::base::Group,
-    hosts::r#impl::{base::Host, virtualization::HostVirtualization},
+    hosts::r#impl::base::Host,
     interfaces::r#impl::base::Interface,
     ports::r#impl::base::Port,
     services::r#impl::{
@@ -132,13 +132,12 @@ impl<'a> TopologyContext<'a> {
 
     pub fn get_host_is_virtualized_by(&self, host_id: &Uuid) -> Option<&Service> {
         if let Some(host) = self.get_host_by_id(*host_id)
-            && let Some(HostVirtualization::Proxmox(proxmox_virtualization)) =
-                &host.base.virtualization
+            && let Some(virtualization) = &host.base.virtualization
         {
             return self
                 .services
                 .iter()
-                .find(|s| s.id == proxmox_virtualization.service_id);
+                .find(|s| s.id == virtualization.service_id());
         }
         None
     }
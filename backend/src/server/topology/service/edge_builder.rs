@@ -1,12 +1,11 @@
 use itertools::Itertools;
 use petgraph::{Graph, graph::NodeIndex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use strum::IntoDiscriminant;
 use uuid::Uuid;
 
 use crate::server::{
     groups::r#impl::{base::Group, types::GroupType},
-    hosts::r#impl::virtualization::HostVirtualization,
     services::r#impl::virtualization::ServiceVirtualization,
     subnets::r#impl::types::{SubnetType, SubnetTypeDiscriminants},
     topology::{
@@ -217,75 +216,71 @@ impl EdgeBuilder {
 
     // Create edges to connect a host that virtualizes other hosts as VMs
     pub fn create_vm_host_edges(ctx: &TopologyContext) -> Vec<Edge> {
-        // Proxmox service interface binding that is present for a given subnet.
+        // Hypervisor service interface binding that is present for a given subnet.
         // There could be multiple host interfaces with a given subnet, we arbitrarily choose the first one so there's
         // one clustering hub rather than multiple hubs
-        // (subnet_id, proxmox_service_id) : (interface_id)
-        let mut subnet_to_promxox_host_interface_id: HashMap<(Uuid, Uuid), Uuid> = HashMap::new();
+        // (subnet_id, hypervisor_service_id) : (interface_id)
+        let mut subnet_to_hypervisor_interface_id: HashMap<(Uuid, Uuid), Uuid> = HashMap::new();
 
-        // Hosts VMs managed by a given proxmox service
-        let mut vm_host_id_to_proxmox_service: HashMap<Uuid, Uuid> = HashMap::new();
+        // Hosts VMs managed by a given hypervisor service
+        let mut vm_host_id_to_hypervisor_service: HashMap<Uuid, Uuid> = HashMap::new();
 
         ctx.hosts.iter().for_each(|h| {
-            if let Some(HostVirtualization::Proxmox(proxmox_virtualization)) =
-                &h.base.virtualization
-            {
-                // Create mapping between subnet and proxmox interface(s) on that subnet
-                if let Some(promxox_service) =
-                    ctx.get_service_by_id(proxmox_virtualization.service_id)
+            if let Some(virtualization) = &h.base.virtualization {
+                // Create mapping between subnet and hypervisor interface(s) on that subnet
+                if let Some(hypervisor_service) = ctx.get_service_by_id(virtualization.service_id())
                 {
-                    promxox_service
+                    hypervisor_service
                         .base
                         .bindings
                         .iter()
                         .filter_map(|b| b.interface_id())
                         .for_each(|i| {
                             if let Some(subnet) = ctx.get_subnet_from_interface_id(i)
-                                && !subnet_to_promxox_host_interface_id
-                                    .contains_key(&(subnet.id, promxox_service.id))
+                                && !subnet_to_hypervisor_interface_id
+                                    .contains_key(&(subnet.id, hypervisor_service.id))
                             {
-                                subnet_to_promxox_host_interface_id
-                                    .entry((subnet.id, promxox_service.id))
+                                subnet_to_hypervisor_interface_id
+                                    .entry((subnet.id, hypervisor_service.id))
                                     .insert_entry(i);
                             }
                         });
                 }
 
-                vm_host_id_to_proxmox_service.insert(h.id, proxmox_virtualization.service_id);
+                vm_host_id_to_hypervisor_service.insert(h.id, virtualization.service_id());
             }
         });
 
-        // Creates edges between interface that proxmox service has on a given subnet with interfaces that the virtualized host has on the subnet
+        // Creates edges between interface that hypervisor service has on a given subnet with interfaces that the virtualized host has on the subnet
         ctx.hosts
             .iter()
             .flat_map(|h| {
-                if let Some(proxmox_service_id) = vm_host_id_to_proxmox_service.get(&h.id) {
+                if let Some(hypervisor_service_id) = vm_host_id_to_hypervisor_service.get(&h.id) {
                     let host_interfaces = ctx.get_interfaces_for_host(h.id);
                     return host_interfaces
                         .into_iter()
                         .filter_map(|i| {
-                            if let Some(proxmox_service_interface_id) =
-                                subnet_to_promxox_host_interface_id
-                                    .get(&(i.base.subnet_id, *proxmox_service_id))
-                                && ctx.interface_will_have_node(proxmox_service_interface_id)
+                            if let Some(hypervisor_interface_id) = subnet_to_hypervisor_interface_id
+                                .get(&(i.base.subnet_id, *hypervisor_service_id))
+                                && ctx.interface_will_have_node(hypervisor_interface_id)
                             {
                                 let is_multi_hop =
-                                    ctx.edge_is_multi_hop(proxmox_service_interface_id, &i.id);
+                                    ctx.edge_is_multi_hop(hypervisor_interface_id, &i.id);
 
                                 let (source_handle, target_handle) =
                                     EdgeBuilder::determine_interface_handles(
                                         ctx,
-                                        proxmox_service_interface_id,
+                                        hypervisor_interface_id,
                                         &i.id,
                                         is_multi_hop,
                                     )?;
 
                                 return Some(Edge {
                                     id: Uuid::new_v4(),
-                                    source: *proxmox_service_interface_id,
+                                    source: *hypervisor_interface_id,
                                     target: i.id,
                                     edge_type: EdgeType::HostVirtualization {
-                                        vm_service_id: *proxmox_service_id,
+                                        vm_service_id: *hypervisor_service_id,
                                     },
                                     label: None,
                                     source_handle,
@@ -378,6 +373,92 @@ impl EdgeBuilder {
             .collect()
     }
 
+    /// Create physical link edges (connecting a host's port to the neighbor it reports via LLDP)
+    pub fn create_physical_link_edges(ctx: &TopologyContext) -> Vec<Edge> {
+        // Both ends of a link usually report it, only draw it once. Links are told apart by
+        // the ports at each end, so parallel and aggregated links between two hosts each
+        // get their own edge.
+        let mut linked_ports: HashSet<[(Uuid, Option<String>); 2]> = HashSet::new();
+
+        ctx.hosts
+            .iter()
+            .filter(|host| !host.base.physical_links.is_empty())
+            .flat_map(|host| {
+                let Some(origin_interface) =
+                    ctx.get_first_non_docker_bridge_interface_for_host(host.id)
+                else {
+                    return Vec::new();
+                };
+
+                host.base
+                    .physical_links
+                    .iter()
+                    .filter_map(|link| {
+                        // Prefer the exact interface the neighbor advertised, then fall back
+                        // to a host matching its system name
+                        let target_interface = ctx
+                            .interfaces
+                            .iter()
+                            .find(|i| i.base.host_id != host.id && link.matches_interface(i))
+                            .or_else(|| {
+                                let neighbor = ctx
+                                    .hosts
+                                    .iter()
+                                    .find(|h| h.id != host.id && link.matches_host_name(h))?;
+                                ctx.get_first_non_docker_bridge_interface_for_host(neighbor.id)
+                            })?;
+
+                        let mut port_pair = [
+                            (host.id, Some(link.local_port.to_lowercase())),
+                            (
+                                target_interface.base.host_id,
+                                link.remote_port.as_ref().map(|p| p.to_lowercase()),
+                            ),
+                        ];
+                        port_pair.sort();
+
+                        if !ctx.interface_will_have_node(&origin_interface.id)
+                            || !ctx.interface_will_have_node(&target_interface.id)
+                            || !linked_ports.insert(port_pair)
+                        {
+                            return None;
+                        }
+
+                        let is_multi_hop =
+                            ctx.edge_is_multi_hop(&origin_interface.id, &target_interface.id);
+
+                        let (source_handle, target_handle) =
+                            EdgeBuilder::determine_interface_handles(
+                                ctx,
+                                &origin_interface.id,
+                                &target_interface.id,
+                                is_multi_hop,
+                            )?;
+
+                        let label = match &link.remote_port {
+                            Some(remote_port) => format!("{} ↔ {}", link.local_port, remote_port),
+                            None => link.local_port.clone(),
+                        };
+
+                        Some(Edge {
+                            id: Uuid::new_v4(),
+                            source: origin_interface.id,
+                            target: target_interface.id,
+                            edge_type: EdgeType::PhysicalLink {
+                                host_id: host.id,
+                                local_port: link.local_port.clone(),
+                            },
+                            label: Some(label),
+                            source_handle,
+                            target_handle,
+                            is_multi_hop,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     /// Figure out handles for two interfaces
     pub fn determine_interface_handles(
         ctx: &TopologyContext,
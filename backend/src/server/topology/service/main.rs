@@ -285,6 +285,7 @@ impl TopologyService {
 
         all_edges.extend(EdgeBuilder::create_group_edges(&ctx));
         all_edges.extend(EdgeBuilder::create_vm_host_edges(&ctx));
+        all_edges.extend(EdgeBuilder::create_physical_link_edges(&ctx));
         let (container_edges, docker_bridge_host_subnet_id_to_group_on) =
             EdgeBuilder::create_containerized_service_edges(
                 &ctx,
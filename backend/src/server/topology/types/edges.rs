@@ -282,6 +282,10 @@ pub enum EdgeType {
         source_binding_id: Uuid,
         target_binding_id: Uuid,
     },
+    PhysicalLink {
+        host_id: Uuid,
+        local_port: String,
+    }, // Cabling reported by a host's LLDP neighbor table
 }
 
 impl HasId for EdgeType {
@@ -298,6 +302,7 @@ impl EntityMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => EntityDiscriminants::Host.color(),
             EdgeType::HostVirtualization { .. } => Concept::Virtualization.color(),
             EdgeType::ServiceVirtualization { .. } => Concept::Virtualization.color(),
+            EdgeType::PhysicalLink { .. } => EntityDiscriminants::Port.color(),
         }
     }
 
@@ -308,6 +313,7 @@ impl EntityMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => EntityDiscriminants::Host.icon(),
             EdgeType::HostVirtualization { .. } => Concept::Virtualization.icon(),
             EdgeType::ServiceVirtualization { .. } => Concept::Virtualization.icon(),
+            EdgeType::PhysicalLink { .. } => Icon::Cable,
         }
     }
 }
@@ -320,6 +326,7 @@ impl TypeMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => "Host Interface",
             EdgeType::HostVirtualization { .. } => "Virtualized Host",
             EdgeType::ServiceVirtualization { .. } => "Virtualized Service",
+            EdgeType::PhysicalLink { .. } => "Physical Link",
         }
     }
 
@@ -330,6 +337,7 @@ impl TypeMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => EdgeStyle::SmoothStep.into(),
             EdgeType::HostVirtualization { .. } => EdgeStyle::Straight.into(),
             EdgeType::ServiceVirtualization { .. } => EdgeStyle::SmoothStep.into(),
+            EdgeType::PhysicalLink { .. } => EdgeStyle::Straight.into(),
         };
 
         let is_dashed = match &self {
@@ -338,6 +346,7 @@ impl TypeMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => true,
             EdgeType::HostVirtualization { .. } => true,
             EdgeType::ServiceVirtualization { .. } => true,
+            EdgeType::PhysicalLink { .. } => false,
         };
 
         let has_start_marker = false;
@@ -348,6 +357,7 @@ impl TypeMetadataProvider for EdgeType {
             EdgeType::Interface { .. } => false,
             EdgeType::HostVirtualization { .. } => false,
             EdgeType::ServiceVirtualization { .. } => false,
+            EdgeType::PhysicalLink { .. } => false,
         };
 
         let is_host_edge = matches!(
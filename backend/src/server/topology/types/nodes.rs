@@ -74,6 +74,7 @@ impl SubnetType {
             SubnetType::DockerBridge => 3,
             SubnetType::MacVlan => 3,
             SubnetType::IpVlan => 3,
+            SubnetType::KubernetesPod => 3,
             SubnetType::Management => 3,
             SubnetType::Storage => 3,
 
@@ -106,6 +107,7 @@ impl SubnetType {
             SubnetType::DockerBridge => 2,
             SubnetType::MacVlan => 3,
             SubnetType::IpVlan => 4,
+            SubnetType::KubernetesPod => 5,
 
             // Special
             SubnetType::Unknown => 999,
@@ -99,6 +99,7 @@ pub fn host(network_id: &Uuid) -> Host {
         virtualization: None,
         hidden: false,
         tags: Vec::new(),
+        physical_links: Vec::new(),
     })
 }
 
@@ -110,6 +111,7 @@ pub fn interface(network_id: &Uuid, subnet_id: &Uuid) -> Interface {
         ip_address: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100)),
         mac_address: Some(MacAddress::new(random_mac)),
         position: 0,
+        dns_names: Vec::new(),
         name: Some("eth0".to_string()),
         host_id: Uuid::nil(), // Placeholder - tests will set correct host_id
     })
@@ -120,6 +122,7 @@ pub fn port(network_id: &Uuid, host_id: &Uuid) -> Port {
         port_type: PortType::default(),
         host_id: *host_id,
         network_id: *network_id,
+        snmp_credential_id: None,
     })
 }
 
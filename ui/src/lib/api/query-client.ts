@@ -85,7 +85,9 @@ export const queryKeys = {
 	},
 	networks: {
 		all: ['networks'] as const,
-		detail: (id: string) => [...queryKeys.networks.all, 'detail', id] as const
+		detail: (id: string) => [...queryKeys.networks.all, 'detail', id] as const,
+		dnsResolverCandidates: (id: string) =>
+			[...queryKeys.networks.all, 'dnsResolverCandidates', id] as const
 	},
 	subnets: {
 		all: ['subnets'] as const,
@@ -655,6 +655,58 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/certificates": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** List all certificates */
+        get: operations["list_certificates"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/certificates/bulk-delete": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Bulk delete certificates */
+        post: operations["bulk_delete_certificates"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/certificates/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Get certificate by ID */
+        get: operations["get_certificate_by_id"];
+        put?: never;
+        post?: never;
+        /** Delete certificate */
+        delete: operations["delete_certificate"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/daemons": {
         parameters: {
             query?: never;
@@ -767,6 +819,49 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/discovery/dhcp-leases/import": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Import an uploaded DHCP lease file
+         * @description Creates or updates a host for every lease inside one of the network's subnets,
+         *     matching existing hosts by MAC address.
+         */
+        post: operations["import_dhcp_leases"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/discovery/dns-zones/import": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Import an uploaded DNS zone file
+         * @description Names discovered interfaces, hosts and services after the zone's A, AAAA and
+         *     CNAME records. Records pointing at addresses without a discovered host are
+         *     returned as dangling.
+         */
+        post: operations["import_dns_zone"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/discovery/start-session": {
         parameters: {
             query?: never;
@@ -880,6 +975,51 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/groups/discovery": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon group discovery
+         * @description Used by daemons to report groups they inferred, such as request paths
+         *     from ingress rules. A group from an earlier discovery run with the same
+         *     name and type is updated instead of duplicated.
+         */
+        post: operations["create_group_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/groups/discovery/proxy-routes": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon reverse proxy routes
+         * @description Used by daemons to report the routes read from a reverse proxy's
+         *     configuration. Each route's upstream is matched to a discovered service by
+         *     address and port, and recorded as a request path group from the proxy to
+         *     that service. Groups are deduplicated like other discovered groups.
+         */
+        post: operations["create_proxy_routes_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/groups/{id}": {
         parameters: {
             query?: never;
@@ -978,6 +1118,95 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/hosts/discovery/dns-records": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon DNS zone records
+         * @description Used by daemons to report the A, AAAA and CNAME records transferred from a
+         *     DNS zone. Records are matched to discovered interfaces by address and used to
+         *     name them, their hosts and their services. Records pointing at addresses
+         *     without a discovered host are returned as dangling.
+         */
+        post: operations["create_dns_records_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/hosts/discovery/libvirt-domains": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon libvirt domains
+         * @description Used by daemons running on a libvirt/KVM hypervisor to report the domains
+         *     defined on it. Domains are matched to discovered hosts by MAC address and
+         *     linked to the Libvirt service of the daemon's host.
+         */
+        post: operations["create_libvirt_domains_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/hosts/discovery/neighbors": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon link neighbors
+         * @description Used by daemons to report the switches they heard advertising themselves
+         *     via LLDP or CDP. Each switch is upserted like a discovered host, and the
+         *     links are recorded on the reporting daemon's host.
+         */
+        post: operations["create_link_neighbors_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/hosts/discovery/proxmox-guests": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Internal endpoint for daemon Proxmox guests
+         * @description Used by daemons to report the VMs and containers listed by the Proxmox VE
+         *     API. Guests are matched to discovered hosts by MAC address and linked to
+         *     the Proxmox VE service of their node.
+         */
+        post: operations["create_proxmox_guests_discovery"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/hosts/{destination_host}/consolidate/{other_host}": {
         parameters: {
             query?: never;
@@ -1221,6 +1450,27 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/networks/{id}/dns-resolver-candidates": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * List DNS resolver candidates
+         * @description DNS servers discovery found on the network (DNS Server, Unbound, Pi-Hole, ...)
+         *     that aren't configured as resolvers yet, one per address they listen on.
+         */
+        get: operations["get_dns_resolver_candidates"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/organizations": {
         parameters: {
             query?: never;
@@ -1594,6 +1844,8 @@ export interface paths {
         /**
          * List all tags
          * @description Returns all tags in the authenticated user's organization.
+         *     Daemons get the tags of the organization owning their network, to resolve
+         *     tag names from container labels.
          *     Supports pagination via `limit` and `offset` query parameters,
          *     and ordering via `group_by`, `order_by`, and `order_direction`.
          */
@@ -1939,7 +2191,7 @@ export interface components {
          * @description API metadata included in all responses
          * @example {
          *       "api_version": 1,
-         *       "server_version": "0.13.5"
+         *       "server_version": "0.13.6"
          *     }
          */
         ApiMeta: {
@@ -1950,7 +2202,7 @@ export interface components {
             api_version: number;
             /**
              * @description Server version (semver)
-             * @example 0.13.5
+             * @example 0.13.6
              */
             server_version: string;
         };
@@ -1964,14 +2216,14 @@ export interface components {
             /**
              * @description Association between a service and a port / interface that the service is listening on
              * @example {
-             *       "created_at": "2026-01-13T22:33:31.255874Z",
-             *       "id": "0af52bdb-4f50-4c41-a558-3f62c024af16",
+             *       "created_at": "2026-10-18T12:47:57.615935922Z",
+             *       "id": "db420b96-adf4-42e4-ae07-12a656971fa7",
              *       "interface_id": "550e8400-e29b-41d4-a716-446655440005",
              *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
              *       "port_id": "550e8400-e29b-41d4-a716-446655440006",
              *       "service_id": "550e8400-e29b-41d4-a716-446655440007",
              *       "type": "Port",
-             *       "updated_at": "2026-01-13T22:33:31.255874Z"
+             *       "updated_at": "2026-10-18T12:47:57.615935922Z"
              *     }
              */
             data?: components["schemas"]["BindingBase"] & {
@@ -2005,6 +2257,20 @@ export interface components {
             meta: components["schemas"]["ApiMeta"];
             success: boolean;
         };
+        ApiResponse_Certificate: {
+            /** @description TLS certificate presented on a port. A port has at most one certificate. */
+            data?: components["schemas"]["CertificateBase"] & {
+                /** Format: date-time */
+                readonly created_at: string;
+                /** Format: uuid */
+                readonly id: string;
+                /** Format: date-time */
+                readonly updated_at: string;
+            };
+            error?: string | null;
+            meta: components["schemas"]["ApiMeta"];
+            success: boolean;
+        };
         ApiResponse_DaemonApiKey: {
             data?: components["schemas"]["DaemonApiKeyBase"] & {
                 /** Format: date-time */
@@ -2064,6 +2330,17 @@ export interface components {
             meta: components["schemas"]["ApiMeta"];
             success: boolean;
         };
+        ApiResponse_DhcpLeaseImportResponse: {
+            data?: {
+                /** @description Leases that created or updated a host */
+                imported: number;
+                /** @description Leases for addresses outside every subnet on the network */
+                skipped: number;
+            };
+            error?: string | null;
+            meta: components["schemas"]["ApiMeta"];
+            success: boolean;
+        };
         ApiResponse_Discovery: {
             data?: components["schemas"]["DiscoveryBase"] & {
                 /** Format: date-time */
@@ -2082,6 +2359,8 @@ export interface components {
             data?: {
                 /** Format: uuid */
                 daemon_id: string;
+                /** @description Records a DNS zone transfer returned that point at an address with no discovered host */
+                dangling_records: components["schemas"]["DnsRecord"][];
                 discovery_type: components["schemas"]["DiscoveryType"];
                 error?: string | null;
                 /** Format: date-time */
@@ -2091,8 +2370,12 @@ export interface components {
                 phase: components["schemas"]["DiscoveryPhase"];
                 /** Format: int32 */
                 progress: number;
+                /** @description Round-trip times measured by network discovery, per scanned subnet */
+                rtt_stats: components["schemas"]["SubnetRttStats"][];
                 /** Format: uuid */
                 session_id: string;
+                /** @description Addresses network discovery left alone because of the network's scan exclusions */
+                skipped: components["schemas"]["SkippedAddresses"][];
                 /** Format: date-time */
                 started_at?: string | null;
             };
@@ -2100,6 +2383,17 @@ export interface components {
             meta: components["schemas"]["ApiMeta"];
             success: boolean;
         };
+        ApiResponse_DnsZoneImportResponse: {
+            data?: {
+                /** @description Records pointing at an address no discovered host has */
+                dangling: components["schemas"]["DnsRecord"][];
+                /** @description Records that named a discovered interface */
+                matched: number;
+            };
+            error?: string | null;
+            meta: components["schemas"]["ApiMeta"];
+            success: boolean;
+        };
         ApiResponse_Group: {
             /**
              * @example {
@@ -2144,6 +2438,7 @@ export interface components {
              *       "interfaces": [
              *         {
              *           "created_at": "2026-01-15T10:30:00Z",
+             *           "dns_names": [],
              *           "host_id": "550e8400-e29b-41d4-a716-446655440003",
              *           "id": "550e8400-e29b-41d4-a716-446655440005",
              *           "ip_address": "192.168.1.100",
@@ -2157,6 +2452,7 @@ export interface components {
              *       ],
              *       "name": "web-server-01",
              *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
+             *       "physical_links": [],
              *       "ports": [
              *         {
              *           "created_at": "2026-01-15T10:30:00Z",
@@ -2165,6 +2461,7 @@ export interface components {
              *           "network_id": "550e8400-e29b-41d4-a716-446655440002",
              *           "number": 80,
              *           "protocol": "Tcp",
+             *           "snmp_credential_id": null,
              *           "type": "Http",
              *           "updated_at": "2026-01-15T10:30:00Z"
              *         }
@@ -2190,6 +2487,7 @@ export interface components {
                 name: string;
                 /** Format: uuid */
                 network_id: string;
+                physical_links?: components["schemas"]["PhysicalLink"][];
                 ports: components["schemas"]["Port"][];
                 services: components["schemas"]["Service"][];
                 source: components["schemas"]["EntitySource"];
@@ -2206,6 +2504,7 @@ export interface components {
             /**
              * @example {
              *       "created_at": "2026-01-15T10:30:00Z",
+             *       "dns_names": [],
              *       "host_id": "550e8400-e29b-41d4-a716-446655440003",
              *       "id": "550e8400-e29b-41d4-a716-446655440005",
              *       "ip_address": "192.168.1.100",
@@ -2264,9 +2563,14 @@ export interface components {
             /**
              * @example {
              *       "created_at": "2026-01-15T10:30:00Z",
+             *       "dns_resolvers": [],
              *       "id": "550e8400-e29b-41d4-a716-446655440002",
              *       "name": "Home Network",
              *       "organization_id": "550e8400-e29b-41d4-a716-446655440001",
+             *       "proxmox_credentials": [],
+             *       "scan_exclusions": [],
+             *       "scan_profiles": [],
+             *       "snmp_credentials": [],
              *       "tags": [],
              *       "updated_at": "2026-01-15T10:30:00Z"
              *     }
@@ -2306,6 +2610,7 @@ export interface components {
              *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
              *       "number": 80,
              *       "protocol": "Tcp",
+             *       "snmp_credential_id": null,
              *       "type": "Http",
              *       "updated_at": "2026-01-15T10:30:00Z"
              *     }
@@ -2374,14 +2679,14 @@ export interface components {
              * @example {
              *       "bindings": [
              *         {
-             *           "created_at": "2026-01-13T22:33:31.251337Z",
-             *           "id": "2adaa4fc-d1a9-4b4b-ae39-c59e23107fab",
+             *           "created_at": "2026-10-18T12:47:57.600361162Z",
+             *           "id": "10ab944d-1cb1-4055-998e-ba557db65d95",
              *           "interface_id": "550e8400-e29b-41d4-a716-446655440005",
              *           "network_id": "550e8400-e29b-41d4-a716-446655440002",
              *           "port_id": "550e8400-e29b-41d4-a716-446655440006",
              *           "service_id": "550e8400-e29b-41d4-a716-446655440007",
              *           "type": "Port",
-             *           "updated_at": "2026-01-13T22:33:31.251337Z"
+             *           "updated_at": "2026-10-18T12:47:57.600361162Z"
              *         }
              *       ],
              *       "created_at": "2026-01-15T10:30:00Z",
@@ -2390,7 +2695,7 @@ export interface components {
              *       "name": "nginx",
              *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
              *       "position": 0,
-             *       "service_definition": "Veeam",
+             *       "service_definition": "Unclaimed Open Ports",
              *       "source": {
              *         "type": "Manual"
              *       },
@@ -2579,6 +2884,8 @@ export interface components {
             data?: {
                 /** Format: uuid */
                 daemon_id: string;
+                /** @description Records a DNS zone transfer returned that point at an address with no discovered host */
+                dangling_records: components["schemas"]["DnsRecord"][];
                 discovery_type: components["schemas"]["DiscoveryType"];
                 error?: string | null;
                 /** Format: date-time */
@@ -2588,8 +2895,12 @@ export interface components {
                 phase: components["schemas"]["DiscoveryPhase"];
                 /** Format: int32 */
                 progress: number;
+                /** @description Round-trip times measured by network discovery, per scanned subnet */
+                rtt_stats: components["schemas"]["SubnetRttStats"][];
                 /** Format: uuid */
                 session_id: string;
+                /** @description Addresses network discovery left alone because of the network's scan exclusions */
+                skipped: components["schemas"]["SkippedAddresses"][];
                 /** Format: date-time */
                 started_at?: string | null;
             }[];
@@ -2597,6 +2908,37 @@ export interface components {
             meta: components["schemas"]["ApiMeta"];
             success: boolean;
         };
+        ApiResponse_Vec_DnsResolver: {
+            data?: {
+                /** Format: uuid */
+                id: string;
+                ip: string;
+                name: string;
+                /** Format: int32 */
+                port: number;
+                /**
+                 * @description Subnets whose addresses are looked up through this resolver. Empty means every
+                 *     subnet in the network.
+                 */
+                subnet_ids: string[];
+            }[];
+            error?: string | null;
+            meta: components["schemas"]["ApiMeta"];
+            success: boolean;
+        };
+        ApiResponse_Vec_Group: {
+            data?: (components["schemas"]["GroupBase"] & {
+                /** Format: date-time */
+                readonly created_at: string;
+                /** Format: uuid */
+                readonly id: string;
+                /** Format: date-time */
+                readonly updated_at: string;
+            })[];
+            error?: string | null;
+            meta: components["schemas"]["ApiMeta"];
+            success: boolean;
+        };
         ApiResponse_Vec_Invite: {
             data?: (components["schemas"]["InviteBase"] & {
                 /** Format: date-time */
@@ -2673,14 +3015,14 @@ export interface components {
         /**
          * @description Association between a service and a port / interface that the service is listening on
          * @example {
-         *       "created_at": "2026-01-13T22:33:31.241489Z",
-         *       "id": "e95ed47c-ed9d-4252-a7a3-e47f27d1c8fc",
+         *       "created_at": "2026-10-18T12:47:57.538462214Z",
+         *       "id": "48455e16-a649-4aca-a1f4-4bfe23becd21",
          *       "interface_id": "550e8400-e29b-41d4-a716-446655440005",
          *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
          *       "port_id": "550e8400-e29b-41d4-a716-446655440006",
          *       "service_id": "550e8400-e29b-41d4-a716-446655440007",
          *       "type": "Port",
-         *       "updated_at": "2026-01-13T22:33:31.241489Z"
+         *       "updated_at": "2026-10-18T12:47:57.538462214Z"
          *     }
          */
         Binding: components["schemas"]["BindingBase"] & {
@@ -2780,6 +3122,39 @@ export interface components {
             /** @description Number of entities affected */
             affected_count: number;
         };
+        /** @description TLS certificate presented on a port. A port has at most one certificate. */
+        Certificate: components["schemas"]["CertificateBase"] & {
+            /** Format: date-time */
+            readonly created_at: string;
+            /** Format: uuid */
+            readonly id: string;
+            /** Format: date-time */
+            readonly updated_at: string;
+        };
+        /** @description The base data for a Certificate entity (everything except id, created_at, updated_at) */
+        CertificateBase: components["schemas"]["CertificateDetails"] & {
+            /** Format: uuid */
+            host_id: string;
+            /** Format: uuid */
+            network_id: string;
+            /** Format: uuid */
+            port_id: string;
+        };
+        /** @description What the daemon read from a TLS handshake */
+        CertificateDetails: {
+            /** @description SHA-256 of the DER-encoded certificate, lowercase hex */
+            fingerprint: string;
+            /** @description Issuer distinguished name */
+            issuer: string;
+            /** Format: date-time */
+            not_after: string;
+            /** Format: date-time */
+            not_before: string;
+            /** @description DNS names and IP addresses from the subjectAltName extension */
+            sans: string[];
+            /** @description Subject distinguished name, ie "CN=pve.local, O=PVE Cluster Node" */
+            subject: string;
+        };
         /** @enum {string} */
         Color: "Pink" | "Rose" | "Red" | "Orange" | "Green" | "Emerald" | "Teal" | "Cyan" | "Blue" | "Indigo" | "Purple" | "Gray" | "Yellow";
         /**
@@ -2844,7 +3219,7 @@ export interface components {
          *           "id": "550e8400-e29b-41d4-a716-446655440007",
          *           "name": "nginx",
          *           "position": 0,
-         *           "service_definition": "Veeam",
+         *           "service_definition": "Unclaimed Open Ports",
          *           "tags": [],
          *           "virtualization": null
          *         }
@@ -2957,6 +3332,22 @@ export interface components {
             has_docker_socket?: boolean;
             interfaced_subnet_ids: string[];
         };
+        /**
+         * @description Network settings a daemon needs to run a discovery, including credentials. They're
+         *     sent with the discovery request, or with the session handed to a polling daemon, and
+         *     never stored on or streamed with sessions.
+         */
+        DaemonDiscoveryConfig: {
+            /** @description DNS servers for reverse lookups during network discovery */
+            dns_resolvers?: components["schemas"]["DnsResolver"][];
+            /** @description Proxmox API tokens for Proxmox discovery */
+            proxmox_credentials?: components["schemas"]["ProxmoxCredential"][];
+            /** @description Addresses, ranges and MACs network discovery must not probe */
+            scan_exclusions?: components["schemas"]["ScanExclusion"][];
+            scan_profile?: null | components["schemas"]["ScanProfile"];
+            /** @description SNMP credentials for network discovery */
+            snmp_credentials?: components["schemas"]["SnmpCredentialSet"];
+        };
         DaemonHeartbeatPayload: {
             mode: components["schemas"]["DaemonMode"];
             name: string;
@@ -3041,11 +3432,87 @@ export interface components {
             severity: components["schemas"]["DeprecationSeverity"];
             sunset_date?: string | null;
         };
-        Discovery: components["schemas"]["DiscoveryBase"] & {
-            /** Format: date-time */
-            readonly created_at: string;
-            /** Format: uuid */
-            readonly id: string;
+        /** @enum {string} */
+        DhcpLeaseFormat: "Dnsmasq" | "IscDhcpd" | "Kea";
+        /** @description Lease file uploaded for import into a network */
+        DhcpLeaseImportRequest: {
+            contents: string;
+            /**
+             * Format: uuid
+             * @description Daemon recorded on the imported hosts' discovery metadata, must be on the network
+             */
+            daemon_id: string;
+            format: components["schemas"]["DhcpLeaseFormat"];
+            /** Format: uuid */
+            network_id: string;
+        };
+        DhcpLeaseImportResponse: {
+            /** @description Leases that created or updated a host */
+            imported: number;
+            /** @description Leases for addresses outside every subnet on the network */
+            skipped: number;
+        };
+        DhcpLeaseSource: {
+            format: components["schemas"]["DhcpLeaseFormat"];
+            path: string;
+            /** @enum {string} */
+            type: "File";
+        } | {
+            path: string;
+            /** @enum {string} */
+            type: "KeaControlSocket";
+        } | {
+            format: components["schemas"]["DhcpLeaseFormat"];
+            /** @enum {string} */
+            type: "Uploaded";
+        };
+        /** @description A domain defined on the libvirt hypervisor of the daemon's host */
+        DiscoveredLibvirtDomain: {
+            /** @description MAC addresses of the domain's network interfaces, matched against discovered interfaces */
+            mac_addresses: string[];
+            name: string;
+            /** Format: uuid */
+            uuid: string;
+        };
+        /** @description A switch a daemon heard advertising itself via LLDP or CDP on one of its own interfaces */
+        DiscoveredLinkNeighbor: {
+            /** @description The switch, created or merged like any other discovered host */
+            host: components["schemas"]["Host"];
+            /** @description Empty unless the advertised management address is in a known subnet */
+            interfaces: components["schemas"]["Interface"][];
+            /** @description The daemon's end of the link, recorded on the daemon's host */
+            link: components["schemas"]["PhysicalLink"];
+        };
+        /** @description A QEMU VM or LXC container listed by the Proxmox VE API */
+        DiscoveredProxmoxGuest: {
+            /** @description MAC addresses of the guest's network devices, matched against discovered interfaces */
+            mac_addresses: string[];
+            /** @description Name of the Proxmox node the guest runs on */
+            node: string;
+            /** @description Addresses of that node, used to find its Proxmox VE service */
+            node_ips: string[];
+            vm_id: string;
+            vm_name: string | null;
+        };
+        /** @description A route read from a reverse proxy's configuration */
+        DiscoveredProxyRoute: {
+            /** @description Hostnames and path the route matches, e.g. `app.example.com/api` */
+            rule: string;
+            /** @description Upstream host as configured on the proxy */
+            upstream_host: string;
+            /**
+             * @description Addresses the daemon resolved the upstream host to. Empty if it couldn't, the
+             *     host is then matched by hostname or name.
+             */
+            upstream_ips: string[];
+            /** Format: int32 */
+            upstream_port: number;
+        };
+        Discovery: components["schemas"]["DiscoveryBase"] & {
+            /** Format: date-time */
+            readonly created_at: string;
+            /** Format: uuid */
+            readonly id: string;
             /** Format: date-time */
             readonly updated_at: string;
         };
@@ -3059,25 +3526,72 @@ export interface components {
             run_type: components["schemas"]["RunType"];
             tags: string[];
         };
+        /** @description Request type for daemons reporting the address records of a DNS zone */
+        DiscoveryDnsRecordsRequest: {
+            records: components["schemas"]["DnsRecord"][];
+            /**
+             * @description The zone the records were transferred from. Names from an earlier transfer of
+             *     the same zone that are no longer in it are removed.
+             */
+            zone: string;
+        };
         /**
          * @description Request type for daemon discovery - accepts full entities with IDs.
          *     Used internally by daemons for host creation/upsert, NOT the external API.
          *     This supports the discovery workflow where daemons manage entity IDs.
          */
         DiscoveryHostRequest: {
+            /** @description TLS certificates, keyed to `ports` by port_id */
+            certificates?: components["schemas"]["Certificate"][];
             host: components["schemas"]["Host"];
             interfaces: components["schemas"]["Interface"][];
+            /**
+             * @description The run read the host's LLDP neighbor table, so `host.physical_links` is complete
+             *     and links missing from it have been unplugged
+             */
+            neighbors_read?: boolean;
             ports: components["schemas"]["Port"][];
             services: components["schemas"]["Service"][];
         };
+        /** @description Request type for daemons reporting the libvirt domains of the host they run on */
+        DiscoveryLibvirtDomainsRequest: {
+            domains: components["schemas"]["DiscoveredLibvirtDomain"][];
+            /**
+             * Format: uuid
+             * @description The Libvirt service on the daemon's host
+             */
+            service_id: string;
+        };
+        /** @description Request type for daemons reporting the switches they're plugged into */
+        DiscoveryLinkNeighborsRequest: {
+            neighbors: components["schemas"]["DiscoveredLinkNeighbor"][];
+        };
         DiscoveryMetadata: components["schemas"]["DiscoveryType"] & {
             /** Format: uuid */
             daemon_id: string;
             /** Format: date-time */
             date: string;
+            hostname_source?: null | components["schemas"]["HostnameSource"];
+            /** @description Found by listening only, nothing was sent to the host */
+            passive?: boolean;
         };
         /** @enum {string} */
         DiscoveryPhase: "Pending" | "Starting" | "Started" | "Scanning" | "Complete" | "Failed" | "Cancelled";
+        /** @description Request type for daemons reporting the guests of a Proxmox VE cluster */
+        DiscoveryProxmoxGuestsRequest: {
+            guests: components["schemas"]["DiscoveredProxmoxGuest"][];
+        };
+        /** @description Request type for daemons reporting the routes configured on a reverse proxy */
+        DiscoveryProxyRoutesRequest: {
+            routes: components["schemas"]["DiscoveredProxyRoute"][];
+            /**
+             * Format: uuid
+             * @description The reverse proxy service the routes were read from
+             */
+            service_id: string;
+            /** @description Source recorded on the request path groups built from the routes */
+            source: components["schemas"]["EntitySource"];
+        };
         DiscoveryType: {
             /** Format: uuid */
             host_id: string;
@@ -3085,6 +3599,11 @@ export interface components {
             type: "SelfReport";
         } | {
             host_naming_fallback: components["schemas"]["HostNamingFallback"];
+            /**
+             * Format: uuid
+             * @description Which of the network's scan profiles to probe hosts with, every port when unset
+             */
+            scan_profile_id: string | null;
             subnet_ids: string[] | null;
             /** @enum {string} */
             type: "Network";
@@ -3094,11 +3613,45 @@ export interface components {
             host_naming_fallback: components["schemas"]["HostNamingFallback"];
             /** @enum {string} */
             type: "Docker";
+        } | {
+            source: components["schemas"]["DhcpLeaseSource"];
+            /** @enum {string} */
+            type: "DhcpLeases";
+        } | {
+            /** @description Kubeconfig context to use instead of the current one */
+            context: string | null;
+            /**
+             * @description Path to a kubeconfig on the daemon's host. The in-cluster service account
+             *     (or the default kubeconfig) is used when unset.
+             */
+            kubeconfig_path: string | null;
+            /** @enum {string} */
+            type: "Kubernetes";
+        } | {
+            /** @description Which of the network's Proxmox API tokens to use, all of them when unset */
+            credential_ids: string[] | null;
+            /** @enum {string} */
+            type: "Proxmox";
+        } | {
+            /**
+             * Format: int64
+             * @description How long to listen for, in seconds. Between 10 seconds and 24 hours.
+             */
+            duration: number;
+            subnet_ids: string[] | null;
+            /** @enum {string} */
+            type: "Passive";
+        } | {
+            source: components["schemas"]["DnsZoneSource"];
+            /** @enum {string} */
+            type: "DnsZone";
         };
         /** @description Progress update from daemon to server during discovery */
         DiscoveryUpdatePayload: {
             /** Format: uuid */
             daemon_id: string;
+            /** @description Records a DNS zone transfer returned that point at an address with no discovered host */
+            dangling_records: components["schemas"]["DnsRecord"][];
             discovery_type: components["schemas"]["DiscoveryType"];
             error?: string | null;
             /** Format: date-time */
@@ -3108,11 +3661,65 @@ export interface components {
             phase: components["schemas"]["DiscoveryPhase"];
             /** Format: int32 */
             progress: number;
+            /** @description Round-trip times measured by network discovery, per scanned subnet */
+            rtt_stats: components["schemas"]["SubnetRttStats"][];
             /** Format: uuid */
             session_id: string;
+            /** @description Addresses network discovery left alone because of the network's scan exclusions */
+            skipped: components["schemas"]["SkippedAddresses"][];
             /** Format: date-time */
             started_at?: string | null;
         };
+        /** @description A name in the zone and one of the addresses it resolves to */
+        DnsRecord: {
+            /** @description The name is a CNAME, the address belongs to its target */
+            alias: boolean;
+            ip: string;
+            /** @description Fully qualified name, lowercase and without the trailing dot */
+            name: string;
+        };
+        /**
+         * @description A DNS server discovery sends reverse (PTR) lookups to instead of the daemon's
+         *     own resolver, for daemons whose resolv.conf doesn't know the network's names
+         */
+        DnsResolver: {
+            /** Format: uuid */
+            id: string;
+            ip: string;
+            name: string;
+            /** Format: int32 */
+            port: number;
+            /**
+             * @description Subnets whose addresses are looked up through this resolver. Empty means every
+             *     subnet in the network.
+             */
+            subnet_ids: string[];
+        };
+        /** @description Zone file uploaded for import into a network */
+        DnsZoneImportRequest: {
+            contents: string;
+            /** Format: uuid */
+            network_id: string;
+            /** @description Zone origin, used for names in the file that aren't fully qualified */
+            zone: string;
+        };
+        DnsZoneImportResponse: {
+            /** @description Records pointing at an address no discovered host has */
+            dangling: components["schemas"]["DnsRecord"][];
+            /** @description Records that named a discovered interface */
+            matched: number;
+        };
+        DnsZoneSource: {
+            /** @description Address of the DNS server, port 53 unless one is given (ie "10.0.0.53:5353") */
+            server: string;
+            /** @enum {string} */
+            type: "Axfr";
+            zone: string;
+        } | {
+            /** @enum {string} */
+            type: "Uploaded";
+            zone: string;
+        };
         DockerVirtualization: {
             container_id?: string | null;
             container_name?: string | null;
@@ -3170,11 +3777,17 @@ export interface components {
             source_binding_id: string;
             /** Format: uuid */
             target_binding_id: string;
+        } | {
+            /** @enum {string} */
+            edge_type: "PhysicalLink";
+            /** Format: uuid */
+            host_id: string;
+            local_port: string;
         };
         /** @enum {string} */
-        EdgeTypeDiscriminants: "Interface" | "HostVirtualization" | "ServiceVirtualization" | "RequestPath" | "HubAndSpoke";
+        EdgeTypeDiscriminants: "Interface" | "HostVirtualization" | "ServiceVirtualization" | "RequestPath" | "HubAndSpoke" | "PhysicalLink";
         /** @enum {string} */
-        EntityDiscriminants: "Organization" | "Invite" | "Share" | "Network" | "DaemonApiKey" | "UserApiKey" | "User" | "Tag" | "Discovery" | "Daemon" | "Host" | "Service" | "Port" | "Binding" | "Interface" | "Subnet" | "Group" | "Topology" | "Unknown";
+        EntityDiscriminants: "Organization" | "Invite" | "Share" | "Network" | "DaemonApiKey" | "UserApiKey" | "User" | "Tag" | "Discovery" | "Daemon" | "Host" | "Service" | "Port" | "Binding" | "Interface" | "Certificate" | "Subnet" | "Group" | "Topology" | "Unknown";
         EntityMetadata: {
             color: components["schemas"]["Color"];
             icon: string;
@@ -3199,6 +3812,30 @@ export interface components {
             /** @enum {string} */
             type: "Unknown";
         };
+        EsxiVirtualization: {
+            /** Format: uuid */
+            service_id: string;
+            /** @description Managed object ID, ie "vm-42" */
+            vm_id?: string | null;
+            vm_name?: string | null;
+        };
+        ExclusionTarget: {
+            ip: string;
+            /** @enum {string} */
+            type: "Ip";
+        } | {
+            cidr: string;
+            /** @enum {string} */
+            type: "Cidr";
+        } | {
+            mac: string;
+            /** @enum {string} */
+            type: "Mac";
+        } | {
+            oui: string;
+            /** @enum {string} */
+            type: "MacOui";
+        };
         ForgotPasswordRequest: {
             /** Format: email */
             email: string;
@@ -3259,6 +3896,7 @@ export interface components {
          *       "id": "550e8400-e29b-41d4-a716-446655440003",
          *       "name": "web-server-01",
          *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
+         *       "physical_links": [],
          *       "source": {
          *         "type": "Manual"
          *       },
@@ -3287,12 +3925,14 @@ export interface components {
             name: string;
             /** Format: uuid */
             network_id: string;
+            /** @description Cabling to neighboring devices, reported by the host itself (LLDP over SNMP) */
+            physical_links: components["schemas"]["PhysicalLink"][];
             source: components["schemas"]["EntitySource"];
             tags: string[];
             virtualization: null | components["schemas"]["HostVirtualization"];
         };
         /** @enum {string} */
-        HostNamingFallback: "Ip" | "BestService";
+        HostNamingFallback: "Ip" | "BestService" | "Mdns";
         /**
          * @description Fields that hosts can be ordered/grouped by.
          * @enum {string}
@@ -3310,6 +3950,7 @@ export interface components {
          *       "interfaces": [
          *         {
          *           "created_at": "2026-01-15T10:30:00Z",
+         *           "dns_names": [],
          *           "host_id": "550e8400-e29b-41d4-a716-446655440003",
          *           "id": "550e8400-e29b-41d4-a716-446655440005",
          *           "ip_address": "192.168.1.100",
@@ -3323,6 +3964,7 @@ export interface components {
          *       ],
          *       "name": "web-server-01",
          *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
+         *       "physical_links": [],
          *       "ports": [
          *         {
          *           "created_at": "2026-01-15T10:30:00Z",
@@ -3331,6 +3973,7 @@ export interface components {
          *           "network_id": "550e8400-e29b-41d4-a716-446655440002",
          *           "number": 80,
          *           "protocol": "Tcp",
+         *           "snmp_credential_id": null,
          *           "type": "Http",
          *           "updated_at": "2026-01-15T10:30:00Z"
          *         }
@@ -3356,6 +3999,7 @@ export interface components {
             name: string;
             /** Format: uuid */
             network_id: string;
+            physical_links?: components["schemas"]["PhysicalLink"][];
             ports: components["schemas"]["Port"][];
             services: components["schemas"]["Service"][];
             source: components["schemas"]["EntitySource"];
@@ -3369,10 +4013,35 @@ export interface components {
             details: components["schemas"]["ProxmoxVirtualization"];
             /** @enum {string} */
             type: "Proxmox";
+        } | {
+            details: components["schemas"]["LibvirtVirtualization"];
+            /** @enum {string} */
+            type: "Libvirt";
+        } | {
+            details: components["schemas"]["EsxiVirtualization"];
+            /** @enum {string} */
+            type: "Esxi";
+        } | {
+            details: components["schemas"]["HyperVVirtualization"];
+            /** @enum {string} */
+            type: "HyperV";
+        };
+        /**
+         * @description How discovery resolved a host's hostname
+         * @enum {string}
+         */
+        HostnameSource: "Dns" | "Netbios" | "Llmnr" | "Snmp" | "DhcpLease" | "Mdns";
+        HyperVVirtualization: {
+            /** Format: uuid */
+            service_id: string;
+            /** @description VM GUID */
+            vm_id?: string | null;
+            vm_name?: string | null;
         };
         /**
          * @example {
          *       "created_at": "2026-01-15T10:30:00Z",
+         *       "dns_names": [],
          *       "host_id": "550e8400-e29b-41d4-a716-446655440003",
          *       "id": "550e8400-e29b-41d4-a716-446655440005",
          *       "ip_address": "192.168.1.100",
@@ -3393,6 +4062,8 @@ export interface components {
             readonly updated_at: string;
         };
         InterfaceBase: {
+            /** @description Names from imported DNS zones (A, AAAA and CNAME records) pointing at this address */
+            dns_names: string[];
             /** Format: uuid */
             host_id: string;
             ip_address: string;
@@ -3458,6 +4129,20 @@ export interface components {
             x: number;
             y: number;
         };
+        KubernetesVirtualization: {
+            namespace: string;
+            pod: string;
+            /** @description Deployment, StatefulSet or DaemonSet that owns the pod, if any */
+            workload?: string | null;
+        };
+        LibvirtVirtualization: {
+            /** Format: uuid */
+            service_id: string;
+            /** @description Domain UUID */
+            vm_id?: string | null;
+            /** @description Domain name */
+            vm_name?: string | null;
+        };
         /** @description Login request from client */
         LoginRequest: {
             /** Format: email */
@@ -3497,9 +4182,14 @@ export interface components {
         /**
          * @example {
          *       "created_at": "2026-01-15T10:30:00Z",
+         *       "dns_resolvers": [],
          *       "id": "550e8400-e29b-41d4-a716-446655440002",
          *       "name": "Home Network",
          *       "organization_id": "550e8400-e29b-41d4-a716-446655440001",
+         *       "proxmox_credentials": [],
+         *       "scan_exclusions": [],
+         *       "scan_profiles": [],
+         *       "snmp_credentials": [],
          *       "tags": [],
          *       "updated_at": "2026-01-15T10:30:00Z"
          *     }
@@ -3513,9 +4203,19 @@ export interface components {
             readonly updated_at: string;
         };
         NetworkBase: {
+            /** @description DNS servers for reverse lookups during network discovery, instead of the daemon's own */
+            dns_resolvers: components["schemas"]["DnsResolver"][];
             name: string;
             /** Format: uuid */
             organization_id: string;
+            /** @description Proxmox VE API tokens, used by Proxmox discovery to link guests to their hypervisor */
+            proxmox_credentials: components["schemas"]["ProxmoxCredential"][];
+            /** @description Addresses, ranges and MACs network discovery never probes */
+            scan_exclusions: components["schemas"]["ScanExclusion"][];
+            /** @description Port sets and timings network discoveries can scan with */
+            scan_profiles: components["schemas"]["ScanProfile"][];
+            /** @description SNMP credential profiles, tried in order during network discovery */
+            snmp_credentials: components["schemas"]["SnmpCredential"][];
             tags: string[];
         };
         /** @description Network configuration for setup */
@@ -3579,7 +4279,7 @@ export interface components {
          *         "offset": 0,
          *         "total_count": 142
          *       },
-         *       "server_version": "0.13.5"
+         *       "server_version": "0.13.6"
          *     }
          */
         PaginatedApiMeta: {
@@ -3592,7 +4292,7 @@ export interface components {
             pagination: components["schemas"]["PaginationMeta"];
             /**
              * @description Server version (semver)
-             * @example 0.13.5
+             * @example 0.13.6
              */
             server_version: string;
         };
@@ -3640,6 +4340,7 @@ export interface components {
                 name: string;
                 /** Format: uuid */
                 network_id: string;
+                physical_links?: components["schemas"]["PhysicalLink"][];
                 ports: components["schemas"]["Port"][];
                 services: components["schemas"]["Service"][];
                 source: components["schemas"]["EntitySource"];
@@ -3785,6 +4486,29 @@ export interface components {
              */
             offset?: number | null;
         };
+        /**
+         * @description A cable between one of this host's ports and a neighboring device, read from the
+         *     host's LLDP neighbor table
+         */
+        PhysicalLink: {
+            /** @description Whether the local port is operationally up */
+            is_up: boolean;
+            /** @description Name of the local port, ie "Gi1/0/12" */
+            local_port: string;
+            /** @description Chassis ID advertised by the neighbor, usually a MAC address */
+            remote_chassis_id: string;
+            /** @description Management address advertised by the neighbor */
+            remote_ip_address: string | null;
+            remote_mac_address: string | null;
+            /** @description Port ID or description advertised by the neighbor */
+            remote_port: string | null;
+            remote_system_name: string | null;
+            /**
+             * Format: int64
+             * @description Speed of the local port in Mbps
+             */
+            speed_mbps: number | null;
+        };
         PlanConfig: {
             /** Format: int64 */
             base_cents: number;
@@ -3809,6 +4533,7 @@ export interface components {
          *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
          *       "number": 80,
          *       "protocol": "Tcp",
+         *       "snmp_credential_id": null,
          *       "type": "Http",
          *       "updated_at": "2026-01-15T10:30:00Z"
          *     }
@@ -3827,6 +4552,11 @@ export interface components {
             host_id: string;
             /** Format: uuid */
             network_id: string;
+            /**
+             * Format: uuid
+             * @description SNMP credential profile that last answered on this port, tried first on later scans
+             */
+            readonly snmp_credential_id: string | null;
         };
         /**
          * @description Input for creating or updating a port.
@@ -3855,6 +4585,23 @@ export interface components {
             /** @description Auto-derived from number+protocol; optional on create */
             type?: string;
         };
+        /** @description A Proxmox VE API token discovery uses to list the cluster's nodes and guests */
+        ProxmoxCredential: {
+            /** @description Proxmox ships with a self-signed certificate */
+            allow_self_signed_certs: boolean;
+            /** Format: uuid */
+            id: string;
+            name: string;
+            /** @description Token ID in the form "user@realm!token" */
+            token_id: string;
+            /**
+             * @description Write-only: returned blank, leave blank in an update to keep the stored value.
+             *     Must be entered again when the URL changes.
+             */
+            token_secret: string;
+            /** @description Base URL of any node in the cluster, ie "https://pve.local:8006" */
+            url: string;
+        };
         ProxmoxVirtualization: {
             /** Format: uuid */
             service_id: string;
@@ -3917,6 +4664,46 @@ export interface components {
             /** @enum {string} */
             type: "AdHoc";
         };
+        /**
+         * @description A device or range network discovery never probes, for hosts that misbehave when
+         *     scanned (old PLCs, fragile printers, honeypots)
+         */
+        ScanExclusion: {
+            /** Format: uuid */
+            id: string;
+            name: string;
+            target: components["schemas"]["ExclusionTarget"];
+        };
+        ScanPorts: {
+            /** @enum {string} */
+            type: "Definitions";
+        } | {
+            /** @enum {string} */
+            type: "Top1000";
+        } | {
+            /** @enum {string} */
+            type: "Full";
+        } | {
+            /** @description Also probe the definition ports, so known services are still found */
+            include_definitions: boolean;
+            ports: string;
+            /** @enum {string} */
+            type: "Custom";
+        };
+        /** @description Which TCP ports network discovery probes on each host, and how hard it pushes them */
+        ScanProfile: {
+            /** @description Ports probed at once on each host. Uses the daemon's default when unset. */
+            batch_size: number | null;
+            /** Format: uuid */
+            id: string;
+            name: string;
+            ports: components["schemas"]["ScanPorts"];
+            /**
+             * Format: int64
+             * @description How long to wait for each TCP connection and HTTP request, in milliseconds
+             */
+            timeout_ms: number;
+        };
         /** @description Server capabilities returned on startup/registration */
         ServerCapabilities: {
             /** @description Deprecation warnings for the daemon */
@@ -3930,14 +4717,14 @@ export interface components {
          * @example {
          *       "bindings": [
          *         {
-         *           "created_at": "2026-01-13T22:33:31.241392Z",
-         *           "id": "276d1c1c-f8e2-452f-80b6-30703f2a043e",
+         *           "created_at": "2026-10-18T12:47:57.538119702Z",
+         *           "id": "70693038-bc59-424d-9e6d-22b63c7f4570",
          *           "interface_id": "550e8400-e29b-41d4-a716-446655440005",
          *           "network_id": "550e8400-e29b-41d4-a716-446655440002",
          *           "port_id": "550e8400-e29b-41d4-a716-446655440006",
          *           "service_id": "550e8400-e29b-41d4-a716-446655440007",
          *           "type": "Port",
-         *           "updated_at": "2026-01-13T22:33:31.241392Z"
+         *           "updated_at": "2026-10-18T12:47:57.538119702Z"
          *         }
          *       ],
          *       "created_at": "2026-01-15T10:30:00Z",
@@ -3946,7 +4733,7 @@ export interface components {
          *       "name": "nginx",
          *       "network_id": "550e8400-e29b-41d4-a716-446655440002",
          *       "position": 0,
-         *       "service_definition": "Veeam",
+         *       "service_definition": "Unclaimed Open Ports",
          *       "source": {
          *         "type": "Manual"
          *       },
@@ -4022,6 +4809,10 @@ export interface components {
             details: components["schemas"]["DockerVirtualization"];
             /** @enum {string} */
             type: "Docker";
+        } | {
+            details: components["schemas"]["KubernetesVirtualization"];
+            /** @enum {string} */
+            type: "Kubernetes";
         };
         /** @description Request body for setting all tags on an entity */
         SetTagsRequest: {
@@ -4072,6 +4863,64 @@ export interface components {
             show_inspect_panel: boolean;
             show_zoom_controls: boolean;
         };
+        /** @description Addresses a discovery session skipped because of one exclusion */
+        SkippedAddresses: {
+            /** @description The first addresses skipped, a CIDR exclusion can cover far more than are listed */
+            addresses: string[];
+            count: number;
+            /** Format: uuid */
+            exclusion_id: string;
+        };
+        /** @enum {string} */
+        SnmpAuthProtocol: "Md5" | "Sha1" | "Sha224" | "Sha256" | "Sha384" | "Sha512";
+        /**
+         * @description A set of SNMP credentials discovery tries against hosts answering on UDP 161.
+         *     Profiles are tried in the order they're configured on the network.
+         */
+        SnmpCredential: components["schemas"]["SnmpVersion"] & {
+            /**
+             * Format: uuid
+             * @description Stable ID, recorded on the ports this profile answered for
+             */
+            id: string;
+            name: string;
+            /** @description Subnets this profile is tried on. Empty means every subnet in the network. */
+            subnet_ids: string[];
+        };
+        /** @description SNMP credentials sent to a daemon with a network discovery request */
+        SnmpCredentialSet: {
+            /** @description Profile that last answered at each address, tried before the others */
+            known?: {
+                [key: string]: string;
+            };
+            profiles?: components["schemas"]["SnmpCredential"][];
+        };
+        /** @enum {string} */
+        SnmpPrivacyProtocol: "Des" | "Aes128" | "Aes192" | "Aes256";
+        /** @enum {string} */
+        SnmpSecurityLevel: "NoAuthNoPriv" | "AuthNoPriv" | "AuthPriv";
+        SnmpVersion: {
+            /** @description Write-only: returned blank, leave blank in an update to keep the stored value */
+            community: string;
+            /** @enum {string} */
+            version: "V1";
+        } | {
+            /** @description Write-only: returned blank, leave blank in an update to keep the stored value */
+            community: string;
+            /** @enum {string} */
+            version: "V2c";
+        } | {
+            /** @description Required for AuthNoPriv and AuthPriv. Write-only, like the community. */
+            auth_password?: string;
+            auth_protocol?: components["schemas"]["SnmpAuthProtocol"];
+            /** @description Required for AuthPriv. Write-only, like the community. */
+            privacy_password?: string;
+            privacy_protocol?: components["schemas"]["SnmpPrivacyProtocol"];
+            security_level?: components["schemas"]["SnmpSecurityLevel"];
+            username: string;
+            /** @enum {string} */
+            version: "V3";
+        };
         /**
          * @example {
          *       "cidr": "192.168.1.0/24",
@@ -4112,8 +4961,25 @@ export interface components {
          * @enum {string}
          */
         SubnetOrderField: "created_at" | "name" | "cidr" | "subnet_type" | "updated_at" | "network_id";
+        /**
+         * @description Round-trip times of the hosts that answered in one subnet. Each host contributes its
+         *     smoothed RTT to the average and its fastest and slowest samples to the range.
+         */
+        SubnetRttStats: {
+            /** Format: int64 */
+            avg_us: number;
+            /** @description Hosts with at least one measurement */
+            hosts: number;
+            /** Format: int64 */
+            max_us: number;
+            /** Format: int64 */
+            min_us: number;
+            samples: number;
+            /** Format: uuid */
+            subnet_id: string;
+        };
         /** @enum {string} */
-        SubnetType: "Internet" | "Remote" | "Gateway" | "VpnTunnel" | "Dmz" | "Lan" | "WiFi" | "IoT" | "Guest" | "DockerBridge" | "MacVlan" | "IpVlan" | "Management" | "Storage" | "Unknown" | "None";
+        SubnetType: "Internet" | "Remote" | "Gateway" | "VpnTunnel" | "Dmz" | "Lan" | "WiFi" | "IoT" | "Guest" | "DockerBridge" | "MacVlan" | "IpVlan" | "KubernetesPod" | "Management" | "Storage" | "Unknown" | "None";
         /**
          * @example {
          *       "color": "Green",
@@ -5893,17 +6759,15 @@ export interface operations {
             };
         };
     };
-    get_daemons: {
+    list_certificates: {
         parameters: {
             query?: {
+                /** @description Filter by host ID */
+                host_id?: string | null;
                 /** @description Filter by network ID */
                 network_id?: string | null;
-                /** @description Primary ordering field (used for grouping). Always sorts ASC to keep groups together. */
-                group_by?: null | components["schemas"]["DaemonOrderField"];
-                /** @description Secondary ordering field (sorting within groups or standalone sort). */
-                order_by?: null | components["schemas"]["DaemonOrderField"];
-                /** @description Direction for order_by field (group_by always uses ASC). */
-                order_direction?: null | components["schemas"]["OrderDirection"];
+                /** @description Filter by specific entity IDs (for selective loading) */
+                ids?: string[] | null;
                 /** @description Maximum number of results to return (1-1000, default: 50). Use 0 for no limit. */
                 limit?: number | null;
                 /** @description Number of results to skip. Default: 0. */
@@ -5915,32 +6779,37 @@ export interface operations {
         };
         requestBody?: never;
         responses: {
-            /** @description List of daemons */
+            /** @description List of certificates */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["PaginatedApiResponse_DaemonResponse"];
+                    "application/json": {
+                        data: components["schemas"]["Certificate"][];
+                        error?: string | null;
+                        meta: components["schemas"]["PaginatedApiMeta"];
+                        success: boolean;
+                    };
                 };
             };
         };
     };
-    bulk_delete_daemons: {
+    bulk_delete_certificates: {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        /** @description Array of daemons IDs to delete */
+        /** @description Array of certificates IDs to delete */
         requestBody: {
             content: {
                 "application/json": string[];
             };
         };
         responses: {
-            /** @description Daemons deleted */
+            /** @description Certificates deleted */
             200: {
                 headers: {
                     [name: string]: unknown;
@@ -5951,37 +6820,28 @@ export interface operations {
             };
         };
     };
-    get_daemon_by_id: {
+    get_certificate_by_id: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Daemon ID */
+                /** @description Certificate ID */
                 id: string;
             };
             cookie?: never;
         };
         requestBody?: never;
         responses: {
-            /** @description Daemon found */
+            /** @description Certificate found */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ApiResponse_DaemonResponse"];
-                };
-            };
-            /** @description Access denied */
-            403: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["ApiErrorResponse"];
+                    "application/json": components["schemas"]["ApiResponse_Certificate"];
                 };
             };
-            /** @description Daemon not found */
+            /** @description Certificate not found */
             404: {
                 headers: {
                     [name: string]: unknown;
@@ -5992,19 +6852,19 @@ export interface operations {
             };
         };
     };
-    delete_daemon: {
+    delete_certificate: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Daemon ID */
+                /** @description Certificate ID */
                 id: string;
             };
             cookie?: never;
         };
         requestBody?: never;
         responses: {
-            /** @description Daemon deleted */
+            /** @description Certificate deleted */
             200: {
                 headers: {
                     [name: string]: unknown;
@@ -6013,7 +6873,7 @@ export interface operations {
                     "application/json": components["schemas"]["ApiResponse"];
                 };
             };
-            /** @description Daemon not found */
+            /** @description Certificate not found */
             404: {
                 headers: {
                     [name: string]: unknown;
@@ -6024,13 +6884,17 @@ export interface operations {
             };
         };
     };
-    list_discoveries: {
+    get_daemons: {
         parameters: {
             query?: {
                 /** @description Filter by network ID */
                 network_id?: string | null;
-                /** @description Filter by daemon ID */
-                daemon_id?: string | null;
+                /** @description Primary ordering field (used for grouping). Always sorts ASC to keep groups together. */
+                group_by?: null | components["schemas"]["DaemonOrderField"];
+                /** @description Secondary ordering field (sorting within groups or standalone sort). */
+                order_by?: null | components["schemas"]["DaemonOrderField"];
+                /** @description Direction for order_by field (group_by always uses ASC). */
+                order_direction?: null | components["schemas"]["OrderDirection"];
                 /** @description Maximum number of results to return (1-1000, default: 50). Use 0 for no limit. */
                 limit?: number | null;
                 /** @description Number of results to skip. Default: 0. */
@@ -6042,42 +6906,169 @@ export interface operations {
         };
         requestBody?: never;
         responses: {
-            /** @description List of discoveries */
+            /** @description List of daemons */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": {
-                        data: components["schemas"]["Discovery"][];
-                        error?: string | null;
-                        meta: components["schemas"]["PaginatedApiMeta"];
-                        success: boolean;
-                    };
+                    "application/json": components["schemas"]["PaginatedApiResponse_DaemonResponse"];
                 };
             };
         };
     };
-    create_discovery: {
+    bulk_delete_daemons: {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
+        /** @description Array of daemons IDs to delete */
         requestBody: {
             content: {
-                "application/json": components["schemas"]["Discovery"];
+                "application/json": string[];
             };
         };
         responses: {
-            /** @description Discovery created successfully */
+            /** @description Daemons deleted */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ApiResponse_Discovery"];
+                    "application/json": components["schemas"]["ApiResponse_BulkDeleteResponse"];
+                };
+            };
+        };
+    };
+    get_daemon_by_id: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Daemon ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Daemon found */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_DaemonResponse"];
+                };
+            };
+            /** @description Access denied */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+            /** @description Daemon not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    delete_daemon: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Daemon ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Daemon deleted */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse"];
+                };
+            };
+            /** @description Daemon not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    list_discoveries: {
+        parameters: {
+            query?: {
+                /** @description Filter by network ID */
+                network_id?: string | null;
+                /** @description Filter by daemon ID */
+                daemon_id?: string | null;
+                /** @description Maximum number of results to return (1-1000, default: 50). Use 0 for no limit. */
+                limit?: number | null;
+                /** @description Number of results to skip. Default: 0. */
+                offset?: number | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description List of discoveries */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": {
+                        data: components["schemas"]["Discovery"][];
+                        error?: string | null;
+                        meta: components["schemas"]["PaginatedApiMeta"];
+                        success: boolean;
+                    };
+                };
+            };
+        };
+    };
+    create_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["Discovery"];
+            };
+        };
+        responses: {
+            /** @description Discovery created successfully */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_Discovery"];
                 };
             };
             /** @description Can't create historical discovery */
@@ -6136,6 +7127,81 @@ export interface operations {
             };
         };
     };
+    import_dhcp_leases: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DhcpLeaseImportRequest"];
+            };
+        };
+        responses: {
+            /** @description Leases imported */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_DhcpLeaseImportResponse"];
+                };
+            };
+            /** @description Lease file couldn't be parsed */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+            /** @description Daemon not found on the network */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    import_dns_zone: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DnsZoneImportRequest"];
+            };
+        };
+        responses: {
+            /** @description Zone imported */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_DnsZoneImportResponse"];
+                };
+            };
+            /** @description Zone file couldn't be parsed */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
     start_session: {
         parameters: {
             query?: never;
@@ -6410,6 +7476,90 @@ export interface operations {
             };
         };
     };
+    create_group_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["Group"];
+            };
+        };
+        responses: {
+            /** @description Group created or updated */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_Group"];
+                };
+            };
+            /** @description Invalid request */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+            /** @description Daemon cannot create groups on other networks */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    create_proxy_routes_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DiscoveryProxyRoutesRequest"];
+            };
+        };
+        responses: {
+            /** @description Request path groups created or updated */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_Vec_Group"];
+                };
+            };
+            /** @description Service is not a reverse proxy */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+            /** @description Daemon has no network assignment */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
     get_group_by_id: {
         parameters: {
             query?: never;
@@ -6665,6 +7815,138 @@ export interface operations {
             };
         };
     };
+    create_dns_records_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DiscoveryDnsRecordsRequest"];
+            };
+        };
+        responses: {
+            /** @description DNS records recorded */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_DnsZoneImportResponse"];
+                };
+            };
+            /** @description Daemon has no network assignment */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    create_libvirt_domains_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DiscoveryLibvirtDomainsRequest"];
+            };
+        };
+        responses: {
+            /** @description Libvirt domains recorded */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse"];
+                };
+            };
+            /** @description Daemon has no network assignment */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    create_link_neighbors_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DiscoveryLinkNeighborsRequest"];
+            };
+        };
+        responses: {
+            /** @description Link neighbors recorded */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse"];
+                };
+            };
+            /** @description Daemon cannot create hosts on other networks */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
+    create_proxmox_guests_discovery: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DiscoveryProxmoxGuestsRequest"];
+            };
+        };
+        responses: {
+            /** @description Proxmox guests recorded */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse"];
+                };
+            };
+            /** @description Daemon has no network assignment */
+            403: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
     consolidate_hosts: {
         parameters: {
             query?: never;
@@ -7378,6 +8660,38 @@ export interface operations {
             };
         };
     };
+    get_dns_resolver_candidates: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Network ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Candidate resolvers */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiResponse_Vec_DnsResolver"];
+                };
+            };
+            /** @description Network not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiErrorResponse"];
+                };
+            };
+        };
+    };
     get_organization: {
         parameters: {
             query?: never;
@@ -207,6 +207,16 @@ export const fieldDefs: FieldDef[] = [
 		section: () => m.daemons_config_sectionDockerDiscovery(),
 		validators: []
 	},
+	{
+		id: 'docker_endpoints',
+		label: () => m.daemons_config_dockerEndpoints(),
+		type: 'string',
+		defaultValue: '',
+		cliFlag: '--docker-endpoints',
+		envVar: 'SCANOPY_DOCKER_ENDPOINTS',
+		helpText: () => m.daemons_config_dockerEndpointsHelp(),
+		section: () => m.daemons_config_sectionDockerDiscovery()
+	},
 	// Network Discovery
 	{
 		id: 'interfaces',
@@ -237,6 +247,15 @@ export const fieldDefs: FieldDef[] = [
 		helpText: () => m.daemons_config_arpPacketsPerSecondHelp(),
 		section: () => m.daemons_config_sectionNetworkDiscovery()
 	},
+	{
+		id: 'syn_scan_rate_pps',
+		label: () => m.daemons_config_synScanRatePps(),
+		type: 'number',
+		cliFlag: '--syn-scan-rate-pps',
+		envVar: 'SCANOPY_SYN_SCAN_RATE_PPS',
+		helpText: () => m.daemons_config_synScanRatePpsHelp(),
+		section: () => m.daemons_config_sectionNetworkDiscovery()
+	},
 	{
 		id: 'use_npcap_arp',
 		label: () => m.daemons_config_useNpcapArp(),
@@ -247,6 +266,26 @@ export const fieldDefs: FieldDef[] = [
 		helpText: () => m.daemons_config_useNpcapArpHelp(),
 		section: () => m.daemons_config_sectionNetworkDiscovery()
 	},
+	{
+		id: 'passive_link_discovery',
+		label: () => m.daemons_config_passiveLinkDiscovery(),
+		type: 'boolean',
+		defaultValue: false,
+		cliFlag: '--passive-link-discovery',
+		envVar: 'SCANOPY_PASSIVE_LINK_DISCOVERY',
+		helpText: () => m.daemons_config_passiveLinkDiscoveryHelp(),
+		section: () => m.daemons_config_sectionNetworkDiscovery()
+	},
+	{
+		id: 'icmp_sweep',
+		label: () => m.daemons_config_icmpSweep(),
+		type: 'boolean',
+		defaultValue: false,
+		cliFlag: '--icmp-sweep',
+		envVar: 'SCANOPY_ICMP_SWEEP',
+		helpText: () => m.daemons_config_icmpSweepHelp(),
+		section: () => m.daemons_config_sectionNetworkDiscovery()
+	},
 	{
 		id: 'concurrentScans',
 		label: () => m.daemons_config_concurrentScans(),
@@ -256,5 +295,25 @@ export const fieldDefs: FieldDef[] = [
 		helpText: () => m.daemons_config_concurrentScansHelp(),
 		placeholder: () => m.common_auto(),
 		section: () => m.daemons_config_sectionNetworkDiscovery()
+	},
+	{
+		id: 'npm_credentials',
+		label: () => m.daemons_config_npmCredentials(),
+		type: 'string',
+		defaultValue: '',
+		cliFlag: '--npm-credentials',
+		envVar: 'SCANOPY_NPM_CREDENTIALS',
+		helpText: () => m.daemons_config_npmCredentialsHelp(),
+		section: () => m.daemons_config_sectionNetworkDiscovery()
+	},
+	{
+		id: 'npm_database',
+		label: () => m.daemons_config_npmDatabase(),
+		type: 'string',
+		defaultValue: '',
+		cliFlag: '--npm-database',
+		envVar: 'SCANOPY_NPM_DATABASE',
+		helpText: () => m.daemons_config_npmDatabaseHelp(),
+		section: () => m.daemons_config_sectionNetworkDiscovery()
 	}
 ];
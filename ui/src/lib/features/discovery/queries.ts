@@ -146,7 +146,8 @@ export function createEmptyDiscoveryFormData(daemon: Daemon | null): Discovery {
 		discovery_type: {
 			type: 'Network',
 			subnet_ids: daemon ? daemon.capabilities.interfaced_subnet_ids : [],
-			host_naming_fallback: 'Ip'
+			host_naming_fallback: 'Ip',
+			scan_profile_id: null
 		},
 		run_type: {
 			type: 'Scheduled',
@@ -50,7 +50,8 @@ export function toHostPrimitive(response: HostResponse): Host {
 		source: response.source,
 		virtualization: response.virtualization ?? null,
 		hidden: response.hidden,
-		tags: response.tags
+		tags: response.tags,
+		physical_links: response.physical_links ?? []
 	};
 }
 
@@ -69,7 +70,9 @@ export function formDataToHostPrimitive(formData: HostFormData): Host {
 		source: formData.source,
 		virtualization: formData.virtualization,
 		hidden: formData.hidden,
-		tags: formData.tags
+		tags: formData.tags,
+		// Discovery owns physical links, host updates never send them
+		physical_links: []
 	};
 }
 
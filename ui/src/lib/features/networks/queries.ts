@@ -31,6 +31,29 @@ export function useNetworksQuery() {
 	}));
 }
 
+/**
+ * Query hook for DNS servers discovery found on a network that aren't resolvers yet
+ * @param getNetworkId - Getter returning the network to look at, null until it has been saved
+ */
+export function useDnsResolverCandidatesQuery(getNetworkId: () => string | null) {
+	return createQuery(() => {
+		const networkId = getNetworkId();
+		return {
+			queryKey: queryKeys.networks.dnsResolverCandidates(networkId ?? ''),
+			queryFn: async () => {
+				const { data } = await apiClient.GET('/api/v1/networks/{id}/dns-resolver-candidates', {
+					params: { path: { id: networkId as string } }
+				});
+				if (!data?.success || !data.data) {
+					throw new Error(data?.error || 'Failed to fetch DNS resolver candidates');
+				}
+				return data.data;
+			},
+			enabled: networkId !== null
+		};
+	});
+}
+
 /**
  * Mutation hook for creating a network
  */
@@ -143,6 +166,11 @@ export function createEmptyNetworkFormData(): Network {
 		created_at: utcTimeZoneSentinel,
 		updated_at: utcTimeZoneSentinel,
 		organization_id: uuidv4Sentinel,
-		tags: []
+		tags: [],
+		snmp_credentials: [],
+		proxmox_credentials: [],
+		dns_resolvers: [],
+		scan_profiles: [],
+		scan_exclusions: []
 	};
 }
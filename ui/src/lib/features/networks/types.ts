@@ -2,3 +2,11 @@ import type { components } from '$lib/api/schema';
 
 // Re-export generated types
 export type Network = components['schemas']['Network'];
+export type SnmpCredential = components['schemas']['SnmpCredential'];
+export type SnmpVersion = components['schemas']['SnmpVersion'];
+export type ProxmoxCredential = components['schemas']['ProxmoxCredential'];
+export type DnsResolver = components['schemas']['DnsResolver'];
+export type ScanProfile = components['schemas']['ScanProfile'];
+export type ScanPorts = components['schemas']['ScanPorts'];
+export type ScanExclusion = components['schemas']['ScanExclusion'];
+export type ExclusionTarget = components['schemas']['ExclusionTarget'];
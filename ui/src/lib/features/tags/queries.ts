@@ -158,6 +158,7 @@ const entityTypeToQueryKeyName: Record<EntityDiscriminants, keyof typeof queryKe
 	Port: null,
 	Binding: null,
 	Interface: null,
+	Certificate: null,
 	Topology: null,
 	Unknown: null
 };